@@ -2,7 +2,9 @@ use std::fs::File;
 use std::process::Command;
 
 use ethers::utils::{Ganache, GanacheInstance};
+use mockito::{mock, Matcher};
 use pretty_assertions::assert_eq;
+use serde_json::json;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::felt;
 use tar::Archive;
@@ -58,8 +60,11 @@ fn get_test_ethereum_node() -> (TestEthereumNodeHandle, EthereumContractAddress)
 // Note: the test requires ganache-cli installed, otherwise it is ignored.
 async fn latest_proved_block_ethereum() {
     let (node_handle, starknet_contract_address) = get_test_ethereum_node();
-    let config =
-        EthereumBaseLayerConfig { node_url: node_handle.0.endpoint(), starknet_contract_address };
+    let config = EthereumBaseLayerConfig {
+        node_url: node_handle.0.endpoint(),
+        starknet_contract_address,
+        batch_requests: true,
+    };
     let contract = EthereumBaseLayerContract::new(config).unwrap();
 
     let first_sn_state_update = (BlockNumber(100), BlockHash(felt!("0x100")));
@@ -79,3 +84,64 @@ async fn latest_proved_block_ethereum() {
         assert_eq!(latest_block, expected);
     }
 }
+
+#[test_with::executable(ganache)]
+#[tokio::test]
+// Note: the test requires ganache-cli installed, otherwise it is ignored.
+async fn proved_block_at_ethereum() {
+    let (node_handle, starknet_contract_address) = get_test_ethereum_node();
+    let config = EthereumBaseLayerConfig {
+        node_url: node_handle.0.endpoint(),
+        starknet_contract_address,
+        batch_requests: true,
+    };
+    let contract = EthereumBaseLayerContract::new(config).unwrap();
+
+    let first_sn_state_update = (BlockNumber(100), BlockHash(felt!("0x100")));
+    let second_sn_state_update = (BlockNumber(200), BlockHash(felt!("0x200")));
+    let third_sn_state_update = (BlockNumber(300), BlockHash(felt!("0x300")));
+
+    // The same Ethereum blocks that latest_proved_block uses with confirmations 25, 15 and 5.
+    let scenarios: Vec<(u64, Option<(BlockNumber, BlockHash)>)> = vec![
+        (6, Some(first_sn_state_update)),
+        (16, Some(second_sn_state_update)),
+        (26, Some(third_sn_state_update)),
+        (1000, None),
+    ];
+    for (l1_block, expected) in scenarios {
+        let proved_block = contract.proved_block_at(l1_block).await.unwrap();
+        assert_eq!(proved_block, expected);
+    }
+}
+
+#[tokio::test]
+async fn latest_proved_block_batches_calls() {
+    let block_number_mock = mock("POST", "/")
+        .match_body(Matcher::Regex("eth_blockNumber".to_owned()))
+        .with_body(json!({"jsonrpc": "2.0", "id": 1, "result": "0x1f"}).to_string())
+        .expect(1)
+        .create();
+    // The responses of a batch may come in any order.
+    let batch_mock = mock("POST", "/")
+        .match_body(Matcher::Regex(r#"^\[.*"eth_call".*"eth_call".*\]$"#.to_owned()))
+        .with_body(
+            json!([
+                {"jsonrpc": "2.0", "id": 1, "result": format!("0x{:064x}", 0x300)},
+                {"jsonrpc": "2.0", "id": 0, "result": format!("0x{:064x}", 300)},
+            ])
+            .to_string(),
+        )
+        .expect(1)
+        .create();
+
+    let config = EthereumBaseLayerConfig {
+        node_url: mockito::server_url(),
+        batch_requests: true,
+        ..Default::default()
+    };
+    let contract = EthereumBaseLayerContract::new(config).unwrap();
+    let latest_block = contract.latest_proved_block(Some(5)).await.unwrap();
+    assert_eq!(latest_block, Some((BlockNumber(300), BlockHash(felt!("0x300")))));
+    block_number_mock.assert();
+    batch_mock.assert();
+}
@@ -6,14 +6,15 @@ use ethers::abi::{Abi, AbiEncode};
 use ethers::contract::Contract;
 use ethers::prelude::{AbiError, Address, ContractError, Http, Middleware, Provider};
 use ethers::providers::ProviderError;
-use ethers::types::{I256, U256};
+use ethers::types::{Bytes, I256, U256, U64};
 use papyrus_config::dumping::{ser_param, ser_required_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializationType, SerializedParam};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::hash::StarkHash;
 use starknet_api::StarknetApiError;
-use url::ParseError;
+use url::{ParseError, Url};
 
 use crate::BaseLayerContract;
 
@@ -33,6 +34,10 @@ pub enum EthereumBaseLayerError {
     BadContract(#[from] ContractError<Provider<Http>>),
     #[error(transparent)]
     StarknetApi(#[from] StarknetApiError),
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("Bad response to a batch request: {0}.")]
+    BadBatchResponse(String),
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -40,6 +45,7 @@ pub struct EthereumBaseLayerConfig {
     // TODO(yair): consider using types.
     pub node_url: String,
     pub starknet_contract_address: String,
+    pub batch_requests: bool,
 }
 
 impl SerializeConfig for EthereumBaseLayerConfig {
@@ -57,6 +63,13 @@ impl SerializeConfig for EthereumBaseLayerConfig {
                 "Starknet contract address in ethereum.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "batch_requests",
+                &self.batch_requests,
+                "Whether to send the Ethereum calls of a single query in one JSON-RPC batch \
+                 request.",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
@@ -66,21 +79,111 @@ impl Default for EthereumBaseLayerConfig {
         Self {
             node_url: "https://mainnet.infura.io/v3/<your_api_key>".to_string(),
             starknet_contract_address: "0xc662c410C0ECf747543f5bA90660f6ABeBD9C8c4".to_string(),
+            batch_requests: true,
         }
     }
 }
 
 pub struct EthereumBaseLayerContract {
     contract: Contract<Provider<Http>>,
+    node_url: Url,
+    http_client: reqwest::Client,
+    batch_requests: bool,
 }
 
 impl EthereumBaseLayerContract {
     pub fn new(config: EthereumBaseLayerConfig) -> Result<Self, EthereumBaseLayerError> {
         let address = config.starknet_contract_address.parse::<Address>()?;
+        let node_url = Url::parse(&config.node_url)?;
         let client: Provider<Http> = Provider::<Http>::try_from(config.node_url)?;
         // The solidity contract was pre-compiled, and only the relevant functions were kept.
         let abi: Abi = serde_json::from_str::<Abi>(include_str!("core_contract_latest_block.abi"))?;
-        Ok(Self { contract: Contract::new(address, abi, Arc::new(client)) })
+        Ok(Self {
+            contract: Contract::new(address, abi, Arc::new(client)),
+            node_url,
+            http_client: reqwest::Client::new(),
+            batch_requests: config.batch_requests,
+        })
+    }
+
+    // Calls the given view functions at the given Ethereum block in a single JSON-RPC batch
+    // request, and returns their raw outputs in the same order.
+    async fn batch_call(
+        &self,
+        function_names: &[&str],
+        ethereum_block_number: u64,
+    ) -> Result<Vec<Bytes>, EthereumBaseLayerError> {
+        let requests = function_names
+            .iter()
+            .enumerate()
+            .map(|(id, function_name)| {
+                let data = self.contract.encode(function_name, ())?;
+                Ok(json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "method": "eth_call",
+                    "params": [
+                        {"to": self.contract.address(), "data": data},
+                        U64::from(ethereum_block_number),
+                    ],
+                }))
+            })
+            .collect::<Result<Vec<_>, AbiError>>()?;
+        let responses: Vec<serde_json::Value> = self
+            .http_client
+            .post(self.node_url.clone())
+            .json(&requests)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        let mut outputs = vec![None; function_names.len()];
+        for response in responses {
+            let output = response
+                .get("id")
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|id| outputs.get_mut(usize::try_from(id).ok()?))
+                .ok_or_else(|| EthereumBaseLayerError::BadBatchResponse(response.to_string()))?;
+            let result = response
+                .get("result")
+                .ok_or_else(|| EthereumBaseLayerError::BadBatchResponse(response.to_string()))?;
+            *output = Some(serde_json::from_value::<Bytes>(result.clone())?);
+        }
+        outputs
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| EthereumBaseLayerError::BadBatchResponse("Missing responses".to_owned()))
+    }
+
+    // Returns the latest proved block at the given Ethereum block, which must already exist.
+    async fn proved_block_at_unchecked(
+        &self,
+        ethereum_block_number: u64,
+    ) -> Result<(BlockNumber, BlockHash), EthereumBaseLayerError> {
+        let (state_block_number, state_block_hash) = if self.batch_requests {
+            let outputs = self
+                .batch_call(&["stateBlockNumber", "stateBlockHash"], ethereum_block_number)
+                .await?;
+            (
+                self.contract.decode_output::<I256, _>("stateBlockNumber", &outputs[0])?,
+                self.contract.decode_output::<U256, _>("stateBlockHash", &outputs[1])?,
+            )
+        } else {
+            let call_state_block_number = self
+                .contract
+                .method::<_, I256>("stateBlockNumber", ())?
+                .block(ethereum_block_number);
+            let call_state_block_hash =
+                self.contract.method::<_, U256>("stateBlockHash", ())?.block(ethereum_block_number);
+            tokio::try_join!(call_state_block_number.call(), call_state_block_hash.call())?
+        };
+
+        Ok((
+            BlockNumber(state_block_number.as_u64()),
+            // TODO: use safe conversion.
+            BlockHash(StarkHash::from_hex_unchecked(state_block_hash.encode_hex().as_str())),
+        ))
     }
 }
 
@@ -101,18 +204,16 @@ impl BaseLayerContract for EthereumBaseLayerContract {
         let Some(ethereum_block_number) = ethereum_block_number else {
             return Ok(None);
         };
+        self.proved_block_at_unchecked(ethereum_block_number.as_u64()).await.map(Some)
+    }
 
-        let call_state_block_number =
-            self.contract.method::<_, I256>("stateBlockNumber", ())?.block(ethereum_block_number);
-        let call_state_block_hash =
-            self.contract.method::<_, U256>("stateBlockHash", ())?.block(ethereum_block_number);
-        let (state_block_number, state_block_hash) =
-            tokio::try_join!(call_state_block_number.call(), call_state_block_hash.call())?;
-
-        Ok(Some((
-            BlockNumber(state_block_number.as_u64()),
-            // TODO: use safe conversion.
-            BlockHash(StarkHash::from_hex_unchecked(state_block_hash.encode_hex().as_str())),
-        )))
+    async fn proved_block_at(
+        &self,
+        l1_block: u64,
+    ) -> Result<Option<(BlockNumber, BlockHash)>, Self::Error> {
+        if l1_block > self.contract.client().get_block_number().await?.as_u64() {
+            return Ok(None);
+        }
+        self.proved_block_at_unchecked(l1_block).await.map(Some)
     }
 }
@@ -17,4 +17,11 @@ pub trait BaseLayerContract {
         &self,
         min_confirmations: Option<u64>,
     ) -> Result<Option<(BlockNumber, BlockHash)>, Self::Error>;
+
+    /// Get the Starknet block that was the latest proved block on the base layer at the given base
+    /// layer block. Returns None if the base layer doesn't have the given block yet.
+    async fn proved_block_at(
+        &self,
+        l1_block: u64,
+    ) -> Result<Option<(BlockNumber, BlockHash)>, Self::Error>;
 }
@@ -4,7 +4,7 @@ mod block_hash_test;
 
 use std::iter::zip;
 
-use starknet_api::block::{BlockBody, BlockHash, BlockHeader};
+use starknet_api::block::{BlockBody, BlockHash, BlockHeader, BlockNumber};
 use starknet_api::core::{
     ChainId,
     EventCommitment,
@@ -33,6 +33,25 @@ pub enum BlockHashError {
     MissingHeaderData,
     #[error(transparent)]
     StarknetApiError(#[from] StarknetApiError),
+    #[error(
+        "The hash of block {block_number} is {stored_block_hash}, but the hashes calculated from \
+         its header are {calculated_block_hashes:?} (one per block hash version, newest first)."
+    )]
+    BlockHashMismatch {
+        block_number: BlockNumber,
+        stored_block_hash: BlockHash,
+        calculated_block_hashes: Vec<BlockHash>,
+    },
+    #[error(
+        "The body of block {block_number} doesn't match the transaction and event commitments in \
+         its header."
+    )]
+    BodyCommitmentsMismatch { block_number: BlockNumber },
+    #[error(
+        "Block {block_number} is hashed with a block hash version that isn't supported (Starknet \
+         0.13.2 or later)."
+    )]
+    UnsupportedBlockHashVersion { block_number: BlockNumber },
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -57,6 +76,59 @@ pub fn validate_header(header: &BlockHeader, chain_id: &ChainId) -> Result<bool,
     Ok(false)
 }
 
+/// Calculates the hashes of a starknet header, one per block hash version that was ever used in
+/// Starknet (newest first). The block hash field of the given header is ignored.
+pub fn calculate_block_hashes(
+    header: &BlockHeader,
+    chain_id: &ChainId,
+) -> Result<Vec<BlockHash>, BlockHashError> {
+    [BlockHashVersion::V3, BlockHashVersion::V2, BlockHashVersion::V1, BlockHashVersion::V0]
+        .into_iter()
+        .map(|version| calculate_block_hash_by_version(header, version, chain_id))
+        .collect()
+}
+
+/// Recalculates the hash of a starknet block and compares it to the hash stored in its header.
+/// Returns the stored and the calculated hashes on a mismatch, or an error if the body doesn't
+/// match the commitments in the header. Blocks of Starknet 0.13.2 or later, whose block hash
+/// version isn't implemented, can't be verified.
+pub fn verify_block_hash(
+    header: &BlockHeader,
+    body: &BlockBody,
+    chain_id: &ChainId,
+) -> Result<(), BlockHashError> {
+    // Only blocks of Starknet 0.13.2 or later have a receipt commitment.
+    if header.receipt_commitment.is_some() {
+        return Err(BlockHashError::UnsupportedBlockHashVersion {
+            block_number: header.block_number,
+        });
+    }
+    let mut calculated_block_hashes = vec![];
+    for version in
+        [BlockHashVersion::V3, BlockHashVersion::V2, BlockHashVersion::V1, BlockHashVersion::V0]
+    {
+        let calculated_block_hash = calculate_block_hash_by_version(header, version, chain_id)?;
+        if calculated_block_hash == header.block_hash {
+            let transaction_commitment =
+                header.transaction_commitment.ok_or(BlockHashError::MissingHeaderData)?;
+            let event_commitment =
+                header.event_commitment.ok_or(BlockHashError::MissingHeaderData)?;
+            if !validate_body(body, &transaction_commitment, &event_commitment)? {
+                return Err(BlockHashError::BodyCommitmentsMismatch {
+                    block_number: header.block_number,
+                });
+            }
+            return Ok(());
+        }
+        calculated_block_hashes.push(calculated_block_hash);
+    }
+    Err(BlockHashError::BlockHashMismatch {
+        block_number: header.block_number,
+        stored_block_hash: header.block_hash,
+        calculated_block_hashes,
+    })
+}
+
 /// Validates the body of a starknet block.
 pub fn validate_body(
     body: &BlockBody,
@@ -1,12 +1,15 @@
 use assert_matches::assert_matches;
-use starknet_api::block::Block;
-use starknet_api::core::ChainId;
+use starknet_api::block::{Block, BlockHash};
+use starknet_api::core::{ChainId, ReceiptCommitment};
+use starknet_api::felt;
 use test_utils::read_json_file;
 
 use crate::block_hash::{
     calculate_block_hash_by_version,
+    calculate_block_hashes,
     calculate_event_commitment_by_version,
     calculate_transaction_commitment_by_version,
+    verify_block_hash,
     BlockHashError,
     BlockHashVersion,
 };
@@ -52,6 +55,21 @@ fn test_deprecated_block_hash_v0() {
     validate_block_hash_util("deprecated_block_hash_v0.json", BlockHashVersion::V0);
 }
 
+#[test]
+fn calculate_block_hashes_contains_the_block_hash() {
+    let chain_id = ChainId::Mainnet;
+    for file_name in [
+        "block_hash.json",
+        "deprecated_block_hash_v2.json",
+        "deprecated_block_hash_v1.json",
+        "deprecated_block_hash_v0.json",
+    ] {
+        let block: Block = serde_json::from_value(read_json_file(file_name)).unwrap();
+        let calculated_hashes = calculate_block_hashes(&block.header, &chain_id).unwrap();
+        assert!(calculated_hashes.contains(&block.header.block_hash));
+    }
+}
+
 #[test]
 fn test_missing_header_data() {
     let chain_id = ChainId::Mainnet;
@@ -62,3 +80,58 @@ fn test_missing_header_data() {
 
     assert_matches!(err, BlockHashError::MissingHeaderData);
 }
+
+#[test]
+fn verify_block_hash_matching() {
+    let block: Block = serde_json::from_value(read_json_file("block_hash.json")).unwrap();
+    verify_block_hash(&block.header, &block.body, &ChainId::Mainnet).unwrap();
+}
+
+#[test]
+fn verify_block_hash_mismatch() {
+    let chain_id = ChainId::Mainnet;
+    let mut block: Block = serde_json::from_value(read_json_file("block_hash.json")).unwrap();
+    let expected_calculated_hash =
+        calculate_block_hash_by_version(&block.header, BlockHashVersion::V3, &chain_id).unwrap();
+    let wrong_block_hash = BlockHash(felt!("0x1234"));
+    block.header.block_hash = wrong_block_hash;
+
+    let err = verify_block_hash(&block.header, &block.body, &chain_id).unwrap_err();
+    assert_matches!(
+        err,
+        BlockHashError::BlockHashMismatch {
+            block_number,
+            stored_block_hash,
+            calculated_block_hashes,
+        } if block_number == block.header.block_number
+            && stored_block_hash == wrong_block_hash
+            && calculated_block_hashes.len() == 4
+            && calculated_block_hashes[0] == expected_calculated_hash
+    );
+}
+
+#[test]
+fn verify_block_hash_body_mismatch() {
+    let mut block: Block = serde_json::from_value(read_json_file("block_hash.json")).unwrap();
+    block.body.transaction_hashes.reverse();
+
+    let err = verify_block_hash(&block.header, &block.body, &ChainId::Mainnet).unwrap_err();
+    assert_matches!(
+        err,
+        BlockHashError::BodyCommitmentsMismatch { block_number }
+        if block_number == block.header.block_number
+    );
+}
+
+#[test]
+fn verify_block_hash_unsupported_version() {
+    let mut block: Block = serde_json::from_value(read_json_file("block_hash.json")).unwrap();
+    block.header.receipt_commitment = Some(ReceiptCommitment(felt!("0x1")));
+
+    let err = verify_block_hash(&block.header, &block.body, &ChainId::Mainnet).unwrap_err();
+    assert_matches!(
+        err,
+        BlockHashError::UnsupportedBlockHashVersion { block_number }
+        if block_number == block.header.block_number
+    );
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+#[path = "central_health_test.rs"]
+mod central_health_test;
+
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// The number of most recent requests to central whose latencies are averaged.
+pub const LATENCY_WINDOW_SIZE: usize = 100;
+
+/// The health of the connection to the central source, as measured by the requests sent to it.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CentralHealth {
+    /// The time at which the last successful request finished, in seconds since the unix epoch.
+    pub last_success_unix_seconds: Option<u64>,
+    /// The average latency in milliseconds of the most recent requests, whether they succeeded or
+    /// not.
+    pub average_latency_millis: Option<u64>,
+}
+
+/// Records the requests sent to the central source.
+#[derive(Clone, Debug, Default)]
+pub struct CentralHealthTracker {
+    last_success: Option<SystemTime>,
+    recent_latencies: VecDeque<Duration>,
+}
+
+impl CentralHealthTracker {
+    /// Records a request that finished after `latency`.
+    pub fn record_request(&mut self, latency: Duration, succeeded: bool) {
+        if succeeded {
+            self.last_success = Some(SystemTime::now());
+        }
+        if self.recent_latencies.len() == LATENCY_WINDOW_SIZE {
+            self.recent_latencies.pop_front();
+        }
+        self.recent_latencies.push_back(latency);
+    }
+
+    /// Returns the health according to the recorded requests.
+    pub fn health(&self) -> CentralHealth {
+        let average_latency = u32::try_from(self.recent_latencies.len())
+            .ok()
+            .filter(|n_latencies| *n_latencies > 0)
+            .map(|n_latencies| self.recent_latencies.iter().sum::<Duration>() / n_latencies);
+        CentralHealth {
+            last_success_unix_seconds: self.last_success.map(|last_success| {
+                last_success.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+            }),
+            average_latency_millis: average_latency
+                .map(|latency| u64::try_from(latency.as_millis()).unwrap_or(u64::MAX)),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use pretty_assertions::assert_eq;
+
+use crate::central_health::{CentralHealth, CentralHealthTracker, LATENCY_WINDOW_SIZE};
+
+#[test]
+fn no_requests() {
+    assert_eq!(CentralHealthTracker::default().health(), CentralHealth::default());
+}
+
+#[test]
+fn last_success() {
+    let mut tracker = CentralHealthTracker::default();
+    tracker.record_request(Duration::from_millis(1), false);
+    assert_eq!(tracker.health().last_success_unix_seconds, None);
+
+    let time_before_success = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    tracker.record_request(Duration::from_millis(1), true);
+    let last_success = tracker.health().last_success_unix_seconds.unwrap();
+    assert!(last_success >= time_before_success);
+
+    // A failure doesn't change the time of the last success.
+    tracker.record_request(Duration::from_millis(1), false);
+    assert_eq!(tracker.health().last_success_unix_seconds, Some(last_success));
+}
+
+#[test]
+fn average_latency_of_recent_requests() {
+    let mut tracker = CentralHealthTracker::default();
+    tracker.record_request(Duration::from_millis(10), true);
+    tracker.record_request(Duration::from_millis(30), false);
+    assert_eq!(tracker.health().average_latency_millis, Some(20));
+
+    // Only the latencies of the last LATENCY_WINDOW_SIZE requests are averaged.
+    for _ in 0..LATENCY_WINDOW_SIZE {
+        tracker.record_request(Duration::from_millis(5), true);
+    }
+    assert_eq!(tracker.health().average_latency_millis, Some(5));
+}
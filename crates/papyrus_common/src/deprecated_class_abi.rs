@@ -1,3 +1,7 @@
+#[cfg(test)]
+#[path = "deprecated_class_abi_test.rs"]
+mod deprecated_class_abi_test;
+
 use std::io::{Result as IOResult, Write};
 
 use serde::Serialize;
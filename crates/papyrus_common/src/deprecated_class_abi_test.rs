@@ -0,0 +1,42 @@
+use serde_json::json;
+use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+
+use crate::deprecated_class_abi::calculate_deprecated_class_abi_length;
+
+#[test]
+fn abi_length_matches_python_json_dumps() {
+    let abi = json!([
+        {
+            "inputs": [{"name": "amount", "type": "felt"}],
+            "name": "increase_balance",
+            "outputs": [],
+            "type": "function"
+        },
+        {"inputs": [], "name": "get_balance", "outputs": [{"name": "res", "type": "felt"}],
+         "stateMutability": "view", "type": "function"}
+    ]);
+    let class = DeprecatedContractClass {
+        abi: Some(serde_json::from_value(abi).unwrap()),
+        ..Default::default()
+    };
+
+    // The ABI length is the length of the ABI as serialized by python's json.dumps(), which adds a
+    // space after every separator.
+    let python_json_dumps = concat!(
+        r#"[{"inputs": [{"name": "amount", "type": "felt"}], "name": "increase_balance", "#,
+        r#""outputs": [], "type": "function"}, {"inputs": [], "name": "get_balance", "#,
+        r#""outputs": [{"name": "res", "type": "felt"}], "stateMutability": "view", "#,
+        r#""type": "function"}]"#
+    );
+    assert_eq!(calculate_deprecated_class_abi_length(&class).unwrap(), python_json_dumps.len());
+
+    let compact = serde_json::to_string(class.abi.as_ref().unwrap()).unwrap();
+    let separators = compact.matches([',', ':']).count();
+    assert_eq!(python_json_dumps.len(), compact.len() + separators);
+}
+
+#[test]
+fn abi_length_of_class_without_abi() {
+    let class = DeprecatedContractClass { abi: None, ..Default::default() };
+    assert_eq!(calculate_deprecated_class_abi_length(&class).unwrap(), 0);
+}
@@ -3,6 +3,7 @@ use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_types_core::felt::Felt;
 
 pub mod block_hash;
+pub mod central_health;
 pub mod class_hash;
 pub mod deprecated_class_abi;
 pub mod metrics;
@@ -20,6 +20,22 @@ pub const PAPYRUS_COMPILED_CLASS_MARKER: &str = "papyrus_compiled_class_marker";
 /// finality.
 pub const PAPYRUS_BASE_LAYER_MARKER: &str = "papyrus_base_layer_marker";
 
+/// The number of blocks the node has a header for but not a state diff (header marker minus state
+/// marker).
+pub const PAPYRUS_STATE_LAG: &str = "papyrus_state_lag";
+
+/// The number of blocks the node has a state diff for but not all of the corresponding compiled
+/// classes (state marker minus compiled class marker).
+pub const PAPYRUS_CASM_LAG: &str = "papyrus_casm_lag";
+
+/// The p2p sync header marker is the first block number for which the p2p sync client has not
+/// received a header.
+pub const PAPYRUS_P2P_SYNC_HEADER_MARKER: &str = "papyrus_p2p_sync_header_marker";
+
+/// The p2p sync state diff marker is the first block number for which the p2p sync client has not
+/// received a state diff.
+pub const PAPYRUS_P2P_SYNC_STATE_DIFF_MARKER: &str = "papyrus_p2p_sync_state_diff_marker";
+
 /// The latency, in seconds, between a block timestamp (as state in its header) and the time the
 /// node stores the header.
 pub const PAPYRUS_HEADER_LATENCY_SEC: &str = "papyrus_header_latency";
@@ -33,6 +49,12 @@ pub const PAPYRUS_NUM_ACTIVE_INBOUND_SESSIONS: &str = "papyrus_num_active_inboun
 /// The number of active sessions this peer has in which it requests data.
 pub const PAPYRUS_NUM_ACTIVE_OUTBOUND_SESSIONS: &str = "papyrus_num_active_outbound_sessions";
 
+/// The number of classes executions found in the class cache.
+pub const PAPYRUS_EXECUTION_CLASS_CACHE_HITS: &str = "papyrus_execution_class_cache_hits";
+
+/// The number of classes executions read from the storage since they weren't in the class cache.
+pub const PAPYRUS_EXECUTION_CLASS_CACHE_MISSES: &str = "papyrus_execution_class_cache_misses";
+
 // TODO: consider making this value non static and add a way to change this while the app is
 // running. e.g via a monitoring endpoint.
 /// Global variable set by the main config to enable collecting profiling metrics.
@@ -40,3 +62,6 @@ pub static COLLECT_PROFILING_METRICS: OnceLock<bool> = OnceLock::new();
 
 /// The height most recently decided by consensus.
 pub const PAPYRUS_CONSENSUS_HEIGHT: &str = "papyrus_consensus_height";
+
+/// The number of rounds consensus has started for the current height.
+pub const PAPYRUS_CONSENSUS_ROUNDS: &str = "papyrus_consensus_rounds";
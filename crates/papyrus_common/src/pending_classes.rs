@@ -1,7 +1,11 @@
-use std::collections::HashMap;
+#[cfg(test)]
+#[path = "pending_classes_test.rs"]
+mod pending_classes_test;
+
 use std::sync::Arc;
 
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use indexmap::IndexMap;
 use starknet_api::core::ClassHash;
 use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use starknet_api::state::ContractClass;
@@ -22,12 +26,32 @@ pub trait PendingClassesTrait {
     fn clear(&mut self);
 }
 
-#[derive(Debug, Default, Eq, PartialEq, Clone)]
+/// The default maximum number of classes (and of compiled classes) in [`PendingClasses`].
+pub const DEFAULT_PENDING_CLASSES_MAX_SIZE: usize = 100;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub struct PendingClasses {
     // Putting the contracts inside Arc so we won't have to clone them when we clone the entire
     // PendingClasses struct.
-    pub classes: HashMap<ClassHash, Arc<ApiContractClass>>,
-    pub compiled_classes: HashMap<ClassHash, Arc<CasmContractClass>>,
+    // The maps are ordered from the least recently added class to the most recently added one.
+    // They aren't public so that every insertion goes through the eviction that bounds their size.
+    classes: IndexMap<ClassHash, Arc<ApiContractClass>>,
+    compiled_classes: IndexMap<ClassHash, Arc<CasmContractClass>>,
+    // Above this amount of classes (or compiled classes), the least recently added ones are
+    // evicted.
+    max_size: usize,
+}
+
+impl PendingClasses {
+    pub fn new(max_size: usize) -> Self {
+        Self { classes: IndexMap::new(), compiled_classes: IndexMap::new(), max_size }
+    }
+}
+
+impl Default for PendingClasses {
+    fn default() -> Self {
+        Self::new(DEFAULT_PENDING_CLASSES_MAX_SIZE)
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -58,7 +82,7 @@ impl PendingClassesTrait for PendingClasses {
     }
 
     fn add_class(&mut self, class_hash: ClassHash, class: ApiContractClass) {
-        self.classes.insert(class_hash, Arc::new(class));
+        insert_and_evict(&mut self.classes, class_hash, Arc::new(class), self.max_size);
     }
 
     fn get_compiled_class(&self, class_hash: ClassHash) -> Option<CasmContractClass> {
@@ -66,7 +90,12 @@ impl PendingClassesTrait for PendingClasses {
     }
 
     fn add_compiled_class(&mut self, class_hash: ClassHash, compiled_class: CasmContractClass) {
-        self.compiled_classes.insert(class_hash, Arc::new(compiled_class));
+        insert_and_evict(
+            &mut self.compiled_classes,
+            class_hash,
+            Arc::new(compiled_class),
+            self.max_size,
+        );
     }
 
     fn clear(&mut self) {
@@ -74,3 +103,18 @@ impl PendingClassesTrait for PendingClasses {
         self.compiled_classes.clear();
     }
 }
+
+// Inserts the value as the most recently added one, and evicts the least recently added values
+// while the map is above the max size.
+fn insert_and_evict<V>(
+    map: &mut IndexMap<ClassHash, V>,
+    class_hash: ClassHash,
+    value: V,
+    max_size: usize,
+) {
+    map.shift_remove(&class_hash);
+    map.insert(class_hash, value);
+    while map.len() > max_size {
+        map.shift_remove_index(0);
+    }
+}
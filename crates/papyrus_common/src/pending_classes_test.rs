@@ -0,0 +1,24 @@
+use starknet_api::core::ClassHash;
+use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+use starknet_api::felt;
+
+use crate::pending_classes::{ApiContractClass, PendingClasses, PendingClassesTrait};
+
+#[test]
+fn add_class_evicts_the_least_recently_added_class() {
+    const MAX_SIZE: usize = 2;
+    let mut pending_classes = PendingClasses::new(MAX_SIZE);
+    let class = ApiContractClass::DeprecatedContractClass(DeprecatedContractClass::default());
+    let class_hashes = [ClassHash(felt!(1_u8)), ClassHash(felt!(2_u8)), ClassHash(felt!(3_u8))];
+
+    pending_classes.add_class(class_hashes[0], class.clone());
+    pending_classes.add_class(class_hashes[1], class.clone());
+    // Re-adding a class makes it the most recently added one.
+    pending_classes.add_class(class_hashes[0], class.clone());
+    pending_classes.add_class(class_hashes[2], class.clone());
+
+    assert_eq!(pending_classes.classes.len(), MAX_SIZE);
+    assert!(pending_classes.get_class(class_hashes[1]).is_none());
+    assert_eq!(pending_classes.get_class(class_hashes[0]), Some(class.clone()));
+    assert_eq!(pending_classes.get_class(class_hashes[2]), Some(class));
+}
@@ -37,6 +37,28 @@ impl From<StateDiffVersion> for Felt {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum StateDiffCommitmentError {
+    #[error(
+        "State diff commitment mismatch. Calculated commitment: {calculated:?}, expected \
+         commitment: {expected:?}."
+    )]
+    Mismatch { calculated: StateDiffCommitment, expected: StateDiffCommitment },
+}
+
+/// Verifies that the commitment of the given state diff equals the expected commitment.
+/// On a mismatch, returns both the calculated and the expected commitments.
+pub fn verify_state_diff_commitment(
+    state_diff: &ThinStateDiff,
+    expected: StateDiffCommitment,
+) -> Result<(), StateDiffCommitmentError> {
+    let calculated = calculate_state_diff_commitment(state_diff, StateDiffVersion::V0);
+    if calculated != expected {
+        return Err(StateDiffCommitmentError::Mismatch { calculated, expected });
+    }
+    Ok(())
+}
+
 /// Calculates the state diff commitment.
 /// The computation is described here: <https://community.starknet.io/t/introducing-p2p-authentication-and-mismatch-resolution-in-v0-12-2/97993>.
 pub fn calculate_state_diff_commitment(
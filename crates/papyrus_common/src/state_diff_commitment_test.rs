@@ -1,3 +1,4 @@
+use assert_matches::assert_matches;
 use pretty_assertions::assert_eq;
 use starknet_api::core::{
     ClassHash,
@@ -11,7 +12,12 @@ use starknet_api::hash::PoseidonHash;
 use starknet_api::state::{StateDiff, StorageKey, ThinStateDiff};
 use starknet_api::{class_hash, contract_address, felt, patricia_key};
 
-use crate::state_diff_commitment::{calculate_state_diff_commitment, StateDiffVersion};
+use crate::state_diff_commitment::{
+    calculate_state_diff_commitment,
+    verify_state_diff_commitment,
+    StateDiffCommitmentError,
+    StateDiffVersion,
+};
 
 #[test]
 fn state_diff_commitment() {
@@ -83,3 +89,30 @@ fn deployed_and_replaced_contracts_are_sorted_for_hashing() {
         "0x5264ab018246d1ab06704c6016285e90962e843561ebb82c4325d7254b1724b"
     );
 }
+
+#[test]
+fn verify_tampered_storage_diff() {
+    let contract_address = contract_address!("0x1");
+    let storage_key = StorageKey(patricia_key!("0x1"));
+    let state_diff = ThinStateDiff {
+        storage_diffs: [(contract_address, [(storage_key, felt!("0x999"))].into())].into(),
+        ..Default::default()
+    };
+    let expected = calculate_state_diff_commitment(&state_diff, StateDiffVersion::V0);
+    verify_state_diff_commitment(&state_diff, expected.clone()).unwrap();
+
+    let mut tampered_state_diff = state_diff;
+    tampered_state_diff.storage_diffs[&contract_address][&storage_key] = felt!("0x998");
+    let tampered_commitment =
+        calculate_state_diff_commitment(&tampered_state_diff, StateDiffVersion::V0);
+
+    let err = verify_state_diff_commitment(&tampered_state_diff, expected.clone()).unwrap_err();
+    let message = err.to_string();
+    assert_matches!(
+        err,
+        StateDiffCommitmentError::Mismatch { calculated, expected: err_expected }
+        if calculated == tampered_commitment && err_expected == expected
+    );
+    assert!(message.contains(&format!("{tampered_commitment:?}")));
+    assert!(message.contains(&format!("{expected:?}")));
+}
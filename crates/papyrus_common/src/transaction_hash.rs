@@ -213,7 +213,14 @@ impl HashChain {
     }
 }
 
+// The maximal length of an ASCII string that fits in a felt (a Cairo short string).
+const MAX_SHORT_STRING_LENGTH: usize = 31;
+
 pub(crate) fn ascii_as_felt(ascii_str: &str) -> Result<Felt, StarknetApiError> {
+    // Longer strings would be silently reduced modulo the field prime by Felt::from_hex.
+    if ascii_str.len() > MAX_SHORT_STRING_LENGTH {
+        return Err(StarknetApiError::OutOfRange { string: ascii_str.to_string() });
+    }
     Felt::from_hex(hex::encode(ascii_str).as_str())
         .map_err(|_| StarknetApiError::OutOfRange { string: ascii_str.to_string() })
 }
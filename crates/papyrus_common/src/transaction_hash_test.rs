@@ -1,9 +1,11 @@
+use assert_matches::assert_matches;
 use pretty_assertions::assert_eq;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use starknet_api::block::BlockNumber;
 use starknet_api::core::ChainId;
 use starknet_api::transaction::{Transaction, TransactionHash};
+use starknet_api::StarknetApiError;
 use starknet_types_core::felt::Felt;
 use test_utils::read_json_file;
 
@@ -24,6 +26,18 @@ fn test_ascii_as_felt() {
     assert_eq!(sn_main_felt, expected_sn_main);
 }
 
+#[test]
+fn test_ascii_as_felt_out_of_range() {
+    let max_length = "A".repeat(31);
+    assert!(ascii_as_felt(&max_length).is_ok());
+
+    // 32 ASCII characters are 256 bits, which don't fit in a felt.
+    let too_long = "A".repeat(32);
+    let err = ascii_as_felt(&too_long).unwrap_err();
+    assert_matches!(&err, StarknetApiError::OutOfRange { string } if *string == too_long);
+    assert!(err.to_string().contains(&too_long));
+}
+
 #[test]
 fn test_constructor_selector() {
     let mut keccak = Keccak256::default();
@@ -16,7 +16,7 @@ pub(crate) fn get_command_matches(
 }
 
 // Takes matched arguments from the command line interface and env variables and updates the config
-// map.
+// map. Arguments that the command defines in addition to the config params are skipped.
 // Supports usize, bool and String.
 pub(crate) fn update_config_map_by_command_args(
     config_map: &mut BTreeMap<ParamPath, Value>,
@@ -25,6 +25,9 @@ pub(crate) fn update_config_map_by_command_args(
 ) -> Result<(), ConfigError> {
     for param_path_id in arg_match.ids() {
         let param_path = param_path_id.as_str();
+        if !types_map.contains_key(param_path) {
+            continue;
+        }
         let new_value = get_arg_by_type(types_map, arg_match, param_path)?;
         update_config_map(config_map, types_map, param_path, new_value)?;
     }
@@ -45,13 +48,15 @@ fn build_args_parser(config_map: &BTreeMap<ParamPath, SerializedParam>) -> Vec<A
     ];
 
     for (param_path, serialized_param) in config_map.iter() {
-        let Some(serialization_type) = serialized_param.content.get_serialization_type() else {
+        let Some(serialization_type) = serialized_param.get_serialization_type() else {
             continue; // Pointer target
         };
         let clap_parser = match serialization_type {
             SerializationType::Number => clap::value_parser!(usize).into(),
             SerializationType::Boolean => clap::value_parser!(bool),
-            SerializationType::String => clap::value_parser!(String),
+            SerializationType::String | SerializationType::ByteSize => {
+                clap::value_parser!(String)
+            }
         };
 
         let arg = Arg::new(param_path)
@@ -74,7 +79,9 @@ fn get_arg_by_type(
     match serialization_type {
         SerializationType::Number => Ok(json!(arg_match.try_get_one::<usize>(param_path)?)),
         SerializationType::Boolean => Ok(json!(arg_match.try_get_one::<bool>(param_path)?)),
-        SerializationType::String => Ok(json!(arg_match.try_get_one::<String>(param_path)?)),
+        SerializationType::String | SerializationType::ByteSize => {
+            Ok(json!(arg_match.try_get_one::<String>(param_path)?))
+        }
     }
 }
 
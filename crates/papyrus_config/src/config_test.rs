@@ -5,7 +5,7 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use assert_matches::assert_matches;
-use clap::Command;
+use clap::{Arg, ArgAction, Command};
 use itertools::chain;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
@@ -15,10 +15,18 @@ use test_utils::get_absolute_path;
 use validator::Validate;
 
 use crate::command::{get_command_matches, update_config_map_by_command_args};
-use crate::converters::deserialize_milliseconds_to_duration;
+use crate::converters::{
+    deserialize_human_bytes,
+    deserialize_milliseconds_to_duration,
+    format_human_bytes,
+    parse_human_bytes,
+    serialize_duration_as_milliseconds,
+    serialize_human_bytes,
+};
 use crate::dumping::{
     append_sub_config_name,
     combine_config_map_and_pointers,
+    ser_byte_size_param,
     ser_generated_param,
     ser_optional_param,
     ser_optional_sub_config,
@@ -30,12 +38,20 @@ use crate::dumping::{
 use crate::loading::{
     load,
     load_and_process_config,
+    load_and_process_config_with_matches,
     split_pointers_map,
     split_values_and_types,
+    update_config_map,
     update_config_map_by_pointers,
     update_optional_values,
 };
-use crate::presentation::get_config_presentation;
+use crate::presentation::{
+    diff_against_defaults,
+    get_config_presentation,
+    get_grouped_config_presentation,
+    REDACTED_VALUE,
+};
+use crate::validators::{validate_non_empty_ascii, validate_socket_addr};
 use crate::{
     ConfigError,
     ParamPath,
@@ -113,7 +129,121 @@ fn test_validation() {
     assert!(outer_config.validate().is_err());
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct MillisecondsDurationConfig {
+    #[serde(
+        serialize_with = "serialize_duration_as_milliseconds",
+        deserialize_with = "deserialize_milliseconds_to_duration"
+    )]
+    dur: Duration,
+}
+
+#[test]
+fn milliseconds_duration_serde_round_trip() {
+    let config = MillisecondsDurationConfig { dur: Duration::from_millis(1500) };
+    let serialized = serde_json::to_value(&config).unwrap();
+    assert_eq!(serialized, json!({"dur": 1500}));
+    let deserialized: MillisecondsDurationConfig = serde_json::from_value(serialized).unwrap();
+    assert_eq!(deserialized, config);
+}
+
+#[test]
+fn human_bytes_parsing() {
+    assert_eq!(parse_human_bytes("32GB"), Ok(32_000_000_000));
+    assert_eq!(parse_human_bytes("64MB"), Ok(64_000_000));
+    assert_eq!(parse_human_bytes("1GiB"), Ok(1 << 30));
+    assert_eq!(parse_human_bytes("32GiB"), Ok(1 << 35));
+    assert_eq!(parse_human_bytes(" 4 kib "), Ok(4096));
+    assert_eq!(parse_human_bytes("7B"), Ok(7));
+    assert_eq!(parse_human_bytes("1024"), Ok(1024));
+
+    for malformed in ["", "GB", "1.5GB", "-1GB", "12XB", "1GB2", "100000000TiB"] {
+        assert!(parse_human_bytes(malformed).is_err(), "{malformed} should be rejected.");
+    }
+
+    assert_eq!(format_human_bytes(1 << 35), "32GiB");
+    assert_eq!(format_human_bytes(3 << 10), "3KiB");
+    assert_eq!(format_human_bytes(1000), "1000B");
+    assert_eq!(format_human_bytes(0), "0B");
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct BytesConfig {
+    #[serde(
+        serialize_with = "serialize_human_bytes",
+        deserialize_with = "deserialize_human_bytes"
+    )]
+    size: usize,
+}
+
+#[test]
+fn human_bytes_serde() {
+    let config: BytesConfig = serde_json::from_value(json!({"size": "32GiB"})).unwrap();
+    assert_eq!(config, BytesConfig { size: 1 << 35 });
+    assert_eq!(serde_json::to_value(&config).unwrap(), json!({"size": "32GiB"}));
+
+    // Raw integers are still accepted.
+    let config: BytesConfig = serde_json::from_value(json!({"size": 1_u64 << 35})).unwrap();
+    assert_eq!(config, BytesConfig { size: 1 << 35 });
+
+    assert!(serde_json::from_value::<BytesConfig>(json!({"size": "32XB"})).is_err());
+}
+
+impl SerializeConfig for BytesConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from([ser_byte_size_param(
+            "size",
+            &format_human_bytes(self.size as u64),
+            "This is a size in bytes.",
+            ParamPrivacyInput::Public,
+        )])
+    }
+}
+
+#[test]
+fn number_accepted_for_byte_size_param() {
+    let (mut config_map, types_map) = split_values_and_types(BytesConfig { size: 1 << 30 }.dump());
+    update_config_map(&mut config_map, &types_map, "size", json!(1234)).unwrap();
+    assert_eq!(config_map["size"], json!("1234B"));
+    // The converted value is still recognized as a byte size.
+    update_config_map(&mut config_map, &types_map, "size", json!(2048)).unwrap();
+    assert_eq!(config_map["size"], json!("2KiB"));
+    // The byte size type survives dumping the config to a file and loading it.
+    let serialized = serde_json::to_string(&BytesConfig { size: 1 << 30 }.dump()).unwrap();
+    let (_, types_map) = split_values_and_types(serde_json::from_str(&serialized).unwrap());
+    assert_eq!(types_map["size"], SerializationType::ByteSize);
+
+    // A string param isn't a byte size even if its value looks like one.
+    let dumped_config =
+        TypicalConfig { a: Duration::from_secs(1), b: "1GiB".to_owned(), c: false }.dump();
+    let (mut config_map, types_map) = split_values_and_types(dumped_config);
+    assert_matches!(
+        update_config_map(&mut config_map, &types_map, "b", json!(1234)),
+        Err(ConfigError::ChangeRequiredParamType { .. })
+    );
+    assert_matches!(
+        update_config_map(&mut config_map, &types_map, "a", json!("1234")),
+        Err(ConfigError::ChangeRequiredParamType { .. })
+    );
+}
+
+#[test]
+fn test_non_empty_ascii_validation() {
+    assert!(validate_non_empty_ascii(&"SN_MAIN").is_ok());
+    assert!(validate_non_empty_ascii(&"").is_err());
+    assert!(validate_non_empty_ascii(&"SN_MAIN\u{e9}").is_err());
+}
+
+#[test]
+fn test_socket_addr_validation() {
+    assert!(validate_socket_addr("0.0.0.0:8080").is_ok());
+    assert!(validate_socket_addr("[::1]:8080").is_ok());
+    assert!(validate_socket_addr("0.0.0.0").is_err());
+    assert!(validate_socket_addr("localhost:8080").is_err());
+    assert!(validate_socket_addr("").is_err());
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, Debug, PartialEq)]
 struct TypicalConfig {
     #[serde(deserialize_with = "deserialize_milliseconds_to_duration")]
     a: Duration,
@@ -225,6 +355,50 @@ fn test_nested_config_presentation() {
     }
 }
 
+#[test]
+fn grouped_config_presentation() {
+    let config =
+        OuterConfig { opt_elem: Some(1), opt_config: None, inner_config: InnerConfig { o: 3 } };
+    let presentation = get_grouped_config_presentation(&config, true).unwrap();
+    assert_eq!(presentation["inner_config"]["o"]["description"], json!("This is o."));
+    assert_eq!(presentation["inner_config"]["o"]["value"], json!(3));
+    assert_eq!(presentation[""]["opt_elem"]["description"], json!("This is elem."));
+
+    let typical_config = TypicalConfig { a: Duration::from_secs(1), b: "bbb".to_owned(), c: false };
+    let dumped_typical_config = append_sub_config_name(typical_config.dump(), "typical");
+    struct WrapperConfig(BTreeMap<ParamPath, SerializedParam>);
+    impl SerializeConfig for WrapperConfig {
+        fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+            self.0.clone()
+        }
+    }
+    let config = WrapperConfig(dumped_typical_config);
+
+    let presentation = get_grouped_config_presentation(&config, true).unwrap();
+    let keys: Vec<_> = presentation["typical"].as_object().unwrap().keys().collect();
+    assert_eq!(keys, vec!["a", "b", "c"]);
+    let public_presentation = get_grouped_config_presentation(&config, false).unwrap();
+    let keys: Vec<_> = public_presentation["typical"].as_object().unwrap().keys().collect();
+    assert_eq!(keys, vec!["a", "b"]);
+}
+
+#[test]
+fn config_diff_against_defaults() {
+    let mut config = TypicalConfig { a: Duration::from_millis(5), b: "bbb".to_owned(), c: false };
+    assert_eq!(
+        diff_against_defaults(&config),
+        BTreeMap::from([
+            ("a".to_owned(), (json!(5), json!(0))),
+            ("b".to_owned(), (json!("bbb"), json!(""))),
+        ])
+    );
+
+    // Private params are redacted.
+    config.c = true;
+    assert_eq!(diff_against_defaults(&config)["c"], (json!(REDACTED_VALUE), json!(REDACTED_VALUE)));
+    assert!(diff_against_defaults(&TypicalConfig::default()).is_empty());
+}
+
 #[test]
 fn test_pointers_flow() {
     let config_map = BTreeMap::from([
@@ -242,6 +416,7 @@ fn test_pointers_flow() {
             description: "This is a.".to_owned(),
             content: SerializedContent::PointerTarget("common_a".to_owned()),
             privacy: ParamPrivacy::Public,
+            value_type: None,
         })
     );
     assert_eq!(
@@ -250,6 +425,7 @@ fn test_pointers_flow() {
             description: "This is a.".to_owned(),
             content: SerializedContent::PointerTarget("common_a".to_owned()),
             privacy: ParamPrivacy::Private,
+            value_type: None,
         })
     );
     assert_eq!(
@@ -258,6 +434,7 @@ fn test_pointers_flow() {
             description: "This is common a".to_owned(),
             content: SerializedContent::DefaultValue(json!(10)),
             privacy: ParamPrivacy::TemporaryValue,
+            value_type: None,
         })
     );
 
@@ -357,6 +534,27 @@ fn test_load_custom_config_file_and_args() {
     assert_eq!(param_path, "command value");
 }
 
+#[test]
+fn test_load_config_with_command_arg() {
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("config.json");
+    CustomConfig { param_path: "default value".to_owned(), seed: 5 }
+        .dump_to_file(&vec![], file_path.to_str().unwrap())
+        .unwrap();
+    let command =
+        Command::new("Program").arg(Arg::new("flag").long("flag").action(ArgAction::SetTrue));
+    let args = vec!["Testing", "--flag", "--param_path", "command value"];
+
+    let (config, arg_matches) = load_and_process_config_with_matches::<CustomConfig>(
+        File::open(file_path).unwrap(),
+        command,
+        args.into_iter().map(|s| s.to_owned()).collect(),
+    )
+    .unwrap();
+    assert_eq!(config.param_path, "command value");
+    assert!(arg_matches.get_flag("flag"));
+}
+
 #[test]
 fn test_load_many_custom_config_files() {
     let custom_config_path = CUSTOM_CONFIG_PATH.to_str().unwrap();
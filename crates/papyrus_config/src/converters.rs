@@ -28,7 +28,7 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use serde::de::Error;
-use serde::{Deserialize, Deserializer};
+use serde::{ser, Deserialize, Deserializer, Serializer};
 
 /// Deserializes milliseconds to duration object.
 pub fn deserialize_milliseconds_to_duration<'de, D>(de: D) -> Result<Duration, D::Error>
@@ -39,6 +39,16 @@ where
     Ok(Duration::from_millis(millis))
 }
 
+/// Serializes a duration object to milliseconds, the inverse of
+/// [`deserialize_milliseconds_to_duration`].
+pub fn serialize_duration_as_milliseconds<S>(duration: &Duration, ser: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let millis: u64 = duration.as_millis().try_into().map_err(<S::Error as ser::Error>::custom)?;
+    ser.serialize_u64(millis)
+}
+
 /// Deserializes seconds to duration object.
 pub fn deserialize_seconds_to_duration<'de, D>(de: D) -> Result<Duration, D::Error>
 where
@@ -48,6 +58,77 @@ where
     Ok(Duration::from_secs(secs))
 }
 
+// The units of the human readable byte sizes, each ordered from the largest to the smallest.
+const BINARY_BYTE_UNITS: [(&str, u64); 4] =
+    [("TiB", 1 << 40), ("GiB", 1 << 30), ("MiB", 1 << 20), ("KiB", 1 << 10)];
+const DECIMAL_BYTE_UNITS: [(&str, u64); 5] =
+    [("TB", 1_000_000_000_000), ("GB", 1_000_000_000), ("MB", 1_000_000), ("KB", 1_000), ("B", 1)];
+
+/// Parses a byte size such as "32GB", "64MB", "1GiB" or "1024". Decimal units (KB, MB, GB, TB)
+/// are powers of 1000 and binary units (KiB, MiB, GiB, TiB) are powers of 1024. Units are case
+/// insensitive.
+pub fn parse_human_bytes(raw_str: &str) -> Result<u64, String> {
+    let raw_str = raw_str.trim();
+    let digits_end = raw_str.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw_str.len());
+    let (number, unit) = raw_str.split_at(digits_end);
+    let number: u64 =
+        number.parse().map_err(|_| format!("\"{raw_str}\" doesn't start with a number."))?;
+    let unit = unit.trim();
+    let multiplier = if unit.is_empty() {
+        1
+    } else {
+        BINARY_BYTE_UNITS
+            .iter()
+            .chain(DECIMAL_BYTE_UNITS.iter())
+            .find_map(|(name, multiplier)| name.eq_ignore_ascii_case(unit).then_some(*multiplier))
+            .ok_or_else(|| format!("\"{raw_str}\" has an unknown unit."))?
+    };
+    number.checked_mul(multiplier).ok_or_else(|| format!("\"{raw_str}\" is too large."))
+}
+
+/// Formats a byte size with the largest binary unit that divides it, e.g. 1 << 35 as "32GiB".
+pub fn format_human_bytes(bytes: u64) -> String {
+    BINARY_BYTE_UNITS
+        .iter()
+        .find(|(_, multiplier)| bytes != 0 && bytes.is_multiple_of(*multiplier))
+        .map(|(name, multiplier)| format!("{}{name}", bytes / multiplier))
+        .unwrap_or_else(|| format!("{bytes}B"))
+}
+
+/// Deserializes a byte size from either a number of bytes or a string parsed by
+/// [`parse_human_bytes`].
+pub fn deserialize_human_bytes<'de, D, T>(de: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<u64>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawBytes {
+        Number(u64),
+        String(String),
+    }
+
+    let bytes = match RawBytes::deserialize(de)? {
+        RawBytes::Number(bytes) => bytes,
+        RawBytes::String(raw_str) => parse_human_bytes(&raw_str).map_err(D::Error::custom)?,
+    };
+    T::try_from(bytes).map_err(|_| D::Error::custom(format!("{bytes} bytes is out of range.")))
+}
+
+/// Serializes a byte size to a string formatted by [`format_human_bytes`], the inverse of
+/// [`deserialize_human_bytes`].
+pub fn serialize_human_bytes<S, T>(bytes: &T, ser: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Copy + TryInto<u64>,
+{
+    let bytes: u64 = (*bytes)
+        .try_into()
+        .map_err(|_| <S::Error as ser::Error>::custom("Byte size should be non-negative."))?;
+    ser.serialize_str(&format_human_bytes(bytes))
+}
+
 /// Serializes a map to "k1:v1 k2:v2" string structure.
 pub fn serialize_optional_map(optional_map: &Option<HashMap<String, String>>) -> String {
     match optional_map {
@@ -131,7 +131,10 @@ fn common_ser_param(
     description: &str,
     privacy: ParamPrivacy,
 ) -> (String, SerializedParam) {
-    (name.to_owned(), SerializedParam { description: description.to_owned(), content, privacy })
+    (
+        name.to_owned(),
+        SerializedParam { description: description.to_owned(), content, privacy, value_type: None },
+    )
 }
 
 /// Serializes a single param of a config.
@@ -150,6 +153,19 @@ pub fn ser_param<T: Serialize>(
     )
 }
 
+/// Serializes a single byte size param of a config, formatted such as "1GiB". When loading the
+/// config, the param also accepts a number of bytes.
+/// The returned pair is designed to be an input to a dumped config map.
+pub fn ser_byte_size_param(
+    name: &str,
+    value: &str,
+    description: &str,
+    privacy: ParamPrivacyInput,
+) -> (String, SerializedParam) {
+    let (name, serialized_param) = ser_param(name, &value, description, privacy);
+    (name, SerializedParam { value_type: Some(SerializationType::ByteSize), ..serialized_param })
+}
+
 /// Serializes expected type for a single required param of a config.
 /// The returned pair is designed to be an input to a dumped config map.
 pub fn ser_required_param(
@@ -285,6 +301,7 @@ pub(crate) fn combine_config_map_and_pointers(
                     description: pointing_serialized_param.description.clone(),
                     content: SerializedContent::PointerTarget(target_param.to_owned()),
                     privacy: pointing_serialized_param.privacy.clone(),
+                    value_type: None,
                 },
             );
         }
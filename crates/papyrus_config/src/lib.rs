@@ -136,6 +136,20 @@ pub struct SerializedParam {
     #[serde(flatten)]
     pub content: SerializedContent,
     pub(crate) privacy: ParamPrivacy,
+    /// The type of the param when it differs from the JSON type of its default value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) value_type: Option<SerializationType>,
+}
+
+impl SerializedParam {
+    fn get_serialization_type(&self) -> Option<SerializationType> {
+        match &self.content {
+            SerializedContent::DefaultValue(_) if self.value_type.is_some() => {
+                self.value_type.clone()
+            }
+            content => content.get_serialization_type(),
+        }
+    }
 }
 
 /// A serialized type of a configuration parameter.
@@ -145,6 +159,8 @@ pub enum SerializationType {
     Number,
     Boolean,
     String,
+    /// A byte size, given as a string such as "1GiB" or as a number of bytes.
+    ByteSize,
 }
 
 /// Errors at the configuration dumping and loading process.
@@ -10,12 +10,13 @@ use std::ops::IndexMut;
 use std::path::PathBuf;
 
 use clap::parser::Values;
-use clap::Command;
+use clap::{ArgMatches, Command};
 use command::{get_command_matches, update_config_map_by_command_args};
 use itertools::any;
 use serde::Deserialize;
 use serde_json::{json, Map, Value};
 
+use crate::converters::format_human_bytes;
 use crate::validators::validate_path_exists;
 use crate::{
     command,
@@ -51,6 +52,16 @@ pub fn load_and_process_config<T: for<'a> Deserialize<'a>>(
     command: Command,
     args: Vec<String>,
 ) -> Result<T, ConfigError> {
+    Ok(load_and_process_config_with_matches(default_config_file, command, args)?.0)
+}
+
+/// Same as [`load_and_process_config`], and also returns the matched arguments, for reading args
+/// that the command defines in addition to the config params.
+pub fn load_and_process_config_with_matches<T: for<'a> Deserialize<'a>>(
+    default_config_file: File,
+    command: Command,
+    args: Vec<String>,
+) -> Result<(T, ArgMatches), ConfigError> {
     let deserialized_default_config: Map<String, Value> =
         serde_json::from_reader(default_config_file)?;
 
@@ -71,7 +82,7 @@ pub fn load_and_process_config<T: for<'a> Deserialize<'a>>(
     // Set values according to the is-none marks.
     update_optional_values(&mut values_map);
     // Build and return a Config object.
-    load(&values_map)
+    Ok((load(&values_map)?, arg_matches))
 }
 
 // Separates a json map into config map of the raw values and pointers map.
@@ -105,7 +116,7 @@ pub(crate) fn split_values_and_types(
     let mut values_map: BTreeMap<ParamPath, Value> = BTreeMap::new();
     let mut types_map: BTreeMap<ParamPath, SerializationType> = BTreeMap::new();
     for (param_path, serialized_param) in config_map {
-        let Some(serialization_type) = serialized_param.content.get_serialization_type() else {
+        let Some(serialization_type) = serialized_param.get_serialization_type() else {
             continue;
         };
         types_map.insert(param_path.clone(), serialization_type);
@@ -195,10 +206,19 @@ pub(crate) fn update_config_map(
     let Some(serialization_type) = types_map.get(param_path) else {
         return Err(ConfigError::ParamNotFound { param_path: param_path.to_string() });
     };
+    // Byte size params changed from numbers to strings, so numbers are still accepted for them to
+    // stay compatible with existing configs.
+    let new_value = match (serialization_type, new_value) {
+        (SerializationType::ByteSize, Value::Number(number)) => match number.as_u64() {
+            Some(bytes) => Value::String(format_human_bytes(bytes)),
+            None => Value::Number(number),
+        },
+        (_, new_value) => new_value,
+    };
     let is_type_matched = match serialization_type {
         SerializationType::Number => new_value.is_number(),
         SerializationType::Boolean => new_value.is_boolean(),
-        SerializationType::String => new_value.is_string(),
+        SerializationType::String | SerializationType::ByteSize => new_value.is_string(),
     };
     if !is_type_matched {
         return Err(ConfigError::ChangeRequiredParamType {
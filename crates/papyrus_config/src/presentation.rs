@@ -1,12 +1,17 @@
 //! presentation of a configuration, with hiding or exposing private parameters.
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::ops::IndexMut;
 
 use itertools::Itertools;
 use serde::Serialize;
+use serde_json::Value;
 
 use crate::dumping::SerializeConfig;
-use crate::{ConfigError, ParamPrivacy};
+use crate::{ConfigError, ParamPath, ParamPrivacy, SerializedContent, SerializedParam};
+
+/// The value presented instead of the value of a private parameter.
+pub const REDACTED_VALUE: &str = "<redacted>";
 
 /// Returns presentation of the public parameters in the config.
 pub fn get_config_presentation<T: Serialize + SerializeConfig>(
@@ -29,6 +34,70 @@ pub fn get_config_presentation<T: Serialize + SerializeConfig>(
     Ok(config_presentation)
 }
 
+/// Returns presentation of the parameters in the config, grouped by their top-level sub-config.
+/// For example, the param 'rpc.server_address' is presented as 'server_address' in the 'rpc'
+/// group, along with its description and value. Parameters that aren't in a sub-config are grouped
+/// under an empty name.
+pub fn get_grouped_config_presentation<T: SerializeConfig>(
+    config: &T,
+    include_private_parameters: bool,
+) -> Result<serde_json::Value, ConfigError> {
+    let mut groups: BTreeMap<String, BTreeMap<String, SerializedParam>> = BTreeMap::new();
+    for (param_path, serialized_param) in config.dump() {
+        if !include_private_parameters && serialized_param.privacy == ParamPrivacy::Private {
+            continue;
+        }
+        let (group, param_name) = param_path.split_once('.').unwrap_or(("", &param_path));
+        groups.entry(group.to_owned()).or_default().insert(param_name.to_owned(), serialized_param);
+    }
+    Ok(serde_json::to_value(groups)?)
+}
+
+/// Returns the parameters whose values differ from the default config, mapped to their (current,
+/// default) values. A missing value (e.g. of a param in an optional sub-config that is None) is
+/// presented as null. The values of private parameters are replaced by
+/// [`REDACTED_VALUE`].
+pub fn diff_against_defaults<T: SerializeConfig + Default>(
+    config: &T,
+) -> BTreeMap<ParamPath, (Value, Value)> {
+    let current_map = config.dump();
+    let default_map = T::default().dump();
+    let param_paths: BTreeSet<&ParamPath> = current_map.keys().chain(default_map.keys()).collect();
+
+    let mut diff = BTreeMap::new();
+    for param_path in param_paths {
+        let current_param = current_map.get(param_path);
+        let default_param = default_map.get(param_path);
+        let current_value = current_param.map(param_value).unwrap_or_default();
+        let default_value = default_param.map(param_value).unwrap_or_default();
+        if current_value == default_value {
+            continue;
+        }
+        let is_private = [current_param, default_param]
+            .into_iter()
+            .flatten()
+            .any(|param| param.privacy == ParamPrivacy::Private);
+        if is_private {
+            let redact = |value: Value| {
+                if value.is_null() { value } else { Value::String(REDACTED_VALUE.to_owned()) }
+            };
+            diff.insert(param_path.clone(), (redact(current_value), redact(default_value)));
+        } else {
+            diff.insert(param_path.clone(), (current_value, default_value));
+        }
+    }
+    diff
+}
+
+// Returns the value of a serialized param, or null for params without a value (e.g. required
+// params).
+fn param_value(serialized_param: &SerializedParam) -> Value {
+    match &serialized_param.content {
+        SerializedContent::DefaultValue(value) => value.clone(),
+        _ => Value::Null,
+    }
+}
+
 // Gets a json in the format:
 // {
 //      a: {
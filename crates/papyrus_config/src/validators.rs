@@ -1,6 +1,7 @@
 //! Utils for config validations.
 
 use std::fmt::Display;
+use std::net::SocketAddr;
 use std::path::Path;
 
 use validator::{Validate, ValidationError, ValidationErrors, ValidationErrorsKind};
@@ -15,6 +16,14 @@ pub fn validate_ascii(name: &impl ToString) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Custom validation for a non-empty ASCII string, e.g. a chain id.
+pub fn validate_non_empty_ascii(name: &impl ToString) -> Result<(), ValidationError> {
+    if name.to_string().is_empty() {
+        return Err(ValidationError::new("The value is empty"));
+    }
+    validate_ascii(name)
+}
+
 /// Custom validation for file or directory path existence.
 pub fn validate_path_exists(file_path: &Path) -> Result<(), ValidationError> {
     if !file_path.exists() {
@@ -35,6 +44,16 @@ pub fn validate_vec_u256(vec: &[u8]) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Custom validation for a socket address string, e.g. a server address.
+pub fn validate_socket_addr(address: &str) -> Result<(), ValidationError> {
+    if address.parse::<SocketAddr>().is_err() {
+        let mut error = ValidationError::new("The value is not a valid socket address");
+        error.message = Some("Expected an IP and a port, e.g. 0.0.0.0:8080.".into());
+        return Err(error);
+    }
+    Ok(())
+}
+
 /// Struct for parsing a validation error.
 #[derive(Debug)]
 pub struct ParsedValidationError {
@@ -1,17 +1,21 @@
 // TODO(shahak): Add a test for executing when there's a missing casm that's not required and when
 // there's a missing casm that is required.
 use std::sync::Arc;
+use std::time::Duration;
 
 use assert_matches::assert_matches;
 use blockifier::abi::abi_utils::get_storage_var_address;
 use blockifier::execution::call_info::Retdata;
+use blockifier::execution::contract_class::ContractClass as BlockifierContractClass;
 use blockifier::execution::errors::ConstructorEntryPointExecutionError;
 use blockifier::execution::stack_trace::gen_transaction_execution_error_trace;
 use blockifier::transaction::errors::TransactionExecutionError as BlockifierTransactionExecutionError;
 use indexmap::indexmap;
+use papyrus_common::state::{DeclaredClassHashEntry, DeployedContract, ReplacedClass};
+use papyrus_storage::state::StateStorageWriter;
 use papyrus_storage::test_utils::get_test_storage;
 use pretty_assertions::assert_eq;
-use starknet_api::block::{BlockNumber, StarknetVersion};
+use starknet_api::block::{BlockNumber, GasPrice, StarknetVersion};
 use starknet_api::core::{
     ChainId,
     ClassHash,
@@ -21,16 +25,31 @@ use starknet_api::core::{
     Nonce,
     PatriciaKey,
 };
-use starknet_api::state::{StateNumber, ThinStateDiff};
-use starknet_api::transaction::{Calldata, Fee};
+use starknet_api::state::{ContractClass, StateNumber, ThinStateDiff};
+use starknet_api::transaction::{
+    Calldata,
+    DeclareTransaction,
+    DeclareTransactionV0V1,
+    DeclareTransactionV2,
+    Fee,
+};
 use starknet_api::{calldata, class_hash, contract_address, felt, patricia_key};
 use starknet_types_core::felt::Felt;
-
-use crate::execution_utils::selector_from_name;
+use validator::Validate;
+
+use crate::execution_utils::{
+    get_class_and_compiled_hash_at,
+    get_nonce_at,
+    get_sierra_size,
+    selector_from_name,
+    ExecutableClass,
+};
 use crate::objects::{
+    sum_fees,
     DeclareTransactionTrace,
     DeployAccountTransactionTrace,
     FeeEstimation,
+    FeeEstimationAdditionError,
     FunctionInvocationResult,
     InvokeTransactionTrace,
     PriceUnit,
@@ -39,6 +58,8 @@ use crate::objects::{
 };
 use crate::test_utils::{
     execute_simulate_transactions,
+    get_test_casm,
+    get_test_deprecated_contract_class,
     prepare_storage,
     TxsScenarioBuilder,
     ACCOUNT_ADDRESS,
@@ -48,19 +69,28 @@ use crate::test_utils::{
     CONTRACT_ADDRESS,
     DEPRECATED_CONTRACT_ADDRESS,
     GAS_PRICE,
+    MISSING_CASM_CLASS_HASH,
+    MISSING_CASM_CONTRACT_ADDRESS,
     NEW_ACCOUNT_ADDRESS,
     SEQUENCER_ADDRESS,
     TEST_ERC20_CONTRACT_ADDRESS,
 };
-use crate::testing_instances::get_test_execution_config;
+use crate::testing_instances::{get_test_class_cache, get_test_execution_config};
 use crate::{
     estimate_fee,
     execute_call,
+    get_declared_class_info,
     get_versioned_constants,
+    simulate_transactions,
     ExecutableTransactionInput,
+    ExecutionConfig,
     ExecutionError,
+    ExecutionInputs,
+    ExecutionResult,
     FeeEstimationResult,
+    NonceOverride,
     RevertedTransaction,
+    DEPRECATED_CONTRACT_SIERRA_SIZE,
 };
 
 // Test calling entry points of a deprecated class.
@@ -77,12 +107,14 @@ fn execute_call_cairo0() {
         storage_reader.clone(),
         None,
         &chain_id,
-        StateNumber::unchecked_right_after_block(BlockNumber(0)),
-        BlockNumber(0),
+        ExecutionInputs::at_block_end(BlockNumber(0)),
         &DEPRECATED_CONTRACT_ADDRESS,
         selector_from_name("without_arg"),
         Calldata::default(),
+        None,
+        None,
         &get_test_execution_config(),
+        &get_test_class_cache(),
         true,
     )
     .unwrap()
@@ -94,12 +126,14 @@ fn execute_call_cairo0() {
         storage_reader.clone(),
         None,
         &chain_id,
-        StateNumber::unchecked_right_after_block(BlockNumber(0)),
-        BlockNumber(0),
+        ExecutionInputs::at_block_end(BlockNumber(0)),
         &DEPRECATED_CONTRACT_ADDRESS,
         selector_from_name("with_arg"),
         Calldata(Arc::new(vec![Felt::from(25u128)])),
+        None,
+        None,
         &get_test_execution_config(),
+        &get_test_class_cache(),
         true,
     )
     .unwrap()
@@ -111,12 +145,14 @@ fn execute_call_cairo0() {
         storage_reader.clone(),
         None,
         &chain_id,
-        StateNumber::unchecked_right_after_block(BlockNumber(0)),
-        BlockNumber(0),
+        ExecutionInputs::at_block_end(BlockNumber(0)),
         &DEPRECATED_CONTRACT_ADDRESS,
         selector_from_name("return_result"),
         Calldata(Arc::new(vec![Felt::from(123u128)])),
+        None,
+        None,
         &get_test_execution_config(),
+        &get_test_class_cache(),
         true,
     )
     .unwrap()
@@ -128,12 +164,14 @@ fn execute_call_cairo0() {
         storage_reader,
         None,
         &chain_id,
-        StateNumber::unchecked_right_after_block(BlockNumber(0)),
-        BlockNumber(0),
+        ExecutionInputs::at_block_end(BlockNumber(0)),
         &DEPRECATED_CONTRACT_ADDRESS,
         selector_from_name("test_storage_read_write"),
         Calldata(Arc::new(vec![Felt::from(123u128), Felt::from(456u128)])),
+        None,
+        None,
         &get_test_execution_config(),
+        &get_test_class_cache(),
         true,
     )
     .unwrap()
@@ -156,12 +194,14 @@ fn execute_call_cairo1() {
         storage_reader,
         None,
         &CHAIN_ID,
-        StateNumber::unchecked_right_after_block(BlockNumber(0)),
-        BlockNumber(0),
+        ExecutionInputs::at_block_end(BlockNumber(0)),
         &CONTRACT_ADDRESS,
         selector_from_name("test_storage_read_write"),
         calldata,
+        None,
+        None,
         &get_test_execution_config(),
+        &get_test_class_cache(),
         true,
     )
     .unwrap()
@@ -170,6 +210,100 @@ fn execute_call_cairo1() {
     assert_eq!(retdata, Retdata(vec![value]));
 }
 
+#[test]
+fn execute_call_with_initial_gas() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    prepare_storage(storage_writer);
+    // The configured initial gas isn't enough for the call, so it succeeds only with an override.
+    let execution_config = ExecutionConfig { initial_gas_cost: 1, ..get_test_execution_config() };
+
+    let call = |initial_gas| {
+        execute_call(
+            storage_reader.clone(),
+            None,
+            &CHAIN_ID,
+            ExecutionInputs::at_block_end(BlockNumber(0)),
+            &CONTRACT_ADDRESS,
+            selector_from_name("test_storage_read_write"),
+            calldata![felt!(1234_u16), felt!(18_u8)],
+            None,
+            initial_gas,
+            &execution_config,
+            &get_test_class_cache(),
+            true,
+        )
+    };
+
+    assert_matches!(
+        call(None),
+        Err(ExecutionError::ContractError(err)) if err.to_string().contains("Out of gas")
+    );
+    assert_eq!(call(Some(10_u64.pow(10))).unwrap().retdata, Retdata(vec![felt!(18_u8)]));
+}
+
+// The account's `assert_only_self` entry point fails unless it's called by the account itself.
+#[test]
+fn execute_call_with_caller_address() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    prepare_storage(storage_writer);
+
+    let call = |caller_address| {
+        execute_call(
+            storage_reader.clone(),
+            None,
+            &CHAIN_ID,
+            ExecutionInputs::at_block_end(BlockNumber(0)),
+            &ACCOUNT_ADDRESS,
+            selector_from_name("assert_only_self"),
+            Calldata::default(),
+            caller_address,
+            None,
+            &get_test_execution_config(),
+            &get_test_class_cache(),
+            true,
+        )
+    };
+
+    assert_matches!(call(None), Err(ExecutionError::ContractError(_)));
+    assert_matches!(
+        call(Some(*TEST_ERC20_CONTRACT_ADDRESS)),
+        Err(ExecutionError::ContractError(_))
+    );
+    assert_eq!(call(Some(*ACCOUNT_ADDRESS)).unwrap().retdata, Retdata::default());
+}
+
+#[test]
+fn class_cache_hit() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    prepare_storage(storage_writer);
+    let class_cache = get_test_class_cache();
+    let call = || {
+        execute_call(
+            storage_reader.clone(),
+            None,
+            &CHAIN_ID,
+            ExecutionInputs::at_block_end(BlockNumber(0)),
+            &CONTRACT_ADDRESS,
+            selector_from_name("test_storage_read_write"),
+            calldata![felt!(1234_u16), felt!(18_u8)],
+            None,
+            None,
+            &get_test_execution_config(),
+            &class_cache,
+            true,
+        )
+        .unwrap()
+    };
+
+    // The contract's class is read from the storage once and then served from the cache.
+    let contract_class_hash = class_hash!("0x2");
+    assert!(class_cache.get(&contract_class_hash).is_none());
+    call();
+    let cached_class = class_cache.get(&contract_class_hash).unwrap();
+    call();
+    assert_eq!(class_cache.get(&contract_class_hash), Some(cached_class));
+}
+
 // TODO(yair): Compare to the expected fee instead of asserting that it is not zero (all
 // estimate_fee tests).
 #[test]
@@ -254,16 +388,76 @@ fn estimate_fees(txs: Vec<ExecutableTransactionInput>) -> FeeEstimationResult {
         &CHAIN_ID,
         storage_reader,
         None,
-        StateNumber::unchecked_right_after_block(BlockNumber(0)),
-        BlockNumber(1),
+        ExecutionInputs::at_block_start(BlockNumber(1)),
         &get_test_execution_config(),
+        &get_test_class_cache(),
         false,
         // TODO(yair): Add test for blob fee estimation.
         true,
+        None,
     )
     .unwrap()
 }
 
+fn fee_estimation(overall_fee: u128, unit: PriceUnit) -> FeeEstimation {
+    FeeEstimation {
+        gas_consumed: felt!(overall_fee),
+        gas_price: GasPrice(1),
+        data_gas_consumed: Felt::ZERO,
+        data_gas_price: GasPrice(1),
+        overall_fee: Fee(overall_fee),
+        unit,
+    }
+}
+
+#[test]
+fn sum_fees_with_same_price_unit() {
+    let fees = [fee_estimation(1, PriceUnit::Fri), fee_estimation(2, PriceUnit::Fri)];
+    assert_eq!(fees[0].checked_add(&fees[1]), Ok(fee_estimation(3, PriceUnit::Fri)));
+    assert_eq!(sum_fees(&fees), Ok(Some(fee_estimation(3, PriceUnit::Fri))));
+    assert_eq!(sum_fees(&[]), Ok(None));
+}
+
+#[test]
+fn sum_fees_with_different_price_units() {
+    let fees = [fee_estimation(1, PriceUnit::Wei), fee_estimation(2, PriceUnit::Fri)];
+    let expected_error = FeeEstimationAdditionError::PriceUnitMismatch {
+        first: PriceUnit::Wei,
+        second: PriceUnit::Fri,
+    };
+    assert_eq!(fees[0].checked_add(&fees[1]), Err(expected_error));
+    assert_eq!(sum_fees(&fees), Err(expected_error));
+}
+
+#[test]
+fn sum_fees_with_different_gas_prices() {
+    let mut fees = [fee_estimation(1, PriceUnit::Fri), fee_estimation(2, PriceUnit::Fri)];
+    fees[1].gas_price = GasPrice(2);
+    assert_eq!(
+        sum_fees(&fees),
+        Err(FeeEstimationAdditionError::GasPriceMismatch {
+            first: GasPrice(1),
+            second: GasPrice(2)
+        })
+    );
+
+    let mut fees = [fee_estimation(1, PriceUnit::Fri), fee_estimation(2, PriceUnit::Fri)];
+    fees[1].data_gas_price = GasPrice(2);
+    assert_eq!(
+        sum_fees(&fees),
+        Err(FeeEstimationAdditionError::DataGasPriceMismatch {
+            first: GasPrice(1),
+            second: GasPrice(2)
+        })
+    );
+}
+
+#[test]
+fn sum_fees_overflow() {
+    let fees = [fee_estimation(u128::MAX, PriceUnit::Fri), fee_estimation(1, PriceUnit::Fri)];
+    assert_eq!(sum_fees(&fees), Err(FeeEstimationAdditionError::Overflow));
+}
+
 #[test]
 fn serialization_precision() {
     let input =
@@ -549,6 +743,36 @@ fn simulate_deploy_account() {
     }
 }
 
+#[test]
+fn simulate_deploy() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    prepare_storage(storage_writer);
+
+    let tx = TxsScenarioBuilder::default().deploy().collect();
+    let simulation_results =
+        execute_simulate_transactions(storage_reader, None, tx, None, false, false);
+
+    let TransactionTrace::DeployAccount(trace) = &simulation_results[0].transaction_trace else {
+        panic!("Wrong trace type, expected DeployAccountTransactionTrace.")
+    };
+    assert_matches!(
+        trace,
+        DeployAccountTransactionTrace {
+            validate_invocation: None,
+            fee_transfer_invocation: None,
+            constructor_invocation: _,
+        }
+    );
+
+    // The deploy account in TxsScenarioBuilder has the same class hash, salt and calldata, and
+    // both are deployed from the zero address.
+    let expected_state_diff = ThinStateDiff {
+        deployed_contracts: indexmap! {*NEW_ACCOUNT_ADDRESS => *ACCOUNT_CLASS_HASH},
+        ..Default::default()
+    };
+    assert_eq!(simulation_results[0].induced_state_diff, expected_state_diff);
+}
+
 #[test]
 fn simulate_invoke_from_new_account() {
     let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
@@ -752,6 +976,176 @@ fn induced_state_diff() {
     assert_eq!(simulation_results[3].induced_state_diff, expected_deploy_account);
 }
 
+#[test]
+fn induced_state_diff_is_deterministic() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    prepare_storage(storage_writer);
+
+    let tx = TxsScenarioBuilder::default()
+        .invoke_deprecated(*ACCOUNT_ADDRESS, *DEPRECATED_CONTRACT_ADDRESS, None, false)
+        .deploy_account()
+        .collect();
+    let serialized_diffs = (0..2)
+        .map(|_| {
+            let simulation_results = execute_simulate_transactions(
+                storage_reader.clone(),
+                None,
+                tx.clone(),
+                None,
+                true,
+                true,
+            );
+            simulation_results
+                .iter()
+                .map(|result| serde_json::to_vec(&result.induced_state_diff).unwrap())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(serialized_diffs[0], serialized_diffs[1]);
+}
+
+fn simulate_with_execution_budget(
+    txs: Vec<ExecutableTransactionInput>,
+    max_execution_duration: Duration,
+) -> ExecutionResult<Vec<TransactionSimulationOutput>> {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    prepare_storage(storage_writer);
+
+    simulate_transactions(
+        txs,
+        None,
+        &CHAIN_ID,
+        storage_reader,
+        None,
+        StateNumber::unchecked_right_after_block(BlockNumber(0)),
+        BlockNumber(1),
+        &get_test_execution_config(),
+        &get_test_class_cache(),
+        true,
+        true,
+        true,
+        Some(max_execution_duration),
+        None,
+    )
+}
+
+#[test]
+fn simulate_within_execution_budget() {
+    let txs = TxsScenarioBuilder::default()
+        .invoke_deprecated(*ACCOUNT_ADDRESS, *DEPRECATED_CONTRACT_ADDRESS, None, false)
+        .collect();
+    let simulation_results = simulate_with_execution_budget(txs, Duration::from_secs(60)).unwrap();
+    assert_eq!(simulation_results.len(), 1);
+}
+
+#[test]
+fn simulate_exceeding_execution_budget() {
+    // A recursion this deep runs millions of Cairo steps, which takes far longer than the budget.
+    let txs = TxsScenarioBuilder::default().invoke_recurse(*ACCOUNT_ADDRESS, 500_000).collect();
+    let result = simulate_with_execution_budget(txs, Duration::from_millis(100));
+    assert_matches!(result, Err(ExecutionError::ExecutionTimeout { transaction_index: 0 }));
+}
+
+#[test]
+fn simulate_with_nonce_override() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    prepare_storage(storage_writer);
+
+    // The stored nonce of the account is 0.
+    let tx_nonce = Nonce(felt!(5_u128));
+    let tx = TxsScenarioBuilder::default()
+        .invoke_deprecated(*ACCOUNT_ADDRESS, *DEPRECATED_CONTRACT_ADDRESS, Some(tx_nonce), true)
+        .collect();
+    let simulate = |nonce_override| {
+        simulate_transactions(
+            tx.clone(),
+            None,
+            &CHAIN_ID,
+            storage_reader.clone(),
+            None,
+            StateNumber::unchecked_right_after_block(BlockNumber(0)),
+            BlockNumber(1),
+            &get_test_execution_config(),
+            &get_test_class_cache(),
+            true,
+            true,
+            true,
+            None,
+            nonce_override,
+        )
+        .map_err(|err| err.to_string())
+    };
+
+    let err = simulate(None).unwrap_err();
+    assert!(err.contains("Invalid transaction nonce"), "{err}");
+
+    // Overriding the nonce of another sender doesn't affect the transaction.
+    let other_sender_override =
+        NonceOverride { sender_address: *DEPRECATED_CONTRACT_ADDRESS, nonce: tx_nonce };
+    let err = simulate(Some(other_sender_override)).unwrap_err();
+    assert!(err.contains("Invalid transaction nonce"), "{err}");
+
+    let nonce_override = NonceOverride { sender_address: *ACCOUNT_ADDRESS, nonce: tx_nonce };
+    let simulation_results = simulate(Some(nonce_override)).unwrap();
+    assert_eq!(
+        simulation_results[0].induced_state_diff.nonces,
+        indexmap! {*ACCOUNT_ADDRESS => Nonce(felt!(6_u128))}
+    );
+}
+
+#[test]
+fn precheck_compiled_classes() {
+    let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
+    prepare_storage(storage_writer);
+
+    // The first transaction has a wrong nonce and the second is sent from a contract whose
+    // compiled class is missing.
+    let txs = TxsScenarioBuilder::default()
+        .invoke_deprecated(
+            *ACCOUNT_ADDRESS,
+            *DEPRECATED_CONTRACT_ADDRESS,
+            Some(Nonce(felt!(5_u128))),
+            false,
+        )
+        .invoke_deprecated(
+            *MISSING_CASM_CONTRACT_ADDRESS,
+            *DEPRECATED_CONTRACT_ADDRESS,
+            None,
+            false,
+        )
+        .collect();
+    let simulate = |precheck_compiled_classes| {
+        simulate_transactions(
+            txs.clone(),
+            None,
+            &CHAIN_ID,
+            storage_reader.clone(),
+            None,
+            StateNumber::unchecked_right_after_block(BlockNumber(0)),
+            BlockNumber(1),
+            &ExecutionConfig { precheck_compiled_classes, ..get_test_execution_config() },
+            &get_test_class_cache(),
+            true,
+            true,
+            true,
+            None,
+            None,
+        )
+    };
+
+    // Without the precheck, the execution stops at the first failing transaction.
+    assert_matches!(
+        simulate(false),
+        Err(ExecutionError::TransactionExecutionError { transaction_index: 0, .. })
+    );
+    // With the precheck, the missing compiled class is reported before executing any transaction.
+    assert_matches!(
+        simulate(true),
+        Err(ExecutionError::MissingCompiledClass { class_hash })
+        if class_hash == *MISSING_CASM_CLASS_HASH
+    );
+}
+
 #[test]
 fn simulate_with_query_bit_outputs_same_as_no_query_bit() {
     let ((storage_reader, storage_writer), _temp_dir) = get_test_storage();
@@ -857,3 +1251,227 @@ fn test_get_versioned_constants() {
     let versioned_constants = get_versioned_constants(Some(&starknet_version_13_1)).unwrap();
     assert_eq!(versioned_constants.invoke_tx_max_n_steps, 4_000_000);
 }
+
+#[test]
+fn validate_fee_contract_addresses() {
+    let valid_config = ExecutionConfig {
+        strk_fee_contract_address: contract_address!("0x1"),
+        eth_fee_contract_address: contract_address!("0x2"),
+        ..Default::default()
+    };
+    assert_matches!(valid_config.validate_fee_contract_addresses(), Ok(()));
+    assert_matches!(valid_config.validate(), Ok(()));
+
+    let zero_strk_address =
+        ExecutionConfig { strk_fee_contract_address: ContractAddress::default(), ..valid_config };
+    assert_matches!(
+        zero_strk_address.validate_fee_contract_addresses(),
+        Err(ExecutionError::ConfigContentError(_))
+    );
+    assert_matches!(zero_strk_address.validate(), Err(_));
+
+    let zero_eth_address =
+        ExecutionConfig { eth_fee_contract_address: ContractAddress::default(), ..valid_config };
+    assert_matches!(
+        zero_eth_address.validate_fee_contract_addresses(),
+        Err(ExecutionError::ConfigContentError(_))
+    );
+    assert_matches!(zero_eth_address.validate(), Err(_));
+
+    let duplicate_addresses = ExecutionConfig {
+        eth_fee_contract_address: valid_config.strk_fee_contract_address,
+        ..valid_config
+    };
+    assert_matches!(
+        duplicate_addresses.validate_fee_contract_addresses(),
+        Err(ExecutionError::ConfigContentError(_))
+    );
+    assert_matches!(duplicate_addresses.validate(), Err(_));
+}
+
+#[test]
+fn zero_class_cache_size_is_invalid() {
+    let valid_config = ExecutionConfig::default();
+    assert_matches!(valid_config.validate(), Ok(()));
+    let zero_cache_size = ExecutionConfig { class_cache_size: 0, ..valid_config };
+    assert_matches!(zero_cache_size.validate(), Err(_));
+}
+
+#[test]
+fn execution_inputs_match_documented_semantics() {
+    assert_eq!(
+        ExecutionInputs::at_block_start(BlockNumber(10)),
+        ExecutionInputs {
+            state_number: StateNumber(BlockNumber(10)),
+            block_context_block_number: BlockNumber(10),
+        }
+    );
+    assert_eq!(
+        ExecutionInputs::at_block_end(BlockNumber(10)),
+        ExecutionInputs {
+            state_number: StateNumber(BlockNumber(11)),
+            block_context_block_number: BlockNumber(10),
+        }
+    );
+}
+
+#[test]
+fn sierra_size() {
+    let contract_class = ContractClass {
+        sierra_program: vec![felt!("0x1"), felt!("0x2"), felt!("0x3")],
+        ..Default::default()
+    };
+    assert_eq!(get_sierra_size(&contract_class), contract_class.sierra_program.len());
+    assert_eq!(get_sierra_size(&ContractClass::default()), 0);
+}
+
+#[test]
+fn class_and_compiled_hash_at() {
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let address0 = contract_address!("0x100");
+    let address1 = contract_address!("0x101");
+    let address2 = contract_address!("0x102");
+    let undeployed_address = contract_address!("0x103");
+    let class_hash0 = class_hash!("0x10");
+    let deprecated_class_hash = class_hash!("0x11");
+    let pending_class_hash = class_hash!("0x12");
+    let compiled_class_hash0 = CompiledClassHash(felt!("0x20"));
+    let pending_compiled_class_hash = CompiledClassHash(felt!("0x22"));
+
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(
+            BlockNumber(0),
+            ThinStateDiff {
+                deployed_contracts: indexmap!(
+                    address0 => class_hash0,
+                    address1 => deprecated_class_hash,
+                ),
+                declared_classes: indexmap!(class_hash0 => compiled_class_hash0),
+                deprecated_declared_classes: vec![deprecated_class_hash],
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let txn = storage_reader.begin_ro_txn().unwrap();
+    let state_number = StateNumber::unchecked_right_after_block(BlockNumber(0));
+
+    // Deployed contracts.
+    assert_eq!(
+        get_class_and_compiled_hash_at(&txn, state_number, None, address0).unwrap(),
+        Some((class_hash0, Some(compiled_class_hash0)))
+    );
+    assert_eq!(
+        get_class_and_compiled_hash_at(&txn, state_number, None, address1).unwrap(),
+        Some((deprecated_class_hash, None))
+    );
+
+    // Undeployed contracts.
+    assert_eq!(
+        get_class_and_compiled_hash_at(&txn, state_number, None, undeployed_address).unwrap(),
+        None
+    );
+    assert_eq!(
+        get_class_and_compiled_hash_at(&txn, StateNumber(BlockNumber(0)), None, address0).unwrap(),
+        None
+    );
+
+    // Pending overlay.
+    let pending_deployed_contracts =
+        vec![DeployedContract { address: address2, class_hash: pending_class_hash }];
+    let pending_replaced_classes =
+        vec![ReplacedClass { address: address0, class_hash: pending_class_hash }];
+    let pending_declared_classes = vec![DeclaredClassHashEntry {
+        class_hash: pending_class_hash,
+        compiled_class_hash: pending_compiled_class_hash,
+    }];
+    let pending =
+        Some((&pending_deployed_contracts, &pending_replaced_classes, &pending_declared_classes));
+    assert_eq!(
+        get_class_and_compiled_hash_at(&txn, state_number, pending, address2).unwrap(),
+        Some((pending_class_hash, Some(pending_compiled_class_hash)))
+    );
+    assert_eq!(
+        get_class_and_compiled_hash_at(&txn, state_number, pending, address0).unwrap(),
+        Some((pending_class_hash, Some(pending_compiled_class_hash)))
+    );
+    assert_eq!(
+        get_class_and_compiled_hash_at(&txn, state_number, pending, address1).unwrap(),
+        Some((deprecated_class_hash, None))
+    );
+    assert_eq!(
+        get_class_and_compiled_hash_at(&txn, state_number, pending, undeployed_address).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn pending_nonce_overrides_stored_nonce() {
+    let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
+    let address = contract_address!("0x100");
+    let stored_nonce = Nonce(felt!("0x1"));
+    let pending_nonce = Nonce(felt!("0x2"));
+
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(
+            BlockNumber(0),
+            ThinStateDiff { nonces: indexmap!(address => stored_nonce), ..Default::default() },
+        )
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let txn = storage_reader.begin_ro_txn().unwrap();
+    let state_number = StateNumber::unchecked_right_after_block(BlockNumber(0));
+    assert_eq!(get_nonce_at(&txn, state_number, None, address).unwrap(), Some(stored_nonce));
+
+    let pending_nonces = indexmap!(address => pending_nonce);
+    assert_eq!(
+        get_nonce_at(&txn, state_number, Some(&pending_nonces), address).unwrap(),
+        Some(pending_nonce)
+    );
+}
+
+#[test]
+fn declared_class_info() {
+    const ABI_LENGTH: usize = 10;
+    const SIERRA_PROGRAM_LENGTH: usize = 100;
+
+    let declare_v1 = DeclareTransaction::V1(DeclareTransactionV0V1::default());
+    let deprecated_class = ExecutableClass::Deprecated(get_test_deprecated_contract_class());
+    let class_info = get_declared_class_info(
+        &declare_v1,
+        deprecated_class,
+        DEPRECATED_CONTRACT_SIERRA_SIZE,
+        ABI_LENGTH,
+    )
+    .unwrap();
+    assert_matches!(class_info.contract_class(), BlockifierContractClass::V0(_));
+    assert_eq!(class_info.sierra_program_length(), DEPRECATED_CONTRACT_SIERRA_SIZE);
+    assert_eq!(class_info.abi_length(), ABI_LENGTH);
+
+    let declare_v2 = DeclareTransaction::V2(DeclareTransactionV2::default());
+    let compiled_class = ExecutableClass::Compiled(get_test_casm());
+    let class_info = get_declared_class_info(
+        &declare_v2,
+        compiled_class.clone(),
+        SIERRA_PROGRAM_LENGTH,
+        ABI_LENGTH,
+    )
+    .unwrap();
+    assert_matches!(class_info.contract_class(), BlockifierContractClass::V1(_));
+    assert_eq!(class_info.sierra_program_length(), SIERRA_PROGRAM_LENGTH);
+    assert_eq!(class_info.abi_length(), ABI_LENGTH);
+
+    // A Sierra class must have a Sierra program.
+    assert_matches!(
+        get_declared_class_info(&declare_v2, compiled_class, 0, ABI_LENGTH),
+        Err(ExecutionError::BadDeclareTransaction { tx, .. }) if tx == declare_v2
+    );
+}
@@ -10,17 +10,30 @@ use blockifier::execution::contract_class::{
     ContractClassV1,
 };
 use blockifier::state::cached_state::{CachedState, CommitmentStateDiff, MutRefState};
+use blockifier::state::global_cache::GlobalContractCache;
 use blockifier::state::state_api::StateReader;
 use blockifier::transaction::objects::TransactionExecutionInfo;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_vm::types::errors::program_errors::ProgramError;
 use indexmap::IndexMap;
-use papyrus_common::state::{DeployedContract, ReplacedClass, StorageEntry};
+use metrics::increment_counter;
+use papyrus_common::metrics::{
+    PAPYRUS_EXECUTION_CLASS_CACHE_HITS,
+    PAPYRUS_EXECUTION_CLASS_CACHE_MISSES,
+};
+use papyrus_common::state::{
+    DeclaredClassHashEntry,
+    DeployedContract,
+    ReplacedClass,
+    StorageEntry,
+};
 use papyrus_storage::compiled_class::CasmStorageReader;
 use papyrus_storage::db::{TransactionKind, RO};
 use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::{StorageError, StorageResult, StorageTxn};
-use starknet_api::core::{ClassHash, ContractAddress, Nonce};
-use starknet_api::state::{StateNumber, StorageKey, ThinStateDiff};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
+use starknet_api::state::{ContractClass, StateNumber, StorageKey, ThinStateDiff};
 use starknet_types_core::felt::Felt;
 use thiserror::Error;
 
@@ -32,6 +45,7 @@ use crate::{
     ExecutionConfig,
     ExecutionError,
     ExecutionResult,
+    SierraSize,
 };
 
 // An error that can occur during the use of the execution utils.
@@ -45,6 +59,29 @@ pub(crate) enum ExecutionUtilsError {
     CasmTableNotSynced,
 }
 
+/// A class in the form it's executed in. Sierra classes are executed through their compiled class.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ExecutableClass {
+    Deprecated(DeprecatedContractClass),
+    Compiled(CasmContractClass),
+}
+
+impl TryFrom<ExecutableClass> for BlockifierContractClass {
+    type Error = ProgramError;
+
+    fn try_from(class: ExecutableClass) -> Result<Self, Self::Error> {
+        match class {
+            ExecutableClass::Deprecated(class) => Ok(Self::V0(ContractClassV0::try_from(class)?)),
+            ExecutableClass::Compiled(class) => Ok(Self::V1(ContractClassV1::try_from(class)?)),
+        }
+    }
+}
+
+/// Returns the size of the sierra program of a class, as needed for executing declare transactions.
+pub fn get_sierra_size(contract_class: &ContractClass) -> SierraSize {
+    contract_class.sierra_program.len()
+}
+
 /// Returns the execution config from the config file.
 impl TryFrom<PathBuf> for ExecutionConfig {
     type Error = ExecutionError;
@@ -59,28 +96,40 @@ pub(crate) fn get_contract_class(
     txn: &StorageTxn<'_, RO>,
     class_hash: &ClassHash,
     state_number: StateNumber,
+    class_cache: &GlobalContractCache,
 ) -> Result<Option<BlockifierContractClass>, ExecutionUtilsError> {
-    match txn.get_state_reader()?.get_class_definition_block_number(class_hash)? {
+    let state_reader = txn.get_state_reader()?;
+    let is_deprecated = match state_reader.get_class_definition_block_number(class_hash)? {
         Some(block_number) if state_number.is_before(block_number) => return Ok(None),
-        Some(_block_number) => {
-            let Some(casm) = txn.get_casm(class_hash)? else {
-                return Err(ExecutionUtilsError::CasmTableNotSynced);
-            };
-            return Ok(Some(BlockifierContractClass::V1(
-                ContractClassV1::try_from(casm).map_err(ExecutionUtilsError::ProgramError)?,
-            )));
-        }
-        None => {}
+        Some(_block_number) => false,
+        None => match state_reader.get_deprecated_class_definition_block_number(class_hash)? {
+            Some(block_number) if !state_number.is_before(block_number) => true,
+            _ => return Ok(None),
+        },
     };
 
-    let Some(deprecated_class) =
-        txn.get_state_reader()?.get_deprecated_class_definition_at(state_number, class_hash)?
-    else {
-        return Ok(None);
+    if let Some(class) = class_cache.get(class_hash) {
+        increment_counter!(PAPYRUS_EXECUTION_CLASS_CACHE_HITS);
+        return Ok(Some(class));
+    }
+    increment_counter!(PAPYRUS_EXECUTION_CLASS_CACHE_MISSES);
+
+    let class = if is_deprecated {
+        let Some(deprecated_class) =
+            state_reader.get_deprecated_class_definition_at(state_number, class_hash)?
+        else {
+            return Ok(None);
+        };
+        ExecutableClass::Deprecated(deprecated_class)
+    } else {
+        let Some(casm) = txn.get_casm(class_hash)? else {
+            return Err(ExecutionUtilsError::CasmTableNotSynced);
+        };
+        ExecutableClass::Compiled(casm)
     };
-    Ok(Some(BlockifierContractClass::V0(
-        ContractClassV0::try_from(deprecated_class).map_err(ExecutionUtilsError::ProgramError)?,
-    )))
+    let class = BlockifierContractClass::try_from(class)?;
+    class_cache.set(*class_hash, class.clone());
+    Ok(Some(class))
 }
 
 /// Given an ExecutableTransactionInput, returns a function that will convert the corresponding
@@ -104,7 +153,9 @@ pub fn get_trace_constructor(
         ExecutableTransactionInput::DeclareV3(..) => {
             |execution_info| Ok(TransactionTrace::Declare(execution_info.try_into()?))
         }
-        ExecutableTransactionInput::DeployAccount(..) => {
+        // There's no trace type for Deploy transactions. Its execution has the same structure as a
+        // deploy account without validation and fee transfer.
+        ExecutableTransactionInput::DeployAccount(..) | ExecutableTransactionInput::Deploy(..) => {
             |execution_info| Ok(TransactionTrace::DeployAccount(execution_info.try_into()?))
         }
         ExecutableTransactionInput::L1Handler(..) => {
@@ -135,7 +186,7 @@ pub fn induced_state_diff(
             replaced_classes.insert(*address, *class_hash);
         }
     }
-    Ok(ThinStateDiff {
+    let mut state_diff = ThinStateDiff {
         deployed_contracts,
         storage_diffs: blockifier_state_diff.storage_updates,
         declared_classes: blockifier_state_diff.class_hash_to_compiled_class_hash,
@@ -143,7 +194,23 @@ pub fn induced_state_diff(
             .map_or_else(Vec::new, |class_hash| vec![class_hash]),
         nonces: blockifier_state_diff.address_to_nonce,
         replaced_classes,
-    })
+    };
+    sort_thin_state_diff(&mut state_diff);
+    Ok(state_diff)
+}
+
+// The blockifier state diff is built from hash maps, so the order of its keys isn't deterministic.
+// Sorting the keys makes the output identical between runs.
+fn sort_thin_state_diff(diff: &mut ThinStateDiff) {
+    diff.deployed_contracts.sort_unstable_keys();
+    diff.declared_classes.sort_unstable_keys();
+    diff.deprecated_declared_classes.sort_unstable();
+    diff.nonces.sort_unstable_keys();
+    diff.replaced_classes.sort_unstable_keys();
+    diff.storage_diffs.sort_unstable_keys();
+    for storage_entries in diff.storage_diffs.values_mut() {
+        storage_entries.sort_unstable_keys();
+    }
 }
 
 /// Get the storage at the given contract and key in the given state. If there's a given pending
@@ -216,3 +283,58 @@ pub fn get_class_hash_at<Mode: TransactionKind>(
     }
     txn.get_state_reader()?.get_class_hash_at(state_number, &contract_address)
 }
+
+/// Get the class hash and the compiled class hash of the contract at the given address, if it
+/// exists. If there's a given pending deployed contracts, replaced classes and declared classes,
+/// search in them as well. The compiled class hash is None if the contract's class is a deprecated
+/// class, which has no compiled class hash.
+pub fn get_class_and_compiled_hash_at<Mode: TransactionKind>(
+    txn: &StorageTxn<'_, Mode>,
+    state_number: StateNumber,
+    pending_deployed_contracts_replaced_and_declared_classes: Option<(
+        &Vec<DeployedContract>,
+        &Vec<ReplacedClass>,
+        &Vec<DeclaredClassHashEntry>,
+    )>,
+    contract_address: ContractAddress,
+) -> StorageResult<Option<(ClassHash, Option<CompiledClassHash>)>> {
+    let Some(class_hash) = get_class_hash_at(
+        txn,
+        state_number,
+        pending_deployed_contracts_replaced_and_declared_classes.map(
+            |(deployed_contracts, replaced_classes, _)| (deployed_contracts, replaced_classes),
+        ),
+        contract_address,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    if let Some((_, _, pending_declared_classes)) =
+        pending_deployed_contracts_replaced_and_declared_classes
+    {
+        for DeclaredClassHashEntry { class_hash: other_class_hash, compiled_class_hash } in
+            pending_declared_classes
+        {
+            if class_hash == *other_class_hash {
+                return Ok(Some((class_hash, Some(*compiled_class_hash))));
+            }
+        }
+    }
+
+    let Some(block_number) =
+        txn.get_state_reader()?.get_class_definition_block_number(&class_hash)?
+    else {
+        return Ok(Some((class_hash, None)));
+    };
+    if state_number.is_before(block_number) {
+        return Ok(Some((class_hash, None)));
+    }
+    let compiled_class_hash = txn
+        .get_state_diff(block_number)?
+        .and_then(|state_diff| state_diff.declared_classes.get(&class_hash).copied())
+        .ok_or_else(|| StorageError::DBInconsistency {
+            msg: format!("Missing declaration of class {class_hash} at block {block_number}."),
+        })?;
+    Ok(Some((class_hash, Some(compiled_class_hash))))
+}
@@ -6,7 +6,7 @@
 //! should run. For example, if you want to simulate transactions at the beginning of block 10, you
 //! should use state_number = 10 and block_context_block_number = 10. If you want to simulate
 //! transactions at the end of block 10, you should use state_number = 11 and
-//! block_context_block_number = 10.
+//! block_context_block_number = 10. [ExecutionInputs] builds these pairs.
 //! See documentation of [StateNumber] for more details.
 #[cfg(test)]
 mod execution_test;
@@ -20,10 +20,13 @@ pub mod testing_instances;
 
 pub mod objects;
 use std::cell::Cell;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::num::NonZeroU128;
 use std::path::Path;
+use std::sync::mpsc::{channel, RecvTimeoutError};
 use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 use blockifier::blockifier::block::{pre_process_block, BlockInfo, BlockNumberHashPair, GasPrices};
 use blockifier::bouncer::BouncerConfig;
@@ -33,11 +36,16 @@ use blockifier::execution::contract_class::{ClassInfo, ContractClass as Blockifi
 use blockifier::execution::entry_point::{
     CallEntryPoint,
     CallType as BlockifierCallType,
+    ConstructorContext,
     EntryPointExecutionContext,
 };
+use blockifier::execution::execution_utils::execute_deployment;
 use blockifier::state::cached_state::CachedState;
+use blockifier::state::global_cache::GlobalContractCache;
+use blockifier::state::state_api::State;
 use blockifier::transaction::errors::TransactionExecutionError as BlockifierTransactionExecutionError;
 use blockifier::transaction::objects::{
+    CommonAccountFields,
     DeprecatedTransactionInfo,
     TransactionExecutionInfo,
     TransactionInfo,
@@ -48,18 +56,29 @@ use blockifier::versioned_constants::VersionedConstants;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
-use execution_utils::{get_trace_constructor, induced_state_diff};
+use execution_utils::{get_trace_constructor, induced_state_diff, ExecutableClass};
 use objects::{PriceUnit, TransactionSimulationOutput};
 use once_cell::sync::Lazy;
+use papyrus_common::pending_classes::PendingClassesTrait;
 use papyrus_common::transaction_hash::get_transaction_hash;
 use papyrus_common::TransactionOptions;
 use papyrus_config::dumping::{ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use papyrus_storage::compiled_class::CasmStorageReader;
 use papyrus_storage::header::HeaderStorageReader;
+use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::{StorageError, StorageReader};
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockNumber, StarknetVersion};
-use starknet_api::core::{ChainId, ClassHash, ContractAddress, EntryPointSelector, PatriciaKey};
+use starknet_api::core::{
+    calculate_contract_address,
+    ChainId,
+    ClassHash,
+    ContractAddress,
+    EntryPointSelector,
+    Nonce,
+    PatriciaKey,
+};
 use starknet_api::data_availability::L1DataAvailabilityMode;
 // TODO: merge multiple EntryPointType structs in SN_API into one.
 use starknet_api::deprecated_contract_class::{
@@ -74,6 +93,7 @@ use starknet_api::transaction::{
     DeclareTransactionV2,
     DeclareTransactionV3,
     DeployAccountTransaction,
+    DeployTransaction,
     Fee,
     InvokeTransaction,
     L1HandlerTransaction,
@@ -84,6 +104,7 @@ use starknet_api::transaction::{
 use starknet_api::{contract_address, felt, patricia_key, StarknetApiError};
 use state_reader::ExecutionStateReader;
 use tracing::trace;
+use validator::{Validate, ValidationError};
 
 use crate::objects::{tx_execution_output_to_fee_estimation, FeeEstimation, PendingData};
 
@@ -94,6 +115,8 @@ const STRK_FEE_CONTRACT_ADDRESS: &str =
 const ETH_FEE_CONTRACT_ADDRESS: &str =
     "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7";
 const INITIAL_GAS_COST: u64 = 10000000000;
+const MAX_CASM_BYTECODE_SIZE: usize = 80 * 1024;
+const CLASS_CACHE_SIZE: usize = 400;
 
 /// Result type for execution functions.
 pub type ExecutionResult<T> = Result<T, ExecutionError>;
@@ -107,7 +130,8 @@ static VERSIONED_CONSTANTS_13_1: Lazy<VersionedConstants> = Lazy::new(|| {
         .expect("Versioned constants JSON file is malformed")
 });
 
-#[derive(Copy, Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Copy, Clone, Serialize, Deserialize, Debug, PartialEq, Validate)]
+#[validate(schema(function = "validate_execution_config"))]
 /// Parameters that are needed for execution.
 pub struct ExecutionConfig {
     /// The strk address to receive fees
@@ -116,6 +140,14 @@ pub struct ExecutionConfig {
     pub eth_fee_contract_address: ContractAddress,
     /// The initial gas cost for a transaction
     pub initial_gas_cost: u64,
+    /// Whether to check that the compiled classes the transactions run are available before
+    /// executing any of them, instead of failing when the missing class is reached.
+    pub precheck_compiled_classes: bool,
+    /// The max size, in felts, of the bytecode of a class compiled from a declared Sierra class.
+    pub max_casm_bytecode_size: usize,
+    /// The max number of classes in the cache of executable classes shared between executions.
+    #[validate(range(min = 1))]
+    pub class_cache_size: usize,
 }
 
 impl Default for ExecutionConfig {
@@ -124,10 +156,46 @@ impl Default for ExecutionConfig {
             strk_fee_contract_address: contract_address!(STRK_FEE_CONTRACT_ADDRESS),
             eth_fee_contract_address: contract_address!(ETH_FEE_CONTRACT_ADDRESS),
             initial_gas_cost: INITIAL_GAS_COST,
+            precheck_compiled_classes: false,
+            max_casm_bytecode_size: MAX_CASM_BYTECODE_SIZE,
+            class_cache_size: CLASS_CACHE_SIZE,
+        }
+    }
+}
+
+impl ExecutionConfig {
+    /// Checks that the fee contract addresses are non-zero and distinct. Otherwise, the fee token
+    /// balances are read from the wrong contract.
+    pub fn validate_fee_contract_addresses(&self) -> ExecutionResult<()> {
+        let zero_address = ContractAddress::default();
+        if self.strk_fee_contract_address == zero_address {
+            return Err(ExecutionError::ConfigContentError(
+                "strk_fee_contract_address must be non-zero".to_string(),
+            ));
+        }
+        if self.eth_fee_contract_address == zero_address {
+            return Err(ExecutionError::ConfigContentError(
+                "eth_fee_contract_address must be non-zero".to_string(),
+            ));
+        }
+        if self.strk_fee_contract_address == self.eth_fee_contract_address {
+            return Err(ExecutionError::ConfigContentError(
+                "strk_fee_contract_address and eth_fee_contract_address must be different"
+                    .to_string(),
+            ));
         }
+        Ok(())
     }
 }
 
+fn validate_execution_config(config: &ExecutionConfig) -> Result<(), ValidationError> {
+    config.validate_fee_contract_addresses().map_err(|err| {
+        let mut error = ValidationError::new("Invalid fee contract addresses");
+        error.message = Some(err.to_string().into());
+        error
+    })
+}
+
 impl SerializeConfig for ExecutionConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
         BTreeMap::from_iter([
@@ -149,6 +217,27 @@ impl SerializeConfig for ExecutionConfig {
                 "The initial gas cost for a transaction",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "precheck_compiled_classes",
+                &self.precheck_compiled_classes,
+                "Whether to check that the compiled classes of the classes the transactions run \
+                 are available before executing any transaction.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_casm_bytecode_size",
+                &self.max_casm_bytecode_size,
+                "Max size, in felts, of the bytecode compiled from the Sierra class of a declare \
+                 transaction that is simulated or whose fee is estimated.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "class_cache_size",
+                &self.class_cache_size,
+                "Max number of classes in the cache of executable classes shared between \
+                 executions.",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
@@ -163,8 +252,8 @@ pub enum ExecutionError {
         #[source]
         err: blockifier::execution::errors::ContractClassError,
     },
-    #[error("Execution config file does not contain a configuration for all blocks")]
-    ConfigContentError,
+    #[error("Invalid execution config: {0}")]
+    ConfigContentError(String),
     #[error(transparent)]
     ConfigFileError(#[from] std::io::Error),
     #[error(transparent)]
@@ -182,6 +271,8 @@ pub enum ExecutionError {
     MissingClassHash,
     #[error("Missing compiled class with hash {class_hash} (The CASM table isn't synced)")]
     MissingCompiledClass { class_hash: ClassHash },
+    #[error("The class with hash {class_hash} is not declared at state number {state_number:?}.")]
+    ClassNotDeclared { class_hash: ClassHash, state_number: StateNumber },
     #[error(transparent)]
     StateError(#[from] blockifier::state::errors::StateError),
     #[error(transparent)]
@@ -192,6 +283,8 @@ pub enum ExecutionError {
         "Execution failed at transaction {transaction_index:?} with error: {execution_error:?}"
     )]
     TransactionExecutionError { transaction_index: usize, execution_error: String },
+    #[error("Execution of transaction {transaction_index:?} exceeded the execution time budget.")]
+    ExecutionTimeout { transaction_index: usize },
     #[error("Failed to calculate transaction hash.")]
     TransactionHashCalculationFailed(StarknetApiError),
     #[error("Unknown builtin name: {builtin_name}")]
@@ -201,23 +294,62 @@ pub enum ExecutionError {
 /// Whether the only-query bit of the transaction version is on.
 pub type OnlyQuery = bool;
 
+/// The state on top of which to execute and the block in which the execution should run. See the
+/// module documentation for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionInputs {
+    /// The state of the storage at the execution.
+    pub state_number: StateNumber,
+    /// The block in which the execution should run.
+    pub block_context_block_number: BlockNumber,
+}
+
+impl ExecutionInputs {
+    /// Inputs for executing at the beginning of the given block, before any of its transactions.
+    pub fn at_block_start(block_number: BlockNumber) -> Self {
+        Self {
+            state_number: StateNumber::right_before_block(block_number),
+            block_context_block_number: block_number,
+        }
+    }
+
+    /// Inputs for executing at the end of the given block, after all of its transactions.
+    pub fn at_block_end(block_number: BlockNumber) -> Self {
+        Self {
+            state_number: StateNumber::unchecked_right_after_block(block_number),
+            block_context_block_number: block_number,
+        }
+    }
+}
+
 /// Gathers all the possible errors that can be returned from the blockifier.
 type BlockifierError = anyhow::Error;
 
+/// Classes read from the storage, shared between the executions it's passed to. A class hash
+/// determines the class, so a cached class is valid in every state in which it's declared. Create
+/// it once, with [`ExecutionConfig::class_cache_size`] classes, and pass it to every execution.
+pub type ClassCache = GlobalContractCache;
+
 /// Executes a StarkNet call and returns the execution result.
+/// The call is made from `caller_address`, or from the zero address if it's not given, and starts
+/// with `initial_gas`, or with the configured initial gas cost if it's not given.
 #[allow(clippy::too_many_arguments)]
 pub fn execute_call(
     storage_reader: StorageReader,
     maybe_pending_data: Option<PendingData>,
     chain_id: &ChainId,
-    state_number: StateNumber,
-    block_context_number: BlockNumber,
+    execution_inputs: ExecutionInputs,
     contract_address: &ContractAddress,
     entry_point_selector: EntryPointSelector,
     calldata: Calldata,
+    caller_address: Option<ContractAddress>,
+    initial_gas: Option<u64>,
     execution_config: &ExecutionConfig,
+    class_cache: &ClassCache,
     override_kzg_da_to_false: bool,
 ) -> ExecutionResult<CallExecution> {
+    let ExecutionInputs { state_number, block_context_block_number: block_context_number } =
+        execution_inputs;
     verify_contract_exists(
         *contract_address,
         &storage_reader,
@@ -232,10 +364,10 @@ pub fn execute_call(
         entry_point_selector,
         calldata,
         storage_address: *contract_address,
-        caller_address: ContractAddress::default(),
+        caller_address: caller_address.unwrap_or_default(),
         call_type: BlockifierCallType::Call,
         // TODO(yair): check if this is the correct value.
-        initial_gas: execution_config.initial_gas_cost,
+        initial_gas: initial_gas.unwrap_or(execution_config.initial_gas_cost),
     };
 
     let mut cached_state = CachedState::new(ExecutionStateReader {
@@ -243,6 +375,9 @@ pub fn execute_call(
         state_number,
         maybe_pending_data: maybe_pending_data.clone(),
         missing_compiled_class: Cell::new(None),
+        undeclared_class: Some(Cell::new(None)),
+        class_cache: class_cache.clone(),
+        nonce_overrides: HashMap::new(),
     });
 
     let block_context = create_block_context(
@@ -270,6 +405,10 @@ pub fn execute_call(
         .map_err(|error| {
             if let Some(class_hash) = cached_state.state.missing_compiled_class.get() {
                 ExecutionError::MissingCompiledClass { class_hash }
+            } else if let Some(class_hash) =
+                cached_state.state.undeclared_class.as_ref().and_then(Cell::get)
+            {
+                ExecutionError::ClassNotDeclared { class_hash, state_number }
             } else {
                 ExecutionError::ContractError(error.into())
             }
@@ -401,7 +540,7 @@ const DEPRECATED_CONTRACT_SIERRA_SIZE: SierraSize = 0;
 // TODO(yair): This should use broadcasted transactions instead of regular transactions, but the
 // blockifier expects regular transactions. Consider changing the blockifier to use broadcasted txs.
 #[allow(missing_docs)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ExecutableTransactionInput {
     Invoke(InvokeTransaction, OnlyQuery),
     // todo(yair): Do we need to support V0?
@@ -410,6 +549,9 @@ pub enum ExecutableTransactionInput {
     DeclareV2(DeclareTransactionV2, CasmContractClass, SierraSize, AbiSize, OnlyQuery),
     DeclareV3(DeclareTransactionV3, CasmContractClass, SierraSize, AbiSize, OnlyQuery),
     DeployAccount(DeployAccountTransaction, OnlyQuery),
+    // Deploy transactions exist only in old blocks. They're executed without validation and fee
+    // charging.
+    Deploy(DeployTransaction, OnlyQuery),
     L1Handler(L1HandlerTransaction, Fee, OnlyQuery),
 }
 
@@ -423,6 +565,17 @@ impl ExecutableTransactionInput {
         }
     }
 
+    // Returns the address of the account that sends the transaction, if there is one.
+    fn sender_address(&self) -> Option<ContractAddress> {
+        match self {
+            Self::Invoke(tx, _) => Some(tx.sender_address()),
+            Self::DeclareV0(tx, ..) | Self::DeclareV1(tx, ..) => Some(tx.sender_address),
+            Self::DeclareV2(tx, ..) => Some(tx.sender_address),
+            Self::DeclareV3(tx, ..) => Some(tx.sender_address),
+            Self::DeployAccount(..) | Self::Deploy(..) | Self::L1Handler(..) => None,
+        }
+    }
+
     /// Applies a non consuming function on the transaction as if it was of type [Transaction] of
     /// StarknetAPI and returns the result without cloning the original transaction.
     // TODO(yair): Refactor this.
@@ -491,6 +644,14 @@ impl ExecutableTransactionInput {
                 };
                 (Self::DeployAccount(tx, only_query), res)
             }
+            ExecutableTransactionInput::Deploy(tx, only_query) => {
+                let as_transaction = Transaction::Deploy(tx);
+                let res = func(&as_transaction, only_query);
+                let Transaction::Deploy(tx) = as_transaction else {
+                    unreachable!("Should be deploy transaction.")
+                };
+                (Self::Deploy(tx, only_query), res)
+            }
             ExecutableTransactionInput::L1Handler(tx, fee, only_query) => {
                 let as_transaction = Transaction::L1Handler(tx);
                 let res = func(&as_transaction, only_query);
@@ -511,6 +672,7 @@ impl ExecutableTransactionInput {
             ExecutableTransactionInput::DeclareV2(..) => TransactionVersion::TWO,
             ExecutableTransactionInput::DeclareV3(..) => TransactionVersion::THREE,
             ExecutableTransactionInput::DeployAccount(tx, ..) => tx.version(),
+            ExecutableTransactionInput::Deploy(tx, ..) => tx.version,
             ExecutableTransactionInput::L1Handler(tx, ..) => tx.version,
         }
     }
@@ -549,13 +711,15 @@ pub fn estimate_fee(
     chain_id: &ChainId,
     storage_reader: StorageReader,
     maybe_pending_data: Option<PendingData>,
-    state_number: StateNumber,
-    block_context_block_number: BlockNumber,
+    execution_inputs: ExecutionInputs,
     execution_config: &ExecutionConfig,
+    class_cache: &ClassCache,
     validate: bool,
     override_kzg_da_to_false: bool,
+    max_execution_duration: Option<Duration>,
 ) -> ExecutionResult<FeeEstimationResult> {
-    let (txs_execution_info, block_context) = execute_transactions(
+    let ExecutionInputs { state_number, block_context_block_number } = execution_inputs;
+    let (txs_execution_info, block_context) = execute_transactions_with_budget(
         txs,
         None,
         chain_id,
@@ -564,9 +728,12 @@ pub fn estimate_fee(
         state_number,
         block_context_block_number,
         execution_config,
+        class_cache,
         false,
         validate,
         override_kzg_da_to_false,
+        max_execution_duration,
+        None,
     )?;
     let mut result = Vec::new();
     for (index, tx_execution_output) in txs_execution_info.into_iter().enumerate() {
@@ -587,6 +754,99 @@ struct TransactionExecutionOutput {
     price_unit: PriceUnit,
 }
 
+// Messages sent from the thread that executes the transactions when there's an execution budget.
+enum ExecutionProgress {
+    TransactionStarted(usize),
+    Finished(Box<ExecutionResult<(Vec<TransactionExecutionOutput>, BlockContext)>>),
+}
+
+// Executes the transactions and fails if the execution of a single transaction takes more than
+// max_execution_duration. The blockifier can't be interrupted, so the execution runs in a separate
+// thread that is left to finish in the background once the budget is exceeded.
+#[allow(clippy::too_many_arguments)]
+fn execute_transactions_with_budget(
+    txs: Vec<ExecutableTransactionInput>,
+    tx_hashes: Option<Vec<TransactionHash>>,
+    chain_id: &ChainId,
+    storage_reader: StorageReader,
+    maybe_pending_data: Option<PendingData>,
+    state_number: StateNumber,
+    block_context_block_number: BlockNumber,
+    execution_config: &ExecutionConfig,
+    class_cache: &ClassCache,
+    charge_fee: bool,
+    validate: bool,
+    override_kzg_da_to_false: bool,
+    max_execution_duration: Option<Duration>,
+    nonce_override: Option<NonceOverride>,
+) -> ExecutionResult<(Vec<TransactionExecutionOutput>, BlockContext)> {
+    let Some(max_execution_duration) = max_execution_duration else {
+        return execute_transactions(
+            txs,
+            tx_hashes,
+            chain_id,
+            storage_reader,
+            maybe_pending_data,
+            state_number,
+            block_context_block_number,
+            execution_config,
+            class_cache,
+            charge_fee,
+            validate,
+            override_kzg_da_to_false,
+            nonce_override,
+            |_| {},
+        );
+    };
+
+    let (progress_sender, progress_receiver) = channel();
+    let chain_id = chain_id.clone();
+    let execution_config = *execution_config;
+    let class_cache = class_cache.clone();
+    let execution_handle = thread::spawn(move || {
+        let result = execute_transactions(
+            txs,
+            tx_hashes,
+            &chain_id,
+            storage_reader,
+            maybe_pending_data,
+            state_number,
+            block_context_block_number,
+            &execution_config,
+            &class_cache,
+            charge_fee,
+            validate,
+            override_kzg_da_to_false,
+            nonce_override,
+            |transaction_index| {
+                // The receiver is dropped if the budget was exceeded, no one is waiting for the
+                // progress in that case.
+                let _ =
+                    progress_sender.send(ExecutionProgress::TransactionStarted(transaction_index));
+            },
+        );
+        let _ = progress_sender.send(ExecutionProgress::Finished(Box::new(result)));
+    });
+
+    let mut transaction_index = 0;
+    loop {
+        match progress_receiver.recv_timeout(max_execution_duration) {
+            Ok(ExecutionProgress::TransactionStarted(started_transaction_index)) => {
+                transaction_index = started_transaction_index;
+            }
+            Ok(ExecutionProgress::Finished(result)) => return *result,
+            Err(RecvTimeoutError::Timeout) => {
+                return Err(ExecutionError::ExecutionTimeout { transaction_index });
+            }
+            // The sender is dropped without sending the result only if the execution panicked.
+            Err(RecvTimeoutError::Disconnected) => match execution_handle.join() {
+                Err(panic) => std::panic::resume_unwind(panic),
+                Ok(()) => unreachable!("The execution thread finished without sending a result."),
+            },
+        }
+    }
+}
+
 // Executes a series of transactions and returns the execution results.
 // TODO(yair): Return structs instead of tuples.
 #[allow(clippy::too_many_arguments)]
@@ -599,16 +859,26 @@ fn execute_transactions(
     state_number: StateNumber,
     block_context_block_number: BlockNumber,
     execution_config: &ExecutionConfig,
+    class_cache: &ClassCache,
     charge_fee: bool,
     validate: bool,
     override_kzg_da_to_false: bool,
+    nonce_override: Option<NonceOverride>,
+    mut on_transaction_start: impl FnMut(usize),
 ) -> ExecutionResult<(Vec<TransactionExecutionOutput>, BlockContext)> {
+    let nonce_overrides = nonce_override
+        .into_iter()
+        .map(|NonceOverride { sender_address, nonce }| (sender_address, nonce))
+        .collect();
     // The starknet state will be from right before the block in which the transactions should run.
     let mut cached_state = CachedState::new(ExecutionStateReader {
         storage_reader: storage_reader.clone(),
         state_number,
         maybe_pending_data: maybe_pending_data.clone(),
         missing_compiled_class: Cell::new(None),
+        undeclared_class: None,
+        class_cache: class_cache.clone(),
+        nonce_overrides,
     });
 
     let block_context = create_block_context(
@@ -630,9 +900,14 @@ fn execute_transactions(
         }
     };
 
+    if execution_config.precheck_compiled_classes {
+        precheck_compiled_classes(&txs, &cached_state.state)?;
+    }
+
     let mut res = vec![];
     for (transaction_index, (tx, tx_hash)) in txs.into_iter().zip(tx_hashes.into_iter()).enumerate()
     {
+        on_transaction_start(transaction_index);
         let transaction_version = tx.transaction_version();
         // TODO: consider supporting match instead.
         let price_unit = if transaction_version == TransactionVersion::ZERO
@@ -659,9 +934,25 @@ fn execute_transactions(
             ) => Some(*class_hash),
             _ => None,
         };
-        let blockifier_tx = to_blockifier_tx(tx, tx_hash, transaction_index)?;
-        let tx_execution_info_result =
-            blockifier_tx.execute(&mut transactional_state, &block_context, charge_fee, validate);
+        let tx_execution_info_result = match tx {
+            // The blockifier doesn't have a Deploy transaction, so the deployment is executed
+            // directly.
+            ExecutableTransactionInput::Deploy(deploy_tx, only_query) => {
+                execute_deploy_transaction(
+                    deploy_tx,
+                    tx_hash,
+                    only_query,
+                    &mut transactional_state,
+                    &block_context,
+                )
+            }
+            tx => to_blockifier_tx(tx, tx_hash, transaction_index)?.execute(
+                &mut transactional_state,
+                &block_context,
+                charge_fee,
+                validate,
+            ),
+        };
         let state_diff =
             induced_state_diff(&mut transactional_state, deprecated_declared_class_hash)?;
         transactional_state.commit();
@@ -682,6 +973,54 @@ fn execute_transactions(
     Ok((res, block_context))
 }
 
+// Returns a MissingCompiledClass error if the compiled class of a class that one of the
+// transactions runs directly is missing. Classes that are only reached through calls during the
+// execution aren't checked.
+fn precheck_compiled_classes(
+    txs: &[ExecutableTransactionInput],
+    state_reader: &ExecutionStateReader,
+) -> ExecutionResult<()> {
+    let txn = state_reader.storage_reader.begin_ro_txn()?;
+    let maybe_pending_data = state_reader.maybe_pending_data.as_ref();
+    let class_hash_at = |contract_address| {
+        execution_utils::get_class_hash_at(
+            &txn,
+            state_reader.state_number,
+            maybe_pending_data.map(|pending_data| {
+                (&pending_data.deployed_contracts, &pending_data.replaced_classes)
+            }),
+            contract_address,
+        )
+    };
+    for tx in txs {
+        let class_hash = match tx {
+            ExecutableTransactionInput::DeployAccount(tx, _) => Some(tx.class_hash()),
+            ExecutableTransactionInput::Deploy(tx, _) => Some(tx.class_hash),
+            ExecutableTransactionInput::L1Handler(tx, ..) => class_hash_at(tx.contract_address)?,
+            tx => tx.sender_address().map(class_hash_at).transpose()?.flatten(),
+        };
+        // Classes that aren't declared yet may be declared by an earlier transaction in the batch,
+        // so they're left for the execution to report.
+        let Some(class_hash) = class_hash else {
+            continue;
+        };
+        if maybe_pending_data.is_some_and(|pending_data| {
+            pending_data.classes.get_compiled_class(class_hash).is_some()
+        }) {
+            continue;
+        }
+        // Deprecated classes don't have a compiled class.
+        match txn.get_state_reader()?.get_class_definition_block_number(&class_hash)? {
+            Some(block_number) if !state_reader.state_number.is_before(block_number) => {}
+            _ => continue,
+        }
+        if txn.get_casm(&class_hash)?.is_none() {
+            return Err(ExecutionError::MissingCompiledClass { class_hash });
+        }
+    }
+    Ok(())
+}
+
 /// Converts a transaction index and [BlockifierTransactionExecutionError] to an [ExecutionError].
 // TODO(yair): Remove once blockifier arranges the errors hierarchy.
 impl From<(usize, BlockifierTransactionExecutionError)> for ExecutionError {
@@ -710,6 +1049,54 @@ fn get_10_blocks_ago(
     }))
 }
 
+/// Executes a Deploy transaction by instantiating the class at the address derived from the
+/// transaction and running its constructor.
+fn execute_deploy_transaction(
+    deploy_tx: DeployTransaction,
+    tx_hash: TransactionHash,
+    only_query: OnlyQuery,
+    state: &mut dyn State,
+    block_context: &BlockContext,
+) -> Result<TransactionExecutionInfo, BlockifierTransactionExecutionError> {
+    let contract_address = calculate_contract_address(
+        deploy_tx.contract_address_salt,
+        deploy_tx.class_hash,
+        &deploy_tx.constructor_calldata,
+        ContractAddress::default(),
+    )?;
+    let tx_info = TransactionInfo::Deprecated(DeprecatedTransactionInfo {
+        common_fields: CommonAccountFields {
+            transaction_hash: tx_hash,
+            version: deploy_tx.version,
+            only_query,
+            ..Default::default()
+        },
+        max_fee: Fee::default(),
+    });
+    let mut context = EntryPointExecutionContext::new_invoke(
+        Arc::new(TransactionContext { block_context: block_context.clone(), tx_info }),
+        false,
+    )?;
+    let ctor_context = ConstructorContext {
+        class_hash: deploy_tx.class_hash,
+        code_address: None,
+        storage_address: contract_address,
+        caller_address: ContractAddress::default(),
+    };
+    let execute_call_info = execute_deployment(
+        state,
+        &mut ExecutionResources::default(),
+        &mut context,
+        ctor_context,
+        deploy_tx.constructor_calldata,
+        block_context.versioned_constants().tx_initial_gas(),
+    )?;
+    Ok(TransactionExecutionInfo {
+        execute_call_info: Some(execute_call_info),
+        ..Default::default()
+    })
+}
+
 fn to_blockifier_tx(
     tx: ExecutableTransactionInput,
     tx_hash: TransactionHash,
@@ -748,21 +1135,15 @@ fn to_blockifier_tx(
             abi_length,
             only_query,
         ) => {
-            let class_v0 = BlockifierContractClass::V0(deprecated_class.try_into().map_err(
-                |e: cairo_vm::types::errors::program_errors::ProgramError| {
-                    ExecutionError::TransactionExecutionError {
-                        transaction_index,
-                        execution_error: e.to_string(),
-                    }
-                },
-            )?);
-            let class_info = ClassInfo::new(&class_v0, DEPRECATED_CONTRACT_SIERRA_SIZE, abi_length)
-                .map_err(|err| ExecutionError::BadDeclareTransaction {
-                    tx: DeclareTransaction::V0(declare_tx.clone()),
-                    err,
-                })?;
+            let declare_tx = DeclareTransaction::V0(declare_tx);
+            let class_info = get_declared_class_info(
+                &declare_tx,
+                ExecutableClass::Deprecated(deprecated_class),
+                DEPRECATED_CONTRACT_SIERRA_SIZE,
+                abi_length,
+            )?;
             BlockifierTransaction::from_api(
-                Transaction::Declare(DeclareTransaction::V0(declare_tx)),
+                Transaction::Declare(declare_tx),
                 tx_hash,
                 Some(class_info),
                 None,
@@ -777,16 +1158,15 @@ fn to_blockifier_tx(
             abi_length,
             only_query,
         ) => {
-            let class_v0 = BlockifierContractClass::V0(
-                deprecated_class.try_into().map_err(BlockifierError::new)?,
-            );
-            let class_info = ClassInfo::new(&class_v0, DEPRECATED_CONTRACT_SIERRA_SIZE, abi_length)
-                .map_err(|err| ExecutionError::BadDeclareTransaction {
-                    tx: DeclareTransaction::V1(declare_tx.clone()),
-                    err,
-                })?;
+            let declare_tx = DeclareTransaction::V1(declare_tx);
+            let class_info = get_declared_class_info(
+                &declare_tx,
+                ExecutableClass::Deprecated(deprecated_class),
+                DEPRECATED_CONTRACT_SIERRA_SIZE,
+                abi_length,
+            )?;
             BlockifierTransaction::from_api(
-                Transaction::Declare(DeclareTransaction::V1(declare_tx)),
+                Transaction::Declare(declare_tx),
                 tx_hash,
                 Some(class_info),
                 None,
@@ -802,18 +1182,15 @@ fn to_blockifier_tx(
             abi_length,
             only_query,
         ) => {
-            let class_v1 = BlockifierContractClass::V1(
-                compiled_class.try_into().map_err(BlockifierError::new)?,
-            );
-            let class_info =
-                ClassInfo::new(&class_v1, sierra_program_length, abi_length).map_err(|err| {
-                    ExecutionError::BadDeclareTransaction {
-                        tx: DeclareTransaction::V2(declare_tx.clone()),
-                        err,
-                    }
-                })?;
+            let declare_tx = DeclareTransaction::V2(declare_tx);
+            let class_info = get_declared_class_info(
+                &declare_tx,
+                ExecutableClass::Compiled(compiled_class),
+                sierra_program_length,
+                abi_length,
+            )?;
             BlockifierTransaction::from_api(
-                Transaction::Declare(DeclareTransaction::V2(declare_tx)),
+                Transaction::Declare(declare_tx),
                 tx_hash,
                 Some(class_info),
                 None,
@@ -829,18 +1206,15 @@ fn to_blockifier_tx(
             abi_length,
             only_query,
         ) => {
-            let class_v1 = BlockifierContractClass::V1(
-                compiled_class.try_into().map_err(BlockifierError::new)?,
-            );
-            let class_info =
-                ClassInfo::new(&class_v1, sierra_program_length, abi_length).map_err(|err| {
-                    ExecutionError::BadDeclareTransaction {
-                        tx: DeclareTransaction::V3(declare_tx.clone()),
-                        err,
-                    }
-                })?;
+            let declare_tx = DeclareTransaction::V3(declare_tx);
+            let class_info = get_declared_class_info(
+                &declare_tx,
+                ExecutableClass::Compiled(compiled_class),
+                sierra_program_length,
+                abi_length,
+            )?;
             BlockifierTransaction::from_api(
-                Transaction::Declare(DeclareTransaction::V3(declare_tx)),
+                Transaction::Declare(declare_tx),
                 tx_hash,
                 Some(class_info),
                 None,
@@ -849,6 +1223,11 @@ fn to_blockifier_tx(
             )
             .map_err(|err| ExecutionError::from((transaction_index, err)))
         }
+        ExecutableTransactionInput::Deploy(..) => Err(ExecutionError::TransactionExecutionError {
+            transaction_index,
+            execution_error: "Deploy transactions can't be converted to a blockifier transaction."
+                .to_string(),
+        }),
         ExecutableTransactionInput::L1Handler(l1_handler_tx, paid_fee, only_query) => {
             BlockifierTransaction::from_api(
                 Transaction::L1Handler(l1_handler_tx),
@@ -863,6 +1242,19 @@ fn to_blockifier_tx(
     }
 }
 
+// Converts the class declared by a declare transaction to the blockifier's class info, which holds
+// the class sizes that the fee depends on.
+fn get_declared_class_info(
+    declare_tx: &DeclareTransaction,
+    class: ExecutableClass,
+    sierra_program_length: SierraSize,
+    abi_length: AbiSize,
+) -> ExecutionResult<ClassInfo> {
+    let class = BlockifierContractClass::try_from(class).map_err(BlockifierError::new)?;
+    ClassInfo::new(&class, sierra_program_length, abi_length)
+        .map_err(|err| ExecutionError::BadDeclareTransaction { tx: declare_tx.clone(), err })
+}
+
 // TODO(dan): add 0_13_1_1 support
 fn get_versioned_constants(
     starknet_version: Option<&StarknetVersion>,
@@ -882,7 +1274,19 @@ fn get_versioned_constants(
     Ok(versioned_constants)
 }
 
+/// A nonce that a sender is assumed to have instead of its stored nonce when simulating
+/// transactions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NonceOverride {
+    /// The address of the sender whose nonce is overridden.
+    pub sender_address: ContractAddress,
+    /// The nonce the sender is assumed to have.
+    pub nonce: Nonce,
+}
+
 /// Simulates a series of transactions and returns the transaction traces and the fee estimations.
+/// With a nonce override, the overridden sender is assumed to have the given nonce instead of its
+/// stored nonce.
 // TODO(yair): Return structs instead of tuples.
 #[allow(clippy::too_many_arguments)]
 pub fn simulate_transactions(
@@ -894,12 +1298,15 @@ pub fn simulate_transactions(
     state_number: StateNumber,
     block_context_block_number: BlockNumber,
     execution_config: &ExecutionConfig,
+    class_cache: &ClassCache,
     charge_fee: bool,
     validate: bool,
     override_kzg_da_to_false: bool,
+    max_execution_duration: Option<Duration>,
+    nonce_override: Option<NonceOverride>,
 ) -> ExecutionResult<Vec<TransactionSimulationOutput>> {
     let trace_constructors = txs.iter().map(get_trace_constructor).collect::<Vec<_>>();
-    let (execution_results, block_context) = execute_transactions(
+    let (execution_results, block_context) = execute_transactions_with_budget(
         txs,
         tx_hashes,
         chain_id,
@@ -908,9 +1315,12 @@ pub fn simulate_transactions(
         state_number,
         block_context_block_number,
         execution_config,
+        class_cache,
         charge_fee,
         validate,
         override_kzg_da_to_false,
+        max_execution_duration,
+        nonce_override,
     )?;
     execution_results
         .into_iter()
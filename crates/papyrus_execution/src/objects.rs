@@ -14,6 +14,7 @@ use cairo_vm::types::builtin_name::BuiltinName;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources as VmExecutionResources;
 use indexmap::IndexMap;
 use itertools::Itertools;
+use num_traits::ToPrimitive;
 use papyrus_common::pending_classes::PendingClasses;
 use papyrus_common::state::{
     DeclaredClassHashEntry,
@@ -107,6 +108,101 @@ pub struct FeeEstimation {
     pub unit: PriceUnit,
 }
 
+/// An error for adding fee estimations.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeEstimationAdditionError {
+    /// The fees have different price units.
+    #[error("Can't add fees with different price units: {first:?} and {second:?}.")]
+    PriceUnitMismatch {
+        /// The price unit of the first fee.
+        first: PriceUnit,
+        /// The price unit of the second fee.
+        second: PriceUnit,
+    },
+    /// The fees were estimated with different gas prices.
+    #[error("Can't add fees with different gas prices: {first:?} and {second:?}.")]
+    GasPriceMismatch {
+        /// The gas price of the first fee.
+        first: GasPrice,
+        /// The gas price of the second fee.
+        second: GasPrice,
+    },
+    /// The fees were estimated with different data gas prices.
+    #[error("Can't add fees with different data gas prices: {first:?} and {second:?}.")]
+    DataGasPriceMismatch {
+        /// The data gas price of the first fee.
+        first: GasPrice,
+        /// The data gas price of the second fee.
+        second: GasPrice,
+    },
+    /// The sum of the fees (or of the gas they consumed) doesn't fit in a u128.
+    #[error("The sum of the fees overflows.")]
+    Overflow,
+}
+
+impl FeeEstimation {
+    /// Returns the total of the two fee estimations. Fails if their price units or gas prices
+    /// differ, or if the total overflows.
+    pub fn checked_add(
+        &self,
+        other: &FeeEstimation,
+    ) -> Result<FeeEstimation, FeeEstimationAdditionError> {
+        if self.unit != other.unit {
+            return Err(FeeEstimationAdditionError::PriceUnitMismatch {
+                first: self.unit,
+                second: other.unit,
+            });
+        }
+        if self.gas_price != other.gas_price {
+            return Err(FeeEstimationAdditionError::GasPriceMismatch {
+                first: self.gas_price,
+                second: other.gas_price,
+            });
+        }
+        if self.data_gas_price != other.data_gas_price {
+            return Err(FeeEstimationAdditionError::DataGasPriceMismatch {
+                first: self.data_gas_price,
+                second: other.data_gas_price,
+            });
+        }
+        let overall_fee = self
+            .overall_fee
+            .0
+            .checked_add(other.overall_fee.0)
+            .ok_or(FeeEstimationAdditionError::Overflow)?;
+        Ok(FeeEstimation {
+            gas_consumed: checked_add_gas(self.gas_consumed, other.gas_consumed)?,
+            gas_price: self.gas_price,
+            data_gas_consumed: checked_add_gas(self.data_gas_consumed, other.data_gas_consumed)?,
+            data_gas_price: self.data_gas_price,
+            overall_fee: Fee(overall_fee),
+            unit: self.unit,
+        })
+    }
+}
+
+// The gas amounts are u128 values stored as felts, so they are added as u128 to detect overflows
+// instead of wrapping around the field prime.
+fn checked_add_gas(first: Felt, second: Felt) -> Result<Felt, FeeEstimationAdditionError> {
+    first
+        .to_u128()
+        .zip(second.to_u128())
+        .and_then(|(first, second)| first.checked_add(second))
+        .map(Felt::from)
+        .ok_or(FeeEstimationAdditionError::Overflow)
+}
+
+/// Returns the total of the given fee estimations, or None if there are none. Fails if the fees
+/// can't be added (see [`FeeEstimation::checked_add`]).
+pub fn sum_fees(
+    fees: &[FeeEstimation],
+) -> Result<Option<FeeEstimation>, FeeEstimationAdditionError> {
+    let Some((first, rest)) = fees.split_first() else {
+        return Ok(None);
+    };
+    rest.iter().try_fold(first.clone(), |total, fee| total.checked_add(fee)).map(Some)
+}
+
 /// The reason for a reverted transaction.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[allow(missing_docs)]
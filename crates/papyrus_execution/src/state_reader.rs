@@ -3,13 +3,11 @@
 mod state_reader_test;
 
 use std::cell::Cell;
+use std::collections::HashMap;
 
-use blockifier::execution::contract_class::{
-    ContractClass as BlockifierContractClass,
-    ContractClassV0,
-    ContractClassV1,
-};
+use blockifier::execution::contract_class::ContractClass as BlockifierContractClass;
 use blockifier::state::errors::StateError;
+use blockifier::state::global_cache::GlobalContractCache;
 use blockifier::state::state_api::{StateReader as BlockifierStateReader, StateResult};
 use papyrus_common::pending_classes::{ApiContractClass, PendingClassesTrait};
 use papyrus_common::state::DeclaredClassHashEntry;
@@ -20,7 +18,7 @@ use starknet_api::state::{StateNumber, StorageKey};
 use starknet_types_core::felt::Felt;
 
 use crate::execution_utils;
-use crate::execution_utils::{get_contract_class, ExecutionUtilsError};
+use crate::execution_utils::{get_contract_class, ExecutableClass, ExecutionUtilsError};
 use crate::objects::PendingData;
 
 /// A view into the state at a specific state number.
@@ -31,6 +29,14 @@ pub struct ExecutionStateReader {
     // We want to return a custom error when missing a compiled class, but we need to return
     // Blockifier's error, so we store the missing class's hash in case of error.
     pub missing_compiled_class: Cell<Option<ClassHash>>,
+    // Same as `missing_compiled_class`, for classes that aren't declared at the state number. None
+    // if the caller doesn't report undeclared classes.
+    pub undeclared_class: Option<Cell<Option<ClassHash>>>,
+    // Executable classes read from the storage, shared between executions.
+    pub class_cache: GlobalContractCache,
+    // Nonces that are returned instead of the stored ones, used for simulating transactions as if
+    // their senders had a different nonce.
+    pub nonce_overrides: HashMap<ContractAddress, Nonce>,
 }
 
 impl BlockifierStateReader for ExecutionStateReader {
@@ -51,6 +57,9 @@ impl BlockifierStateReader for ExecutionStateReader {
 
     // Returns the default value if the contract address is not found.
     fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        if let Some(nonce) = self.nonce_overrides.get(&contract_address) {
+            return Ok(*nonce);
+        }
         Ok(execution_utils::get_nonce_at(
             &self.storage_reader.begin_ro_txn().map_err(storage_err_to_state_err)?,
             self.state_number,
@@ -84,27 +93,32 @@ impl BlockifierStateReader for ExecutionStateReader {
             .as_ref()
             .and_then(|pending_data| pending_data.classes.get_compiled_class(class_hash))
         {
-            return Ok(BlockifierContractClass::V1(
-                ContractClassV1::try_from(pending_casm).map_err(StateError::ProgramError)?,
-            ));
+            return ExecutableClass::Compiled(pending_casm)
+                .try_into()
+                .map_err(StateError::ProgramError);
         }
         if let Some(ApiContractClass::DeprecatedContractClass(pending_deprecated_class)) = self
             .maybe_pending_data
             .as_ref()
             .and_then(|pending_data| pending_data.classes.get_class(class_hash))
         {
-            return Ok(BlockifierContractClass::V0(
-                ContractClassV0::try_from(pending_deprecated_class)
-                    .map_err(StateError::ProgramError)?,
-            ));
+            return ExecutableClass::Deprecated(pending_deprecated_class)
+                .try_into()
+                .map_err(StateError::ProgramError);
         }
         match get_contract_class(
             &self.storage_reader.begin_ro_txn().map_err(storage_err_to_state_err)?,
             &class_hash,
             self.state_number,
+            &self.class_cache,
         ) {
             Ok(Some(contract_class)) => Ok(contract_class),
-            Ok(None) => Err(StateError::UndeclaredClassHash(class_hash)),
+            Ok(None) => {
+                if let Some(undeclared_class) = &self.undeclared_class {
+                    undeclared_class.set(Some(class_hash));
+                }
+                Err(StateError::UndeclaredClassHash(class_hash))
+            }
             Err(ExecutionUtilsError::CasmTableNotSynced) => {
                 self.missing_compiled_class.set(Some(class_hash));
                 Err(StateError::StateReadError("Casm table not fully synced".to_string()))
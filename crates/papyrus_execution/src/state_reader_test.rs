@@ -1,4 +1,5 @@
 use std::cell::Cell;
+use std::collections::HashMap;
 
 use assert_matches::assert_matches;
 use blockifier::execution::contract_class::{
@@ -7,6 +8,7 @@ use blockifier::execution::contract_class::{
     ContractClassV1,
 };
 use blockifier::state::errors::StateError;
+use blockifier::state::global_cache::{GlobalContractCache, GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST};
 use blockifier::state::state_api::StateReader;
 use cairo_lang_utils::bigint::BigUintAsHex;
 use indexmap::indexmap;
@@ -150,6 +152,9 @@ fn read_state() {
         state_number: state_number0,
         maybe_pending_data: None,
         missing_compiled_class: Cell::new(None),
+        undeclared_class: Some(Cell::new(None)),
+        class_cache: GlobalContractCache::new(GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST),
+        nonce_overrides: HashMap::new(),
     };
     let storage_after_block_0 = state_reader0.get_storage_at(address0, storage_key0).unwrap();
     assert_eq!(storage_after_block_0, Felt::default());
@@ -163,6 +168,7 @@ fn read_state() {
         compiled_contract_class_after_block_0, Err(StateError::UndeclaredClassHash(class_hash))
         if class_hash == class_hash0
     );
+    assert_eq!(state_reader0.undeclared_class.as_ref().unwrap().get().unwrap(), class_hash0);
     assert_eq!(state_reader0.get_compiled_class_hash(class_hash0).unwrap(), compiled_class_hash0);
 
     let state_number1 = StateNumber::unchecked_right_after_block(BlockNumber(1));
@@ -171,6 +177,9 @@ fn read_state() {
         state_number: state_number1,
         maybe_pending_data: None,
         missing_compiled_class: Cell::new(None),
+        undeclared_class: Some(Cell::new(None)),
+        class_cache: GlobalContractCache::new(GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST),
+        nonce_overrides: HashMap::new(),
     };
     let storage_after_block_1 = state_reader1.get_storage_at(address0, storage_key0).unwrap();
     assert_eq!(storage_after_block_1, storage_value0);
@@ -193,6 +202,9 @@ fn read_state() {
         state_number: state_number2,
         maybe_pending_data: None,
         missing_compiled_class: Cell::new(None),
+        undeclared_class: Some(Cell::new(None)),
+        class_cache: GlobalContractCache::new(GLOBAL_CONTRACT_CACHE_SIZE_FOR_TEST),
+        nonce_overrides: HashMap::new(),
     };
     let nonce_after_block_2 = state_reader2.get_nonce_at(address0).unwrap();
     assert_eq!(nonce_after_block_2, nonce0);
@@ -37,6 +37,7 @@ use starknet_api::transaction::{
     DeclareTransactionV2,
     DeployAccountTransaction,
     DeployAccountTransactionV1,
+    DeployTransaction,
     Fee,
     InvokeTransaction,
     InvokeTransactionV1,
@@ -48,7 +49,7 @@ use test_utils::read_json_file;
 
 use crate::execution_utils::selector_from_name;
 use crate::objects::{PendingData, TransactionSimulationOutput};
-use crate::testing_instances::get_test_execution_config;
+use crate::testing_instances::{get_test_class_cache, get_test_execution_config};
 use crate::{simulate_transactions, ExecutableTransactionInput, OnlyQuery, SierraSize};
 
 lazy_static! {
@@ -66,12 +67,17 @@ lazy_static! {
     pub static ref CONTRACT_ADDRESS: ContractAddress = contract_address!("0x2");
     pub static ref ACCOUNT_CLASS_HASH: ClassHash = class_hash!("0x333");
     pub static ref ACCOUNT_ADDRESS: ContractAddress = contract_address!("0x444");
+    // A contract whose class is declared but its compiled class isn't stored.
+    pub static ref MISSING_CASM_CLASS_HASH: ClassHash = class_hash!("0x3");
+    pub static ref MISSING_CASM_CONTRACT_ADDRESS: ContractAddress = contract_address!("0x3");
     // Taken from the trace of the deploy account transaction.
     pub static ref NEW_ACCOUNT_ADDRESS: ContractAddress =
         contract_address!("0x0153ade9ef510502c4f3b879c049dcc3ad5866706cae665f0d9df9b01e794fdb");
     pub static ref TEST_ERC20_CONTRACT_CLASS_HASH: ClassHash = class_hash!("0x1010");
     pub static ref TEST_ERC20_CONTRACT_ADDRESS: ContractAddress = contract_address!("0x1001");
     pub static ref ACCOUNT_INITIAL_BALANCE: Felt = felt!(2 * MAX_FEE.0);
+    pub static ref RECURSIVE_CONTRACT_CLASS_HASH: ClassHash = class_hash!("0x5");
+    pub static ref RECURSIVE_CONTRACT_ADDRESS: ContractAddress = contract_address!("0x5");
 }
 
 // Sierra size must be > 0.
@@ -95,6 +101,11 @@ pub fn get_test_erc20_fee_contract_class() -> DeprecatedContractClass {
 pub fn get_test_account_class() -> DeprecatedContractClass {
     get_test_instance("account_class.json")
 }
+// A deprecated class with a `recurse` entry point, taken from the cairo0 test contract of
+// Blockifier.
+pub fn get_test_recursive_contract_class() -> DeprecatedContractClass {
+    get_test_instance("recursive_contract_class.json")
+}
 
 pub fn prepare_storage(mut storage_writer: StorageWriter) {
     let class_hash0 = class_hash!("0x2");
@@ -130,6 +141,8 @@ pub fn prepare_storage(mut storage_writer: StorageWriter) {
                     *CONTRACT_ADDRESS => class_hash0,
                     *DEPRECATED_CONTRACT_ADDRESS => class_hash1,
                     *ACCOUNT_ADDRESS => *ACCOUNT_CLASS_HASH,
+                    *MISSING_CASM_CONTRACT_ADDRESS => *MISSING_CASM_CLASS_HASH,
+                    *RECURSIVE_CONTRACT_ADDRESS => *RECURSIVE_CONTRACT_CLASS_HASH,
                 ),
                 storage_diffs: indexmap!(
                     *TEST_ERC20_CONTRACT_ADDRESS => indexmap!(
@@ -142,18 +155,21 @@ pub fn prepare_storage(mut storage_writer: StorageWriter) {
                 ),
                 declared_classes: indexmap!(
                     // The class is not used in the execution, so it can be default.
-                    class_hash0 => CompiledClassHash::default()
+                    class_hash0 => CompiledClassHash::default(),
+                    *MISSING_CASM_CLASS_HASH => CompiledClassHash::default()
                 ),
                 deprecated_declared_classes: vec![
                     *TEST_ERC20_CONTRACT_CLASS_HASH,
                     class_hash1,
                     *ACCOUNT_CLASS_HASH,
+                    *RECURSIVE_CONTRACT_CLASS_HASH,
                 ],
                 nonces: indexmap!(
                     *TEST_ERC20_CONTRACT_ADDRESS => Nonce::default(),
                     *CONTRACT_ADDRESS => Nonce::default(),
                     *DEPRECATED_CONTRACT_ADDRESS => Nonce::default(),
                     *ACCOUNT_ADDRESS => Nonce::default(),
+                    *RECURSIVE_CONTRACT_ADDRESS => Nonce::default(),
                 ),
                 replaced_classes: indexmap!(),
             },
@@ -161,11 +177,15 @@ pub fn prepare_storage(mut storage_writer: StorageWriter) {
         .unwrap()
         .append_classes(
             BlockNumber(0),
-            &[(class_hash0, &ContractClass::default())],
+            &[
+                (class_hash0, &ContractClass::default()),
+                (*MISSING_CASM_CLASS_HASH, &ContractClass::default()),
+            ],
             &[
                 (*TEST_ERC20_CONTRACT_CLASS_HASH, &get_test_erc20_fee_contract_class()),
                 (class_hash1, &get_test_deprecated_contract_class()),
                 (*ACCOUNT_CLASS_HASH, &get_test_account_class()),
+                (*RECURSIVE_CONTRACT_CLASS_HASH, &get_test_recursive_contract_class()),
             ],
         )
         .unwrap()
@@ -212,10 +232,13 @@ pub fn execute_simulate_transactions(
         StateNumber::unchecked_right_after_block(BlockNumber(0)),
         BlockNumber(1),
         &get_test_execution_config(),
+        &get_test_class_cache(),
         charge_fee,
         validate,
         // TODO: Consider testing without overriding DA (It's already tested in the RPC)
         true,
+        None,
+        None,
     )
     .unwrap()
 }
@@ -277,6 +300,29 @@ impl TxsScenarioBuilder {
         self
     }
 
+    // Invokes the `recurse` entry point of the recursive contract, which recurses `depth` times.
+    pub fn invoke_recurse(mut self, sender_address: ContractAddress, depth: u128) -> Self {
+        let calldata = calldata![
+            *RECURSIVE_CONTRACT_ADDRESS.0.key(), // Contract address.
+            selector_from_name("recurse").0,     // EP selector.
+            felt!(1_u8),                         // Calldata length.
+            felt!(depth)                         // Calldata: depth.
+        ];
+        let nonce = self.next_nonce(sender_address);
+        let tx = ExecutableTransactionInput::Invoke(
+            InvokeTransaction::V1(InvokeTransactionV1 {
+                calldata,
+                max_fee: *MAX_FEE,
+                sender_address,
+                nonce,
+                ..Default::default()
+            }),
+            false,
+        );
+        self.txs.push(tx);
+        self
+    }
+
     pub fn declare_deprecated_class(mut self, sender_address: ContractAddress) -> Self {
         let tx = ExecutableTransactionInput::DeclareV1(
             DeclareTransactionV0V1 {
@@ -326,6 +372,16 @@ impl TxsScenarioBuilder {
         self
     }
 
+    // Deploy transactions exist only in old blocks, so this is used to test re-execution.
+    pub fn deploy(mut self) -> TxsScenarioBuilder {
+        let tx = ExecutableTransactionInput::Deploy(
+            DeployTransaction { class_hash: *ACCOUNT_CLASS_HASH, ..Default::default() },
+            false,
+        );
+        self.txs.push(tx);
+        self
+    }
+
     // TODO(yair): add l1 handler transaction.
 
     fn next_nonce(&mut self, sender_address: ContractAddress) -> Nonce {
@@ -28,7 +28,7 @@ use crate::objects::{
     RevertReason,
     TransactionTrace,
 };
-use crate::ExecutionConfig;
+use crate::{ClassCache, ExecutionConfig};
 
 /// Creates ExecutionConfig for tests.
 pub fn get_test_execution_config() -> ExecutionConfig {
@@ -36,9 +36,17 @@ pub fn get_test_execution_config() -> ExecutionConfig {
         strk_fee_contract_address: contract_address!("0x1001"),
         eth_fee_contract_address: contract_address!("0x1001"),
         initial_gas_cost: 10_u64.pow(10),
+        precheck_compiled_classes: false,
+        max_casm_bytecode_size: 80 * 1024,
+        class_cache_size: 400,
     }
 }
 
+/// Creates an empty ClassCache for tests, with the size of the test ExecutionConfig.
+pub fn get_test_class_cache() -> ClassCache {
+    ClassCache::new(get_test_execution_config().class_cache_size)
+}
+
 auto_impl_get_test_instance! {
     pub enum TransactionTrace {
         Invoke(InvokeTransactionTrace) = 0,
@@ -0,0 +1,27 @@
+#[cfg(test)]
+#[path = "block_number_generator_test.rs"]
+mod block_number_generator_test;
+
+use rand::Rng;
+
+/// Generates random numbers of blocks up to an upper bound, so that requests are only sent on
+/// blocks that are fully available in the node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockNumberGenerator {
+    upper_bound: u64,
+}
+
+impl BlockNumberGenerator {
+    /// Creates a generator of block numbers from zero to the given bound (inclusive).
+    pub fn new(upper_bound: u64) -> Self {
+        Self { upper_bound }
+    }
+
+    pub fn upper_bound(&self) -> u64 {
+        self.upper_bound
+    }
+
+    pub fn random_block_number<R: Rng>(&self, rng: &mut R) -> u64 {
+        rng.gen_range(0..=self.upper_bound)
+    }
+}
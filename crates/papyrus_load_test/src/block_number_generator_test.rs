@@ -0,0 +1,11 @@
+use crate::block_number_generator::BlockNumberGenerator;
+
+#[test]
+fn random_block_numbers_are_bounded() {
+    const UPPER_BOUND: u64 = 5;
+    let generator = BlockNumberGenerator::new(UPPER_BOUND);
+    let mut rng = rand::thread_rng();
+    for _ in 0..1000 {
+        assert!(generator.random_block_number(&mut rng) <= UPPER_BOUND);
+    }
+}
@@ -8,7 +8,7 @@ use reqwest::{Client, Url};
 use serde_json::Value as jsonVal;
 
 use crate::{
-    get_last_block_number,
+    get_available_block_bound,
     get_random_block_number,
     path_in_resources,
     GET_BLOCK_TRANSACTION_COUNT_BY_HASH_WEIGHT,
@@ -290,7 +290,8 @@ pub async fn get_block_number_and_transaction_index_args() -> Vec<String> {
 pub async fn get_block_number_and_contract_address_args() -> Vec<String> {
     let (block_number, contract_address) = get_random_block_number_and_contract_address().await;
     // A block number which in it the contract was already deployed.
-    let after_block_number = rand::thread_rng().gen_range(block_number..=get_last_block_number());
+    let after_block_number =
+        rand::thread_rng().gen_range(block_number..=get_available_block_bound());
     vec![after_block_number.to_string(), contract_address]
 }
 
@@ -299,7 +300,8 @@ pub async fn get_block_number_and_contract_address_args() -> Vec<String> {
 pub async fn get_block_hash_and_contract_address_args() -> Vec<String> {
     let (block_number, contract_address) = get_random_block_number_and_contract_address().await;
     // A block number which in it the contract was already deployed.
-    let after_block_number = rand::thread_rng().gen_range(block_number..=get_last_block_number());
+    let after_block_number =
+        rand::thread_rng().gen_range(block_number..=get_available_block_bound());
     let after_block_hash = get_block_hash_by_block_number(after_block_number).await;
     vec![after_block_hash, contract_address]
 }
@@ -350,7 +352,8 @@ pub async fn get_random_contract_address_deployed_in_block(block_number: u64) ->
 pub async fn get_block_number_and_class_hash_args() -> Vec<String> {
     let (block_number, class_hash) = get_random_block_number_and_class_hash().await;
     // A block number which in it the class was already declared.
-    let after_block_number = rand::thread_rng().gen_range(block_number..=get_last_block_number());
+    let after_block_number =
+        rand::thread_rng().gen_range(block_number..=get_available_block_bound());
     vec![after_block_number.to_string(), class_hash]
 }
 
@@ -359,7 +362,8 @@ pub async fn get_block_number_and_class_hash_args() -> Vec<String> {
 pub async fn get_block_hash_and_class_hash_args() -> Vec<String> {
     let (block_number, class_hash) = get_random_block_number_and_class_hash().await;
     // A block number which in it the class was already declared.
-    let after_block_number = rand::thread_rng().gen_range(block_number..=get_last_block_number());
+    let after_block_number =
+        rand::thread_rng().gen_range(block_number..=get_available_block_bound());
     let after_block_hash = get_block_hash_by_block_number(after_block_number).await;
     vec![after_block_hash, class_hash]
 }
@@ -411,8 +415,8 @@ pub async fn get_random_class_hash_declared_in_block(block_number: u64) -> Optio
 // a contract that was already deployed in this range.
 pub async fn get_block_range_and_contract_address_args() -> Vec<String> {
     let (block_number, contract_address) = get_random_block_number_and_contract_address().await;
-    let from_block = rand::thread_rng().gen_range(block_number..=get_last_block_number());
-    let to_block = rand::thread_rng().gen_range(from_block..=get_last_block_number());
+    let from_block = rand::thread_rng().gen_range(block_number..=get_available_block_bound());
+    let to_block = rand::thread_rng().gen_range(from_block..=get_available_block_bound());
     vec![from_block.to_string(), to_block.to_string(), contract_address]
 }
 
@@ -3,44 +3,99 @@
 // within this crate
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
+pub mod block_number_generator;
 #[cfg(feature = "compare_endpoints")]
 #[cfg(test)]
 mod compare_endpoints;
 pub mod create_files;
 pub mod create_request;
 #[cfg(test)]
+mod lib_test;
+#[cfg(test)]
 mod precision_test;
 pub mod scenarios;
 pub mod transactions;
 
 use std::{env, fs};
 
+use block_number_generator::BlockNumberGenerator;
 use goose::goose::{GooseUser, TransactionError};
-use once_cell::sync::{Lazy, OnceCell};
-use rand::Rng;
+use once_cell::sync::OnceCell;
 use serde::Deserialize;
 use serde_json::{json, Value as jsonVal};
 
-type PostResult = Result<jsonVal, Box<TransactionError>>;
+type PostResult = Result<jsonVal, PostError>;
 
-pub async fn post_jsonrpc_request(user: &mut GooseUser, request: &jsonVal) -> PostResult {
-    let version_id = &*RPC_VERSION_ID;
-    let response = user
-        .post_json(&format!("/rpc/{version_id}"), request)
-        .await?
-        .response
-        .map_err(|e| Box::new(e.into()))?;
-    // The purpose of this struct and the line afterward is to report on failed requests.
-    // The "response.json::<TransactionReceiptResponse>" deserialize the body of response to
-    // TransactionReceiptResponse. If the response is an error, the result field doesn't exist in
-    // the body, the deserialization will fail, and the function will return an error.
-    #[derive(Deserialize)]
-    struct TransactionReceiptResponse {
-        result: jsonVal,
+/// An error of a JSON-RPC request sent by the load test.
+#[derive(Debug, thiserror::Error)]
+pub enum PostError {
+    /// The request couldn't be sent, or its response couldn't be parsed.
+    #[error("Failed sending the request: {0}.")]
+    Transaction(Box<TransactionError>),
+    /// The node responded with a JSON-RPC error.
+    #[error("{error}")]
+    JsonRpc {
+        error: JsonRpcError,
+        // The error reported to goose for the failed request.
+        transaction_error: Box<TransactionError>,
+    },
+}
+
+impl From<Box<TransactionError>> for PostError {
+    fn from(error: Box<TransactionError>) -> Self {
+        Self::Transaction(error)
     }
-    let response =
-        response.json::<TransactionReceiptResponse>().await.map_err(|e| Box::new(e.into()))?;
-    Ok(response.result)
+}
+
+impl From<PostError> for Box<TransactionError> {
+    fn from(error: PostError) -> Self {
+        match error {
+            PostError::Transaction(transaction_error)
+            | PostError::JsonRpc { transaction_error, .. } => transaction_error,
+        }
+    }
+}
+
+/// The error object of a JSON-RPC error response.
+#[derive(Clone, Debug, Deserialize, PartialEq, thiserror::Error)]
+#[error("JSON-RPC error {code}: {message}.")]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default)]
+    pub data: Option<jsonVal>,
+}
+
+/// The body of a JSON-RPC response.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonRpcResponse {
+    Success { result: jsonVal },
+    Error { error: JsonRpcError },
+}
+
+impl JsonRpcResponse {
+    fn into_result(self) -> Result<jsonVal, JsonRpcError> {
+        match self {
+            Self::Success { result } => Ok(result),
+            Self::Error { error } => Err(error),
+        }
+    }
+}
+
+pub async fn post_jsonrpc_request(user: &mut GooseUser, request: &jsonVal) -> PostResult {
+    let version_id = rpc_version_id();
+    let goose_response = user.post_json(&format!("/rpc/{version_id}"), request).await?;
+    let mut request_metric = goose_response.request;
+    let response = goose_response.response.map_err(|e| Box::new(e.into()))?;
+    let response = response.json::<JsonRpcResponse>().await.map_err(|e| Box::new(e.into()))?;
+    response.into_result().map_err(|error| {
+        // Mark the request as failed with the JSON-RPC error, so it's reported by goose.
+        let transaction_error = user
+            .set_failure(&error.to_string(), &mut request_metric, None, None)
+            .expect_err("set_failure always returns an error.");
+        PostError::JsonRpc { error, transaction_error }
+    })
 }
 
 pub fn jsonrpc_request(method: &str, params: jsonVal) -> jsonVal {
@@ -63,11 +118,26 @@ pub fn get_last_block_number() -> u64 {
     })
 }
 
-// Returns a random block from zero to the last block for which this load test is relevant.
-pub fn get_random_block_number() -> u64 {
+static AVAILABLE_BLOCK_BOUND: OnceCell<u64> = OnceCell::new();
+
+/// Limits the random block numbers to blocks up to the given bound, e.g., to the blocks whose state
+/// is available in the node.
+/// Returns the given bound as an error if the bound was already set or used.
+pub fn set_available_block_bound(bound: u64) -> Result<(), u64> {
+    AVAILABLE_BLOCK_BOUND.set(bound)
+}
+
+// Returns the highest block number this load test sends requests on. It's the last block number,
+// unless a lower bound on the available blocks was set.
+pub fn get_available_block_bound() -> u64 {
     let last_block = get_last_block_number();
-    let mut rng = rand::thread_rng();
-    rng.gen_range(0..=last_block)
+    AVAILABLE_BLOCK_BOUND.get().map_or(last_block, |bound| last_block.min(*bound))
+}
+
+// Returns a random block from zero to the highest block for which this load test is relevant.
+pub fn get_random_block_number() -> u64 {
+    BlockNumberGenerator::new(get_available_block_bound())
+        .random_block_number(&mut rand::thread_rng())
 }
 
 // Returns the path to the file_name inside the resources folder in payprus_loadtest module.
@@ -109,7 +179,30 @@ const TRACE_BLOCK_TRANSACTIONS_BY_HASH_WEIGHT: usize = 10;
 const TRACE_BLOCK_TRANSACTIONS_BY_NUMBER_WEIGHT: usize = 10;
 const TRACE_TRANSACTION_WEIGHT: usize = 10;
 
-static RPC_VERSION_ID: Lazy<String> = Lazy::new(|| match std::env::var("VERSION_ID") {
-    Ok(version_id) => version_id,
-    Err(_) => unreachable!("VERSION_ID environment variable is not set"),
-});
+/// The RPC version the load test uses if it isn't given one.
+pub const DEFAULT_RPC_VERSION_ID: &str = "V0_7";
+/// The environment variable that sets the RPC version, unless it's set by the command line.
+pub const RPC_VERSION_ID_ENV_VAR: &str = "VERSION_ID";
+
+static RPC_VERSION_ID: OnceCell<String> = OnceCell::new();
+
+/// Sets the RPC version the load test uses, overriding the environment variable.
+/// Returns the given version as an error if the RPC version was already set or used.
+pub fn set_rpc_version_id(version_id: String) -> Result<(), String> {
+    RPC_VERSION_ID.set(version_id)
+}
+
+fn rpc_version_id() -> &'static str {
+    RPC_VERSION_ID.get_or_init(|| rpc_version_id_or_default(env::var(RPC_VERSION_ID_ENV_VAR).ok()))
+}
+
+// Returns the given RPC version, or the default one with a warning if no version is given.
+fn rpc_version_id_or_default(version_id: Option<String>) -> String {
+    version_id.unwrap_or_else(|| {
+        eprintln!(
+            "Warning: {RPC_VERSION_ID_ENV_VAR} isn't set, using the default RPC version \
+             {DEFAULT_RPC_VERSION_ID}."
+        );
+        DEFAULT_RPC_VERSION_ID.to_owned()
+    })
+}
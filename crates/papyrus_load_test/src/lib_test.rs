@@ -0,0 +1,27 @@
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::{rpc_version_id_or_default, JsonRpcError, JsonRpcResponse, DEFAULT_RPC_VERSION_ID};
+
+#[test]
+fn jsonrpc_success_response() {
+    let body = r#"{"jsonrpc": "2.0", "id": "0", "result": {"block_number": 7}}"#;
+    let response = serde_json::from_str::<JsonRpcResponse>(body).unwrap();
+    assert_eq!(response.into_result(), Ok(json!({"block_number": 7})));
+}
+
+#[test]
+fn jsonrpc_error_response() {
+    let body =
+        r#"{"jsonrpc": "2.0", "id": "0", "error": {"code": 24, "message": "Block not found"}}"#;
+    let response = serde_json::from_str::<JsonRpcResponse>(body).unwrap();
+    let error = response.into_result().unwrap_err();
+    assert_eq!(error, JsonRpcError { code: 24, message: "Block not found".to_owned(), data: None });
+    assert_eq!(error.to_string(), "JSON-RPC error 24: Block not found.");
+}
+
+#[test]
+fn rpc_version_id_defaults_to_latest_version() {
+    assert_eq!(rpc_version_id_or_default(None), DEFAULT_RPC_VERSION_ID);
+    assert_eq!(rpc_version_id_or_default(Some("V0_6".to_owned())), "V0_6");
+}
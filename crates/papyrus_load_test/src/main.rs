@@ -1,36 +1,63 @@
 // This code is inspired by the pathfinder load test.
-// first set the env variable VERSION_ID to the version of the node you want to test.
+// The RPC version of the node you want to test is taken from the --rpc_version_id flag, then from
+// the env variable VERSION_ID, and defaults to the latest supported version.
 // To run this load test, run locally a node and then run:
 //      cargo run -r -p papyrus_load_test -- -t 5m -H http://127.0.0.1:8080 --scenarios=generalrequestv004
+// To test a specific RPC version run:
+//      cargo run -r -p papyrus_load_test -- --rpc_version_id V0_6 -t 5m -H http://127.0.0.1:8080
+// To override the weights of the requests run:
+//      cargo run -r -p papyrus_load_test -- --weights_file weights.json -t 5m -H http://127.0.0.1:8080
+// where weights.json maps transaction names to weights, e.g. {"get_events_with_address": 20}.
 // To see all the available scenarios run:
 //      cargo run -r -p papyrus_load_test -- --scenarios-list
 // To create the files of requests run:
 //      cargo run -r -p papyrus_load_test -- --create_files http://127.0.0.1:8080/rpc/V0_7
+// To create the files only with blocks whose state is available, e.g., up to block 1000, run:
+//      cargo run -r -p papyrus_load_test -- --available_block_bound 1000 --create_files <URL>
 // For more options run:
 //      cargo run -r -p papyrus_load_test -- --help
 
 use std::env;
 use std::fs::File;
+use std::path::Path;
 
 use assert_matches::assert_matches;
+use goose::config::GooseConfiguration;
 use goose::{util, GooseAttack};
+use gumdrop::{Options, ParsingStyle};
 use papyrus_load_test::create_files::create_files;
-use papyrus_load_test::scenarios;
+use papyrus_load_test::scenarios::RequestWeights;
+use papyrus_load_test::{scenarios, set_available_block_bound, set_rpc_version_id};
 use serde::Serialize;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let args: Vec<String> = env::args().collect();
-    assert_matches!(std::env::var("VERSION_ID"), Ok(_));
+    let mut args: Vec<String> = env::args().collect();
+    if let Some(version_id) = take_flag_value(&mut args, "--rpc_version_id") {
+        assert_matches!(set_rpc_version_id(version_id), Ok(()));
+    }
+    let weights = match take_flag_value(&mut args, "--weights_file") {
+        Some(path) => RequestWeights::from_file(Path::new(&path))?,
+        None => RequestWeights::default(),
+    };
+    if let Some(bound) = take_flag_value(&mut args, "--available_block_bound") {
+        let bound = bound.parse().expect("--available_block_bound expects a block number.");
+        assert_matches!(set_available_block_bound(bound), Ok(()));
+    }
     if args.len() > 1 && args[1].eq("--create_files") {
         create_files(&args[2]).await;
         return Ok(());
     }
 
-    let metrics = GooseAttack::initialize()?
-        // The choice between versions must be also in the environment variable VERSION_ID.
+    let configuration = GooseConfiguration::parse_args(&args[1..], ParsingStyle::AllOptions)?;
+    if configuration.help_requested() {
+        println!("Usage: {} [OPTIONS]\n\n{}", args[0], GooseConfiguration::usage());
+        return Ok(());
+    }
+    let metrics = GooseAttack::initialize_with_config(configuration)?
+        // The choice between versions must match the RPC version of the load test.
         // TODO(dvir): Add scenarios for v0_5 and v0_6.
-        .register_scenario(scenarios::general_request_v0_4())
+        .register_scenario(scenarios::general_request_v0_4(&weights))
         .execute()
         .await?;
 
@@ -62,6 +89,15 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+// Removes a flag that isn't a goose flag and its value from the arguments, before goose parses
+// them. Returns the value of the flag if it was given.
+fn take_flag_value(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let index = args.iter().position(|arg| arg == flag)?;
+    let value = args.get(index + 1).unwrap_or_else(|| panic!("{flag} expects a value.")).clone();
+    args.drain(index..=index + 1);
+    Some(value)
+}
+
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct Entry {
     name: String,
@@ -1,4 +1,8 @@
+use std::fs;
+use std::path::Path;
+
 use goose::goose::Scenario;
+use serde::Deserialize;
 
 use crate::{
     transactions as txs,
@@ -35,8 +39,108 @@ use crate::{
     TRACE_TRANSACTION_WEIGHT,
 };
 
+#[cfg(test)]
+#[path = "scenarios_test.rs"]
+mod scenarios_test;
+
+/// The weight of each request in the load test scenarios, named after the request's transaction.
+/// Weights that are missing from a weights file get their default value.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequestWeights {
+    pub block_hash_and_number: usize,
+    pub block_number: usize,
+    pub chain_id: usize,
+    pub get_block_transaction_count_by_hash: usize,
+    pub get_block_transaction_count_by_number: usize,
+    pub get_block_with_full_transactions_by_hash: usize,
+    pub get_block_with_full_transactions_by_number: usize,
+    pub get_block_with_transaction_hashes_by_hash: usize,
+    pub get_block_with_transaction_hashes_by_number: usize,
+    pub get_class_at_by_hash: usize,
+    pub get_class_at_by_number: usize,
+    pub get_class_by_hash: usize,
+    pub get_class_by_number: usize,
+    pub get_class_hash_at_by_hash: usize,
+    pub get_class_hash_at_by_number: usize,
+    pub get_events_without_address: usize,
+    pub get_events_with_address: usize,
+    pub get_nonce_by_hash: usize,
+    pub get_nonce_by_number: usize,
+    pub get_state_update_by_hash: usize,
+    pub get_state_update_by_number: usize,
+    pub get_storage_at_by_hash: usize,
+    pub get_storage_at_by_number: usize,
+    pub get_transaction_by_block_id_and_index_by_hash: usize,
+    pub get_transaction_by_block_id_and_index_by_number: usize,
+    pub get_transaction_by_hash: usize,
+    pub get_transaction_receipt: usize,
+    pub syncing: usize,
+    pub trace_block_transactions_by_hash: usize,
+    pub trace_block_transactions_by_number: usize,
+    pub trace_transaction: usize,
+}
+
+impl Default for RequestWeights {
+    fn default() -> Self {
+        Self {
+            block_hash_and_number: BLOCK_HASH_AND_NUMBER_WEIGHT,
+            block_number: BLOCK_NUMBER_WEIGHT,
+            chain_id: CHAIN_ID_WEIGHT,
+            get_block_transaction_count_by_hash: GET_BLOCK_TRANSACTION_COUNT_BY_HASH_WEIGHT,
+            get_block_transaction_count_by_number: GET_BLOCK_TRANSACTION_COUNT_BY_NUMBER_WEIGHT,
+            get_block_with_full_transactions_by_hash:
+                GET_BLOCK_WITH_FULL_TRANSACTIONS_BY_HASH_WEIGHT,
+            get_block_with_full_transactions_by_number:
+                GET_BLOCK_WITH_FULL_TRANSACTIONS_BY_NUMBER_WEIGHT,
+            get_block_with_transaction_hashes_by_hash:
+                GET_BLOCK_WITH_TRANSACTION_HASHES_BY_HASH_WEIGHT,
+            get_block_with_transaction_hashes_by_number:
+                GET_BLOCK_WITH_TRANSACTION_HASHES_BY_NUMBER_WEIGHT,
+            get_class_at_by_hash: GET_CLASS_AT_BY_HASH_WEIGHT,
+            get_class_at_by_number: GET_CLASS_AT_BY_NUMBER_WEIGHT,
+            get_class_by_hash: GET_CLASS_BY_HASH_WEIGHT,
+            get_class_by_number: GET_CLASS_BY_NUMBER_WEIGHT,
+            get_class_hash_at_by_hash: GET_CLASS_HASH_AT_BY_HASH_WEIGHT,
+            get_class_hash_at_by_number: GET_CLASS_HASH_AT_BY_NUMBER_WEIGHT,
+            get_events_without_address: GET_EVENTS_WITHOUT_ADDRESS_WEIGHT,
+            get_events_with_address: GET_EVENTS_WITH_ADDRESS_WEIGHT,
+            get_nonce_by_hash: GET_NONCE_BY_HASH_WEIGHT,
+            get_nonce_by_number: GET_NONCE_BY_NUMBER_WEIGHT,
+            get_state_update_by_hash: GET_STATE_UPDATE_BY_HASH_WEIGHT,
+            get_state_update_by_number: GET_STATE_UPDATE_BY_NUMBER_WEIGHT,
+            get_storage_at_by_hash: GET_STORAGE_AT_BY_HASH_WEIGHT,
+            get_storage_at_by_number: GET_STORAGE_AT_BY_NUMBER_WEIGHT,
+            get_transaction_by_block_id_and_index_by_hash:
+                GET_TRANSACTION_BY_BLOCK_ID_AND_INDEX_BY_HASH_WEIGHT,
+            get_transaction_by_block_id_and_index_by_number:
+                GET_TRANSACTION_BY_BLOCK_ID_AND_INDEX_BY_NUMBER_WEIGHT,
+            get_transaction_by_hash: GET_TRANSACTION_BY_HASH_WEIGHT,
+            get_transaction_receipt: GET_TRANSACTION_RECEIPT_WEIGHT,
+            syncing: SYNCING_WEIGHT,
+            trace_block_transactions_by_hash: TRACE_BLOCK_TRANSACTIONS_BY_HASH_WEIGHT,
+            trace_block_transactions_by_number: TRACE_BLOCK_TRANSACTIONS_BY_NUMBER_WEIGHT,
+            trace_transaction: TRACE_TRANSACTION_WEIGHT,
+        }
+    }
+}
+
+impl RequestWeights {
+    /// Reads the weights from a JSON file that maps transaction names to their weights. Weights
+    /// must be positive.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let weights: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(&fs::read_to_string(path)?)?;
+        // Goose panics when registering a transaction with a zero weight.
+        if let Some((name, _)) = weights.iter().find(|(_, weight)| weight.as_u64() == Some(0)) {
+            anyhow::bail!("The weight of {name} must be positive.");
+        }
+        Ok(serde_json::from_value(weights.into())?)
+    }
+}
+
 // TODO(dvir): add also traceTransaction, simulateTransactions, estimateFee and call endpoints.
-pub fn general_request_v0_4() -> Scenario {
+pub fn general_request_v0_4(weights: &RequestWeights) -> Scenario {
     let mut scenario = Scenario::new("general_request_v0_4");
     // This is the scenario name to run from the command line.
     // This name must be alphanumeric, so instead of letting Goose do the conversion from the
@@ -44,64 +148,64 @@ pub fn general_request_v0_4() -> Scenario {
     scenario.machine_name = "generalrequestv04".to_string();
 
     let trans_and_weights = vec![
-        (txs::block_hash_and_number(), BLOCK_HASH_AND_NUMBER_WEIGHT),
-        (txs::block_number(), BLOCK_NUMBER_WEIGHT),
-        (txs::chain_id(), CHAIN_ID_WEIGHT),
-        (txs::get_block_transaction_count_by_hash(), GET_BLOCK_TRANSACTION_COUNT_BY_HASH_WEIGHT),
+        (txs::block_hash_and_number(), weights.block_hash_and_number),
+        (txs::block_number(), weights.block_number),
+        (txs::chain_id(), weights.chain_id),
+        (txs::get_block_transaction_count_by_hash(), weights.get_block_transaction_count_by_hash),
         (
             txs::get_block_transaction_count_by_number(),
-            GET_BLOCK_TRANSACTION_COUNT_BY_NUMBER_WEIGHT,
+            weights.get_block_transaction_count_by_number,
         ),
         (
             txs::get_block_with_full_transactions_by_hash(),
-            GET_BLOCK_WITH_FULL_TRANSACTIONS_BY_HASH_WEIGHT,
+            weights.get_block_with_full_transactions_by_hash,
         ),
         (
             txs::get_block_with_full_transactions_by_number(),
-            GET_BLOCK_WITH_FULL_TRANSACTIONS_BY_NUMBER_WEIGHT,
+            weights.get_block_with_full_transactions_by_number,
         ),
         (
             txs::get_block_with_transaction_hashes_by_hash(),
-            GET_BLOCK_WITH_TRANSACTION_HASHES_BY_HASH_WEIGHT,
+            weights.get_block_with_transaction_hashes_by_hash,
         ),
         (
             txs::get_block_with_transaction_hashes_by_number(),
-            GET_BLOCK_WITH_TRANSACTION_HASHES_BY_NUMBER_WEIGHT,
+            weights.get_block_with_transaction_hashes_by_number,
         ),
-        (txs::get_class_at_by_hash(), GET_CLASS_AT_BY_HASH_WEIGHT),
-        (txs::get_class_at_by_number(), GET_CLASS_AT_BY_NUMBER_WEIGHT),
-        (txs::get_class_by_hash(), GET_CLASS_BY_HASH_WEIGHT),
-        (txs::get_class_by_number(), GET_CLASS_BY_NUMBER_WEIGHT),
-        (txs::get_class_hash_at_by_hash(), GET_CLASS_HASH_AT_BY_HASH_WEIGHT),
-        (txs::get_class_hash_at_by_number(), GET_CLASS_HASH_AT_BY_NUMBER_WEIGHT),
-        (txs::get_events_without_address(), GET_EVENTS_WITHOUT_ADDRESS_WEIGHT),
-        (txs::get_events_with_address(), GET_EVENTS_WITH_ADDRESS_WEIGHT),
-        (txs::get_nonce_by_hash(), GET_NONCE_BY_HASH_WEIGHT),
-        (txs::get_nonce_by_number(), GET_NONCE_BY_NUMBER_WEIGHT),
-        (txs::get_state_update_by_hash(), GET_STATE_UPDATE_BY_HASH_WEIGHT),
-        (txs::get_state_update_by_number(), GET_STATE_UPDATE_BY_NUMBER_WEIGHT),
-        (txs::get_storage_at_by_hash(), GET_STORAGE_AT_BY_HASH_WEIGHT),
-        (txs::get_storage_at_by_number(), GET_STORAGE_AT_BY_NUMBER_WEIGHT),
+        (txs::get_class_at_by_hash(), weights.get_class_at_by_hash),
+        (txs::get_class_at_by_number(), weights.get_class_at_by_number),
+        (txs::get_class_by_hash(), weights.get_class_by_hash),
+        (txs::get_class_by_number(), weights.get_class_by_number),
+        (txs::get_class_hash_at_by_hash(), weights.get_class_hash_at_by_hash),
+        (txs::get_class_hash_at_by_number(), weights.get_class_hash_at_by_number),
+        (txs::get_events_without_address(), weights.get_events_without_address),
+        (txs::get_events_with_address(), weights.get_events_with_address),
+        (txs::get_nonce_by_hash(), weights.get_nonce_by_hash),
+        (txs::get_nonce_by_number(), weights.get_nonce_by_number),
+        (txs::get_state_update_by_hash(), weights.get_state_update_by_hash),
+        (txs::get_state_update_by_number(), weights.get_state_update_by_number),
+        (txs::get_storage_at_by_hash(), weights.get_storage_at_by_hash),
+        (txs::get_storage_at_by_number(), weights.get_storage_at_by_number),
         (
             txs::get_transaction_by_block_id_and_index_by_hash(),
-            GET_TRANSACTION_BY_BLOCK_ID_AND_INDEX_BY_HASH_WEIGHT,
+            weights.get_transaction_by_block_id_and_index_by_hash,
         ),
         (
             txs::get_transaction_by_block_id_and_index_by_number(),
-            GET_TRANSACTION_BY_BLOCK_ID_AND_INDEX_BY_NUMBER_WEIGHT,
+            weights.get_transaction_by_block_id_and_index_by_number,
         ),
-        (txs::get_transaction_by_hash(), GET_TRANSACTION_BY_HASH_WEIGHT),
-        (txs::get_transaction_receipt(), GET_TRANSACTION_RECEIPT_WEIGHT),
-        (txs::syncing(), SYNCING_WEIGHT),
+        (txs::get_transaction_by_hash(), weights.get_transaction_by_hash),
+        (txs::get_transaction_receipt(), weights.get_transaction_receipt),
+        (txs::syncing(), weights.syncing),
     ];
     for (transaction, weight) in trans_and_weights.into_iter() {
         scenario = scenario.register_transaction(transaction.set_weight(weight).unwrap());
     }
 
     let new_trans_and_weights = vec![
-        (txs::trace_block_transactions_by_hash(), TRACE_BLOCK_TRANSACTIONS_BY_HASH_WEIGHT),
-        (txs::trace_block_transactions_by_number(), TRACE_BLOCK_TRANSACTIONS_BY_NUMBER_WEIGHT),
-        (txs::trace_transaction(), TRACE_TRANSACTION_WEIGHT),
+        (txs::trace_block_transactions_by_hash(), weights.trace_block_transactions_by_hash),
+        (txs::trace_block_transactions_by_number(), weights.trace_block_transactions_by_number),
+        (txs::trace_transaction(), weights.trace_transaction),
     ];
     for (transaction, weight) in new_trans_and_weights.into_iter() {
         scenario = scenario.register_transaction(transaction.set_weight(weight).unwrap());
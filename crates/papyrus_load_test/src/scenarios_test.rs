@@ -0,0 +1,45 @@
+use std::io::Write;
+
+use pretty_assertions::assert_eq;
+use tempfile::NamedTempFile;
+
+use crate::scenarios::{general_request_v0_4, RequestWeights};
+use crate::GET_EVENTS_WITH_ADDRESS_WEIGHT;
+
+#[test]
+fn scenario_uses_weights_from_file() {
+    let custom_weight = GET_EVENTS_WITH_ADDRESS_WEIGHT + 7;
+    let mut weights_file = NamedTempFile::new().unwrap();
+    write!(weights_file, r#"{{"get_events_with_address": {custom_weight}}}"#).unwrap();
+
+    let weights = RequestWeights::from_file(weights_file.path()).unwrap();
+    assert_eq!(
+        weights,
+        RequestWeights { get_events_with_address: custom_weight, ..RequestWeights::default() }
+    );
+
+    let scenario = general_request_v0_4(&weights);
+    let weight_of = |name: &str| {
+        scenario.transactions.iter().find(|transaction| transaction.name == name).unwrap().weight
+    };
+    assert_eq!(weight_of("get_events_with_address"), custom_weight);
+    assert_eq!(
+        weight_of("get_events_without_address"),
+        RequestWeights::default().get_events_without_address
+    );
+}
+
+#[test]
+fn weights_file_with_unknown_transaction_is_rejected() {
+    let mut weights_file = NamedTempFile::new().unwrap();
+    write!(weights_file, r#"{{"unknown_transaction": 1}}"#).unwrap();
+    assert!(RequestWeights::from_file(weights_file.path()).is_err());
+}
+
+#[test]
+fn weights_file_with_zero_weight_is_rejected() {
+    let mut weights_file = NamedTempFile::new().unwrap();
+    write!(weights_file, r#"{{"get_events_with_address": 0}}"#).unwrap();
+    let err = RequestWeights::from_file(weights_file.path()).unwrap_err();
+    assert!(err.to_string().contains("get_events_with_address"));
+}
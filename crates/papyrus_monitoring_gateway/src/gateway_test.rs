@@ -1,5 +1,6 @@
 use std::net::{SocketAddr, TcpListener};
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::body::Body;
 use axum::http::{Request, StatusCode};
@@ -8,11 +9,13 @@ use axum::Router;
 use http_body::combinators::UnsyncBoxBody;
 use metrics::{absolute_counter, describe_counter, register_counter};
 use metrics_exporter_prometheus::PrometheusBuilder;
+use papyrus_common::central_health::{CentralHealth, CentralHealthTracker};
 use papyrus_storage::{table_names, test_utils};
 use pretty_assertions::assert_eq;
 use serde_json::{json, Value};
 use starknet_client::reader::MockStarknetReader;
 use starknet_client::writer::MockStarknetWriter;
+use tokio::sync::RwLock;
 use tower::ServiceExt;
 
 use crate::{app, is_ready, MONITORING_PREFIX};
@@ -25,6 +28,12 @@ const TEST_PEER_ID: &str = "peer_id";
 
 // TODO(dan): consider using a proper fixture.
 fn setup_app() -> Router {
+    setup_app_with_central_health_tracker(Arc::default())
+}
+
+fn setup_app_with_central_health_tracker(
+    central_health_tracker: Arc<RwLock<CentralHealthTracker>>,
+) -> Router {
     let ((storage_reader, _), _temp_dir) = test_utils::get_test_storage();
     app(
         String::from("https://default_url"),
@@ -35,6 +44,7 @@ fn setup_app() -> Router {
         SECRET.to_string(),
         None,
         TEST_PEER_ID.to_string(),
+        central_health_tracker,
     )
 }
 
@@ -180,6 +190,7 @@ async fn with_metrics() {
         String::new(),
         Some(prometheus_handle),
         TEST_PEER_ID.to_string(),
+        Arc::default(),
     );
 
     // Register a metric.
@@ -239,3 +250,18 @@ fn serialization_precision() {
     let deserialized = serde_json::to_string(&serialized).unwrap();
     assert_eq!(input, deserialized);
 }
+
+#[tokio::test]
+async fn central_health() {
+    let central_health_tracker = Arc::new(RwLock::new(CentralHealthTracker::default()));
+    central_health_tracker.write().await.record_request(Duration::from_millis(10), true);
+    let app = setup_app_with_central_health_tracker(central_health_tracker.clone());
+    let response = request_app(app, "centralHealth").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let body: CentralHealth = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body, central_health_tracker.read().await.health());
+    assert_eq!(body.average_latency_millis, Some(10));
+}
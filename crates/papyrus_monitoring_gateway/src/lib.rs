@@ -18,8 +18,10 @@ use axum::routing::get;
 use axum::{Json, Router};
 use metrics_exporter_prometheus::{BuildError, PrometheusBuilder, PrometheusHandle};
 use metrics_process::Collector;
+use papyrus_common::central_health::{CentralHealth, CentralHealthTracker};
 use papyrus_config::converters::{deserialize_optional_map, serialize_optional_map};
 use papyrus_config::dumping::{ser_generated_param, ser_param, SerializeConfig};
+use papyrus_config::validators::validate_socket_addr;
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializationType, SerializedParam};
 use papyrus_storage::mmap_file::MMapFileStats;
 use papyrus_storage::{DbStats, StorageError, StorageReader};
@@ -29,6 +31,7 @@ use serde::{Deserialize, Serialize};
 use starknet_client::reader::{StarknetFeederGatewayClient, StarknetReader};
 use starknet_client::writer::{StarknetGatewayClient, StarknetWriter};
 use starknet_client::RetryConfig;
+use tokio::sync::RwLock;
 use tracing::{debug, info, instrument};
 use validator::Validate;
 
@@ -37,6 +40,7 @@ const PROCESS_METRICS_PREFIX: &str = "papyrus_";
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Validate)]
 pub struct MonitoringGatewayConfig {
+    #[validate(custom = "validate_socket_addr")]
     pub server_address: String,
     pub collect_metrics: bool,
     #[serde(deserialize_with = "deserialize_optional_map")]
@@ -120,6 +124,7 @@ pub struct MonitoringServer {
     version: &'static str,
     prometheus_handle: Option<PrometheusHandle>,
     own_peer_id: String,
+    central_health_tracker: Arc<RwLock<CentralHealthTracker>>,
 }
 
 impl MonitoringServer {
@@ -130,6 +135,7 @@ impl MonitoringServer {
         storage_reader: StorageReader,
         version: &'static str,
         own_peer_id: String,
+        central_health_tracker: Arc<RwLock<CentralHealthTracker>>,
     ) -> Result<Self, BuildError> {
         let prometheus_handle = if config.collect_metrics {
             let mut builder = PrometheusBuilder::new();
@@ -150,6 +156,7 @@ impl MonitoringServer {
             version,
             prometheus_handle,
             own_peer_id,
+            central_health_tracker,
         })
     }
 
@@ -179,6 +186,7 @@ impl MonitoringServer {
             self.config.present_full_config_secret.clone(),
             self.prometheus_handle.clone(),
             self.own_peer_id.clone(),
+            self.central_health_tracker.clone(),
         );
         debug!("Starting monitoring gateway.");
         axum::Server::bind(&server_address).serve(app.into_make_service()).await
@@ -195,6 +203,7 @@ fn app(
     present_full_config_secret: String,
     prometheus_handle: Option<PrometheusHandle>,
     own_peer_id: String,
+    central_health_tracker: Arc<RwLock<CentralHealthTracker>>,
 ) -> Router {
     let is_ready_retry_config =
         RetryConfig { retry_base_millis: 50, retry_max_delay_millis: 1000, max_retries: 0 };
@@ -256,6 +265,10 @@ fn app(
             get(move || is_ready(starknet_client, starknet_feeder_client)),
         )
         .route(format!("/{MONITORING_PREFIX}/peer_id").as_str(), get(move || async { own_peer_id }))
+        .route(
+            format!("/{MONITORING_PREFIX}/centralHealth").as_str(),
+            get(move || central_health(central_health_tracker)),
+        )
 }
 
 async fn is_ready<TStarknetWriter: StarknetWriter, TStarknetReader: StarknetReader>(
@@ -326,6 +339,14 @@ async fn node_version(version: &'static str) -> String {
     version.to_string()
 }
 
+/// Returns the health of the connection to the central source.
+#[instrument(skip(central_health_tracker), level = "debug", ret)]
+async fn central_health(
+    central_health_tracker: Arc<RwLock<CentralHealthTracker>>,
+) -> Json<CentralHealth> {
+    central_health_tracker.read().await.health().into()
+}
+
 #[derive(thiserror::Error, Debug)]
 enum ServerError {
     #[error(transparent)]
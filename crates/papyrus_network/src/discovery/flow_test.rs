@@ -24,8 +24,12 @@ struct DiscoveryMixedBehaviour {
 
 impl DiscoveryMixedBehaviour {
     pub fn new(key: Keypair, bootstrap_peer_multiaddr: Option<Multiaddr>) -> Self {
-        let mixed_behaviour =
-            MixedBehaviour::new(key, bootstrap_peer_multiaddr, Default::default());
+        let mixed_behaviour = MixedBehaviour::new(
+            key,
+            bootstrap_peer_multiaddr,
+            Default::default(),
+            Default::default(),
+        );
         Self {
             identify: mixed_behaviour.identify,
             kademlia: mixed_behaviour.kademlia,
@@ -6,9 +6,10 @@ use libp2p::swarm::SwarmEvent;
 use libp2p::{Multiaddr, Swarm};
 use libp2p_swarm_test::SwarmExt;
 
-use crate::gossipsub_impl::Topic;
+use crate::gossipsub_impl::{MessageAcceptance, Topic};
 use crate::mixed_behaviour::MixedBehaviour;
 use crate::network_manager::GenericNetworkManager;
+use crate::peer_manager::PeerManagerConfig;
 use crate::sqmr;
 use crate::sqmr::Bytes;
 
@@ -16,7 +17,12 @@ const TIMEOUT: Duration = Duration::from_secs(1);
 
 async fn create_swarm(bootstrap_peer_multiaddr: Option<Multiaddr>) -> Swarm<MixedBehaviour> {
     let mut swarm = Swarm::new_ephemeral(|keypair| {
-        MixedBehaviour::new(keypair.clone(), bootstrap_peer_multiaddr, sqmr::Config::default())
+        MixedBehaviour::new(
+            keypair.clone(),
+            bootstrap_peer_multiaddr,
+            sqmr::Config::default(),
+            PeerManagerConfig::default(),
+        )
     });
     // Not using SwarmExt::listen because it panics if the swarm emits other events
     let expected_listener_id = swarm.listen_on(Protocol::Memory(0).into()).unwrap();
@@ -119,3 +125,78 @@ async fn broadcast_subscriber_end_to_end_test() {
         }
     }
 }
+
+#[tokio::test]
+async fn rejected_message_is_not_propagated() {
+    let topic = Topic::new("TOPIC");
+    // The swarms are connected in a line: swarm1 <-> validating_swarm <-> swarm2. This way,
+    // messages from swarm1 reach swarm2 only if the validating swarm propagates them.
+    let validating_swarm = create_swarm(None).await;
+    let validating_peer_multiaddr = validating_swarm
+        .external_addresses()
+        .next()
+        .unwrap()
+        .clone()
+        .with_p2p(*validating_swarm.local_peer_id())
+        .unwrap();
+    let mut swarm1 = create_swarm(None).await;
+    swarm1.dial(validating_peer_multiaddr.clone()).unwrap();
+    let mut swarm2 = create_swarm(None).await;
+    swarm2.dial(validating_peer_multiaddr).unwrap();
+
+    let mut validating_network_manager = create_network_manager(validating_swarm);
+    let mut network_manager1 = create_network_manager(swarm1);
+    let mut network_manager2 = create_network_manager(swarm2);
+
+    let mut validating_subscriber_channels = validating_network_manager
+        .register_broadcast_topic_with_validation::<Number>(topic.clone(), BUFFER_SIZE)
+        .unwrap();
+    let mut subscriber_channels1 =
+        network_manager1.register_broadcast_topic::<Number>(topic.clone(), BUFFER_SIZE).unwrap();
+    let mut subscriber_channels2 =
+        network_manager2.register_broadcast_topic::<Number>(topic.clone(), BUFFER_SIZE).unwrap();
+
+    tokio::select! {
+        _ = validating_network_manager.run() => panic!("network manager ended"),
+        _ = network_manager1.run() => panic!("network manager ended"),
+        _ = network_manager2.run() => panic!("network manager ended"),
+        result = tokio::time::timeout(
+            TIMEOUT, async move {
+                // TODO(shahak): Remove this sleep once we fix the bug of broadcasting while there
+                // are no peers.
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                let rejected_number = Number(1);
+                let accepted_number = Number(2);
+                let validating_receiver =
+                    &mut validating_subscriber_channels.broadcasted_messages_receiver;
+
+                subscriber_channels1
+                    .messages_to_broadcast_sender
+                    .send(rejected_number)
+                    .await
+                    .unwrap();
+                let (received_number, _report_callback, validation_result_sender) =
+                    validating_receiver.next().await.unwrap();
+                assert_eq!(received_number.unwrap(), rejected_number);
+                validation_result_sender.send(MessageAcceptance::Reject).unwrap();
+
+                subscriber_channels1
+                    .messages_to_broadcast_sender
+                    .send(accepted_number)
+                    .await
+                    .unwrap();
+                let (received_number, _report_callback, validation_result_sender) =
+                    validating_receiver.next().await.unwrap();
+                assert_eq!(received_number.unwrap(), accepted_number);
+                validation_result_sender.send(MessageAcceptance::Accept).unwrap();
+
+                // The first message that reaches swarm2 is the accepted one.
+                let (received_number, _report_callback) =
+                    subscriber_channels2.broadcasted_messages_receiver.next().await.unwrap();
+                assert_eq!(received_number.unwrap(), accepted_number);
+            }
+        ) => {
+            result.unwrap()
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use libp2p::gossipsub::TopicHash;
+use futures::channel::oneshot;
+use libp2p::gossipsub::{MessageId, TopicHash};
 use libp2p::{gossipsub, PeerId};
 use tracing::error;
 
@@ -11,18 +12,50 @@ pub type Topic = gossipsub::IdentTopic;
 #[cfg(not(test))]
 pub type Topic = gossipsub::Sha256Topic;
 
+/// The verdict of a subscriber that registered a topic with validation on a received message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageAcceptance {
+    /// The message is valid and will be propagated to other peers.
+    Accept,
+    /// The message is invalid. It won't be propagated and its sender will be penalized.
+    Reject,
+    /// The message won't be propagated, but its sender won't be penalized.
+    Ignore,
+}
+
+impl From<MessageAcceptance> for gossipsub::MessageAcceptance {
+    fn from(acceptance: MessageAcceptance) -> Self {
+        match acceptance {
+            MessageAcceptance::Accept => gossipsub::MessageAcceptance::Accept,
+            MessageAcceptance::Reject => gossipsub::MessageAcceptance::Reject,
+            MessageAcceptance::Ignore => gossipsub::MessageAcceptance::Ignore,
+        }
+    }
+}
+
+/// Sender for the validation result of a received message. Dropping it without sending is
+/// treated as [`MessageAcceptance::Ignore`].
+pub type ValidationResultSender = oneshot::Sender<MessageAcceptance>;
+
 #[derive(Debug)]
 pub enum ExternalEvent {
     #[allow(dead_code)]
-    Received { originated_peer_id: PeerId, message: Bytes, topic_hash: TopicHash },
+    Received {
+        originated_peer_id: PeerId,
+        message: Bytes,
+        topic_hash: TopicHash,
+        message_id: MessageId,
+        propagation_source: PeerId,
+    },
 }
 
 impl From<gossipsub::Event> for mixed_behaviour::Event {
     fn from(event: gossipsub::Event) -> Self {
         match event {
             gossipsub::Event::Message {
+                propagation_source,
+                message_id,
                 message: gossipsub::Message { data, topic, source, .. },
-                ..
             } => {
                 let Some(originated_peer_id) = source else {
                     error!(
@@ -38,6 +71,8 @@ impl From<gossipsub::Event> for mixed_behaviour::Event {
                         originated_peer_id,
                         message: data,
                         topic_hash: topic,
+                        message_id,
+                        propagation_source,
                     },
                 ))
             }
@@ -30,7 +30,6 @@ use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-// TODO: add peer manager config to the network config
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Validate)]
 pub struct NetworkConfig {
     pub tcp_port: u16,
@@ -40,6 +39,10 @@ pub struct NetworkConfig {
     #[serde(deserialize_with = "deserialize_seconds_to_duration")]
     pub idle_connection_timeout: Duration,
     pub bootstrap_peer_multiaddr: Option<Multiaddr>,
+    #[validate(range(min = 1))]
+    pub peer_reports_threshold: usize,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub peer_reports_window: Duration,
     #[validate(custom = "validate_vec_u256")]
     #[serde(deserialize_with = "deserialize_optional_vec_u8")]
     pub(crate) secret_key: Option<Vec<u8>>,
@@ -73,6 +76,20 @@ impl SerializeConfig for NetworkConfig {
                  alive.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "peer_reports_threshold",
+                &self.peer_reports_threshold,
+                "The number of times a peer should be reported within peer_reports_window in \
+                 order to be blocked.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "peer_reports_window",
+                &self.peer_reports_window.as_secs(),
+                "Time in seconds during which peer_reports_threshold reports on a peer cause it \
+                 to be blocked.",
+                ParamPrivacyInput::Public,
+            ),
         ]);
         config.extend(ser_optional_param(
             &self.bootstrap_peer_multiaddr,
@@ -100,6 +117,8 @@ impl Default for NetworkConfig {
             session_timeout: Duration::from_secs(120),
             idle_connection_timeout: Duration::from_secs(120),
             bootstrap_peer_multiaddr: None,
+            peer_reports_threshold: 1,
+            peer_reports_window: Duration::from_secs(60),
             secret_key: None,
         }
     }
@@ -60,11 +60,12 @@ impl MixedBehaviour {
         keypair: Keypair,
         bootstrap_peer_multiaddr: Option<Multiaddr>,
         streamed_bytes_config: sqmr::Config,
+        peer_manager_config: PeerManagerConfig,
     ) -> Self {
         let public_key = keypair.public();
         let local_peer_id = PeerId::from_public_key(&public_key);
         Self {
-            peer_manager: peer_manager::PeerManager::new(PeerManagerConfig::default()),
+            peer_manager: peer_manager::PeerManager::new(peer_manager_config),
             discovery: bootstrap_peer_multiaddr
                 .map(|bootstrap_peer_multiaddr| {
                     discovery::Behaviour::new(
@@ -86,6 +87,9 @@ impl MixedBehaviour {
                 gossipsub::MessageAuthenticity::Signed(keypair),
                 gossipsub::ConfigBuilder::default()
                     .max_transmit_size(ONE_MEGA)
+                    // Messages are propagated only after the network manager reports their
+                    // validation result.
+                    .validate_messages()
                     .build()
                     .expect("Failed to build gossipsub config"),
             )
@@ -11,7 +11,7 @@ use futures::future::{ready, BoxFuture, Ready};
 use futures::sink::With;
 use futures::stream::{self, BoxStream, FuturesUnordered, Map, Stream};
 use futures::{pin_mut, FutureExt, Sink, SinkExt, StreamExt};
-use libp2p::gossipsub::{SubscriptionError, TopicHash};
+use libp2p::gossipsub::{MessageId, SubscriptionError, TopicHash};
 use libp2p::swarm::SwarmEvent;
 use libp2p::{PeerId, StreamProtocol, Swarm};
 use metrics::gauge;
@@ -21,8 +21,9 @@ use tracing::{debug, error, info, trace};
 
 use self::swarm_trait::SwarmTrait;
 use crate::bin_utils::build_swarm;
-use crate::gossipsub_impl::Topic;
+use crate::gossipsub_impl::{MessageAcceptance, Topic, ValidationResultSender};
 use crate::mixed_behaviour::{self, BridgedBehaviour};
+use crate::peer_manager::PeerManagerConfig;
 use crate::sqmr::{self, InboundSessionId, OutboundSessionId, SessionId};
 use crate::utils::StreamHashMap;
 use crate::{gossipsub_impl, NetworkConfig};
@@ -45,10 +46,14 @@ pub struct GenericNetworkManager<SwarmT: SwarmTrait> {
     sqmr_outbound_report_receivers: HashMap<OutboundSessionId, ReportReceiver>,
     // Splitting the broadcast receivers from the broadcasted senders in order to poll all
     // receivers simultaneously.
-    // Each receiver has a matching sender and vice versa (i.e the maps have the same keys).
+    // Each receiver has a matching sender in exactly one of the senders maps and vice versa.
     messages_to_broadcast_receivers: StreamHashMap<TopicHash, Receiver<Bytes>>,
     broadcasted_messages_senders: HashMap<TopicHash, Sender<(Bytes, ReportSender)>>,
+    validated_broadcasted_messages_senders:
+        HashMap<TopicHash, Sender<(Bytes, ReportSender, ValidationResultSender)>>,
     reported_peer_receivers: FuturesUnordered<BoxFuture<'static, Option<PeerId>>>,
+    message_validation_results:
+        FuturesUnordered<BoxFuture<'static, (MessageId, PeerId, MessageAcceptance)>>,
     // Fields for metrics
     num_active_inbound_sessions: usize,
     num_active_outbound_sessions: usize,
@@ -67,6 +72,11 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
                     self.broadcast_message(message, topic_hash);
                 }
                 Some(Some(peer_id)) = self.reported_peer_receivers.next() => self.swarm.report_peer(peer_id),
+                Some((message_id, propagation_source, acceptance)) = self.message_validation_results.next() => {
+                    self.swarm.report_message_validation_result(
+                        &message_id, &propagation_source, acceptance.into()
+                    );
+                }
             }
         }
     }
@@ -75,6 +85,8 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
         gauge!(papyrus_metrics::PAPYRUS_NUM_CONNECTED_PEERS, 0f64);
         let reported_peer_receivers = FuturesUnordered::new();
         reported_peer_receivers.push(futures::future::pending().boxed());
+        let message_validation_results = FuturesUnordered::new();
+        message_validation_results.push(futures::future::pending().boxed());
         Self {
             swarm,
             inbound_protocol_to_buffer_size: HashMap::new(),
@@ -85,13 +97,29 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
             sqmr_outbound_report_receivers: HashMap::new(),
             messages_to_broadcast_receivers: StreamHashMap::new(HashMap::new()),
             broadcasted_messages_senders: HashMap::new(),
+            validated_broadcasted_messages_senders: HashMap::new(),
             reported_peer_receivers,
+            message_validation_results,
             num_active_inbound_sessions: 0,
             num_active_outbound_sessions: 0,
         }
     }
 
-    /// TODO: Support multiple protocols where they're all different versions of the same protocol
+    /// Register a new server for receiving queries and sending multiple responses to each of them.
+    /// Each item of the returned receiver is a query and a sender for the responses to it. Closing
+    /// the sender finishes the session. For example:
+    /// ```rust,ignore
+    /// let mut query_receiver =
+    ///     network_manager.register_sqmr_protocol_server::<Query, Response>(protocol, buffer_size);
+    /// while let Some((query_result, mut responses_sender)) = query_receiver.next().await {
+    ///     for response in get_responses(query_result?) {
+    ///         responses_sender.feed(response).await?;
+    ///     }
+    ///     responses_sender.close().await?;
+    /// }
+    /// ```
+    /// Panics if the given protocol is already registered as a server.
+    // TODO: Support multiple protocols where they're all different versions of the same protocol
     pub fn register_sqmr_protocol_server<Query, Response>(
         &mut self,
         protocol: String,
@@ -160,6 +188,22 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
         Box::new(payload_sender)
     }
 
+    /// Unregister a protocol that was registered as a server and/or as a client. The channels
+    /// returned on registration are closed and new inbound queries for it are rejected. Sessions
+    /// that are already running are not affected.
+    /// Panics if the given protocol isn't registered.
+    pub fn unregister_sqmr_protocol(&mut self, protocol: String) {
+        let protocol = StreamProtocol::try_from_owned(protocol)
+            .expect("Could not parse protocol into StreamProtocol.");
+        self.swarm.remove_supported_inbound_protocol(protocol.clone());
+        self.inbound_protocol_to_buffer_size.remove(&protocol);
+        let was_server = self.sqmr_inbound_query_senders.remove(&protocol).is_some();
+        let was_client = self.sqmr_outbound_payload_receivers.remove(&protocol).is_some();
+        if !was_server && !was_client {
+            panic!("Protocol '{}' isn't registered.", protocol);
+        }
+    }
+
     /// Register a new subscriber for broadcasting and receiving broadcasts for a given topic.
     /// Panics if this topic is already subscribed.
     pub fn register_broadcast_topic<T>(
@@ -171,14 +215,73 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
         T: TryFrom<Bytes>,
         Bytes: From<T>,
     {
-        self.swarm.subscribe_to_topic(&topic)?;
+        let messages_to_broadcast_sender =
+            self.subscribe_to_broadcast_topic(&topic, buffer_size)?;
+
+        let (broadcasted_messages_sender, broadcasted_messages_receiver) =
+            futures::channel::mpsc::channel(buffer_size);
+        self.broadcasted_messages_senders.insert(topic.hash(), broadcasted_messages_sender);
+
+        let broadcasted_messages_fn: BroadcastReceivedMessagesConverterFn<T> =
+            |(x, report_sender)| (T::try_from(x), report_sender);
+        let broadcasted_messages_receiver =
+            broadcasted_messages_receiver.map(broadcasted_messages_fn);
+
+        Ok(BroadcastSubscriberChannels {
+            messages_to_broadcast_sender,
+            broadcasted_messages_receiver,
+        })
+    }
+
+    /// Register a new subscriber for broadcasting and receiving broadcasts for a given topic, where
+    /// the subscriber validates each received message before it's propagated to other peers.
+    /// Each received message comes with a [`ValidationResultSender`] and the message is propagated
+    /// only if [`MessageAcceptance::Accept`] is sent on it.
+    /// Panics if this topic is already subscribed.
+    pub fn register_broadcast_topic_with_validation<T>(
+        &mut self,
+        topic: Topic,
+        buffer_size: usize,
+    ) -> Result<ValidatingBroadcastSubscriberChannels<T>, SubscriptionError>
+    where
+        T: TryFrom<Bytes>,
+        Bytes: From<T>,
+    {
+        let messages_to_broadcast_sender =
+            self.subscribe_to_broadcast_topic(&topic, buffer_size)?;
+
+        let (broadcasted_messages_sender, broadcasted_messages_receiver) =
+            futures::channel::mpsc::channel(buffer_size);
+        self.validated_broadcasted_messages_senders
+            .insert(topic.hash(), broadcasted_messages_sender);
+
+        let broadcasted_messages_fn: ValidatingBroadcastReceivedMessagesConverterFn<T> =
+            |(x, report_sender, validation_result_sender)| {
+                (T::try_from(x), report_sender, validation_result_sender)
+            };
+        let broadcasted_messages_receiver =
+            broadcasted_messages_receiver.map(broadcasted_messages_fn);
+
+        Ok(ValidatingBroadcastSubscriberChannels {
+            messages_to_broadcast_sender,
+            broadcasted_messages_receiver,
+        })
+    }
+
+    fn subscribe_to_broadcast_topic<T>(
+        &mut self,
+        topic: &Topic,
+        buffer_size: usize,
+    ) -> Result<BroadcastSubscriberSender<T>, SubscriptionError>
+    where
+        Bytes: From<T>,
+    {
+        self.swarm.subscribe_to_topic(topic)?;
 
         let topic_hash = topic.hash();
 
         let (messages_to_broadcast_sender, messages_to_broadcast_receiver) =
             futures::channel::mpsc::channel(buffer_size);
-        let (broadcasted_messages_sender, broadcasted_messages_receiver) =
-            futures::channel::mpsc::channel(buffer_size);
 
         let insert_result = self
             .messages_to_broadcast_receivers
@@ -187,27 +290,9 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
             panic!("Topic '{}' has already been registered.", topic);
         }
 
-        let insert_result = self
-            .broadcasted_messages_senders
-            .insert(topic_hash.clone(), broadcasted_messages_sender);
-        if insert_result.is_some() {
-            panic!("Topic '{}' has already been registered.", topic);
-        }
-
         let messages_to_broadcast_fn: fn(T) -> Ready<Result<Bytes, SendError>> =
             |x| ready(Ok(Bytes::from(x)));
-        let messages_to_broadcast_sender =
-            messages_to_broadcast_sender.with(messages_to_broadcast_fn);
-
-        let broadcasted_messages_fn: BroadcastReceivedMessagesConverterFn<T> =
-            |(x, report_sender)| (T::try_from(x), report_sender);
-        let broadcasted_messages_receiver =
-            broadcasted_messages_receiver.map(broadcasted_messages_fn);
-
-        Ok(BroadcastSubscriberChannels {
-            messages_to_broadcast_sender,
-            broadcasted_messages_receiver,
-        })
+        Ok(messages_to_broadcast_sender.with(messages_to_broadcast_fn))
     }
 
     fn handle_swarm_event(&mut self, event: SwarmEvent<mixed_behaviour::Event>) {
@@ -324,6 +409,16 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
                 // TODO: consider returning error instead of panic.
                 let Some(query_sender) = self.sqmr_inbound_query_senders.get_mut(&protocol_name)
                 else {
+                    debug!(
+                        "Received an inbound query for the unregistered protocol \
+                         {protocol_name:?}. Closing session {inbound_session_id:?}"
+                    );
+                    self.swarm.close_inbound_session(inbound_session_id).unwrap_or_else(|e| {
+                        error!(
+                            "Failed to close session for an unregistered protocol. Session id: \
+                             {inbound_session_id:?} not found error: {e:?}"
+                        )
+                    });
                     return;
                 };
                 let (response_sender, response_receiver) = futures::channel::mpsc::channel(
@@ -396,16 +491,64 @@ impl<SwarmT: SwarmTrait> GenericNetworkManager<SwarmT> {
 
     fn handle_gossipsub_behaviour_event(&mut self, event: gossipsub_impl::ExternalEvent) {
         match event {
-            gossipsub_impl::ExternalEvent::Received { originated_peer_id, message, topic_hash } => {
+            gossipsub_impl::ExternalEvent::Received {
+                originated_peer_id,
+                message,
+                topic_hash,
+                message_id,
+                propagation_source,
+            } => {
                 let (report_sender, report_receiver) = oneshot::channel::<()>();
                 self.handle_new_report_receiver(originated_peer_id, report_receiver);
+                if let Some(sender) =
+                    self.validated_broadcasted_messages_senders.get_mut(&topic_hash)
+                {
+                    let (validation_result_sender, validation_result_receiver) =
+                        oneshot::channel::<MessageAcceptance>();
+                    self.message_validation_results.push(
+                        validation_result_receiver
+                            .map(move |result| {
+                                (
+                                    message_id,
+                                    propagation_source,
+                                    result.unwrap_or(MessageAcceptance::Ignore),
+                                )
+                            })
+                            .boxed(),
+                    );
+                    let send_result =
+                        sender.try_send((message, report_sender, validation_result_sender));
+                    if let Err(e) = send_result {
+                        if e.is_disconnected() {
+                            panic!("Receiver was dropped. This should never happen.")
+                        } else if e.is_full() {
+                            error!(
+                                "Receiver buffer is full. Dropping broadcasted message for topic \
+                                 with hash: {topic_hash:?}."
+                            );
+                        }
+                    }
+                    return;
+                }
                 let Some(sender) = self.broadcasted_messages_senders.get_mut(&topic_hash) else {
                     error!(
                         "Received a message from a topic we're not subscribed to with hash \
                          {topic_hash:?}"
                     );
+                    self.swarm.report_message_validation_result(
+                        &message_id,
+                        &propagation_source,
+                        MessageAcceptance::Ignore.into(),
+                    );
                     return;
                 };
+                // The subscriber didn't ask to validate messages of this topic, so they're
+                // propagated immediately.
+                self.swarm.report_message_validation_result(
+                    &message_id,
+                    &propagation_source,
+                    MessageAcceptance::Accept.into(),
+                );
                 let send_result = sender.try_send((message, report_sender));
                 if let Err(e) = send_result {
                     if e.is_disconnected() {
@@ -544,6 +687,8 @@ impl NetworkManager {
             session_timeout,
             idle_connection_timeout,
             bootstrap_peer_multiaddr,
+            peer_reports_threshold,
+            peer_reports_window,
             secret_key,
         } = config;
 
@@ -557,6 +702,11 @@ impl NetworkManager {
                 key,
                 bootstrap_peer_multiaddr.clone(),
                 sqmr::Config { session_timeout },
+                PeerManagerConfig {
+                    peer_reports_threshold,
+                    peer_reports_window,
+                    ..Default::default()
+                },
             )
         });
         Self::generic_new(swarm)
@@ -728,6 +878,21 @@ pub struct BroadcastSubscriberChannels<T: TryFrom<Bytes>> {
     pub broadcasted_messages_receiver: BroadcastSubscriberReceiver<T>,
 }
 
+pub type ValidatingBroadcastSubscriberReceiver<T> = Map<
+    Receiver<(Bytes, ReportSender, ValidationResultSender)>,
+    ValidatingBroadcastReceivedMessagesConverterFn<T>,
+>;
+
+type ValidatingBroadcastReceivedMessagesConverterFn<T> =
+    fn(
+        (Bytes, ReportSender, ValidationResultSender),
+    ) -> (Result<T, <T as TryFrom<Bytes>>::Error>, ReportSender, ValidationResultSender);
+
+pub struct ValidatingBroadcastSubscriberChannels<T: TryFrom<Bytes>> {
+    pub messages_to_broadcast_sender: BroadcastSubscriberSender<T>,
+    pub broadcasted_messages_receiver: ValidatingBroadcastSubscriberReceiver<T>,
+}
+
 #[cfg(feature = "testing")]
 pub type MockBroadcastedMessagesSender<T> = With<
     Sender<(Bytes, ReportSender)>,
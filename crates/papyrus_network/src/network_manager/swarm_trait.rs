@@ -1,5 +1,5 @@
 use futures::stream::Stream;
-use libp2p::gossipsub::{SubscriptionError, TopicHash};
+use libp2p::gossipsub::{MessageAcceptance, MessageId, SubscriptionError, TopicHash};
 use libp2p::swarm::dial_opts::DialOpts;
 use libp2p::swarm::{DialError, NetworkBehaviour, SwarmEvent};
 use libp2p::{Multiaddr, PeerId, StreamProtocol, Swarm};
@@ -49,9 +49,18 @@ pub trait SwarmTrait: Stream<Item = Event> + Unpin {
 
     fn broadcast_message(&mut self, message: Bytes, topic_hash: TopicHash);
 
+    fn report_message_validation_result(
+        &mut self,
+        message_id: &MessageId,
+        propagation_source: &PeerId,
+        acceptance: MessageAcceptance,
+    );
+
     fn report_peer(&mut self, peer_id: PeerId);
 
     fn add_new_supported_inbound_protocol(&mut self, protocol_name: StreamProtocol);
+
+    fn remove_supported_inbound_protocol(&mut self, protocol_name: StreamProtocol);
 }
 
 impl SwarmTrait for Swarm<mixed_behaviour::MixedBehaviour> {
@@ -121,6 +130,22 @@ impl SwarmTrait for Swarm<mixed_behaviour::MixedBehaviour> {
         }
     }
 
+    fn report_message_validation_result(
+        &mut self,
+        message_id: &MessageId,
+        propagation_source: &PeerId,
+        acceptance: MessageAcceptance,
+    ) {
+        let result = self.behaviour_mut().gossipsub.report_message_validation_result(
+            message_id,
+            propagation_source,
+            acceptance,
+        );
+        if let Err(err) = result {
+            error!("Error occured while propagating the message with id {message_id:?}: {err:?}");
+        }
+    }
+
     fn report_peer(&mut self, peer_id: PeerId) {
         let _ = self.behaviour_mut().peer_manager.report_peer(peer_id, ReputationModifier::Bad {});
     }
@@ -128,4 +153,8 @@ impl SwarmTrait for Swarm<mixed_behaviour::MixedBehaviour> {
     fn add_new_supported_inbound_protocol(&mut self, protocol: StreamProtocol) {
         self.behaviour_mut().sqmr.add_new_supported_inbound_protocol(protocol);
     }
+
+    fn remove_supported_inbound_protocol(&mut self, protocol: StreamProtocol) {
+        self.behaviour_mut().sqmr.remove_supported_inbound_protocol(&protocol);
+    }
 }
@@ -14,7 +14,7 @@ use futures::stream::Stream;
 use futures::{pin_mut, Future, SinkExt, StreamExt};
 use lazy_static::lazy_static;
 use libp2p::core::ConnectedPoint;
-use libp2p::gossipsub::{SubscriptionError, TopicHash};
+use libp2p::gossipsub::{MessageAcceptance, MessageId, SubscriptionError, TopicHash};
 use libp2p::swarm::ConnectionId;
 use libp2p::{Multiaddr, PeerId, StreamProtocol};
 use tokio::select;
@@ -186,6 +186,14 @@ impl SwarmTrait for MockSwarm {
         }
     }
 
+    fn report_message_validation_result(
+        &mut self,
+        _message_id: &MessageId,
+        _propagation_source: &PeerId,
+        _acceptance: MessageAcceptance,
+    ) {
+    }
+
     fn report_peer(&mut self, peer_id: PeerId) {
         for sender in &self.reported_peer_senders {
             sender.unbounded_send(peer_id).unwrap();
@@ -197,6 +205,8 @@ impl SwarmTrait for MockSwarm {
         }
     }
 
+    fn remove_supported_inbound_protocol(&mut self, _protocol_name: StreamProtocol) {}
+
     fn get_peer_id_from_session_id(
         &self,
         _session_id: crate::sqmr::SessionId,
@@ -309,6 +319,63 @@ async fn process_incoming_query() {
     }
 }
 
+#[tokio::test]
+async fn unregister_sqmr_protocol_rejects_new_queries() {
+    let protocol: StreamProtocol = SIGNED_BLOCK_HEADER_PROTOCOL;
+
+    // Setup mock swarm and tell it to return an event of new inbound query.
+    let mut mock_swarm = MockSwarm::default();
+    let inbound_session_id = InboundSessionId { value: 0 };
+    mock_swarm.pending_events.push(Event::Behaviour(mixed_behaviour::Event::ExternalEvent(
+        mixed_behaviour::ExternalEvent::Sqmr(GenericEvent::NewInboundSession {
+            query: VEC1.clone(),
+            inbound_session_id,
+            peer_id: PeerId::random(),
+            protocol_name: protocol.clone(),
+        }),
+    )));
+
+    // Create a future that will return when the session is closed with the responses sent on the
+    // swarm.
+    let get_responses_fut = mock_swarm.get_responses_sent_to_inbound_session(inbound_session_id);
+
+    let mut network_manager = GenericNetworkManager::generic_new(mock_swarm);
+
+    let mut inbound_query_receiver = network_manager
+        .register_sqmr_protocol_server::<Vec<u8>, Vec<u8>>(protocol.to_string(), BUFFER_SIZE);
+    let mut payload_sender = network_manager
+        .register_sqmr_protocol_client::<Vec<u8>, Vec<u8>>(protocol.to_string(), BUFFER_SIZE);
+
+    network_manager.unregister_sqmr_protocol(protocol.to_string());
+
+    // The channels returned on registration should be closed.
+    assert!(inbound_query_receiver.next().await.is_none());
+    let (responses_sender, _responses_receiver) =
+        futures::channel::mpsc::channel::<Result<Vec<u8>, Infallible>>(BUFFER_SIZE);
+    let (_report_sender, report_receiver) = oneshot::channel::<()>();
+    assert!(
+        payload_sender
+            .send(SqmrClientPayload {
+                query: VEC1.clone(),
+                report_receiver,
+                responses_sender: Box::new(responses_sender),
+            })
+            .await
+            .is_err()
+    );
+
+    // The inbound session should be closed without any responses.
+    select! {
+        responses = get_responses_fut => assert!(responses.is_empty()),
+        _ = network_manager.run() => {
+            panic!("GenericNetworkManager::run finished before the session was closed");
+        }
+        _ = sleep(Duration::from_secs(5)) => {
+            panic!("Test timed out");
+        }
+    }
+}
+
 #[tokio::test]
 async fn broadcast_message() {
     let topic = Topic::new("TOPIC");
@@ -349,6 +416,8 @@ async fn receive_broadcasted_message_and_report_it() {
             originated_peer_id,
             message: message.clone(),
             topic_hash: topic.hash(),
+            message_id: MessageId::new(&[]),
+            propagation_source: originated_peer_id,
         }),
     )));
     let mut reported_peer_receiver = mock_swarm.get_reported_peers_stream();
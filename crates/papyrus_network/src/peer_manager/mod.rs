@@ -1,12 +1,12 @@
-use std::collections::HashMap;
-use std::time::Duration;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
 
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use libp2p::swarm::dial_opts::DialOpts;
 use libp2p::swarm::ToSwarm;
 use libp2p::PeerId;
-use tracing::info;
+use tracing::{debug, info};
 
 pub use self::behaviour_impl::ToOtherBehaviourEvent;
 use self::peer::PeerTrait;
@@ -37,12 +37,17 @@ pub struct PeerManager<P: PeerTrait + 'static> {
     peers_pending_dial_with_sessions: HashMap<PeerId, Vec<OutboundSessionId>>,
     sessions_received_when_no_peers: Vec<OutboundSessionId>,
     sleep_waiting_for_unblocked_peer: Option<BoxFuture<'static, ()>>,
+    // The times of the reports on each peer that are still inside the reports window.
+    peer_reports: HashMap<PeerId, VecDeque<Instant>>,
 }
 
 #[derive(Clone)]
 pub struct PeerManagerConfig {
-    target_num_for_peers: usize,
-    blacklist_timeout: Duration,
+    pub(crate) target_num_for_peers: usize,
+    pub(crate) blacklist_timeout: Duration,
+    /// The number of reports on a peer within `peer_reports_window` that cause it to be blocked.
+    pub(crate) peer_reports_threshold: usize,
+    pub(crate) peer_reports_window: Duration,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -61,6 +66,8 @@ impl Default for PeerManagerConfig {
             target_num_for_peers: 100,
             // 1 year.
             blacklist_timeout: Duration::from_secs(3600 * 24 * 365),
+            peer_reports_threshold: 1,
+            peer_reports_window: Duration::from_secs(60),
         }
     }
 }
@@ -81,6 +88,7 @@ where
             peers_pending_dial_with_sessions: HashMap::new(),
             sessions_received_when_no_peers: Vec::new(),
             sleep_waiting_for_unblocked_peer: None,
+            peer_reports: HashMap::new(),
         }
     }
 
@@ -185,12 +193,7 @@ where
     ) -> Result<(), PeerManagerError> {
         // TODO(shahak): Add time blacklisted to log.
         info!("Peer {:?} reported as misbehaving.", peer_id);
-        if let Some(peer) = self.peers.get_mut(&peer_id) {
-            peer.update_reputation(reason);
-            Ok(())
-        } else {
-            Err(PeerManagerError::NoSuchPeer(peer_id))
-        }
+        self.add_report_to_peer(peer_id, reason)
     }
 
     fn report_session(
@@ -199,17 +202,45 @@ where
         reason: ReputationModifier,
     ) -> Result<(), PeerManagerError> {
         if let Some(peer_id) = self.session_to_peer_map.get(&outbound_session_id) {
-            if let Some(peer) = self.peers.get_mut(peer_id) {
-                peer.update_reputation(reason);
-                Ok(())
-            } else {
-                Err(PeerManagerError::NoSuchPeer(*peer_id))
-            }
+            self.add_report_to_peer(*peer_id, reason)
         } else {
             Err(PeerManagerError::NoSuchSession(outbound_session_id))
         }
     }
 
+    /// Records a report on the peer and blocks it if it was reported at least
+    /// `peer_reports_threshold` times within the last `peer_reports_window`.
+    fn add_report_to_peer(
+        &mut self,
+        peer_id: PeerId,
+        reason: ReputationModifier,
+    ) -> Result<(), PeerManagerError> {
+        let Some(peer) = self.peers.get_mut(&peer_id) else {
+            return Err(PeerManagerError::NoSuchPeer(peer_id));
+        };
+        let now = Instant::now();
+        let reports = self.peer_reports.entry(peer_id).or_default();
+        reports.push_back(now);
+        while reports
+            .front()
+            .is_some_and(|report_time| now - *report_time > self.config.peer_reports_window)
+        {
+            reports.pop_front();
+        }
+        if reports.len() >= self.config.peer_reports_threshold {
+            reports.clear();
+            peer.update_reputation(reason);
+        } else {
+            debug!(
+                "Peer {:?} has {} reports out of {} needed for blocking it.",
+                peer_id,
+                reports.len(),
+                self.config.peer_reports_threshold
+            );
+        }
+        Ok(())
+    }
+
     fn more_peers_needed(&self) -> bool {
         // TODO: consider if we should count blocked peers (and in what cases? what if they are
         // blocked temporarily?)
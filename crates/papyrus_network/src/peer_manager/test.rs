@@ -324,6 +324,36 @@ async fn timed_out_peer_not_assignable_to_queries() {
     assert_matches!(peer_manager.assign_peer_to_session(outbound_session_id), None);
 }
 
+#[tokio::test]
+async fn peer_blocked_after_reaching_reports_threshold() {
+    const PEER_REPORTS_THRESHOLD: usize = 3;
+
+    // Create a new peer manager
+    let config = PeerManagerConfig {
+        peer_reports_threshold: PEER_REPORTS_THRESHOLD,
+        peer_reports_window: Duration::from_secs(3600),
+        ..Default::default()
+    };
+    let mut peer_manager = PeerManager::new(config);
+
+    // Add a peer to the peer manager
+    let peer = Peer::new(PeerId::random(), Multiaddr::empty());
+    let peer_id = peer.peer_id();
+    peer_manager.add_peer(peer);
+
+    // Report the peer one time less than the threshold and check it's still assignable.
+    for i in 0..PEER_REPORTS_THRESHOLD - 1 {
+        peer_manager.report_peer(peer_id, ReputationModifier::Bad {}).unwrap();
+        let outbound_session_id = OutboundSessionId { value: i };
+        assert_eq!(peer_manager.assign_peer_to_session(outbound_session_id), Some(peer_id));
+    }
+
+    // Report the peer once more and check it's excluded from the assignment.
+    peer_manager.report_peer(peer_id, ReputationModifier::Bad {}).unwrap();
+    let outbound_session_id = OutboundSessionId { value: PEER_REPORTS_THRESHOLD };
+    assert_matches!(peer_manager.assign_peer_to_session(outbound_session_id), None);
+}
+
 #[test]
 fn wrap_around_in_peer_assignment() {
     // Create a new peer manager
@@ -255,6 +255,11 @@ impl Behaviour {
             self.supported_inbound_protocols.insert(protocol);
         }
     }
+
+    /// Stops supporting the given protocol on connections established from now on.
+    pub fn remove_supported_inbound_protocol(&mut self, protocol: &StreamProtocol) {
+        self.supported_inbound_protocols.remove(protocol);
+    }
 }
 
 impl NetworkBehaviour for Behaviour {
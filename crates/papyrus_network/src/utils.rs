@@ -38,6 +38,11 @@ impl<K: Unpin + Clone + Eq + Hash, V: Stream + Unpin> StreamHashMap<K, V> {
     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
         self.map.insert(key, value)
     }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.finished_streams.remove(key);
+        self.map.remove(key)
+    }
 }
 
 impl<K: Unpin + Clone + Eq + Hash, V: Stream + Unpin> Stream for StreamHashMap<K, V> {
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use std::{env, fs};
 
 use futures_util::pin_mut;
@@ -24,8 +25,9 @@ async fn main() {
     ])
     .expect("Load config");
     let (storage_reader, _) = open_storage(config.storage).expect("Open storage");
-    let central_source = CentralSource::new(config.central, VERSION_FULL, storage_reader)
-        .expect("Create new client");
+    let central_source =
+        CentralSource::new(config.central, VERSION_FULL, storage_reader, Arc::default())
+            .expect("Create new client");
     let last_block_number = central_source
         .get_latest_block()
         .await
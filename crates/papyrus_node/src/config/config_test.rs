@@ -24,7 +24,7 @@ use validator::Validate;
 
 #[cfg(feature = "rpc")]
 use crate::config::pointers::CONFIG_POINTERS;
-use crate::config::{node_command, NodeConfig, DEFAULT_CONFIG_PATH};
+use crate::config::{node_command, NodeConfig, DEFAULT_CONFIG_PATH, VALIDATE_CONFIG_ARG};
 
 // Returns the required and generated params in default_config.json with the default value from the
 // config presentation.
@@ -112,6 +112,18 @@ fn test_default_config_process() {
     assert_eq!(NodeConfig::load_and_process(get_args(vec![])).unwrap(), NodeConfig::default());
 }
 
+#[test]
+fn load_with_validate_config_flag() {
+    env::set_current_dir(get_absolute_path("")).expect("Couldn't set working dir.");
+    let (config, arg_matches) =
+        NodeConfig::load_and_process_with_matches(get_args(vec!["--validate-config"])).unwrap();
+    assert_eq!(config, NodeConfig::default());
+    assert!(arg_matches.get_flag(VALIDATE_CONFIG_ARG));
+
+    let (_, arg_matches) = NodeConfig::load_and_process_with_matches(get_args(vec![])).unwrap();
+    assert!(!arg_matches.get_flag(VALIDATE_CONFIG_ARG));
+}
+
 #[test]
 fn test_update_dumped_config_by_command() {
     let args = get_args(vec![
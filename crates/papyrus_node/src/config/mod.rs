@@ -12,7 +12,7 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 use std::{env, fs, io};
 
-use clap::{arg, value_parser, Arg, ArgMatches, Command};
+use clap::{arg, value_parser, Arg, ArgAction, ArgMatches, Command};
 use itertools::{chain, Itertools};
 use lazy_static::lazy_static;
 use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerConfig;
@@ -23,7 +23,7 @@ use papyrus_config::dumping::{
     ser_pointer_target_param,
     SerializeConfig,
 };
-use papyrus_config::loading::load_and_process_config;
+use papyrus_config::loading::load_and_process_config_with_matches;
 use papyrus_config::{ConfigError, ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_consensus::config::ConsensusConfig;
 use papyrus_monitoring_gateway::MonitoringGatewayConfig;
@@ -37,6 +37,7 @@ use papyrus_sync::sources::central::CentralSourceConfig;
 use papyrus_sync::SyncConfig;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use starknet_api::block::BlockHash;
 use starknet_api::core::ChainId;
 use starknet_client::RetryConfig;
 use validator::Validate;
@@ -54,19 +55,23 @@ pub struct NodeConfig {
     pub rpc: RpcConfig,
     pub central: CentralSourceConfig,
     pub base_layer: EthereumBaseLayerConfig,
+    #[validate]
     pub monitoring_gateway: MonitoringGatewayConfig,
     #[validate]
     pub storage: StorageConfig,
     /// None if the syncing should be disabled.
+    #[validate]
     pub sync: Option<SyncConfig>,
     /// One of p2p_sync or sync must be None.
     /// If P2P sync is active, then network must be active too.
     // TODO(yair): Change NodeConfig to have an option of enum of SyncConfig or P2PSyncConfig.
     pub p2p_sync: Option<P2PSyncClientConfig>,
+    #[validate]
     pub consensus: Option<ConsensusConfig>,
     // TODO(shahak): Make network non-optional once it's developed enough.
     pub network: Option<NetworkConfig>,
     pub collect_profiling_metrics: bool,
+    pub genesis_hash: BlockHash,
 }
 
 // Default configuration values.
@@ -84,6 +89,7 @@ impl Default for NodeConfig {
             consensus: None,
             network: None,
             collect_profiling_metrics: false,
+            genesis_hash: BlockHash::default(),
         }
     }
 }
@@ -100,12 +106,20 @@ impl SerializeConfig for NodeConfig {
             ser_optional_sub_config(&self.p2p_sync, "p2p_sync"),
             ser_optional_sub_config(&self.consensus, "consensus"),
             ser_optional_sub_config(&self.network, "network"),
-            BTreeMap::from_iter([ser_param(
-                "collect_profiling_metrics",
-                &self.collect_profiling_metrics,
-                "If true, collect profiling metrics for the node.",
-                ParamPrivacyInput::Public,
-            )]),
+            BTreeMap::from_iter([
+                ser_param(
+                    "collect_profiling_metrics",
+                    &self.collect_profiling_metrics,
+                    "If true, collect profiling metrics for the node.",
+                    ParamPrivacyInput::Public,
+                ),
+                ser_param(
+                    "genesis_hash",
+                    &self.genesis_hash,
+                    "The parent hash of the first block of the chain.",
+                    ParamPrivacyInput::Public,
+                ),
+            ]),
         ];
         #[cfg(feature = "rpc")]
         sub_configs.push(append_sub_config_name(self.rpc.dump(), "rpc"));
@@ -118,14 +132,32 @@ impl NodeConfig {
     /// Creates a config object. Selects the values from the default file and from resources with
     /// higher priority.
     pub fn load_and_process(args: Vec<String>) -> Result<Self, ConfigError> {
+        Ok(Self::load_and_process_with_matches(args)?.0)
+    }
+
+    /// Same as [`NodeConfig::load_and_process`], and also returns the matched command line
+    /// arguments, for reading the args of [`node_command`] that aren't config params.
+    pub fn load_and_process_with_matches(
+        args: Vec<String>,
+    ) -> Result<(Self, ArgMatches), ConfigError> {
         let default_config_file = std::fs::File::open(Path::new(DEFAULT_CONFIG_PATH))?;
-        load_and_process_config(default_config_file, node_command(), args)
+        load_and_process_config_with_matches(default_config_file, node_command(), args)
     }
 }
 
+/// Command line flag for validating the config and exiting, without running the node. It's not a
+/// config param.
+pub const VALIDATE_CONFIG_ARG: &str = "validate-config";
+
 /// The command line interface of this node.
 pub fn node_command() -> Command {
     Command::new("Papyrus")
         .version(VERSION_FULL)
         .about("Papyrus is a StarkNet full node written in Rust.")
+        .arg(
+            Arg::new(VALIDATE_CONFIG_ARG)
+                .long(VALIDATE_CONFIG_ARG)
+                .action(ArgAction::SetTrue)
+                .help("Validate the config and exit without running the node"),
+        )
 }
@@ -2,7 +2,7 @@
 mod main_test;
 
 use std::env::args;
-use std::future::{pending, Future};
+use std::future::pending;
 use std::process::exit;
 use std::sync::Arc;
 use std::time::Duration;
@@ -10,6 +10,7 @@ use std::time::Duration;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerConfig;
+use papyrus_common::central_health::CentralHealthTracker;
 use papyrus_common::metrics::COLLECT_PROFILING_METRICS;
 use papyrus_common::pending_classes::{ApiContractClass, PendingClasses};
 use papyrus_common::BlockHashAndNumber;
@@ -27,7 +28,7 @@ use papyrus_network::network_manager::{
     SqmrQueryReceiver,
 };
 use papyrus_network::{network_manager, NetworkConfig};
-use papyrus_node::config::NodeConfig;
+use papyrus_node::config::{NodeConfig, VALIDATE_CONFIG_ARG};
 use papyrus_node::version::VERSION_FULL;
 use papyrus_p2p_sync::client::{
     P2PSyncClient,
@@ -56,12 +57,14 @@ use papyrus_sync::sources::central::{CentralError, CentralSource, CentralSourceC
 use papyrus_sync::sources::pending::PendingSource;
 use papyrus_sync::{StateSync, StateSyncError, SyncConfig};
 use starknet_api::block::BlockHash;
-use starknet_api::felt;
+use starknet_api::core::ChainId;
 use starknet_api::transaction::{Event, Transaction, TransactionHash, TransactionOutput};
 use starknet_client::reader::objects::pending_data::{PendingBlock, PendingBlockOrDeprecated};
 use starknet_client::reader::PendingData;
+use tokio::signal::unix::{signal, SignalKind};
 use tokio::sync::RwLock;
-use tokio::task::{JoinError, JoinHandle};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use tracing::metadata::LevelFilter;
 use tracing::{debug_span, error, info, warn, Instrument};
 use tracing_subscriber::prelude::*;
@@ -70,11 +73,6 @@ use tracing_subscriber::{fmt, EnvFilter};
 // TODO(yair): Add to config.
 const DEFAULT_LEVEL: LevelFilter = LevelFilter::INFO;
 
-// TODO(shahak): Consider adding genesis hash to the config to support chains that have
-// different genesis hash.
-// TODO: Consider moving to a more general place.
-const GENESIS_HASH: &str = "0x0";
-
 // TODO(dvir): add this to config.
 // Duration between updates to the storage metrics (those in the collect_storage_metrics function).
 const STORAGE_METRICS_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
@@ -86,17 +84,29 @@ async fn create_rpc_server_future(
     pending_data: Arc<RwLock<PendingData>>,
     pending_classes: Arc<RwLock<PendingClasses>>,
     storage_reader: StorageReader,
-) -> anyhow::Result<impl Future<Output = Result<(), JoinError>>> {
+    shutdown: CancellationToken,
+) -> anyhow::Result<JoinHandle<()>> {
     let (_, server_handle) = run_server(
         &config.rpc,
         shared_highest_block,
         pending_data,
         pending_classes,
         storage_reader,
+        config.genesis_hash,
         VERSION_FULL,
     )
     .await?;
-    Ok(tokio::spawn(server_handle.stopped()))
+    let stop_handle = server_handle.clone();
+    Ok(tokio::spawn(async move {
+        tokio::select! {
+            _ = server_handle.stopped() => {}
+            // Resolves once the server has stopped serving and dropped its storage reader.
+            _ = shutdown.cancelled() => {
+                let _ = stop_handle.stop();
+                stop_handle.stopped().await;
+            }
+        }
+    }))
 }
 
 #[cfg(not(feature = "rpc"))]
@@ -106,8 +116,9 @@ async fn create_rpc_server_future(
     _pending_data: Arc<RwLock<PendingData>>,
     _pending_classes: Arc<RwLock<PendingClasses>>,
     _storage_reader: StorageReader,
-) -> anyhow::Result<impl Future<Output = Result<(), JoinError>>> {
-    Ok(pending())
+    _shutdown: CancellationToken,
+) -> anyhow::Result<JoinHandle<()>> {
+    Ok(tokio::spawn(pending()))
 }
 
 fn run_consensus(
@@ -129,13 +140,31 @@ fn run_consensus(
         start_height,
         validator_id,
         consensus_channels.broadcasted_messages_receiver,
+        config.message_cache_size,
+        config.proposal_validation_timeout,
     )))
 }
 
-async fn run_threads(config: NodeConfig) -> anyhow::Result<()> {
+// The pending data before anything was synced, on top of the genesis block hash.
+fn initial_pending_data(genesis_hash: BlockHash) -> PendingData {
+    PendingData {
+        // The pending data might change later to DeprecatedPendingBlock, depending on the response
+        // from the feeder gateway.
+        block: PendingBlockOrDeprecated::Current(PendingBlock {
+            parent_block_hash: genesis_hash,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+// Runs the node's tasks until one of them stops or until `shutdown` is cancelled. On shutdown, the
+// sync is stopped first so that its in-flight commits finish, then the rest of the tasks are
+// stopped, releasing the storage.
+async fn run_threads(config: NodeConfig, shutdown: CancellationToken) -> anyhow::Result<()> {
     let (storage_reader, storage_writer) = open_storage(config.storage.clone())?;
 
-    let storage_metrics_handle = if config.monitoring_gateway.collect_metrics {
+    let mut storage_metrics_handle = if config.monitoring_gateway.collect_metrics {
         spawn_storage_metrics_collector(storage_reader.clone(), STORAGE_METRICS_UPDATE_INTERVAL)
     } else {
         tokio::spawn(pending())
@@ -149,9 +178,11 @@ async fn run_threads(config: NodeConfig) -> anyhow::Result<()> {
         maybe_consensus_channels,
         local_peer_id,
     ) = run_network(config.network.clone(), config.consensus.clone())?;
-    let network_handle = tokio::spawn(network_future);
+    let mut network_handle = tokio::spawn(network_future);
 
     // Monitoring server.
+    // The central sync is the only writer of the central health.
+    let central_health_tracker = Arc::new(RwLock::new(CentralHealthTracker::default()));
     let monitoring_server = MonitoringServer::new(
         config.monitoring_gateway.clone(),
         get_config_presentation(&config, true)?,
@@ -159,29 +190,26 @@ async fn run_threads(config: NodeConfig) -> anyhow::Result<()> {
         storage_reader.clone(),
         VERSION_FULL,
         local_peer_id,
+        central_health_tracker.clone(),
     )?;
-    let monitoring_server_handle = monitoring_server.spawn_server().await;
+    let mut monitoring_server_handle = monitoring_server.spawn_server().await;
 
     // The sync is the only writer of the syncing state.
     let shared_highest_block = Arc::new(RwLock::new(None));
-    let pending_data = Arc::new(RwLock::new(PendingData {
-        // The pending data might change later to DeprecatedPendingBlock, depending on the response
-        // from the feeder gateway.
-        block: PendingBlockOrDeprecated::Current(PendingBlock {
-            parent_block_hash: BlockHash(felt!(GENESIS_HASH)),
-            ..Default::default()
-        }),
-        ..Default::default()
+    let pending_data = Arc::new(RwLock::new(initial_pending_data(config.genesis_hash)));
+    let pending_classes = Arc::new(RwLock::new(match &config.sync {
+        Some(sync_config) => PendingClasses::new(sync_config.pending_classes_max_size),
+        None => PendingClasses::default(),
     }));
-    let pending_classes = Arc::new(RwLock::new(PendingClasses::default()));
 
     // JSON-RPC server.
-    let server_handle_future = create_rpc_server_future(
+    let mut server_handle_future = create_rpc_server_future(
         &config,
         shared_highest_block.clone(),
         pending_data.clone(),
         pending_classes.clone(),
         storage_reader.clone(),
+        shutdown.clone(),
     )
     .await?;
 
@@ -206,18 +234,32 @@ async fn run_threads(config: NodeConfig) -> anyhow::Result<()> {
         }
         None => pending().boxed(),
     };
-    let p2p_sync_server_handle = tokio::spawn(p2p_sync_server_future);
+    let mut p2p_sync_server_handle = tokio::spawn(p2p_sync_server_future);
 
     // Sync task.
+    let is_sync_running = config.sync.is_some();
     let (sync_future, p2p_sync_client_future) = match (config.sync, config.p2p_sync) {
         (Some(_), Some(_)) => {
             panic!("One of --sync.#is_none or --p2p_sync.#is_none must be turned on");
         }
         (Some(sync_config), None) => {
-            let configs = (sync_config, config.central, config.base_layer);
+            let configs = (
+                sync_config,
+                config.central,
+                config.base_layer,
+                config.storage.db_config.chain_id.clone(),
+                config.genesis_hash,
+            );
             let storage = (storage_reader.clone(), storage_writer);
-            let sync_fut =
-                run_sync(configs, shared_highest_block, pending_data, pending_classes, storage);
+            let sync_fut = run_sync(
+                configs,
+                shared_highest_block,
+                pending_data,
+                pending_classes,
+                central_health_tracker,
+                storage,
+                shutdown.clone(),
+            );
             (sync_fut.boxed(), pending().boxed())
         }
         (None, Some(p2p_sync_client_config)) => {
@@ -236,10 +278,10 @@ async fn run_threads(config: NodeConfig) -> anyhow::Result<()> {
         }
         (None, None) => (pending().boxed(), pending().boxed()),
     };
-    let sync_handle = tokio::spawn(sync_future);
-    let p2p_sync_client_handle = tokio::spawn(p2p_sync_client_future);
+    let mut sync_handle = tokio::spawn(sync_future);
+    let mut p2p_sync_client_handle = tokio::spawn(p2p_sync_client_future);
 
-    let consensus_handle = if let Some(consensus_channels) = maybe_consensus_channels {
+    let mut consensus_handle = if let Some(consensus_channels) = maybe_consensus_channels {
         run_consensus(
             config.consensus.expect("If consensus_channels is Some, consensus must be Some too."),
             storage_reader.clone(),
@@ -249,55 +291,88 @@ async fn run_threads(config: NodeConfig) -> anyhow::Result<()> {
         tokio::spawn(pending())
     };
 
+    let mut shutdown_requested = false;
     tokio::select! {
-        res = storage_metrics_handle => {
+        biased;
+        _ = shutdown.cancelled() => {
+            info!("Shutting down.");
+            shutdown_requested = true;
+        }
+        res = &mut storage_metrics_handle => {
             error!("collecting storage metrics stopped.");
             res?
         }
-        res = server_handle_future => {
+        res = &mut server_handle_future => {
             error!("RPC server stopped.");
             res?
         }
-        res = monitoring_server_handle => {
+        res = &mut monitoring_server_handle => {
             error!("Monitoring server stopped.");
             res??
         }
-        res = sync_handle => {
+        res = &mut sync_handle => {
             error!("Sync stopped.");
             res??
         }
-        res = p2p_sync_client_handle => {
+        res = &mut p2p_sync_client_handle => {
             error!("P2P Sync stopped.");
             res??
         }
-        res = p2p_sync_server_handle => {
+        res = &mut p2p_sync_server_handle => {
             error!("P2P Sync server stopped");
             res?
         }
-        res = network_handle => {
+        res = &mut network_handle => {
             error!("Network stopped.");
             res??
         }
-        res = consensus_handle => {
+        res = &mut consensus_handle => {
             error!("Consensus stopped.");
             res??
         }
     };
-    error!("Task ended with unexpected Ok.");
+    if !shutdown_requested {
+        error!("Task ended with unexpected Ok.");
+        return Ok(());
+    }
+
+    // The sync finishes committing the data it's processing before it stops.
+    if is_sync_running {
+        sync_handle.await??;
+    } else {
+        abort_task(sync_handle).await;
+    }
+    abort_task(p2p_sync_client_handle).await;
+    abort_task(consensus_handle).await;
+    #[cfg(feature = "rpc")]
+    server_handle_future.await?;
+    #[cfg(not(feature = "rpc"))]
+    abort_task(server_handle_future).await;
+    abort_task(p2p_sync_server_handle).await;
+    abort_task(network_handle).await;
+    abort_task(monitoring_server_handle).await;
+    abort_task(storage_metrics_handle).await;
+    info!("Shut down gracefully.");
     return Ok(());
 
     async fn run_sync(
-        configs: (SyncConfig, CentralSourceConfig, EthereumBaseLayerConfig),
+        configs: (SyncConfig, CentralSourceConfig, EthereumBaseLayerConfig, ChainId, BlockHash),
         shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
         pending_data: Arc<RwLock<PendingData>>,
         pending_classes: Arc<RwLock<PendingClasses>>,
+        central_health_tracker: Arc<RwLock<CentralHealthTracker>>,
         storage: (StorageReader, StorageWriter),
+        shutdown: CancellationToken,
     ) -> Result<(), StateSyncError> {
-        let (sync_config, central_config, base_layer_config) = configs;
+        let (sync_config, central_config, base_layer_config, chain_id, genesis_hash) = configs;
         let (storage_reader, storage_writer) = storage;
-        let central_source =
-            CentralSource::new(central_config.clone(), VERSION_FULL, storage_reader.clone())
-                .map_err(CentralError::ClientCreation)?;
+        let central_source = CentralSource::new(
+            central_config.clone(),
+            VERSION_FULL,
+            storage_reader.clone(),
+            central_health_tracker,
+        )
+        .map_err(CentralError::ClientCreation)?;
         let pending_source = PendingSource::new(central_config, VERSION_FULL)
             .map_err(CentralError::ClientCreation)?;
         let base_layer_source = EthereumBaseLayerSource::new(base_layer_config)
@@ -312,8 +387,11 @@ async fn run_threads(config: NodeConfig) -> anyhow::Result<()> {
             base_layer_source,
             storage_reader.clone(),
             storage_writer,
+            chain_id,
+            genesis_hash,
+            None,
         );
-        sync.run().await
+        sync.run(shutdown).await
     }
 
     async fn run_p2p_sync_client(
@@ -414,6 +492,31 @@ fn configure_tracing() {
     tracing_subscriber::registry().with(fmt_layer).with(level_filter_layer).init();
 }
 
+// Stops a task and waits until it's dropped, along with the resources it holds.
+async fn abort_task<T>(handle: JoinHandle<T>) {
+    handle.abort();
+    let _ = handle.await;
+}
+
+// Cancels `shutdown` once the process receives SIGTERM or SIGINT. A second signal exits the process
+// right away, in case the shutdown is stuck.
+async fn cancel_on_shutdown_signal(shutdown: CancellationToken) {
+    let mut sigterm = signal(SignalKind::terminate()).expect("Failed to listen to SIGTERM.");
+    let mut sigint = signal(SignalKind::interrupt()).expect("Failed to listen to SIGINT.");
+    tokio::select! {
+        _ = sigterm.recv() => info!("Received SIGTERM."),
+        _ = sigint.recv() => info!("Received SIGINT."),
+    }
+    shutdown.cancel();
+
+    tokio::select! {
+        _ = sigterm.recv() => {}
+        _ = sigint.recv() => {}
+    }
+    error!("Received a second shutdown signal, exiting without waiting for the shutdown.");
+    exit(1);
+}
+
 fn spawn_storage_metrics_collector(
     storage_reader: StorageReader,
     update_interval: Duration,
@@ -433,23 +536,29 @@ fn spawn_storage_metrics_collector(
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let config = NodeConfig::load_and_process(args().collect());
-    if let Err(ConfigError::CommandInput(clap_err)) = config {
+    let config_and_matches = NodeConfig::load_and_process_with_matches(args().collect());
+    if let Err(ConfigError::CommandInput(clap_err)) = config_and_matches {
         clap_err.exit();
     }
 
     configure_tracing();
 
-    let config = config?;
+    let (config, arg_matches) = config_and_matches?;
     if let Err(errors) = config_validate(&config) {
         error!("{}", errors);
         exit(1);
     }
+    if arg_matches.get_flag(VALIDATE_CONFIG_ARG) {
+        info!("The config is valid.");
+        return Ok(());
+    }
 
     COLLECT_PROFILING_METRICS
         .set(config.collect_profiling_metrics)
         .expect("This should be the first and only time we set this value.");
 
     info!("Booting up.");
-    run_threads(config).await
+    let shutdown = CancellationToken::new();
+    tokio::spawn(cancel_on_shutdown_signal(shutdown.clone()));
+    run_threads(config, shutdown).await
 }
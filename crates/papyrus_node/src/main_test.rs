@@ -1,12 +1,17 @@
+use std::fs;
 use std::time::Duration;
 
 use metrics_exporter_prometheus::PrometheusBuilder;
 use papyrus_node::config::NodeConfig;
 use papyrus_storage::{open_storage, StorageConfig};
+use starknet_api::block::BlockHash;
+use starknet_api::core::ChainId;
+use starknet_api::felt;
 use tempfile::TempDir;
 use test_utils::prometheus_is_contained;
+use tokio_util::sync::CancellationToken;
 
-use crate::{run_threads, spawn_storage_metrics_collector};
+use crate::{initial_pending_data, run_threads, spawn_storage_metrics_collector};
 
 // The mission of this test is to ensure that if an error is returned from one of the spawned tasks,
 // the node will stop, and this error will be returned. This is done by checking the case of an
@@ -19,10 +24,82 @@ async fn run_threads_stop() {
 
     // Error when not supplying legal central URL.
     config.central.url = "_not_legal_url".to_string();
-    let error = run_threads(config).await.expect_err("Should be an error.");
+    let error =
+        run_threads(config, CancellationToken::new()).await.expect_err("Should be an error.");
     assert_eq!("relative URL without a base", error.to_string());
 }
 
+// On shutdown, all the tasks should stop and return Ok, after which nothing holds the storage
+// anymore and it can be opened again.
+#[tokio::test]
+async fn run_threads_shutdown() {
+    let mut config = NodeConfig::default();
+    let temp_dir = TempDir::new().unwrap();
+    config.storage.db_config.path_prefix = temp_dir.path().into();
+    config.rpc.server_address = "127.0.0.1:0".to_string();
+    config.monitoring_gateway.server_address = "127.0.0.1:0".to_string();
+    // Keep the sync failing on a recoverable error without retrying.
+    config.central.url = "http://127.0.0.1:1".to_string();
+    config.central.retry_config.max_retries = 0;
+
+    let shutdown = CancellationToken::new();
+    let node_handle = tokio::spawn(run_threads(config.clone(), shutdown.clone()));
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(!node_handle.is_finished());
+
+    shutdown.cancel();
+    tokio::time::timeout(Duration::from_secs(10), node_handle)
+        .await
+        .expect("The node should shut down.")
+        .unwrap()
+        .unwrap();
+    open_storage(config.storage).expect("The storage should be released on shutdown.");
+}
+
+// Without a sync, there is nothing to wait for on shutdown and the rest of the tasks are stopped.
+#[tokio::test]
+async fn run_threads_shutdown_without_sync() {
+    let mut config = NodeConfig::default();
+    let temp_dir = TempDir::new().unwrap();
+    config.storage.db_config.path_prefix = temp_dir.path().into();
+    config.rpc.server_address = "127.0.0.1:0".to_string();
+    config.monitoring_gateway.server_address = "127.0.0.1:0".to_string();
+    config.sync = None;
+
+    let shutdown = CancellationToken::new();
+    let node_handle = tokio::spawn(run_threads(config.clone(), shutdown.clone()));
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(!node_handle.is_finished());
+
+    shutdown.cancel();
+    tokio::time::timeout(Duration::from_secs(10), node_handle)
+        .await
+        .expect("The node should shut down.")
+        .unwrap()
+        .unwrap();
+    open_storage(config.storage).expect("The storage should be released on shutdown.");
+}
+
+// The storage path contains the chain id, so a storage can belong to a different chain only if it
+// was moved. In that case, the node should refuse to start.
+#[tokio::test]
+async fn run_threads_chain_id_mismatch() {
+    let mut config = NodeConfig::default();
+    let temp_dir = TempDir::new().unwrap();
+    config.storage.db_config.path_prefix = temp_dir.path().into();
+    config.storage.db_config.chain_id = ChainId::Sepolia;
+    open_storage(config.storage.clone()).unwrap();
+
+    fs::rename(temp_dir.path().join("SN_SEPOLIA"), temp_dir.path().join("SN_MAIN")).unwrap();
+    config.storage.db_config.chain_id = ChainId::Mainnet;
+    let error =
+        run_threads(config, CancellationToken::new()).await.expect_err("Should be an error.");
+    assert_eq!(
+        "The storage belongs to chain SN_SEPOLIA, but the configured chain is SN_MAIN.",
+        error.to_string()
+    );
+}
+
 // TODO(dvir): use here metrics names from the storage instead of hard-coded ones. This will be done
 // only after changes to the metrics structure in papyrus.
 #[tokio::test]
@@ -41,3 +118,11 @@ async fn storage_metrics_collector() {
 
     assert!(prometheus_is_contained(handle.render(), "storage_free_pages_number", &[]).is_some());
 }
+
+#[test]
+fn pending_data_parent_is_the_configured_genesis_hash() {
+    let genesis_hash = BlockHash(felt!("0x1234"));
+    let config = NodeConfig { genesis_hash, ..Default::default() };
+    let pending_data = initial_pending_data(config.genesis_hash);
+    assert_eq!(pending_data.block.parent_block_hash(), genesis_hash);
+}
@@ -0,0 +1,44 @@
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+use test_utils::get_absolute_path;
+
+// Runs the node binary from the repository root, where the default config file is, with the
+// required params set.
+fn run_node(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_papyrus_node"))
+        .current_dir(get_absolute_path(""))
+        .args(["--base_layer.node_url", "http://localhost:8545"])
+        .args(args)
+        .output()
+        .expect("Failed to run the node.")
+}
+
+#[test]
+fn validate_config_exits_without_running_the_node() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = run_node(&[
+        "--validate-config",
+        "--storage.db_config.path_prefix",
+        temp_dir.path().to_str().unwrap(),
+    ]);
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+    // The storage wasn't opened.
+    assert_eq!(temp_dir.path().read_dir().unwrap().count(), 0);
+}
+
+#[test]
+fn validate_config_fails_on_invalid_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let output = run_node(&[
+        "--validate-config",
+        "--storage.db_config.path_prefix",
+        temp_dir.path().to_str().unwrap(),
+        "--monitoring_gateway.server_address",
+        "not_a_socket_address",
+    ]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("server_address"));
+}
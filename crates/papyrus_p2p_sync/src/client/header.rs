@@ -1,5 +1,6 @@
 use futures::future::BoxFuture;
 use futures::{FutureExt, StreamExt};
+use papyrus_common::metrics as papyrus_metrics;
 use papyrus_protobuf::sync::SignedBlockHeader;
 use papyrus_storage::header::{HeaderStorageReader, HeaderStorageWriter};
 use papyrus_storage::{StorageError, StorageReader, StorageWriter};
@@ -37,6 +38,7 @@ impl DataStreamBuilder<SignedBlockHeader> for HeaderStreamBuilder {
 
     const TYPE_DESCRIPTION: &'static str = "headers";
     const BLOCK_NUMBER_LIMIT: BlockNumberLimit = BlockNumberLimit::Unlimited;
+    const MARKER_METRIC_NAME: &'static str = papyrus_metrics::PAPYRUS_P2P_SYNC_HEADER_MARKER;
 
     fn parse_data_for_block<'a>(
         signed_headers_receiver: &'a mut ResponseReceiver<SignedBlockHeader>,
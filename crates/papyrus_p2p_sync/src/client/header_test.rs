@@ -1,4 +1,6 @@
 use futures::{SinkExt, StreamExt};
+use metrics_exporter_prometheus::PrometheusBuilder;
+use papyrus_common::metrics::PAPYRUS_P2P_SYNC_HEADER_MARKER;
 use papyrus_network::network_manager::SqmrClientPayload;
 use papyrus_protobuf::sync::{
     BlockHashOrNumber,
@@ -9,7 +11,9 @@ use papyrus_protobuf::sync::{
     SignedBlockHeader,
 };
 use papyrus_storage::header::HeaderStorageReader;
+use prometheus_parse::Value::Gauge;
 use starknet_api::block::{BlockHeader, BlockNumber};
+use test_utils::prometheus_is_contained;
 use tokio::time::timeout;
 
 use super::test_utils::{
@@ -175,3 +179,64 @@ async fn sync_sends_new_header_query_if_it_got_partial_responses() {
 }
 
 // TODO(shahak): Add negative tests.
+
+#[tokio::test]
+async fn header_marker_metric_is_updated() {
+    // Smaller than HEADER_QUERY_LENGTH so that all the headers are sent in a single query.
+    const NUM_HEADERS: u64 = 3;
+
+    let TestArgs {
+        p2p_sync,
+        mut header_payload_receiver,
+        // The test will fail if we drop these
+        state_diff_payload_receiver: _state_diff_query_receiver,
+        ..
+    } = setup();
+    let prometheus_handle = PrometheusBuilder::new().install_recorder().unwrap();
+    let block_hashes_and_signatures =
+        create_block_hashes_and_signatures(NUM_HEADERS.try_into().unwrap());
+
+    // Create a future that will receive a query, send responses and validate the metric.
+    let parse_queries_future = async move {
+        let SqmrClientPayload {
+            query: _query,
+            report_receiver: _report_receiver,
+            responses_sender: mut headers_sender,
+        } = header_payload_receiver.next().await.unwrap();
+
+        for (i, (block_hash, block_signature)) in
+            block_hashes_and_signatures.into_iter().enumerate()
+        {
+            let block_number = BlockNumber(i.try_into().unwrap());
+            headers_sender
+                .send(Ok(DataOrFin(Some(SignedBlockHeader {
+                    block_header: BlockHeader {
+                        block_number,
+                        block_hash,
+                        state_diff_length: Some(0),
+                        ..Default::default()
+                    },
+                    signatures: vec![block_signature],
+                }))))
+                .await
+                .unwrap();
+
+            tokio::time::sleep(SLEEP_DURATION_TO_LET_SYNC_ADVANCE).await;
+
+            let header_marker = prometheus_is_contained(
+                prometheus_handle.render(),
+                PAPYRUS_P2P_SYNC_HEADER_MARKER,
+                &[],
+            );
+            assert_eq!(header_marker, Some(Gauge(block_number.unchecked_next().0 as f64)));
+        }
+    };
+
+    tokio::select! {
+        sync_result = p2p_sync.run() => {
+            sync_result.unwrap();
+            panic!("P2P sync aborted with no failure.");
+        }
+        _ = parse_queries_future => {}
+    }
+}
@@ -16,6 +16,7 @@ use futures::future::{ready, Ready};
 use futures::sink::With;
 use futures::{SinkExt, Stream};
 use header::HeaderStreamBuilder;
+use papyrus_common::state_diff_commitment::StateDiffCommitmentError;
 use papyrus_config::converters::deserialize_seconds_to_duration;
 use papyrus_config::dumping::{ser_optional_param, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
@@ -138,6 +139,9 @@ pub enum P2PSyncError {
     )]
     EmptyStateDiffPart,
     // TODO(shahak): Remove this and report to network on invalid data once that's possible.
+    #[error(transparent)]
+    StateDiffCommitment(#[from] StateDiffCommitmentError),
+    // TODO(shahak): Remove this and report to network on invalid data once that's possible.
     #[error("Network returned more responses than expected for a query.")]
     TooManyResponses,
     // TODO(shahak): Remove this and report to network on invalid data once that's possible.
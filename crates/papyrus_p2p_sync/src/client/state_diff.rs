@@ -2,6 +2,8 @@ use std::collections::HashSet;
 
 use futures::future::BoxFuture;
 use futures::{FutureExt, StreamExt};
+use papyrus_common::metrics as papyrus_metrics;
+use papyrus_common::state_diff_commitment::verify_state_diff_commitment;
 use papyrus_proc_macros::latency_histogram;
 use papyrus_protobuf::sync::StateDiffChunk;
 use papyrus_storage::header::HeaderStorageReader;
@@ -31,6 +33,7 @@ impl DataStreamBuilder<StateDiffChunk> for StateDiffStreamBuilder {
 
     const TYPE_DESCRIPTION: &'static str = "state diffs";
     const BLOCK_NUMBER_LIMIT: BlockNumberLimit = BlockNumberLimit::HeaderMarker;
+    const MARKER_METRIC_NAME: &'static str = papyrus_metrics::PAPYRUS_P2P_SYNC_STATE_DIFF_MARKER;
 
     #[latency_histogram("p2p_sync_state_diff_parse_data_for_block_latency_seconds", true)]
     fn parse_data_for_block<'a>(
@@ -42,12 +45,12 @@ impl DataStreamBuilder<StateDiffChunk> for StateDiffStreamBuilder {
             let mut result = ThinStateDiff::default();
             let mut prev_result_len = 0;
             let mut current_state_diff_len = 0;
-            let target_state_diff_len = storage_reader
+            let header = storage_reader
                 .begin_ro_txn()?
                 .get_block_header(block_number)?
-                .expect("A header with number lower than the header marker is missing")
-                .state_diff_length
-                .ok_or(P2PSyncError::OldHeaderInStorage {
+                .expect("A header with number lower than the header marker is missing");
+            let target_state_diff_len =
+                header.state_diff_length.ok_or(P2PSyncError::OldHeaderInStorage {
                     block_number,
                     missing_field: "state_diff_length",
                 })?;
@@ -87,6 +90,9 @@ impl DataStreamBuilder<StateDiffChunk> for StateDiffStreamBuilder {
             }
 
             validate_deprecated_declared_classes_non_conflicting(&result)?;
+            if let Some(expected_commitment) = header.state_diff_commitment {
+                verify_state_diff_commitment(&result, expected_commitment)?;
+            }
             Ok(Some((result, block_number)))
         }
         .boxed()
@@ -3,6 +3,11 @@ use std::time::Duration;
 use assert_matches::assert_matches;
 use futures::{FutureExt, SinkExt, StreamExt};
 use indexmap::indexmap;
+use papyrus_common::state_diff_commitment::{
+    calculate_state_diff_commitment,
+    StateDiffCommitmentError,
+    StateDiffVersion,
+};
 use papyrus_network::network_manager::SqmrClientPayload;
 use papyrus_protobuf::sync::{
     BlockHashOrNumber,
@@ -19,7 +24,13 @@ use papyrus_storage::state::StateStorageReader;
 use rand::RngCore;
 use rand_chacha::ChaCha8Rng;
 use starknet_api::block::{BlockHeader, BlockNumber};
-use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::core::{
+    ClassHash,
+    CompiledClassHash,
+    ContractAddress,
+    Nonce,
+    StateDiffCommitment,
+};
 use starknet_api::state::{StorageKey, ThinStateDiff};
 use starknet_types_core::felt::Felt;
 use static_assertions::const_assert;
@@ -304,10 +315,54 @@ async fn state_diff_conflicting() {
     .await;
 }
 
+#[tokio::test]
+async fn state_diff_commitment_mismatch() {
+    let state_diff_chunk =
+        StateDiffChunk::DeprecatedDeclaredClass(DeprecatedDeclaredClass::default());
+    let expected_commitment = StateDiffCommitment::default();
+    let calculated_commitment = calculate_state_diff_commitment(
+        &ThinStateDiff {
+            deprecated_declared_classes: vec![ClassHash::default()],
+            ..Default::default()
+        },
+        StateDiffVersion::V0,
+    );
+    validate_state_diff_fails_with_commitment(
+        1,
+        Some(expected_commitment.clone()),
+        vec![Some(state_diff_chunk)],
+        |error| {
+            assert_matches!(
+                error,
+                P2PSyncError::StateDiffCommitment(StateDiffCommitmentError::Mismatch {
+                    calculated,
+                    expected,
+                }) if calculated == calculated_commitment && expected == expected_commitment
+            )
+        },
+    )
+    .await;
+}
+
 async fn validate_state_diff_fails(
     state_diff_length_in_header: usize,
     state_diff_chunks: Vec<Option<StateDiffChunk>>,
     error_validator: impl Fn(P2PSyncError),
+) {
+    validate_state_diff_fails_with_commitment(
+        state_diff_length_in_header,
+        None,
+        state_diff_chunks,
+        error_validator,
+    )
+    .await
+}
+
+async fn validate_state_diff_fails_with_commitment(
+    state_diff_length_in_header: usize,
+    state_diff_commitment_in_header: Option<StateDiffCommitment>,
+    state_diff_chunks: Vec<Option<StateDiffChunk>>,
+    error_validator: impl Fn(P2PSyncError),
 ) {
     let TestArgs {
         p2p_sync,
@@ -333,6 +388,7 @@ async fn validate_state_diff_fails(
                     block_number: BlockNumber(0),
                     block_hash,
                     state_diff_length: Some(state_diff_length_in_header),
+                    state_diff_commitment: state_diff_commitment_in_header,
                     ..Default::default()
                 },
                 signatures: vec![block_signature],
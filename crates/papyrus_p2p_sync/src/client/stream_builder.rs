@@ -43,6 +43,8 @@ where
 
     const TYPE_DESCRIPTION: &'static str;
     const BLOCK_NUMBER_LIMIT: BlockNumberLimit;
+    // The name of the gauge reporting the first block for which this data wasn't received yet.
+    const MARKER_METRIC_NAME: &'static str;
 
     // Async functions in trait don't work well with argument references
     fn parse_data_for_block<'a>(
@@ -122,6 +124,7 @@ where
                     }
                     info!("Added {:?} for block {}.", Self::TYPE_DESCRIPTION, current_block_number);
                     current_block_number = current_block_number.unchecked_next();
+                    metrics::gauge!(Self::MARKER_METRIC_NAME, current_block_number.0 as f64);
                     if stop_sync_at_block_number.is_some_and(|stop_sync_at_block_number| {
                         current_block_number >= stop_sync_at_block_number
                     }) {
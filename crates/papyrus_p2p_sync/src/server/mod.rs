@@ -231,10 +231,11 @@ impl FetchBlockDataFromDb for SignedBlockHeader {
         block_number: BlockNumber,
         txn: &StorageTxn<'_, db::RO>,
     ) -> Result<Vec<Self>, P2PSyncServerError> {
-        let mut header =
-            txn.get_block_header(block_number)?.ok_or(P2PSyncServerError::BlockNotFound {
+        let (mut header, signature) = txn.get_block_header_and_signature(block_number)?.ok_or(
+            P2PSyncServerError::BlockNotFound {
                 block_hash_or_number: BlockHashOrNumber::Number(block_number),
-            })?;
+            },
+        )?;
         // TODO(shahak) Remove this once central sync fills the state_diff_length field.
         if header.state_diff_length.is_none() {
             header.state_diff_length = Some(
@@ -245,9 +246,7 @@ impl FetchBlockDataFromDb for SignedBlockHeader {
                     .len(),
             );
         }
-        let signature = txn
-            .get_block_signature(block_number)?
-            .ok_or(P2PSyncServerError::SignatureNotFound { block_number })?;
+        let signature = signature.ok_or(P2PSyncServerError::SignatureNotFound { block_number })?;
         Ok(vec![SignedBlockHeader { block_header: header, signatures: vec![signature] }])
     }
 }
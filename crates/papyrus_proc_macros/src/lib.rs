@@ -2,7 +2,25 @@ use std::str::FromStr;
 
 use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, ExprLit, ItemFn, ItemTrait, LitBool, LitStr, Meta, TraitItem};
+use syn::{
+    parse_macro_input,
+    Data,
+    DeriveInput,
+    ExprLit,
+    Fields,
+    ImplItem,
+    ItemFn,
+    ItemImpl,
+    ItemTrait,
+    LitBool,
+    LitStr,
+    Meta,
+    ReturnType,
+    TraitItem,
+};
+
+#[cfg(test)]
+mod versioned_rpc_test;
 
 /// This macro is a wrapper around the "rpc" macro supplied by the jsonrpsee library that generates
 /// a server and client traits from a given trait definition. The wrapper gets a version id and
@@ -29,14 +47,47 @@ use syn::{parse_macro_input, ExprLit, ItemFn, ItemTrait, LitBool, LitStr, Meta,
 ///     #[method(name = "V0_6_0_blockNumber")]
 ///     fn block_number(&self) -> Result<BlockNumber, Error>;
 /// }
+///
+/// pub fn deprecated_method_hook(method_name: &str) {}
+/// ```
+///
+/// Methods can be marked with the `deprecated_method` attribute. The generated
+/// `deprecated_method_hook` function should be called by the server with the name of every called
+/// method, and it logs a warning the first time each deprecated method is called. Since the hook
+/// has a fixed name, every versioned trait should be defined in its own module.
+///
+/// Given this code:
+/// ```rust,ignore
+/// #[versioned_rpc("V0_6_0")]
+/// pub trait JsonRpc {
+///     #[deprecated_method]
+///     #[method(name = "blockNumber")]
+///     fn block_number(&self) -> Result<BlockNumber, Error>;
+/// }
+/// ```
+///
+/// The macro will generate the same trait as above, and this hook:
+/// ```rust,ignore
+/// pub fn deprecated_method_hook(method_name: &str) {
+///     if method_name == "starknet_V0_6_0_blockNumber" {
+///         static WARNED: std::sync::Once = std::sync::Once::new();
+///         WARNED.call_once(|| tracing::warn!(...));
+///     }
+/// }
 /// ```
 #[proc_macro_attribute]
 pub fn versioned_rpc(attr: TokenStream, input: TokenStream) -> TokenStream {
     let version = parse_macro_input!(attr as syn::LitStr);
     let item_trait = parse_macro_input!(input as ItemTrait);
 
+    versioned_rpc_impl(version, item_trait).into()
+}
+
+fn versioned_rpc_impl(version: LitStr, item_trait: ItemTrait) -> proc_macro2::TokenStream {
     let trait_name = &item_trait.ident;
     let visibility = &item_trait.vis;
+    // The full names of the deprecated methods, as the server sees them.
+    let mut deprecated_methods = Vec::new();
 
     // generate the new method signatures with the version prefix
     let versioned_methods = item_trait
@@ -44,11 +95,16 @@ pub fn versioned_rpc(attr: TokenStream, input: TokenStream) -> TokenStream {
         .iter()
         .map(|item| {
             if let TraitItem::Fn(method) = item {
+                let is_deprecated =
+                    method.attrs.iter().any(|attr| attr.path().is_ident(DEPRECATED_METHOD_ATTR));
                 let new_method = syn::TraitItemFn {
                     attrs: method
                         .attrs
                         .iter()
-                        .filter(|attr| !matches!(attr.meta, Meta::NameValue(_)))
+                        .filter(|attr| {
+                            !matches!(attr.meta, Meta::NameValue(_))
+                                && !attr.path().is_ident(DEPRECATED_METHOD_ATTR)
+                        })
                         .map(|attr| {
                             let mut new_attr = attr.clone();
                             if attr.path().is_ident("method") {
@@ -56,11 +112,15 @@ pub fn versioned_rpc(attr: TokenStream, input: TokenStream) -> TokenStream {
                                     if meta.path.is_ident("name") {
                                         let value = meta.value()?;
                                         let method_name: LitStr = value.parse()?;
-                                        let new_meta_str = format!(
-                                            "method(name = \"{}_{}\")",
-                                            version.value(),
-                                            method_name.value()
-                                        );
+                                        let versioned_method_name =
+                                            format!("{}_{}", version.value(), method_name.value());
+                                        if is_deprecated {
+                                            deprecated_methods.push(format!(
+                                                "{RPC_NAMESPACE}_{versioned_method_name}"
+                                            ));
+                                        }
+                                        let new_meta_str =
+                                            format!("method(name = \"{versioned_method_name}\")");
                                         new_attr.meta = syn::parse_str::<Meta>(&new_meta_str)?;
                                     }
                                     Ok(())
@@ -82,7 +142,7 @@ pub fn versioned_rpc(attr: TokenStream, input: TokenStream) -> TokenStream {
 
     // generate the versioned trait with the new method signatures
     let versioned_trait = syn::ItemTrait {
-        attrs: vec![syn::parse_quote!(#[rpc(server, client, namespace = "starknet")])],
+        attrs: vec![syn::parse_quote!(#[rpc(server, client, namespace = #RPC_NAMESPACE)])],
         vis: visibility.clone(),
         unsafety: None,
         auto_token: None,
@@ -96,9 +156,31 @@ pub fn versioned_rpc(attr: TokenStream, input: TokenStream) -> TokenStream {
         trait_token: item_trait.trait_token,
     };
 
-    versioned_trait.to_token_stream().into()
+    quote! {
+        #versioned_trait
+
+        /// Logs a warning the first time each deprecated method of this API version is called.
+        #visibility fn deprecated_method_hook(method_name: &str) {
+            let _ = method_name;
+            #(
+                if method_name == #deprecated_methods {
+                    static WARNED: std::sync::Once = std::sync::Once::new();
+                    WARNED.call_once(|| {
+                        tracing::warn!(
+                            "The RPC method {} is deprecated and will be removed in a future \
+                             version.",
+                            #deprecated_methods
+                        )
+                    });
+                }
+            )*
+        }
+    }
 }
 
+const RPC_NAMESPACE: &str = "starknet";
+const DEPRECATED_METHOD_ATTR: &str = "deprecated_method";
+
 /// This macro will emit a histogram metric with the given name and the latency of the function.
 /// The macro also receives a boolean for whether it will be emitted only when
 /// profiling is activated or at all times.
@@ -116,6 +198,18 @@ pub fn versioned_rpc(attr: TokenStream, input: TokenStream) -> TokenStream {
 /// took to execute foo.
 /// The metric will be emitted regardless of the value of the profiling configuration,
 /// since the config value is false.
+///
+/// An optional third boolean argument makes the macro also increment a counter named
+/// “metric_name_calls” on every call, under the same profiling condition as the histogram:
+///
+/// ```rust,ignore
+/// #[latency_histogram("metric_name", false, true)]
+/// fn foo() {
+///     // Some code ...
+/// }
+/// ```
+/// The counter isn't named “metric_name_count” since that name is already taken by the count
+/// sample of the histogram.
 #[proc_macro_attribute]
 pub fn latency_histogram(attr: TokenStream, input: TokenStream) -> TokenStream {
     let mut input_fn = parse_macro_input!(input as ItemFn);
@@ -140,6 +234,22 @@ pub fn latency_histogram(attr: TokenStream, input: TokenStream) -> TokenStream {
         .clone();
     let metric_name = parse_macro_input!(metric_name_as_tokenstream as ExprLit);
     let controll_with_config = parse_macro_input!(controll_with_config_as_tokenstream as LitBool);
+    let count_calls = match parts.get(2) {
+        Some(count_calls_as_tokenstream) => {
+            let count_calls_as_tokenstream = count_calls_as_tokenstream.clone();
+            parse_macro_input!(count_calls_as_tokenstream as LitBool).value
+        }
+        None => false,
+    };
+    let count_update = if count_calls {
+        let syn::Lit::Str(metric_name_str) = &metric_name.lit else {
+            panic!("Expecting the metric name to be a string literal.");
+        };
+        let counter_name = format!("{}_calls", metric_name_str.value());
+        quote! { metrics::increment_counter!(#counter_name); }
+    } else {
+        quote! {}
+    };
     let origin_block = &mut input_fn.block;
 
     // Create a new block with the metric update.
@@ -152,6 +262,7 @@ pub fn latency_histogram(attr: TokenStream, input: TokenStream) -> TokenStream {
             let return_value=#origin_block;
             if let Some(start_time) = start_function_time {
                 metrics::histogram!(#metric_name, start_time.elapsed().as_secs_f64());
+                #count_update
             }
             return_value
         }
@@ -165,3 +276,174 @@ pub fn latency_histogram(attr: TokenStream, input: TokenStream) -> TokenStream {
 
     modified_function.to_token_stream().into()
 }
+
+/// This macro is applied on an implementation of a versioned RPC server trait and emits, for every
+/// method in the implementation, a histogram metric named `rpc_method_latency_seconds` with the
+/// latency of the method. The metric is labeled with the name of the method and with the version
+/// given to the macro.
+///
+/// The macro must be placed above the `async_trait` attribute so that it sees the methods before
+/// they are desugared into boxed futures, and the latency of asynchronous methods covers their
+/// whole execution.
+///
+/// # Example
+/// Given this code:
+///
+/// ```rust,ignore
+/// #[rpc_method_latency("V0_6")]
+/// #[async_trait]
+/// impl JsonRpcV0_6Server for JsonRpcServerImpl {
+///     async fn get_block(&self) -> RpcResult<Block> {
+///         // Some code ...
+///     }
+/// }
+/// ```
+/// Every call to get_block will update the histogram metric
+/// `rpc_method_latency_seconds{method="get_block", version="V0_6"}` with the time it took to
+/// execute it.
+#[proc_macro_attribute]
+pub fn rpc_method_latency(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let version = parse_macro_input!(attr as LitStr);
+    let mut item_impl = parse_macro_input!(input as ItemImpl);
+
+    for item in item_impl.items.iter_mut() {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let method_name = method.sig.ident.to_string();
+        let return_type = match &method.sig.output {
+            ReturnType::Default => quote! { () },
+            ReturnType::Type(_, ty) => ty.to_token_stream(),
+        };
+        let origin_block = &method.block;
+        // Wrap the original block so that early returns and the ? operator inside it don't skip
+        // the metric update.
+        let wrapped_block = if method.sig.asyncness.is_some() {
+            quote! { async move #origin_block.await }
+        } else {
+            quote! { (move || #origin_block)() }
+        };
+        let expanded_block = quote! {
+            {
+                let start_method_time = std::time::Instant::now();
+                let return_value: #return_type = #wrapped_block;
+                metrics::histogram!(
+                    "rpc_method_latency_seconds",
+                    start_method_time.elapsed().as_secs_f64(),
+                    "method" => #method_name,
+                    "version" => #version
+                );
+                return_value
+            }
+        };
+        method.block =
+            syn::parse2(expanded_block).expect("Parse tokens in rpc_method_latency attribute.");
+    }
+
+    item_impl.to_token_stream().into()
+}
+
+/// This macro derives the `GetTestInstance` trait of the `test_utils` crate, so that types can opt
+/// in to it at their definition site instead of being listed in `auto_impl_get_test_instance`.
+///
+/// Every field gets its own test instance. For enums, a random variant is chosen and its fields get
+/// their own test instances. Type parameters are required to implement `GetTestInstance` as well.
+///
+/// # Example
+/// Given this code:
+///
+/// ```rust,ignore
+/// #[derive(GetTestInstance)]
+/// struct Foo {
+///     bar: u64,
+///     baz: Vec<Felt>,
+/// }
+/// ```
+///
+/// The macro will generate this code:
+/// ```rust,ignore
+/// impl test_utils::GetTestInstance for Foo {
+///     fn get_test_instance(rng: &mut rand_chacha::ChaCha8Rng) -> Self {
+///         Self {
+///             bar: test_utils::GetTestInstance::get_test_instance(rng),
+///             baz: test_utils::GetTestInstance::get_test_instance(rng),
+///         }
+///     }
+/// }
+/// ```
+#[proc_macro_derive(GetTestInstance)]
+pub fn derive_get_test_instance(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    for type_param in input.generics.type_params_mut() {
+        type_param.bounds.push(syn::parse_quote!(test_utils::GetTestInstance));
+    }
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let name = &input.ident;
+
+    let body = match &input.data {
+        Data::Struct(data) => fields_test_instance(quote! { Self }, &data.fields),
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return syn::Error::new_spanned(
+                    &input.ident,
+                    "GetTestInstance can't be derived for an enum without variants.",
+                )
+                .to_compile_error()
+                .into();
+            }
+            let number_of_variants = data.variants.len();
+            let variant_arms = data.variants.iter().enumerate().map(|(index, variant)| {
+                let variant_name = &variant.ident;
+                let variant_instance =
+                    fields_test_instance(quote! { Self::#variant_name }, &variant.fields);
+                quote! { #index => #variant_instance, }
+            });
+            quote! {
+                use rand_chacha::rand_core::RngCore;
+                let variant = rng.next_u64() as usize % #number_of_variants;
+                match variant {
+                    #(#variant_arms)*
+                    _ => unreachable!("The variant index is smaller than the number of variants."),
+                }
+            }
+        }
+        Data::Union(_) => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "GetTestInstance can't be derived for unions.",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    quote! {
+        impl #impl_generics test_utils::GetTestInstance for #name #type_generics #where_clause {
+            fn get_test_instance(rng: &mut rand_chacha::ChaCha8Rng) -> Self {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+// Returns an expression that constructs the given struct or enum variant with a test instance for
+// each of its fields.
+fn fields_test_instance(
+    constructor: proc_macro2::TokenStream,
+    fields: &Fields,
+) -> proc_macro2::TokenStream {
+    let field_instance = quote! { test_utils::GetTestInstance::get_test_instance(rng) };
+    match fields {
+        Fields::Named(fields) => {
+            let field_names = fields.named.iter().map(|field| &field.ident);
+            quote! { #constructor { #(#field_names: #field_instance,)* } }
+        }
+        Fields::Unnamed(fields) => {
+            let field_instances = fields.unnamed.iter().map(|_| &field_instance);
+            quote! { #constructor(#(#field_instances,)*) }
+        }
+        Fields::Unit => constructor,
+    }
+}
@@ -0,0 +1,43 @@
+use syn::{parse_quote, ItemTrait, LitStr};
+
+use crate::versioned_rpc_impl;
+
+#[test]
+fn deprecated_method_hook_warns_on_deprecated_methods() {
+    let version: LitStr = parse_quote!("V0_1");
+    let item_trait: ItemTrait = parse_quote! {
+        pub trait JsonRpc {
+            #[deprecated_method]
+            #[method(name = "oldMethod")]
+            fn old_method(&self) -> RpcResult<u64>;
+
+            #[method(name = "newMethod")]
+            fn new_method(&self) -> RpcResult<u64>;
+        }
+    };
+
+    let expanded = versioned_rpc_impl(version, item_trait).to_string();
+
+    // The deprecated_method attribute is consumed by the macro.
+    assert!(!expanded.contains("deprecated_method ]"));
+    assert!(expanded.contains("pub fn deprecated_method_hook (method_name : & str)"));
+    assert!(expanded.contains("if method_name == \"starknet_V0_1_oldMethod\""));
+    assert!(expanded.contains("tracing :: warn !"));
+    assert!(!expanded.contains("\"starknet_V0_1_newMethod\""));
+}
+
+#[test]
+fn deprecated_method_hook_is_empty_without_deprecated_methods() {
+    let version: LitStr = parse_quote!("V0_1");
+    let item_trait: ItemTrait = parse_quote! {
+        pub trait JsonRpc {
+            #[method(name = "newMethod")]
+            fn new_method(&self) -> RpcResult<u64>;
+        }
+    };
+
+    let expanded = versioned_rpc_impl(version, item_trait).to_string();
+
+    assert!(expanded.contains("pub fn deprecated_method_hook (method_name : & str)"));
+    assert!(!expanded.contains("tracing :: warn !"));
+}
@@ -0,0 +1,53 @@
+use papyrus_proc_macros::GetTestInstance;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use test_utils::{get_rng, GetTestInstance};
+
+#[derive(Debug, PartialEq, GetTestInstance)]
+struct NamedStruct {
+    number: u64,
+    flag: bool,
+    items: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, GetTestInstance)]
+struct TupleStruct(u64, NamedStruct);
+
+#[derive(Debug, PartialEq, GetTestInstance)]
+struct GenericStruct<T> {
+    inner: T,
+}
+
+#[derive(Debug, PartialEq, GetTestInstance)]
+enum Enum {
+    Unit,
+    Tuple(u64, bool),
+    Named { inner: TupleStruct },
+}
+
+#[test]
+fn derived_struct_instance_is_deterministic() {
+    let seed = 0;
+    let instance = TupleStruct::get_test_instance(&mut ChaCha8Rng::seed_from_u64(seed));
+    assert_eq!(instance, TupleStruct::get_test_instance(&mut ChaCha8Rng::seed_from_u64(seed)));
+    assert_eq!(
+        GenericStruct::<NamedStruct>::get_test_instance(&mut ChaCha8Rng::seed_from_u64(seed)),
+        GenericStruct {
+            inner: NamedStruct::get_test_instance(&mut ChaCha8Rng::seed_from_u64(seed))
+        }
+    );
+}
+
+#[test]
+fn derived_enum_instance_covers_all_variants() {
+    let mut rng = get_rng();
+    let (mut unit, mut tuple, mut named) = (false, false, false);
+    for _ in 0..100 {
+        match Enum::get_test_instance(&mut rng) {
+            Enum::Unit => unit = true,
+            Enum::Tuple(..) => tuple = true,
+            Enum::Named { .. } => named = true,
+        }
+    }
+    assert!(unit && tuple && named);
+}
@@ -1,7 +1,7 @@
 use metrics_exporter_prometheus::PrometheusBuilder;
 use papyrus_common::metrics::COLLECT_PROFILING_METRICS;
 use papyrus_proc_macros::latency_histogram;
-use prometheus_parse::Value::Untyped;
+use prometheus_parse::Value::{Counter, Untyped};
 use test_utils::prometheus_is_contained;
 
 #[test]
@@ -22,6 +22,11 @@ fn latency_histogram_test() {
         start_function_time
     }
 
+    #[latency_histogram("baz_histogram", false, true)]
+    fn baz() -> usize {
+        1000
+    }
+
     let handle = PrometheusBuilder::new().install_recorder().unwrap();
 
     assert!(handle.render().is_empty());
@@ -37,4 +42,17 @@ fn latency_histogram_test() {
         prometheus_is_contained(handle.render(), "foo_histogram_sum", &[]),
         Some(Untyped(1000f64))
     );
+    // Calls aren't counted when the count argument isn't given.
+    assert_eq!(prometheus_is_contained(handle.render(), "foo_histogram_calls", &[]), None);
+
+    assert_eq!(baz(), 1000);
+    assert_eq!(baz(), 1000);
+    assert_eq!(
+        prometheus_is_contained(handle.render(), "baz_histogram_count", &[]),
+        Some(Untyped(2f64))
+    );
+    assert_eq!(
+        prometheus_is_contained(handle.render(), "baz_histogram_calls", &[]),
+        Some(Counter(2f64))
+    );
 }
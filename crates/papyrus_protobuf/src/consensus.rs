@@ -2,7 +2,7 @@ use starknet_api::block::BlockHash;
 use starknet_api::core::ContractAddress;
 use starknet_api::transaction::Transaction;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Proposal {
     pub height: u64,
     pub proposer: ContractAddress,
@@ -10,13 +10,13 @@ pub struct Proposal {
     pub block_hash: BlockHash,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum VoteType {
     Prevote,
     Precommit,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Vote {
     pub vote_type: VoteType,
     pub height: u64,
@@ -24,7 +24,7 @@ pub struct Vote {
     pub voter: ContractAddress,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum ConsensusMessage {
     Proposal(Proposal),
     Vote(Vote),
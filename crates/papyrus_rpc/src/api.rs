@@ -1,9 +1,12 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
 
+use jsonrpsee::server::logger::{HttpRequest, Logger, MethodKind, TransportProtocol};
+use jsonrpsee::types::Params;
 use jsonrpsee::{Methods, RpcModule};
 use papyrus_common::pending_classes::PendingClasses;
 use papyrus_common::BlockHashAndNumber;
-use papyrus_execution::ExecutionConfig;
+use papyrus_execution::{ClassCache, ExecutionConfig};
 use papyrus_storage::StorageReader;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockNumber};
@@ -15,7 +18,7 @@ use tokio::sync::RwLock;
 
 use crate::v0_6::api::api_impl::JsonRpcServerImpl as JsonRpcServerV0_6Impl;
 use crate::v0_7::api::api_impl::JsonRpcServerImpl as JsonRpcServerV0_7Impl;
-use crate::version_config;
+use crate::{v0_6, v0_7, version_config};
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Tag {
@@ -25,6 +28,9 @@ pub enum Tag {
     /// Currently constructed block
     #[serde(rename = "pending")]
     Pending,
+    /// The most recent block that was accepted on L1
+    #[serde(rename = "l1_accepted")]
+    L1Accepted,
 }
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -55,10 +61,14 @@ pub struct CallRequest {
 #[allow(clippy::too_many_arguments)]
 pub fn get_methods_from_supported_apis(
     chain_id: &ChainId,
+    genesis_hash: BlockHash,
     execution_config: ExecutionConfig,
+    class_cache: ClassCache,
     storage_reader: StorageReader,
     max_events_chunk_size: usize,
     max_events_keys: usize,
+    max_execution_transactions: usize,
+    max_execution_calldata_length: usize,
     starting_block: BlockHashAndNumber,
     shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
     pending_data: Arc<RwLock<PendingData>>,
@@ -68,10 +78,14 @@ pub fn get_methods_from_supported_apis(
     let mut methods: Methods = Methods::new();
     let server_gen = JsonRpcServerImplGenerator {
         chain_id: chain_id.clone(),
+        genesis_hash,
         execution_config,
+        class_cache,
         storage_reader,
         max_events_chunk_size,
         max_events_keys,
+        max_execution_transactions,
+        max_execution_calldata_length,
         starting_block,
         shared_highest_block,
         pending_data,
@@ -110,10 +124,14 @@ pub trait JsonRpcServerTrait: Sized {
     #[allow(clippy::too_many_arguments)]
     fn new(
         chain_id: ChainId,
+        genesis_hash: BlockHash,
         execution_config: ExecutionConfig,
+        class_cache: ClassCache,
         storage_reader: StorageReader,
         max_events_chunk_size: usize,
         max_events_keys: usize,
+        max_execution_transactions: usize,
+        max_execution_calldata_length: usize,
         starting_block: BlockHashAndNumber,
         shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
         pending_data: Arc<RwLock<PendingData>>,
@@ -127,10 +145,14 @@ pub trait JsonRpcServerTrait: Sized {
 #[derive(Clone)]
 struct JsonRpcServerImplGenerator {
     chain_id: ChainId,
+    genesis_hash: BlockHash,
     execution_config: ExecutionConfig,
+    class_cache: ClassCache,
     storage_reader: StorageReader,
     max_events_chunk_size: usize,
     max_events_keys: usize,
+    max_execution_transactions: usize,
+    max_execution_calldata_length: usize,
     starting_block: BlockHashAndNumber,
     shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
     pending_data: Arc<RwLock<PendingData>>,
@@ -141,10 +163,14 @@ struct JsonRpcServerImplGenerator {
 
 type JsonRpcServerImplParams = (
     ChainId,
+    BlockHash,
     ExecutionConfig,
+    ClassCache,
     StorageReader,
     usize,
     usize,
+    usize,
+    usize,
     BlockHashAndNumber,
     Arc<RwLock<Option<BlockHashAndNumber>>>,
     Arc<RwLock<PendingData>>,
@@ -156,10 +182,14 @@ impl JsonRpcServerImplGenerator {
     fn get_params(self) -> JsonRpcServerImplParams {
         (
             self.chain_id,
+            self.genesis_hash,
             self.execution_config,
+            self.class_cache,
             self.storage_reader,
             self.max_events_chunk_size,
             self.max_events_keys,
+            self.max_execution_transactions,
+            self.max_execution_calldata_length,
             self.starting_block,
             self.shared_highest_block,
             self.pending_data,
@@ -174,10 +204,14 @@ impl JsonRpcServerImplGenerator {
     {
         let (
             chain_id,
+            genesis_hash,
             fee_contract_address,
+            class_cache,
             storage_reader,
             max_events_chunk_size,
             max_events_keys,
+            max_execution_transactions,
+            max_execution_calldata_length,
             starting_block,
             shared_highest_block,
             pending_data,
@@ -187,10 +221,14 @@ impl JsonRpcServerImplGenerator {
         Into::<Methods>::into(
             T::new(
                 chain_id,
+                genesis_hash,
                 fee_contract_address,
+                class_cache,
                 storage_reader,
                 max_events_chunk_size,
                 max_events_keys,
+                max_execution_transactions,
+                max_execution_calldata_length,
                 starting_block,
                 shared_highest_block,
                 pending_data,
@@ -201,3 +239,47 @@ impl JsonRpcServerImplGenerator {
         )
     }
 }
+
+/// A logger that warns when deprecated methods are called, by passing every called method to the
+/// deprecated method hooks of all the API versions.
+/// Whenever adding a new API version we need to add its hook here.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct DeprecatedMethodLogger;
+
+impl Logger for DeprecatedMethodLogger {
+    type Instant = ();
+
+    fn on_call(
+        &self,
+        method_name: &str,
+        _params: Params<'_>,
+        _kind: MethodKind,
+        _transport: TransportProtocol,
+    ) {
+        v0_6::api::deprecated_method_hook(method_name);
+        v0_7::api::deprecated_method_hook(method_name);
+    }
+
+    fn on_connect(&self, _remote_addr: SocketAddr, _request: &HttpRequest, _t: TransportProtocol) {}
+
+    fn on_request(&self, _transport: TransportProtocol) -> Self::Instant {}
+
+    fn on_result(
+        &self,
+        _method_name: &str,
+        _success_or_error: jsonrpsee::helpers::MethodResponseResult,
+        _started_at: Self::Instant,
+        _transport: TransportProtocol,
+    ) {
+    }
+
+    fn on_response(
+        &self,
+        _result: &str,
+        _started_at: Self::Instant,
+        _transport: TransportProtocol,
+    ) {
+    }
+
+    fn on_disconnect(&self, _remote_addr: SocketAddr, _transport: TransportProtocol) {}
+}
@@ -9,6 +9,7 @@ mod pending;
 mod rpc_metrics;
 #[cfg(test)]
 mod rpc_test;
+mod sierra_compilation;
 mod syncing_state;
 #[cfg(test)]
 mod test_utils;
@@ -28,10 +29,15 @@ use jsonrpsee::types::error::INTERNAL_ERROR_MSG;
 use jsonrpsee::types::ErrorObjectOwned;
 use papyrus_common::pending_classes::PendingClasses;
 use papyrus_common::BlockHashAndNumber;
-use papyrus_config::dumping::{append_sub_config_name, ser_param, SerializeConfig};
-use papyrus_config::validators::validate_ascii;
+use papyrus_config::dumping::{
+    append_sub_config_name,
+    ser_optional_param,
+    ser_param,
+    SerializeConfig,
+};
+use papyrus_config::validators::{validate_non_empty_ascii, validate_socket_addr};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
-use papyrus_execution::ExecutionConfig;
+use papyrus_execution::{ClassCache, ExecutionConfig};
 use papyrus_storage::base_layer::BaseLayerStorageReader;
 use papyrus_storage::body::events::EventIndex;
 use papyrus_storage::db::TransactionKind;
@@ -39,7 +45,7 @@ use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::{StorageReader, StorageScope, StorageTxn};
 use rpc_metrics::MetricLogger;
 use serde::{Deserialize, Serialize};
-use starknet_api::block::{BlockNumber, BlockStatus};
+use starknet_api::block::{BlockHash, BlockNumber, BlockStatus};
 use starknet_api::core::ChainId;
 use starknet_client::reader::PendingData;
 use starknet_client::writer::StarknetGatewayClient;
@@ -49,8 +55,12 @@ use tracing::{debug, error, info, instrument};
 pub use v0_7::api::CompiledContractClass;
 use validator::Validate;
 
-use crate::api::get_methods_from_supported_apis;
-use crate::middleware::{deny_requests_with_unsupported_path, proxy_rpc_request};
+use crate::api::{get_methods_from_supported_apis, DeprecatedMethodLogger};
+use crate::middleware::{
+    deny_requests_with_unsupported_path,
+    proxy_rpc_request,
+    run_rate_limit_proxy,
+};
 use crate::syncing_state::get_last_synced_block;
 pub use crate::v0_6::transaction::{
     InvokeTransaction as InvokeTransactionRPC0_6,
@@ -59,24 +69,29 @@ pub use crate::v0_6::transaction::{
 };
 pub use crate::v0_6::write_api_result::AddInvokeOkResult as AddInvokeOkResultRPC0_6;
 
-// TODO(shahak): Consider adding genesis hash to the config to support chains that have
-// different genesis hash.
-// TODO: Consider moving to a more general place.
-const GENESIS_HASH: &str = "0x0";
-
 /// Maximum size of a supported transaction body - 10MB.
 pub const SERVER_MAX_BODY_SIZE: u32 = 10 * 1024 * 1024;
 
+// The address of the server when it's behind the rate limit proxy.
+const RATE_LIMITED_SERVER_ADDRESS: &str = "127.0.0.1:0";
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Validate)]
 pub struct RpcConfig {
-    #[validate(custom = "validate_ascii")]
+    #[validate(custom = "validate_non_empty_ascii")]
     pub chain_id: ChainId,
+    #[validate(custom = "validate_socket_addr")]
     pub server_address: String,
     pub max_events_chunk_size: usize,
     pub max_events_keys: usize,
+    pub max_execution_transactions: usize,
+    pub max_execution_calldata_length: usize,
     pub collect_metrics: bool,
+    #[validate(range(min = 1))]
+    pub rate_limit_per_sec: Option<u32>,
+    pub rate_limit_trust_forwarded_headers: bool,
     pub starknet_url: String,
     pub starknet_gateway_retry_config: RetryConfig,
+    #[validate]
     pub execution_config: ExecutionConfig,
 }
 
@@ -87,7 +102,11 @@ impl Default for RpcConfig {
             server_address: String::from("0.0.0.0:8080"),
             max_events_chunk_size: 1000,
             max_events_keys: 100,
+            max_execution_transactions: 100,
+            max_execution_calldata_length: 100000,
             collect_metrics: false,
+            rate_limit_per_sec: None,
+            rate_limit_trust_forwarded_headers: false,
             starknet_url: String::from("https://alpha-mainnet.starknet.io/"),
             starknet_gateway_retry_config: RetryConfig {
                 retry_base_millis: 50,
@@ -126,12 +145,34 @@ impl SerializeConfig for RpcConfig {
                 "Maximum number of keys supported by the node in get_events requests.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "max_execution_transactions",
+                &self.max_execution_transactions,
+                "Maximum number of transactions supported by the node in simulate and estimate_fee \
+                 requests.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_execution_calldata_length",
+                &self.max_execution_calldata_length,
+                "Maximum total calldata length supported by the node in call, simulate and \
+                 estimate_fee requests.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "collect_metrics",
                 &self.collect_metrics,
                 "If true, collect metrics for the rpc.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "rate_limit_trust_forwarded_headers",
+                &self.rate_limit_trust_forwarded_headers,
+                "If true, the rate limit identifies clients by the X-Forwarded-For or X-Real-IP \
+                 headers instead of by the address of their connection. Enable only behind a \
+                 trusted reverse proxy.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "starknet_url",
                 &self.starknet_url,
@@ -139,6 +180,14 @@ impl SerializeConfig for RpcConfig {
                 ParamPrivacyInput::Public,
             ),
         ]);
+        self_params_dump.extend(ser_optional_param(
+            &self.rate_limit_per_sec,
+            100,
+            "rate_limit_per_sec",
+            "Maximum number of requests per second allowed from a single client IP. WebSocket \
+             connections aren't supported when set.",
+            ParamPrivacyInput::Public,
+        ));
 
         self_params_dump
             .append(&mut append_sub_config_name(self.execution_config.dump(), "execution_config"));
@@ -208,16 +257,21 @@ pub async fn run_server(
     pending_data: Arc<RwLock<PendingData>>,
     pending_classes: Arc<RwLock<PendingClasses>>,
     storage_reader: StorageReader,
+    genesis_hash: BlockHash,
     node_version: &'static str,
 ) -> anyhow::Result<(SocketAddr, ServerHandle)> {
     let starting_block = get_last_synced_block(storage_reader.clone())?;
     debug!("Starting JSON-RPC.");
     let methods = get_methods_from_supported_apis(
         &config.chain_id,
+        genesis_hash,
         config.execution_config,
+        ClassCache::new(config.execution_config.class_cache_size),
         storage_reader,
         config.max_events_chunk_size,
         config.max_events_keys,
+        config.max_execution_transactions,
+        config.max_execution_calldata_length,
         starting_block,
         shared_highest_block,
         pending_data,
@@ -228,7 +282,12 @@ pub async fn run_server(
             config.starknet_gateway_retry_config,
         )?),
     );
-    let addr;
+    // With a rate limit, the server is reached only through the rate limit proxy.
+    let server_address = match config.rate_limit_per_sec {
+        Some(_) => RATE_LIMITED_SERVER_ADDRESS,
+        None => config.server_address.as_str(),
+    };
+    let mut addr;
     let handle;
     let server_builder =
         ServerBuilder::default().max_request_body_size(SERVER_MAX_BODY_SIZE).set_middleware(
@@ -239,16 +298,26 @@ pub async fn run_server(
 
     if config.collect_metrics {
         let server = server_builder
-            .set_logger(MetricLogger::new(&methods))
-            .build(&config.server_address)
+            .set_logger((MetricLogger::new(&methods), DeprecatedMethodLogger))
+            .build(server_address)
             .await?;
         addr = server.local_addr()?;
         handle = server.start(methods);
     } else {
-        let server = server_builder.build(&config.server_address).await?;
+        let server =
+            server_builder.set_logger(DeprecatedMethodLogger).build(server_address).await?;
         addr = server.local_addr()?;
         handle = server.start(methods);
     }
+    if let Some(rate_limit_per_sec) = config.rate_limit_per_sec {
+        addr = run_rate_limit_proxy(
+            &config.server_address,
+            addr,
+            rate_limit_per_sec,
+            config.rate_limit_trust_forwarded_headers,
+            handle.clone(),
+        )?;
+    }
     info!(local_address = %addr, "JSON-RPC is running.");
     Ok((addr, handle))
 }
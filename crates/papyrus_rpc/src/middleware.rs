@@ -1,8 +1,21 @@
-use hyper::{Body, Request};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr, TcpListener};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use hyper::client::HttpConnector;
+use hyper::server::conn::AddrStream;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Client, Request, Response, StatusCode, Uri};
 use jsonrpsee::core::http_helpers::read_body;
+use jsonrpsee::server::ServerHandle;
 use regex::Regex;
-use tower::BoxError;
-use tracing::{debug, instrument};
+use tower::{BoxError, Service};
+use tracing::{debug, error, instrument};
 
 use crate::version_config::{VersionState, VERSION_CONFIG, VERSION_PATTERN};
 use crate::SERVER_MAX_BODY_SIZE;
@@ -121,3 +134,206 @@ fn is_supported_path(path: &str) -> bool {
         .expect("should be a valid regex");
     re.is_match(path)
 }
+
+// JSON-RPC error code for exceeding a server limit (EIP-1474).
+const RATE_LIMIT_ERROR_CODE: i32 = -32005;
+const RATE_LIMIT_ERROR_MESSAGE: &str = "Too many requests";
+// How often the buckets of idle clients are dropped.
+const IDLE_BUCKETS_EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+const WEBSOCKET_NOT_SUPPORTED_MESSAGE: &str =
+    "WebSocket connections aren't supported when the rate limit is enabled";
+
+/// Runs a proxy on `listen_address` that forwards requests to the JsonRPC server at
+/// `server_address`, limiting the number of requests per second each client can make. Requests
+/// over the limit are answered with HTTP 429 and a JsonRPC error without reaching the server.
+/// Returns the address the proxy listens on. The proxy stops together with the server.
+///
+/// The server doesn't expose the address of the connection to its middleware, so the limit is
+/// enforced by a proxy that accepts the connections itself. Clients are identified by the IP of
+/// their connection. If `trust_forwarded_headers` is set, which should only be done behind a
+/// trusted reverse proxy, clients are identified by the first IP in the `X-Forwarded-For` header
+/// (or by `X-Real-IP`) when present.
+///
+/// The proxy forwards plain HTTP requests only. WebSocket upgrade requests are answered with HTTP
+/// 501, so WebSocket subscriptions are unavailable while the rate limit is enabled.
+pub(crate) fn run_rate_limit_proxy(
+    listen_address: &str,
+    server_address: SocketAddr,
+    requests_per_sec: u32,
+    trust_forwarded_headers: bool,
+    server_handle: ServerHandle,
+) -> anyhow::Result<SocketAddr> {
+    let listener = TcpListener::bind(listen_address)?;
+    listener.set_nonblocking(true)?;
+    let local_address = listener.local_addr()?;
+    let proxy = hyper::Server::from_tcp(listener)?;
+
+    let limiter = Arc::new(Mutex::new(RateLimiter::new(requests_per_sec)));
+    // The eviction stops once the proxy, which owns the limiter, stops.
+    let evicted_limiter = Arc::downgrade(&limiter);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(IDLE_BUCKETS_EVICTION_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(limiter) = evicted_limiter.upgrade() else {
+                return;
+            };
+            limiter.lock().expect("Rate limiter lock poisoned").evict_idle_buckets();
+        }
+    });
+    let client = Client::new();
+    let make_service = make_service_fn(move |connection: &AddrStream| {
+        let rate_limit = RateLimit {
+            inner: forward_to(client.clone(), server_address),
+            limiter: limiter.clone(),
+            peer_ip: connection.remote_addr().ip(),
+            trust_forwarded_headers,
+        };
+        async move { Ok::<_, Infallible>(rate_limit) }
+    });
+    let proxy = proxy.serve(make_service).with_graceful_shutdown(server_handle.stopped());
+    tokio::spawn(async move {
+        if let Err(err) = proxy.await {
+            error!("JSON-RPC rate limit proxy failed: {err}");
+        }
+    });
+    Ok(local_address)
+}
+
+fn forward_to(
+    client: Client<HttpConnector>,
+    server_address: SocketAddr,
+) -> impl Service<
+    Request<Body>,
+    Response = Response<Body>,
+    Error = hyper::Error,
+    Future = impl Future<Output = Result<Response<Body>, hyper::Error>> + Send,
+> + Clone {
+    service_fn(move |mut req: Request<Body>| {
+        let client = client.clone();
+        async move {
+            if req.headers().contains_key(hyper::header::UPGRADE) {
+                return Ok(websocket_not_supported_response());
+            }
+            let path_and_query =
+                req.uri().path_and_query().map(|path_and_query| path_and_query.as_str());
+            *req.uri_mut() = Uri::builder()
+                .scheme("http")
+                .authority(server_address.to_string())
+                .path_and_query(path_and_query.unwrap_or("/"))
+                .build()
+                .expect("Should be able to build a uri from a valid uri");
+            client.request(req).await
+        }
+    })
+}
+
+// Rate limits the requests of a single connection of the proxy.
+#[derive(Clone)]
+struct RateLimit<S> {
+    inner: S,
+    limiter: Arc<Mutex<RateLimiter>>,
+    peer_ip: IpAddr,
+    trust_forwarded_headers: bool,
+}
+
+impl<S> Service<Request<Body>> for RateLimit<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let client_ip = match self.trust_forwarded_headers {
+            true => get_forwarded_client_ip(&req).unwrap_or(self.peer_ip),
+            false => self.peer_ip,
+        };
+        let allowed =
+            self.limiter.lock().expect("Rate limiter lock poisoned").try_acquire(client_ip);
+        if !allowed {
+            debug!(%client_ip, "Request rate limit exceeded.");
+            return Box::pin(async { Ok(too_many_requests_response()) });
+        }
+        let fut = self.inner.call(req);
+        Box::pin(async move { fut.await.map_err(Into::into) })
+    }
+}
+
+fn get_forwarded_client_ip(req: &Request<Body>) -> Option<IpAddr> {
+    let headers = req.headers();
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .or_else(|| headers.get("x-real-ip").and_then(|value| value.to_str().ok()))
+        .and_then(|ip| ip.trim().parse().ok())
+}
+
+fn too_many_requests_response() -> Response<Body> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "error": {"code": RATE_LIMIT_ERROR_CODE, "message": RATE_LIMIT_ERROR_MESSAGE},
+        "id": null,
+    });
+    Response::builder()
+        .status(StatusCode::TOO_MANY_REQUESTS)
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("Should be able to build a response")
+}
+
+fn websocket_not_supported_response() -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::NOT_IMPLEMENTED)
+        .body(Body::from(WEBSOCKET_NOT_SUPPORTED_MESSAGE))
+        .expect("Should be able to build a response")
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+// Token bucket per client, refilled at `requests_per_sec` with a burst of the same size.
+struct RateLimiter {
+    requests_per_sec: f64,
+    buckets: HashMap<IpAddr, TokenBucket>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_sec: u32) -> Self {
+        Self { requests_per_sec: requests_per_sec.into(), buckets: HashMap::new() }
+    }
+
+    fn try_acquire(&mut self, client_ip: IpAddr) -> bool {
+        let now = Instant::now();
+        let capacity = self.requests_per_sec;
+        let bucket = self
+            .buckets
+            .entry(client_ip)
+            .or_insert(TokenBucket { tokens: capacity, last_refill: now });
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * capacity).min(capacity);
+        bucket.last_refill = now;
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+        bucket.tokens -= 1.0;
+        true
+    }
+
+    // A bucket idle for a second is full again, so dropping it doesn't change behavior.
+    fn evict_idle_buckets(&mut self) {
+        let now = Instant::now();
+        self.buckets
+            .retain(|_, bucket| now.duration_since(bucket.last_refill) < Duration::from_secs(1));
+    }
+}
@@ -10,8 +10,8 @@ use papyrus_storage::header::HeaderStorageWriter;
 use papyrus_storage::state::StateStorageWriter;
 use papyrus_storage::test_utils::get_test_storage;
 use pretty_assertions::assert_eq;
-use prometheus_parse::Value::Counter;
-use starknet_api::block::{BlockBody, BlockHeader, BlockNumber};
+use prometheus_parse::Value::{Counter, Untyped};
+use starknet_api::block::{BlockBody, BlockHash, BlockHeader, BlockNumber};
 use starknet_api::state::ThinStateDiff;
 use test_utils::{prometheus_is_contained, send_request};
 
@@ -32,6 +32,9 @@ use crate::test_utils::{
     get_test_rpc_config,
 };
 
+// The count sample of the histogram emitted by the rpc_method_latency attribute.
+const METHOD_LATENCY_COUNT: &str = "rpc_method_latency_seconds_count";
+
 #[test]
 fn get_method_and_version_test() {
     let method_name = "starknet_V0_6_0_blockNumber";
@@ -167,6 +170,7 @@ async fn server_metrics() {
         get_test_pending_data(),
         get_test_pending_classes(),
         storage_reader,
+        BlockHash::default(),
         "NODE VERSION",
     )
     .await
@@ -230,4 +234,24 @@ async fn server_metrics() {
     assert_eq!(failing_block_number, "0");
     assert_eq!(incoming_get_state_update, "1");
     assert_eq!(failing_get_state_update, "1");
+
+    // Every method call, including failing ones, emits a latency sample labeled by the method.
+    let block_number_labels = [(METHOD_LABEL, "block_number"), (VERSION_LABEL, "V0_6")];
+    assert_eq!(
+        prometheus_is_contained(
+            prometheus_handle.render(),
+            METHOD_LATENCY_COUNT,
+            &block_number_labels
+        ),
+        Some(Untyped(1f64))
+    );
+    let get_state_update_labels = [(METHOD_LABEL, "get_state_update"), (VERSION_LABEL, "V0_6")];
+    assert_eq!(
+        prometheus_is_contained(
+            prometheus_handle.render(),
+            METHOD_LATENCY_COUNT,
+            &get_state_update_labels
+        ),
+        Some(Untyped(1f64))
+    );
 }
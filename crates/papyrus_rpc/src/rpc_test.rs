@@ -1,4 +1,5 @@
 use std::error::Error as StdError;
+use std::net::SocketAddr;
 use std::{panic, vec};
 
 use assert_matches::assert_matches;
@@ -12,11 +13,14 @@ use jsonrpsee::types::ErrorObjectOwned;
 use papyrus_storage::base_layer::BaseLayerStorageWriter;
 use papyrus_storage::header::HeaderStorageWriter;
 use papyrus_storage::test_utils::get_test_storage;
+use papyrus_storage::StorageReader;
 use pretty_assertions::assert_eq;
 use rand::seq::SliceRandom;
+use reqwest::StatusCode;
 use starknet_api::block::{BlockHash, BlockHeader, BlockNumber, BlockStatus};
 use test_utils::get_rng;
 use tower::BoxError;
+use validator::Validate;
 
 use crate::middleware::proxy_rpc_request;
 use crate::test_utils::{
@@ -26,7 +30,7 @@ use crate::test_utils::{
     get_test_rpc_config,
 };
 use crate::version_config::VERSION_CONFIG;
-use crate::{get_block_status, run_server, SERVER_MAX_BODY_SIZE};
+use crate::{get_block_status, run_server, RpcConfig, SERVER_MAX_BODY_SIZE};
 
 #[tokio::test]
 async fn run_server_no_blocks() {
@@ -41,6 +45,7 @@ async fn run_server_no_blocks() {
         pending_data,
         pending_classes,
         storage_reader,
+        BlockHash::default(),
         "NODE VERSION",
     )
     .await
@@ -59,6 +64,110 @@ async fn run_server_no_blocks() {
     };
 }
 
+const RATE_LIMIT_PER_SEC: u32 = 2;
+
+async fn run_rate_limited_server(
+    storage_reader: StorageReader,
+    trust_forwarded_headers: bool,
+) -> SocketAddr {
+    let mut config = get_test_rpc_config();
+    config.rate_limit_per_sec = Some(RATE_LIMIT_PER_SEC);
+    config.rate_limit_trust_forwarded_headers = trust_forwarded_headers;
+    let (addr, _handle) = run_server(
+        &config,
+        get_test_highest_block(),
+        get_test_pending_data(),
+        get_test_pending_classes(),
+        storage_reader,
+        BlockHash::default(),
+        "NODE VERSION",
+    )
+    .await
+    .unwrap();
+    addr
+}
+
+async fn send_rate_limited_request(
+    addr: SocketAddr,
+    forwarded_for: Option<&str>,
+) -> reqwest::Response {
+    let mut request = reqwest::Client::new()
+        .post(format!("http://{addr}/rpc/v0_7"))
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(r#"{"jsonrpc":"2.0","id":"1","method":"starknet_chainId"}"#);
+    if let Some(forwarded_for) = forwarded_for {
+        request = request.header("x-forwarded-for", forwarded_for);
+    }
+    request.send().await.unwrap()
+}
+
+#[tokio::test]
+async fn rate_limit() {
+    let ((storage_reader, _), _temp_dir) = get_test_storage();
+    let addr = run_rate_limited_server(storage_reader, false).await;
+
+    // A burst beyond the limit is throttled.
+    for _ in 0..RATE_LIMIT_PER_SEC {
+        assert_eq!(send_rate_limited_request(addr, None).await.status(), StatusCode::OK);
+    }
+    let res = send_rate_limited_request(addr, None).await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    let body: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(body["error"]["code"], -32005);
+
+    // Forwarded headers are ignored unless they are trusted.
+    let res = send_rate_limited_request(addr, Some("2.2.2.2")).await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // The limit is restored over time.
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    assert_eq!(send_rate_limited_request(addr, None).await.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn rate_limit_with_trusted_forwarded_headers() {
+    let ((storage_reader, _), _temp_dir) = get_test_storage();
+    let addr = run_rate_limited_server(storage_reader, true).await;
+
+    for _ in 0..RATE_LIMIT_PER_SEC {
+        let res = send_rate_limited_request(addr, Some("1.1.1.1")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+    let res = send_rate_limited_request(addr, Some("1.1.1.1")).await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // Other clients behind the proxy have their own limit.
+    let res = send_rate_limited_request(addr, Some("2.2.2.2, 1.1.1.1")).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    // Requests without the headers are identified by the connection.
+    assert_eq!(send_rate_limited_request(addr, None).await.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn rate_limit_proxy_rejects_websocket_upgrades() {
+    let ((storage_reader, _), _temp_dir) = get_test_storage();
+    let addr = run_rate_limited_server(storage_reader, false).await;
+
+    let res = reqwest::Client::new()
+        .get(format!("http://{addr}/rpc/v0_7"))
+        .header(header::CONNECTION, "Upgrade")
+        .header(header::UPGRADE, "websocket")
+        .header(header::SEC_WEBSOCKET_VERSION, "13")
+        .header(header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ==")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_IMPLEMENTED);
+}
+
+#[test]
+fn zero_rate_limit_is_invalid() {
+    let config = RpcConfig { rate_limit_per_sec: Some(0), ..get_test_rpc_config() };
+    assert!(config.validate().is_err());
+    let config = RpcConfig { rate_limit_per_sec: Some(1), ..get_test_rpc_config() };
+    assert!(config.validate().is_ok());
+}
+
 /// Given an HTTP request, using the "read_body" function from jsonrpsee library,
 /// parse the body, make sure it's a formatted JSON and within the MAX_BODY_SIZE length.
 async fn get_json_rpc_body(request: Request<Body>) -> Vec<u8> {
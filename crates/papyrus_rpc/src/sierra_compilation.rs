@@ -0,0 +1,73 @@
+use cairo_lang_starknet_classes::casm_contract_class::{
+    CasmContractClass,
+    StarknetSierraCompilationError,
+};
+use cairo_lang_starknet_classes::contract_class::{
+    ContractClass as CairoLangContractClass,
+    ContractEntryPoint as CairoLangContractEntryPoint,
+    ContractEntryPoints,
+};
+use cairo_lang_utils::bigint::BigUintAsHex;
+use papyrus_common::class_hash::calculate_class_hash;
+use papyrus_execution::execution_utils::get_sierra_size;
+use papyrus_execution::{AbiSize, SierraSize};
+use starknet_api::core::ClassHash;
+use starknet_api::state::{ContractClass, EntryPoint, EntryPointType};
+
+// The class of a broadcasted declare transaction, with what the blockifier needs to execute it.
+pub(crate) struct CompiledDeclaredClass {
+    // The blockifier declares the class under the class hash of the transaction, so unlike
+    // deprecated declares, it has to be calculated.
+    pub class_hash: ClassHash,
+    pub casm: CasmContractClass,
+    pub sierra_program_length: SierraSize,
+    pub abi_length: AbiSize,
+}
+
+// Compiles the Sierra program of a declared class to the casm that the blockifier executes. This
+// is CPU heavy, so it shouldn't run on an async worker.
+pub(crate) fn compile_declared_class(
+    contract_class: ContractClass,
+    contract_class_version: String,
+    max_casm_bytecode_size: usize,
+) -> Result<CompiledDeclaredClass, StarknetSierraCompilationError> {
+    let to_cairo_lang_entry_points = |entry_point_type| {
+        contract_class
+            .entry_points_by_type
+            .get(&entry_point_type)
+            .into_iter()
+            .flatten()
+            .map(|entry_point: &EntryPoint| CairoLangContractEntryPoint {
+                selector: entry_point.selector.0.to_biguint(),
+                function_idx: entry_point.function_idx.0,
+            })
+            .collect()
+    };
+    let cairo_lang_contract_class = CairoLangContractClass {
+        sierra_program: contract_class
+            .sierra_program
+            .iter()
+            .map(|felt| BigUintAsHex { value: felt.to_biguint() })
+            .collect(),
+        sierra_program_debug_info: None,
+        contract_class_version,
+        entry_points_by_type: ContractEntryPoints {
+            external: to_cairo_lang_entry_points(EntryPointType::External),
+            l1_handler: to_cairo_lang_entry_points(EntryPointType::L1Handler),
+            constructor: to_cairo_lang_entry_points(EntryPointType::Constructor),
+        },
+        // The abi isn't needed for the compilation.
+        abi: None,
+    };
+    let casm = CasmContractClass::from_contract_class(
+        cairo_lang_contract_class,
+        false,
+        max_casm_bytecode_size,
+    )?;
+    Ok(CompiledDeclaredClass {
+        class_hash: calculate_class_hash(&contract_class),
+        casm,
+        sierra_program_length: get_sierra_size(&contract_class),
+        abi_length: contract_class.abi.len(),
+    })
+}
@@ -1,3 +1,6 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use papyrus_common::BlockHashAndNumber;
 use papyrus_storage::compiled_class::CasmStorageReader;
 use papyrus_storage::header::HeaderStorageReader;
@@ -5,6 +8,8 @@ use papyrus_storage::{StorageReader, StorageResult};
 use serde::ser::Serializer;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_client::reader::PendingData;
+use tokio::sync::RwLock;
 
 /// Represents the syncing status of the node.
 #[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq)]
@@ -57,3 +62,27 @@ pub(crate) fn get_last_synced_block(
         txn.get_block_header(block_number)?.expect("No header for last compiled class").block_hash;
     Ok(BlockHashAndNumber { block_hash, block_number })
 }
+
+// Returns the number of seconds since the pending block's timestamp, or None if the pending data
+// isn't on top of the latest block.
+pub(crate) async fn get_pending_block_age_seconds(
+    storage_reader: StorageReader,
+    pending_data: &Arc<RwLock<PendingData>>,
+) -> StorageResult<Option<u64>> {
+    let txn = storage_reader.begin_ro_txn()?;
+    let Some(latest_block_number) = txn.get_header_marker()?.prev() else {
+        return Ok(None);
+    };
+    let Some(latest_header) = txn.get_block_header(latest_block_number)? else {
+        return Ok(None);
+    };
+    let pending_data = pending_data.read().await;
+    if pending_data.block.parent_block_hash() != latest_header.block_hash {
+        return Ok(None);
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Current time should be after the unix epoch")
+        .as_secs();
+    Ok(Some(now.saturating_sub(pending_data.block.timestamp().0)))
+}
@@ -8,13 +8,14 @@ use jsonrpsee::types::ErrorObjectOwned;
 use jsonschema::JSONSchema;
 use papyrus_common::pending_classes::PendingClasses;
 use papyrus_common::BlockHashAndNumber;
-use papyrus_execution::ExecutionConfig;
+use papyrus_execution::{ClassCache, ExecutionConfig};
 use papyrus_storage::test_utils::get_test_storage_by_scope;
 use papyrus_storage::{StorageScope, StorageWriter};
 use pretty_assertions::assert_eq;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use starknet_api::block::BlockHash;
 use starknet_api::core::{ChainId, ContractAddress, PatriciaKey};
 use starknet_api::{contract_address, felt, patricia_key};
 use starknet_client::reader::PendingData;
@@ -34,10 +35,14 @@ pub fn get_test_rpc_config() -> RpcConfig {
             eth_fee_contract_address: contract_address!("0x1001"),
             strk_fee_contract_address: contract_address!("0x1001"),
             initial_gas_cost: 10000000000,
+            precheck_compiled_classes: false,
+            max_casm_bytecode_size: 80 * 1024,
+            class_cache_size: 400,
         },
         server_address: String::from("127.0.0.1:0"),
         max_events_chunk_size: 10,
         max_events_keys: 10,
+        max_execution_transactions: 10,
         collect_metrics: false,
         ..Default::default()
     }
@@ -66,6 +71,38 @@ pub(crate) fn get_test_rpc_server_and_storage_writer_from_params<T: JsonRpcServe
     pending_data: Option<Arc<RwLock<PendingData>>>,
     pending_classes: Option<Arc<RwLock<PendingClasses>>>,
     storage_scope: Option<StorageScope>,
+) -> (RpcModule<T>, StorageWriter) {
+    build_test_rpc_server_and_storage_writer(
+        mock_client,
+        shared_highest_block,
+        pending_data,
+        pending_classes,
+        storage_scope,
+        BlockHash::default(),
+    )
+}
+
+pub(crate) fn get_test_rpc_server_and_storage_writer_with_genesis_hash<T: JsonRpcServerTrait>(
+    genesis_hash: BlockHash,
+    pending_data: Arc<RwLock<PendingData>>,
+) -> (RpcModule<T>, StorageWriter) {
+    build_test_rpc_server_and_storage_writer(
+        None,
+        None,
+        Some(pending_data),
+        None,
+        None,
+        genesis_hash,
+    )
+}
+
+fn build_test_rpc_server_and_storage_writer<T: JsonRpcServerTrait>(
+    mock_client: Option<MockStarknetWriter>,
+    shared_highest_block: Option<Arc<RwLock<Option<BlockHashAndNumber>>>>,
+    pending_data: Option<Arc<RwLock<PendingData>>>,
+    pending_classes: Option<Arc<RwLock<PendingClasses>>>,
+    storage_scope: Option<StorageScope>,
+    genesis_hash: BlockHash,
 ) -> (RpcModule<T>, StorageWriter) {
     let mock_client = mock_client.unwrap_or_default();
     let shared_highest_block = shared_highest_block.unwrap_or(get_test_highest_block());
@@ -79,10 +116,14 @@ pub(crate) fn get_test_rpc_server_and_storage_writer_from_params<T: JsonRpcServe
     (
         T::new(
             config.chain_id,
+            genesis_hash,
             config.execution_config,
+            ClassCache::new(config.execution_config.class_cache_size),
             storage_reader,
             config.max_events_chunk_size,
             config.max_events_keys,
+            config.max_execution_transactions,
+            config.max_execution_calldata_length,
             BlockHashAndNumber::default(),
             shared_highest_block,
             pending_data,
@@ -15,9 +15,12 @@ use papyrus_execution::{
     execute_call,
     execution_utils,
     simulate_transactions as exec_simulate_transactions,
+    ClassCache,
     ExecutableTransactionInput,
     ExecutionConfig,
+    ExecutionInputs,
 };
+use papyrus_proc_macros::rpc_method_latency;
 use papyrus_storage::body::events::{EventIndex, EventsReader};
 use papyrus_storage::body::{BodyStorageReader, TransactionIndex};
 use papyrus_storage::db::TransactionKind;
@@ -25,7 +28,6 @@ use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::{StorageError, StorageReader, StorageTxn};
 use starknet_api::block::{BlockHash, BlockNumber, BlockStatus};
 use starknet_api::core::{ChainId, ClassHash, ContractAddress, GlobalRoot, Nonce};
-use starknet_api::hash::StarkHash;
 use starknet_api::state::{StateNumber, StorageKey};
 use starknet_api::transaction::{
     EventContent,
@@ -62,6 +64,7 @@ use super::super::broadcasted_transaction::{
     BroadcastedTransaction,
 };
 use super::super::error::{
+    execution_request_too_large,
     ContractError,
     JsonRpcError,
     TransactionExecutionError,
@@ -80,8 +83,10 @@ use super::super::state::{AcceptedStateUpdate, PendingStateUpdate, StateUpdate};
 use super::super::transaction::{
     get_block_tx_hashes_by_number,
     get_block_txs_by_number,
+    DeployAccountTransaction,
     Event,
     GeneralTransactionReceipt,
+    InvokeTransaction,
     L1HandlerMsgHash,
     MessageFromL1,
     PendingTransactionFinalityStatus,
@@ -106,6 +111,7 @@ use super::super::write_api_result::{
     AddInvokeOkResult,
 };
 use super::{
+    broadcasted_to_executable_txns,
     execution_error_to_error_object_owned,
     stored_txn_to_executable_txn,
     BlockHashAndNumber,
@@ -131,7 +137,6 @@ use crate::{
     internal_server_error,
     verify_storage_scope,
     ContinuationTokenAsStruct,
-    GENESIS_HASH,
 };
 
 const IGNORE_L1_DA_MODE: bool = true;
@@ -144,10 +149,14 @@ lazy_static! {
 /// Rpc server.
 pub struct JsonRpcServerImpl {
     pub chain_id: ChainId,
+    pub genesis_hash: BlockHash,
     pub execution_config: ExecutionConfig,
+    pub class_cache: ClassCache,
     pub storage_reader: StorageReader,
     pub max_events_chunk_size: usize,
     pub max_events_keys: usize,
+    pub max_execution_transactions: usize,
+    pub max_execution_calldata_length: usize,
     pub starting_block: BlockHashAndNumber,
     pub shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
     pub pending_data: Arc<RwLock<PendingData>>,
@@ -155,6 +164,7 @@ pub struct JsonRpcServerImpl {
     pub writer_client: Arc<dyn StarknetWriter>,
 }
 
+#[rpc_method_latency("V0_6")]
 #[async_trait]
 impl JsonRpcServer for JsonRpcServerImpl {
     #[instrument(skip(self), level = "debug", err, ret)]
@@ -184,7 +194,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
 
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         if let BlockId::Tag(Tag::Pending) = block_id {
-            let block = read_pending_data(&self.pending_data, &txn).await?.block;
+            let block = read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?.block;
             let pending_block_header = PendingBlockHeader {
                 parent_hash: block.parent_block_hash(),
                 sequencer_address: block.sequencer_address(),
@@ -227,7 +237,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
 
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         if let BlockId::Tag(Tag::Pending) = block_id {
-            let block = read_pending_data(&self.pending_data, &txn).await?.block;
+            let block = read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?.block;
             let pending_block_header = PendingBlockHeader {
                 parent_hash: block.parent_block_hash(),
                 sequencer_address: block.sequencer_address(),
@@ -292,7 +302,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         let maybe_pending_storage_diffs = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(
-                read_pending_data(&self.pending_data, &txn)
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash)
                     .await?
                     .state_update
                     .state_diff
@@ -350,7 +360,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         } else {
             // The transaction is not in any non-pending block. Search for it in the pending block
             // and if it's not found, return error.
-            let client_transaction = read_pending_data(&self.pending_data, &txn)
+            let client_transaction = read_pending_data(&self.pending_data, &txn, self.genesis_hash)
                 .await?
                 .block
                 .transactions()
@@ -377,31 +387,32 @@ impl JsonRpcServer for JsonRpcServerImpl {
         verify_storage_scope(&self.storage_reader)?;
 
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
-        let (starknet_api_transaction, transaction_hash) =
-            if let BlockId::Tag(Tag::Pending) = block_id {
-                let client_transaction = read_pending_data(&self.pending_data, &txn)
-                    .await?
-                    .block
-                    .transactions()
-                    .get(index.0)
-                    .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?
-                    .clone();
-                let transaction_hash = client_transaction.transaction_hash();
-                (client_transaction.try_into().map_err(internal_server_error)?, transaction_hash)
-            } else {
-                let block_number = get_accepted_block_number(&txn, block_id)?;
-
-                let tx_index = TransactionIndex(block_number, index);
-                let transaction = txn
-                    .get_transaction(tx_index)
-                    .map_err(internal_server_error)?
-                    .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?;
-                let transaction_hash = txn
-                    .get_transaction_hash_by_idx(&tx_index)
-                    .map_err(internal_server_error)?
-                    .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?;
-                (transaction, transaction_hash)
-            };
+        let (starknet_api_transaction, transaction_hash) = if let BlockId::Tag(Tag::Pending) =
+            block_id
+        {
+            let client_transaction = read_pending_data(&self.pending_data, &txn, self.genesis_hash)
+                .await?
+                .block
+                .transactions()
+                .get(index.0)
+                .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?
+                .clone();
+            let transaction_hash = client_transaction.transaction_hash();
+            (client_transaction.try_into().map_err(internal_server_error)?, transaction_hash)
+        } else {
+            let block_number = get_accepted_block_number(&txn, block_id)?;
+
+            let tx_index = TransactionIndex(block_number, index);
+            let transaction = txn
+                .get_transaction(tx_index)
+                .map_err(internal_server_error)?
+                .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?;
+            let transaction_hash = txn
+                .get_transaction_hash_by_idx(&tx_index)
+                .map_err(internal_server_error)?
+                .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?;
+            (transaction, transaction_hash)
+        };
 
         Ok(TransactionWithHash {
             transaction: starknet_api_transaction.try_into()?,
@@ -415,8 +426,11 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
         if let BlockId::Tag(Tag::Pending) = block_id {
-            let transactions_len =
-                read_pending_data(&self.pending_data, &txn).await?.block.transactions().len();
+            let transactions_len = read_pending_data(&self.pending_data, &txn, self.genesis_hash)
+                .await?
+                .block
+                .transactions()
+                .len();
             Ok(transactions_len)
         } else {
             let block_number = get_accepted_block_number(&txn, block_id)?;
@@ -431,7 +445,8 @@ impl JsonRpcServer for JsonRpcServerImpl {
     async fn get_state_update(&self, block_id: BlockId) -> RpcResult<StateUpdate> {
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         if let BlockId::Tag(Tag::Pending) = block_id {
-            let state_update = read_pending_data(&self.pending_data, &txn).await?.state_update;
+            let state_update =
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?.state_update;
             return Ok(StateUpdate::PendingStateUpdate(PendingStateUpdate {
                 old_root: state_update.old_root,
                 state_diff: state_update.state_diff.into(),
@@ -450,7 +465,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
             Ok(parent_block_number) => {
                 BlockHeader::from(get_block_header_by_number(&txn, parent_block_number)?).new_root
             }
-            Err(_) => GlobalRoot(StarkHash::from_hex_unchecked(GENESIS_HASH)),
+            Err(_) => GlobalRoot::default(),
         };
 
         // Get the block state diff.
@@ -547,7 +562,8 @@ impl JsonRpcServer for JsonRpcServerImpl {
 
             // TODO(shahak): Consider cloning the transactions and the receipts in order to free
             // the lock sooner (Check which is better).
-            let pending_block = read_pending_data(&self.pending_data, &txn).await?.block;
+            let pending_block =
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?.block;
 
             let client_transaction_receipt = pending_block
                 .transaction_receipts()
@@ -642,7 +658,10 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let maybe_pending_deployed_contracts_and_replaced_classes =
             if let BlockId::Tag(Tag::Pending) = block_id {
                 let pending_state_diff =
-                    read_pending_data(&self.pending_data, &txn).await?.state_update.state_diff;
+                    read_pending_data(&self.pending_data, &txn, self.genesis_hash)
+                        .await?
+                        .state_update
+                        .state_diff;
                 Some((pending_state_diff.deployed_contracts, pending_state_diff.replaced_classes))
             } else {
                 None
@@ -670,7 +689,13 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
         let maybe_pending_nonces = if let BlockId::Tag(Tag::Pending) = block_id {
-            Some(read_pending_data(&self.pending_data, &txn).await?.state_update.state_diff.nonces)
+            Some(
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash)
+                    .await?
+                    .state_update
+                    .state_diff
+                    .nonces,
+            )
         } else {
             None
         };
@@ -800,7 +825,8 @@ impl JsonRpcServer for JsonRpcServerImpl {
         }
 
         if include_pending_block {
-            let pending_block = read_pending_data(&self.pending_data, &txn).await?.block;
+            let pending_block =
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?.block;
             let pending_transaction_receipts = pending_block.transaction_receipts();
             // Extract the first transaction offset and event offset from the starting EventIndex.
             let (transaction_start, event_start) = if start_event_index.0.0 > latest_block_number {
@@ -874,10 +900,11 @@ impl JsonRpcServer for JsonRpcServerImpl {
 
     #[instrument(skip(self), level = "debug", err, ret)]
     async fn call(&self, request: CallRequest, block_id: BlockId) -> RpcResult<Vec<Felt>> {
+        self.verify_execution_request_size(1, request.calldata.0.len())?;
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         let maybe_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(client_pending_data_to_execution_pending_data(
-                read_pending_data(&self.pending_data, &txn).await?,
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?,
                 self.pending_classes.read().await.clone(),
             ))
         } else {
@@ -886,8 +913,9 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let block_number = get_accepted_block_number(&txn, block_id)?;
         let block_not_reverted_validator = BlockNotRevertedValidator::new(block_number, &txn)?;
         drop(txn);
-        let state_number = StateNumber::unchecked_right_after_block(block_number);
+        let execution_inputs = ExecutionInputs::at_block_end(block_number);
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -898,12 +926,14 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 reader,
                 maybe_pending_data,
                 &chain_id,
-                state_number,
-                block_number,
+                execution_inputs,
                 &contract_address_copy,
                 request.entry_point_selector,
                 request.calldata,
+                None,
+                None,
                 &execution_config,
+                &class_cache,
                 IGNORE_L1_DA_MODE,
             )
         })
@@ -977,28 +1007,35 @@ impl JsonRpcServer for JsonRpcServerImpl {
         block_id: BlockId,
     ) -> RpcResult<Vec<FeeEstimate>> {
         trace!("Estimating fee of transactions: {:#?}", transactions);
+        self.verify_execution_request_size(
+            transactions.len(),
+            transactions.iter().map(broadcasted_transaction_calldata_length).sum(),
+        )?;
         let validate = !simulation_flags.contains(&SimulationFlag::SkipValidate);
+        let executable_txns = broadcasted_to_executable_txns(
+            transactions,
+            self.execution_config.max_casm_bytecode_size,
+        )
+        .await?;
 
         let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
         let maybe_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(client_pending_data_to_execution_pending_data(
-                read_pending_data(&self.pending_data, &storage_txn).await?,
+                read_pending_data(&self.pending_data, &storage_txn, self.genesis_hash).await?,
                 self.pending_classes.read().await.clone(),
             ))
         } else {
             None
         };
 
-        let executable_txns =
-            transactions.into_iter().map(|tx| tx.try_into()).collect::<Result<_, _>>()?;
-
         let block_number = get_accepted_block_number(&storage_txn, block_id)?;
         let block_not_reverted_validator =
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
         drop(storage_txn);
-        let state_number = StateNumber::unchecked_right_after_block(block_number);
+        let execution_inputs = ExecutionInputs::at_block_end(block_number);
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1009,11 +1046,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 &chain_id,
                 reader,
                 maybe_pending_data,
-                state_number,
-                block_number,
+                execution_inputs,
                 &execution_config,
+                &class_cache,
                 validate,
                 IGNORE_L1_DA_MODE,
+                None,
             )
         })
         .await
@@ -1048,14 +1086,21 @@ impl JsonRpcServer for JsonRpcServerImpl {
         simulation_flags: Vec<SimulationFlag>,
     ) -> RpcResult<Vec<SimulatedTransaction>> {
         trace!("Simulating transactions: {:#?}", transactions);
-        let executable_txns =
-            transactions.into_iter().map(|tx| tx.try_into()).collect::<Result<_, _>>()?;
+        self.verify_execution_request_size(
+            transactions.len(),
+            transactions.iter().map(broadcasted_transaction_calldata_length).sum(),
+        )?;
+        let executable_txns = broadcasted_to_executable_txns(
+            transactions,
+            self.execution_config.max_casm_bytecode_size,
+        )
+        .await?;
 
         let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
         let maybe_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(client_pending_data_to_execution_pending_data(
-                read_pending_data(&self.pending_data, &storage_txn).await?,
+                read_pending_data(&self.pending_data, &storage_txn, self.genesis_hash).await?,
                 self.pending_classes.read().await.clone(),
             ))
         } else {
@@ -1068,6 +1113,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         drop(storage_txn);
         let state_number = StateNumber::unchecked_right_after_block(block_number);
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1085,9 +1131,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 state_number,
                 block_number,
                 &execution_config,
+                &class_cache,
                 charge_fee,
                 validate,
                 IGNORE_L1_DA_MODE,
+                None,
+                None,
             )
         })
         .await
@@ -1116,7 +1165,8 @@ impl JsonRpcServer for JsonRpcServerImpl {
     ) -> RpcResult<TransactionTrace> {
         let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
-        let pending_block = read_pending_data(&self.pending_data, &storage_txn).await?.block;
+        let pending_block =
+            read_pending_data(&self.pending_data, &storage_txn, self.genesis_hash).await?.block;
         // Search for the transaction inside the pending block.
         let (
             maybe_pending_data,
@@ -1219,6 +1269,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         drop(storage_txn);
 
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1233,9 +1284,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 state_number,
                 block_number,
                 &execution_config,
+                &class_cache,
                 true,
                 true,
                 IGNORE_L1_DA_MODE,
+                None,
+                None,
             )
         })
         .await
@@ -1259,7 +1313,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
         let maybe_client_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
-            Some(read_pending_data(&self.pending_data, &storage_txn).await?)
+            Some(read_pending_data(&self.pending_data, &storage_txn, self.genesis_hash).await?)
         } else {
             None
         };
@@ -1335,6 +1389,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         drop(storage_txn);
 
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1350,9 +1405,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 state_number,
                 block_number,
                 &execution_config,
+                &class_cache,
                 true,
                 true,
                 IGNORE_L1_DA_MODE,
+                None,
+                None,
             )
         })
         .await
@@ -1381,7 +1439,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         let maybe_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(client_pending_data_to_execution_pending_data(
-                read_pending_data(&self.pending_data, &storage_txn).await?,
+                read_pending_data(&self.pending_data, &storage_txn, self.genesis_hash).await?,
                 self.pending_classes.read().await.clone(),
             ))
         } else {
@@ -1398,8 +1456,9 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let block_not_reverted_validator =
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
         drop(storage_txn);
-        let state_number = StateNumber::unchecked_right_after_block(block_number);
+        let execution_inputs = ExecutionInputs::at_block_end(block_number);
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1410,11 +1469,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 &chain_id,
                 reader,
                 maybe_pending_data,
-                state_number,
-                block_number,
+                execution_inputs,
                 &execution_config,
+                &class_cache,
                 false,
                 IGNORE_L1_DA_MODE,
+                None,
             )
         })
         .await
@@ -1453,13 +1513,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
 async fn read_pending_data<Mode: TransactionKind>(
     pending_data: &Arc<RwLock<PendingData>>,
     txn: &StorageTxn<'_, Mode>,
+    genesis_hash: BlockHash,
 ) -> RpcResult<PendingData> {
     let latest_header = match get_latest_block_number(txn)? {
         Some(latest_block_number) => get_block_header_by_number(txn, latest_block_number)?,
-        None => starknet_api::block::BlockHeader {
-            parent_hash: BlockHash(StarkHash::from_hex_unchecked(GENESIS_HASH)),
-            ..Default::default()
-        },
+        // Before the first block, the pending block is on top of the genesis hash.
+        None => starknet_api::block::BlockHeader { block_hash: genesis_hash, ..Default::default() },
     };
     let pending_data = &pending_data.read().await;
     if pending_data.block.parent_block_hash() == latest_header.block_hash {
@@ -1489,13 +1548,55 @@ fn do_event_keys_match_filter(event_content: &EventContent, filter: &EventFilter
     })
 }
 
+fn broadcasted_transaction_calldata_length(tx: &BroadcastedTransaction) -> usize {
+    match tx {
+        BroadcastedTransaction::Declare(_) => 0,
+        BroadcastedTransaction::DeployAccount(DeployAccountTransaction::Version1(tx)) => {
+            tx.constructor_calldata.0.len()
+        }
+        BroadcastedTransaction::DeployAccount(DeployAccountTransaction::Version3(tx)) => {
+            tx.constructor_calldata.0.len()
+        }
+        BroadcastedTransaction::Invoke(InvokeTransaction::Version0(tx)) => tx.calldata.0.len(),
+        BroadcastedTransaction::Invoke(InvokeTransaction::Version1(tx)) => tx.calldata.0.len(),
+        BroadcastedTransaction::Invoke(InvokeTransaction::Version3(tx)) => tx.calldata.0.len(),
+    }
+}
+
+impl JsonRpcServerImpl {
+    // Rejects execution requests that exceed the configured limits before executing them.
+    fn verify_execution_request_size(
+        &self,
+        n_transactions: usize,
+        calldata_length: usize,
+    ) -> RpcResult<()> {
+        if n_transactions > self.max_execution_transactions {
+            return Err(ErrorObjectOwned::from(execution_request_too_large(format!(
+                "Got {n_transactions} transactions, the maximum is {}.",
+                self.max_execution_transactions
+            ))));
+        }
+        if calldata_length > self.max_execution_calldata_length {
+            return Err(ErrorObjectOwned::from(execution_request_too_large(format!(
+                "Got a total calldata length of {calldata_length}, the maximum is {}.",
+                self.max_execution_calldata_length
+            ))));
+        }
+        Ok(())
+    }
+}
+
 impl JsonRpcServerTrait for JsonRpcServerImpl {
     fn new(
         chain_id: ChainId,
+        genesis_hash: BlockHash,
         execution_config: ExecutionConfig,
+        class_cache: ClassCache,
         storage_reader: StorageReader,
         max_events_chunk_size: usize,
         max_events_keys: usize,
+        max_execution_transactions: usize,
+        max_execution_calldata_length: usize,
         starting_block: BlockHashAndNumber,
         shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
         pending_data: Arc<RwLock<PendingData>>,
@@ -1504,10 +1605,14 @@ impl JsonRpcServerTrait for JsonRpcServerImpl {
     ) -> Self {
         Self {
             chain_id,
+            genesis_hash,
             execution_config,
+            class_cache,
             storage_reader,
             max_events_chunk_size,
             max_events_keys,
+            max_execution_transactions,
+            max_execution_calldata_length,
             starting_block,
             shared_highest_block,
             pending_data,
@@ -8,6 +8,7 @@ use jsonrpsee::types::ErrorObjectOwned;
 use papyrus_common::deprecated_class_abi::calculate_deprecated_class_abi_length;
 use papyrus_common::pending_classes::ApiContractClass;
 use papyrus_common::BlockHashAndNumber;
+use papyrus_execution::execution_utils::get_sierra_size;
 use papyrus_execution::objects::PriceUnit;
 use papyrus_execution::{AbiSize, ExecutableTransactionInput, ExecutionError, SierraSize};
 use papyrus_proc_macros::versioned_rpc;
@@ -29,13 +30,17 @@ use super::block::Block;
 use super::broadcasted_transaction::{
     BroadcastedDeclareTransaction,
     BroadcastedDeclareV1Transaction,
+    BroadcastedDeclareV2Transaction,
+    BroadcastedDeclareV3Transaction,
     BroadcastedTransaction,
 };
 use super::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use super::error::{
+    ClassNotDeclaredError,
     ContractError,
     JsonRpcError,
     BLOCK_NOT_FOUND,
+    COMPILATION_FAILED,
     CONTRACT_NOT_FOUND,
     INVALID_CONTINUATION_TOKEN,
 };
@@ -58,7 +63,8 @@ use super::transaction::{
     TypedInvokeTransaction,
 };
 use super::write_api_result::{AddDeclareOkResult, AddDeployAccountOkResult, AddInvokeOkResult};
-use crate::api::{BlockId, CallRequest};
+use crate::api::{BlockHashOrNumber, BlockId, CallRequest};
+use crate::sierra_compilation::{compile_declared_class, CompiledDeclaredClass};
 use crate::syncing_state::SyncingState;
 use crate::{internal_server_error, ContinuationTokenAsStruct};
 
@@ -328,14 +334,36 @@ pub enum SimulationFlag {
     SkipFeeCharge,
 }
 
-impl TryFrom<BroadcastedTransaction> for ExecutableTransactionInput {
-    type Error = ErrorObjectOwned;
-    fn try_from(value: BroadcastedTransaction) -> Result<Self, Self::Error> {
-        // TODO(yair): pass the right value for only_query field.
-        match value {
-            BroadcastedTransaction::Declare(tx) => Ok(tx.try_into()?),
-            BroadcastedTransaction::DeployAccount(tx) => Ok(Self::DeployAccount(tx.into(), false)),
-            BroadcastedTransaction::Invoke(tx) => Ok(Self::Invoke(tx.into(), false)),
+// Converts the broadcasted transactions on a blocking thread, since the Sierra classes of declare
+// transactions are compiled in the conversion.
+pub(crate) async fn broadcasted_to_executable_txns(
+    transactions: Vec<BroadcastedTransaction>,
+    max_casm_bytecode_size: usize,
+) -> RpcResult<Vec<ExecutableTransactionInput>> {
+    tokio::task::spawn_blocking(move || {
+        transactions
+            .into_iter()
+            .map(|tx| broadcasted_to_executable_txn(tx, max_casm_bytecode_size))
+            .collect()
+    })
+    .await
+    .map_err(internal_server_error)?
+}
+
+pub(crate) fn broadcasted_to_executable_txn(
+    tx: BroadcastedTransaction,
+    max_casm_bytecode_size: usize,
+) -> Result<ExecutableTransactionInput, ErrorObjectOwned> {
+    // TODO(yair): pass the right value for only_query field.
+    match tx {
+        BroadcastedTransaction::Declare(tx) => {
+            broadcasted_declare_to_executable_txn(tx, max_casm_bytecode_size)
+        }
+        BroadcastedTransaction::DeployAccount(tx) => {
+            Ok(ExecutableTransactionInput::DeployAccount(tx.into(), false))
+        }
+        BroadcastedTransaction::Invoke(tx) => {
+            Ok(ExecutableTransactionInput::Invoke(tx.into(), false))
         }
     }
 }
@@ -412,8 +440,8 @@ pub(crate) fn stored_txn_to_executable_txn(
                 false,
             ))
         }
-        starknet_api::transaction::Transaction::Deploy(_) => {
-            Err(internal_server_error("Deploy txns not supported in execution"))
+        starknet_api::transaction::Transaction::Deploy(deploy_tx) => {
+            Ok(ExecutableTransactionInput::Deploy(deploy_tx, false))
         }
         starknet_api::transaction::Transaction::DeployAccount(deploy_account_tx) => {
             Ok(ExecutableTransactionInput::DeployAccount(deploy_account_tx, false))
@@ -465,53 +493,124 @@ fn get_class_lengths(
         .ok_or_else(|| {
             internal_server_error(format!("Missing deprecated class definition of {class_hash}."))
         })
-        .map(|contract_class| (contract_class.sierra_program.len(), contract_class.abi.len()))
+        .map(|contract_class| (get_sierra_size(&contract_class), contract_class.abi.len()))
 }
 
-impl TryFrom<BroadcastedDeclareTransaction> for ExecutableTransactionInput {
-    type Error = ErrorObjectOwned;
-    fn try_from(value: BroadcastedDeclareTransaction) -> Result<Self, Self::Error> {
-        match value {
-            BroadcastedDeclareTransaction::V1(BroadcastedDeclareV1Transaction {
-                r#type: _,
-                contract_class,
-                sender_address,
-                nonce,
-                max_fee,
-                signature,
-            }) => {
-                let sn_api_contract_class =
-                    user_deprecated_contract_class_to_sn_api(contract_class)?;
-                let abi_length = calculate_deprecated_class_abi_length(&sn_api_contract_class)
-                    .map_err(internal_server_error)?;
-                Ok(Self::DeclareV1(
-                    starknet_api::transaction::DeclareTransactionV0V1 {
-                        max_fee,
-                        signature,
-                        nonce,
-                        // The blockifier doesn't need the class hash, but it uses the SN_API
-                        // DeclareTransactionV0V1 which requires it.
-                        class_hash: ClassHash::default(),
-                        sender_address,
-                    },
-                    sn_api_contract_class,
-                    abi_length,
-                    // TODO(yair): pass the right value for only_query field.
-                    false,
-                ))
-            }
-            BroadcastedDeclareTransaction::V2(_) => {
-                // TODO(yair): We need a way to get the casm of a declare V2 transaction.
-                Err(internal_server_error("Declare V2 is not supported yet in execution."))
-            }
-            BroadcastedDeclareTransaction::V3(_) => {
-                // TODO(yair): We need a way to get the casm of a declare V3 transaction.
-                Err(internal_server_error("Declare V3 is not supported yet in execution."))
-            }
+fn broadcasted_declare_to_executable_txn(
+    tx: BroadcastedDeclareTransaction,
+    max_casm_bytecode_size: usize,
+) -> Result<ExecutableTransactionInput, ErrorObjectOwned> {
+    match tx {
+        BroadcastedDeclareTransaction::V1(BroadcastedDeclareV1Transaction {
+            r#type: _,
+            contract_class,
+            sender_address,
+            nonce,
+            max_fee,
+            signature,
+        }) => {
+            let sn_api_contract_class = user_deprecated_contract_class_to_sn_api(contract_class)?;
+            let abi_length = calculate_deprecated_class_abi_length(&sn_api_contract_class)
+                .map_err(internal_server_error)?;
+            Ok(ExecutableTransactionInput::DeclareV1(
+                starknet_api::transaction::DeclareTransactionV0V1 {
+                    max_fee,
+                    signature,
+                    nonce,
+                    // The blockifier doesn't need the class hash, but it uses the SN_API
+                    // DeclareTransactionV0V1 which requires it.
+                    class_hash: ClassHash::default(),
+                    sender_address,
+                },
+                sn_api_contract_class,
+                abi_length,
+                // TODO(yair): pass the right value for only_query field.
+                false,
+            ))
+        }
+        BroadcastedDeclareTransaction::V2(BroadcastedDeclareV2Transaction {
+            r#type: _,
+            contract_class,
+            compiled_class_hash,
+            sender_address,
+            nonce,
+            max_fee,
+            signature,
+        }) => {
+            let CompiledDeclaredClass { class_hash, casm, sierra_program_length, abi_length } =
+                compile_broadcasted_class(contract_class, max_casm_bytecode_size)?;
+            Ok(ExecutableTransactionInput::DeclareV2(
+                starknet_api::transaction::DeclareTransactionV2 {
+                    max_fee,
+                    signature,
+                    nonce,
+                    class_hash,
+                    compiled_class_hash,
+                    sender_address,
+                },
+                casm,
+                sierra_program_length,
+                abi_length,
+                // TODO(yair): pass the right value for only_query field.
+                false,
+            ))
+        }
+        BroadcastedDeclareTransaction::V3(BroadcastedDeclareV3Transaction {
+            r#type: _,
+            sender_address,
+            compiled_class_hash,
+            signature,
+            nonce,
+            contract_class,
+            resource_bounds,
+            tip,
+            paymaster_data,
+            account_deployment_data,
+            nonce_data_availability_mode,
+            fee_data_availability_mode,
+        }) => {
+            let CompiledDeclaredClass { class_hash, casm, sierra_program_length, abi_length } =
+                compile_broadcasted_class(contract_class, max_casm_bytecode_size)?;
+            Ok(ExecutableTransactionInput::DeclareV3(
+                starknet_api::transaction::DeclareTransactionV3 {
+                    resource_bounds: resource_bounds.into(),
+                    tip,
+                    signature,
+                    nonce,
+                    class_hash,
+                    compiled_class_hash,
+                    sender_address,
+                    nonce_data_availability_mode,
+                    fee_data_availability_mode,
+                    paymaster_data,
+                    account_deployment_data,
+                },
+                casm,
+                sierra_program_length,
+                abi_length,
+                // TODO(yair): pass the right value for only_query field.
+                false,
+            ))
         }
     }
 }
 
+fn compile_broadcasted_class(
+    contract_class: ContractClass,
+    max_casm_bytecode_size: usize,
+) -> Result<CompiledDeclaredClass, ErrorObjectOwned> {
+    compile_declared_class(
+        starknet_api::state::ContractClass {
+            sierra_program: contract_class.sierra_program,
+            entry_points_by_type: contract_class.entry_points_by_type.to_hash_map(),
+            abi: contract_class.abi,
+        },
+        contract_class.contract_class_version,
+        max_casm_bytecode_size,
+    )
+    .map_err(|err| JsonRpcError { data: Some(err.to_string()), ..COMPILATION_FAILED }.into())
+}
+
 fn user_deprecated_contract_class_to_sn_api(
     value: starknet_client::writer::objects::transaction::DeprecatedContractClass,
 ) -> Result<starknet_api::deprecated_contract_class::ContractClass, ErrorObjectOwned> {
@@ -655,6 +754,14 @@ pub(crate) fn execution_error_to_error_object_owned(err: ExecutionError) -> Erro
             rpc_err.into()
         }
         ExecutionError::ContractNotFound { .. } => CONTRACT_NOT_FOUND.into(),
+        ExecutionError::ClassNotDeclared { class_hash, state_number } => {
+            // The execution reads the state at the end of the block before the state number.
+            let block_number = state_number.0.prev().unwrap_or_default();
+            let block_id = BlockId::HashOrNumber(BlockHashOrNumber::Number(block_number));
+            let rpc_err: JsonRpcError<ClassNotDeclaredError> =
+                ClassNotDeclaredError { class_hash, block_id }.into();
+            rpc_err.into()
+        }
         _ => internal_server_error(err),
     }
 }
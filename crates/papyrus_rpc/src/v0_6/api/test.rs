@@ -9,6 +9,7 @@ use async_trait::async_trait;
 use indexmap::{indexmap, IndexMap};
 use itertools::Itertools;
 use jsonrpsee::core::Error;
+use jsonrpsee::types::error::ErrorCode;
 use jsonrpsee::Methods;
 use jsonschema::JSONSchema;
 use lazy_static::lazy_static;
@@ -30,6 +31,7 @@ use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{
     Block as StarknetApiBlock,
+    BlockBody,
     BlockHash,
     BlockHeader,
     BlockNumber,
@@ -190,7 +192,6 @@ use crate::{
     internal_server_error_with_msg,
     run_server,
     ContinuationTokenAsStruct,
-    GENESIS_HASH,
 };
 
 const NODE_VERSION: &str = "NODE VERSION";
@@ -639,6 +640,31 @@ async fn get_block_w_full_transactions() {
     };
 }
 
+#[tokio::test]
+async fn get_block_l1_accepted_tag_is_rejected() {
+    let method_name = "starknet_V0_6_getBlockWithTxHashes";
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    let header = BlockHeader::default();
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(header.block_number, &header)
+        .unwrap()
+        .append_body(header.block_number, BlockBody::default())
+        .unwrap()
+        .append_state_diff(header.block_number, starknet_api::state::ThinStateDiff::default())
+        .unwrap()
+        .update_base_layer_block_marker(&BlockNumber(1))
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let err =
+        module.call::<_, Block>(method_name, [BlockId::Tag(Tag::L1Accepted)]).await.unwrap_err();
+    assert_matches!(err, Error::Call(err) if err.code() == ErrorCode::InvalidParams.code());
+}
+
 #[tokio::test]
 async fn get_block_w_transaction_hashes() {
     let method_name = "starknet_V0_6_getBlockWithTxHashes";
@@ -2499,7 +2525,7 @@ async fn test_get_events(
     let mut rng = get_rng();
 
     let mut event_index_to_event = HashMap::<EventIndex, Event>::new();
-    let mut parent_hash = BlockHash(felt!(GENESIS_HASH));
+    let mut parent_hash = BlockHash::default();
     let mut rw_txn = storage_writer.begin_rw_txn().unwrap();
     for (i, block_metadata) in block_metadatas.iter().enumerate() {
         let block_number = BlockNumber(i as u64);
@@ -3159,6 +3185,7 @@ async fn serialize_returns_valid_json() {
         get_test_pending_data(),
         get_test_pending_classes(),
         storage_reader,
+        BlockHash::default(),
         NODE_VERSION,
     )
     .await
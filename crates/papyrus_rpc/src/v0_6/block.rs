@@ -1,4 +1,6 @@
+use jsonrpsee::types::error::ErrorCode;
 use jsonrpsee::types::ErrorObjectOwned;
+use papyrus_storage::base_layer::BaseLayerStorageReader;
 use papyrus_storage::db::TransactionKind;
 use papyrus_storage::header::HeaderStorageReader;
 use papyrus_storage::{StorageError, StorageReader, StorageTxn};
@@ -119,6 +121,14 @@ pub(crate) fn get_accepted_block_number<Mode: TransactionKind>(
         BlockId::Tag(Tag::Latest | Tag::Pending) => {
             get_latest_block_number(txn)?.ok_or_else(|| ErrorObjectOwned::from(BLOCK_NOT_FOUND))?
         }
+        // The l1_accepted tag was added in v0_7.
+        BlockId::Tag(Tag::L1Accepted) => {
+            return Err(ErrorObjectOwned::owned(
+                ErrorCode::InvalidParams.code(),
+                "The l1_accepted block tag isn't supported in this version.",
+                None::<()>,
+            ));
+        }
     })
 }
 
@@ -1,5 +1,8 @@
 use jsonrpsee::types::ErrorObjectOwned;
 use serde::{Deserialize, Serialize};
+use starknet_api::core::ClassHash;
+
+use crate::api::BlockId;
 
 #[derive(Clone, Debug)]
 pub struct JsonRpcError<T: Serialize> {
@@ -51,6 +54,11 @@ pub const INVALID_CONTINUATION_TOKEN: JsonRpcError<String> = JsonRpcError {
 pub const TOO_MANY_KEYS_IN_FILTER: JsonRpcError<String> =
     JsonRpcError { code: 34, message: "Too many keys provided in a filter", data: None };
 
+// Not part of the spec. Returned when a request exceeds the node limits.
+pub fn execution_request_too_large(data: String) -> JsonRpcError<String> {
+    JsonRpcError { code: 101, message: "Execution request is too large", data: Some(data) }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 pub struct ContractError {
     pub revert_error: String,
@@ -72,6 +80,24 @@ impl From<TransactionExecutionError> for JsonRpcError<TransactionExecutionError>
         Self { code: 41, message: "Transaction execution error", data: Some(tx_execution_error) }
     }
 }
+
+/// Not part of the spec. Returned when executing a contract whose class isn't declared at the
+/// requested block.
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct ClassNotDeclaredError {
+    pub class_hash: ClassHash,
+    pub block_id: BlockId,
+}
+
+impl From<ClassNotDeclaredError> for JsonRpcError<ClassNotDeclaredError> {
+    fn from(class_not_declared_error: ClassNotDeclaredError) -> Self {
+        Self {
+            code: 102,
+            message: "Class not declared at the requested block",
+            data: Some(class_not_declared_error),
+        }
+    }
+}
 pub const CLASS_ALREADY_DECLARED: JsonRpcError<String> =
     JsonRpcError { code: 51, message: "Class already declared", data: None };
 
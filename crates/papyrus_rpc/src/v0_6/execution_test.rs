@@ -8,6 +8,7 @@ use assert_matches::assert_matches;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use indexmap::indexmap;
 use jsonrpsee::core::Error;
+use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
 use lazy_static::lazy_static;
 use papyrus_common::pending_classes::{ApiContractClass, PendingClasses, PendingClassesTrait};
@@ -56,9 +57,10 @@ use starknet_api::deprecated_contract_class::{
     EntryPointType,
 };
 use starknet_api::hash::StarkHash;
-use starknet_api::state::ThinStateDiff;
+use starknet_api::state::{ContractClass as StarknetApiContractClass, ThinStateDiff};
 use starknet_api::transaction::{
     Calldata,
+    DeclareTransactionV2,
     Fee,
     L1HandlerTransaction,
     TransactionHash,
@@ -90,6 +92,7 @@ use tokio::sync::RwLock;
 
 use super::api::api_impl::JsonRpcServerImpl;
 use super::api::{
+    broadcasted_to_executable_txn,
     decompress_program,
     FeeEstimate,
     SimulatedTransaction,
@@ -99,9 +102,15 @@ use super::api::{
 use super::broadcasted_transaction::{
     BroadcastedDeclareTransaction,
     BroadcastedDeclareV1Transaction,
+    BroadcastedDeclareV2Transaction,
     BroadcastedTransaction,
 };
-use super::error::{TransactionExecutionError, BLOCK_NOT_FOUND, CONTRACT_NOT_FOUND};
+use super::error::{
+    TransactionExecutionError,
+    BLOCK_NOT_FOUND,
+    COMPILATION_FAILED,
+    CONTRACT_NOT_FOUND,
+};
 use super::execution::{
     DeclareTransactionTrace,
     DeployAccountTransactionTrace,
@@ -136,6 +145,10 @@ use crate::test_utils::{
 };
 use crate::version_config::VERSION_0_6 as VERSION;
 
+// A Sierra class, shared with the tests of other crates. The path is relative to the resources
+// directory.
+const CLASS_JSON: &str = "../../papyrus_common/resources/class.json";
+
 lazy_static! {
     pub static ref GAS_PRICE: GasPricePerToken = GasPricePerToken{
         price_in_wei: GasPrice(100 * u128::pow(10, 9)),
@@ -1195,7 +1208,7 @@ fn broadcasted_to_executable_declare_v1() {
     let broadcasted_declare_v1 =
         BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V1(tx));
     assert_matches!(
-        broadcasted_declare_v1.try_into(),
+        to_executable(broadcasted_declare_v1),
         Ok(ExecutableTransactionInput::DeclareV1(_tx, _class, _abi_length, _only_query))
     );
 }
@@ -1260,7 +1273,7 @@ fn broadcasted_to_executable_deploy_account() {
         DeployAccountTransaction::get_test_instance(&mut rng),
     );
     assert_matches!(
-        broadcasted_deploy_account.try_into(),
+        to_executable(broadcasted_deploy_account),
         Ok(ExecutableTransactionInput::DeployAccount(_tx, _only_query))
     );
 }
@@ -1268,14 +1281,78 @@ fn broadcasted_to_executable_deploy_account() {
 #[test]
 fn broadcasted_to_executable_invoke() {
     let mut rng = get_rng();
-    let broadcasted_deploy_account =
-        BroadcastedTransaction::Invoke(InvokeTransaction::get_test_instance(&mut rng));
-    assert_matches!(
-        broadcasted_deploy_account.try_into(),
-        Ok(ExecutableTransactionInput::Invoke(_tx, _only_query))
+    let tx = InvokeTransaction::get_test_instance(&mut rng);
+    let broadcasted_invoke = BroadcastedTransaction::Invoke(tx.clone());
+    let executable = to_executable(broadcasted_invoke).unwrap();
+    assert_eq!(executable, ExecutableTransactionInput::Invoke(tx.into(), false));
+}
+
+#[test]
+fn broadcasted_to_executable_declare_v2() {
+    let mut rng = get_rng();
+    let contract_class =
+        serde_json::from_value::<StarknetApiContractClass>(read_json_file(CLASS_JSON)).unwrap();
+    // The hash of the class in class.json.
+    let class_hash =
+        class_hash!("0x29927c8af6bccf3f6fda035981e765a7bdbf18a2dc0d630494f8758aa908e2b");
+    let mut tx = BroadcastedDeclareV2Transaction::get_test_instance(&mut rng);
+    tx.contract_class = contract_class.clone().into();
+    let broadcasted_declare_v2 =
+        BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V2(tx.clone()));
+
+    let Ok(ExecutableTransactionInput::DeclareV2(
+        declare_tx,
+        casm,
+        sierra_program_length,
+        abi_length,
+        only_query,
+    )) = to_executable(broadcasted_declare_v2)
+    else {
+        panic!("Expected a DeclareV2 executable transaction.");
+    };
+    assert_eq!(
+        declare_tx,
+        DeclareTransactionV2 {
+            max_fee: tx.max_fee,
+            signature: tx.signature,
+            nonce: tx.nonce,
+            class_hash,
+            compiled_class_hash: tx.compiled_class_hash,
+            sender_address: tx.sender_address,
+        }
+    );
+    assert_eq!(sierra_program_length, contract_class.sierra_program.len());
+    assert_eq!(abi_length, contract_class.abi.len());
+    assert!(!only_query);
+    assert_eq!(
+        casm.entry_points_by_type.external.len(),
+        tx.contract_class.entry_points_by_type.external.len()
     );
 }
 
+#[test]
+fn broadcasted_to_executable_declare_v2_compilation_failure() {
+    let mut rng = get_rng();
+    let mut tx = BroadcastedDeclareV2Transaction::get_test_instance(&mut rng);
+    tx.contract_class =
+        serde_json::from_value::<StarknetApiContractClass>(read_json_file(CLASS_JSON))
+            .unwrap()
+            .into();
+    let broadcasted_declare_v2 =
+        BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V2(tx));
+
+    // The compiled class exceeds the max bytecode size.
+    let err = broadcasted_to_executable_txn(broadcasted_declare_v2, 1).unwrap_err();
+    assert_eq!(err.code(), COMPILATION_FAILED.code);
+}
+
+// Converts with the max bytecode size of the test config.
+fn to_executable(
+    tx: BroadcastedTransaction,
+) -> Result<ExecutableTransactionInput, ErrorObjectOwned> {
+    broadcasted_to_executable_txn(tx, get_test_rpc_config().execution_config.max_casm_bytecode_size)
+}
+
 #[test]
 fn get_decompressed_program() {
     let compressed = get_test_compressed_program();
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
@@ -12,9 +13,12 @@ use papyrus_execution::{
     execute_call,
     execution_utils,
     simulate_transactions as exec_simulate_transactions,
+    ClassCache,
     ExecutableTransactionInput,
     ExecutionConfig,
+    ExecutionInputs,
 };
+use papyrus_proc_macros::rpc_method_latency;
 use papyrus_storage::body::events::{EventIndex, EventsReader};
 use papyrus_storage::body::{BodyStorageReader, TransactionIndex};
 use papyrus_storage::compiled_class::CasmStorageReader;
@@ -23,7 +27,6 @@ use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::{StorageError, StorageReader, StorageTxn};
 use starknet_api::block::{BlockHash, BlockNumber, BlockStatus};
 use starknet_api::core::{ChainId, ClassHash, ContractAddress, GlobalRoot, Nonce};
-use starknet_api::hash::StarkHash;
 use starknet_api::state::{StateNumber, StorageKey};
 use starknet_api::transaction::{
     EventContent,
@@ -65,12 +68,15 @@ use super::super::broadcasted_transaction::{
     BroadcastedTransaction,
 };
 use super::super::error::{
+    execution_request_too_large,
     ContractError,
     JsonRpcError,
     TransactionExecutionError,
     BLOCK_NOT_FOUND,
     CLASS_HASH_NOT_FOUND,
+    COMPILED_CLASS_NOT_FOUND,
     CONTRACT_NOT_FOUND,
+    INVALID_CONTINUATION_TOKEN,
     INVALID_TRANSACTION_HASH,
     INVALID_TRANSACTION_INDEX,
     NO_BLOCKS,
@@ -79,12 +85,20 @@ use super::super::error::{
     TRANSACTION_HASH_NOT_FOUND,
 };
 use super::super::execution::TransactionTrace;
-use super::super::state::{AcceptedStateUpdate, PendingStateUpdate, StateUpdate};
+use super::super::state::{
+    AcceptedStateUpdate,
+    DeployedContract,
+    PendingStateUpdate,
+    StateUpdate,
+    ThinStateDiff,
+};
 use super::super::transaction::{
     get_block_tx_hashes_by_number,
     get_block_txs_by_number,
+    DeployAccountTransaction,
     Event,
     GeneralTransactionReceipt,
+    InvokeTransaction,
     L1HandlerMsgHash,
     L1L2MsgHash,
     MessageFromL1,
@@ -112,6 +126,7 @@ use super::super::write_api_result::{
     AddInvokeOkResult,
 };
 use super::{
+    broadcasted_to_executable_txns,
     execution_error_to_error_object_owned,
     stored_txn_to_executable_txn,
     BlockHashAndNumber,
@@ -119,6 +134,7 @@ use super::{
     CallRequest,
     CompiledContractClass,
     ContinuationToken,
+    DeployedContractsChunk,
     EventFilter,
     EventsChunk,
     GatewayContractClass,
@@ -129,7 +145,12 @@ use super::{
 };
 use crate::api::{BlockHashOrNumber, JsonRpcServerTrait, Tag};
 use crate::pending::client_pending_data_to_execution_pending_data;
-use crate::syncing_state::{get_last_synced_block, SyncStatus, SyncingState};
+use crate::syncing_state::{
+    get_last_synced_block,
+    get_pending_block_age_seconds,
+    SyncStatus,
+    SyncingState,
+};
 use crate::version_config::VERSION_0_7 as VERSION;
 use crate::{
     get_block_status,
@@ -137,11 +158,14 @@ use crate::{
     internal_server_error,
     verify_storage_scope,
     ContinuationTokenAsStruct,
-    GENESIS_HASH,
 };
 
 const DONT_IGNORE_L1_DA_MODE: bool = false;
 
+// The number of deployed contracts table entries read for a single chunk of deployed contracts,
+// so that requests on early blocks don't scan all the contracts deployed after them.
+const MAX_SCANNED_DEPLOYED_CONTRACTS_ENTRIES: usize = 10_000;
+
 // TODO(yael): implement address 0x1 as a const function in starknet_api.
 lazy_static! {
     pub static ref BLOCK_HASH_TABLE_ADDRESS: ContractAddress = ContractAddress::from(1_u8);
@@ -150,10 +174,14 @@ lazy_static! {
 /// Rpc server.
 pub struct JsonRpcServerImpl {
     pub chain_id: ChainId,
+    pub genesis_hash: BlockHash,
     pub execution_config: ExecutionConfig,
+    pub class_cache: ClassCache,
     pub storage_reader: StorageReader,
     pub max_events_chunk_size: usize,
     pub max_events_keys: usize,
+    pub max_execution_transactions: usize,
+    pub max_execution_calldata_length: usize,
     pub starting_block: BlockHashAndNumber,
     pub shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
     pub pending_data: Arc<RwLock<PendingData>>,
@@ -161,6 +189,7 @@ pub struct JsonRpcServerImpl {
     pub writer_client: Arc<dyn StarknetWriter>,
 }
 
+#[rpc_method_latency("V0_7")]
 #[async_trait]
 impl JsonRpcServer for JsonRpcServerImpl {
     #[instrument(skip(self), level = "debug", err, ret)]
@@ -327,7 +356,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         let maybe_pending_storage_diffs = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(
-                read_pending_data(&self.pending_data, &txn)
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash)
                     .await?
                     .state_update
                     .state_diff
@@ -385,7 +414,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         } else {
             // The transaction is not in any non-pending block. Search for it in the pending block
             // and if it's not found, return error.
-            let client_transaction = read_pending_data(&self.pending_data, &txn)
+            let client_transaction = read_pending_data(&self.pending_data, &txn, self.genesis_hash)
                 .await?
                 .block
                 .transactions()
@@ -412,31 +441,32 @@ impl JsonRpcServer for JsonRpcServerImpl {
         verify_storage_scope(&self.storage_reader)?;
 
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
-        let (starknet_api_transaction, transaction_hash) =
-            if let BlockId::Tag(Tag::Pending) = block_id {
-                let client_transaction = read_pending_data(&self.pending_data, &txn)
-                    .await?
-                    .block
-                    .transactions()
-                    .get(index.0)
-                    .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?
-                    .clone();
-                let transaction_hash = client_transaction.transaction_hash();
-                (client_transaction.try_into().map_err(internal_server_error)?, transaction_hash)
-            } else {
-                let block_number = get_accepted_block_number(&txn, block_id)?;
-
-                let tx_index = TransactionIndex(block_number, index);
-                let transaction = txn
-                    .get_transaction(tx_index)
-                    .map_err(internal_server_error)?
-                    .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?;
-                let transaction_hash = txn
-                    .get_transaction_hash_by_idx(&tx_index)
-                    .map_err(internal_server_error)?
-                    .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?;
-                (transaction, transaction_hash)
-            };
+        let (starknet_api_transaction, transaction_hash) = if let BlockId::Tag(Tag::Pending) =
+            block_id
+        {
+            let client_transaction = read_pending_data(&self.pending_data, &txn, self.genesis_hash)
+                .await?
+                .block
+                .transactions()
+                .get(index.0)
+                .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?
+                .clone();
+            let transaction_hash = client_transaction.transaction_hash();
+            (client_transaction.try_into().map_err(internal_server_error)?, transaction_hash)
+        } else {
+            let block_number = get_accepted_block_number(&txn, block_id)?;
+
+            let tx_index = TransactionIndex(block_number, index);
+            let transaction = txn
+                .get_transaction(tx_index)
+                .map_err(internal_server_error)?
+                .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?;
+            let transaction_hash = txn
+                .get_transaction_hash_by_idx(&tx_index)
+                .map_err(internal_server_error)?
+                .ok_or_else(|| ErrorObjectOwned::from(INVALID_TRANSACTION_INDEX))?;
+            (transaction, transaction_hash)
+        };
 
         Ok(TransactionWithHash {
             transaction: starknet_api_transaction.try_into()?,
@@ -450,8 +480,11 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
         if let BlockId::Tag(Tag::Pending) = block_id {
-            let transactions_len =
-                read_pending_data(&self.pending_data, &txn).await?.block.transactions().len();
+            let transactions_len = read_pending_data(&self.pending_data, &txn, self.genesis_hash)
+                .await?
+                .block
+                .transactions()
+                .len();
             Ok(transactions_len)
         } else {
             let block_number = get_accepted_block_number(&txn, block_id)?;
@@ -466,7 +499,8 @@ impl JsonRpcServer for JsonRpcServerImpl {
     async fn get_state_update(&self, block_id: BlockId) -> RpcResult<StateUpdate> {
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         if let BlockId::Tag(Tag::Pending) = block_id {
-            let state_update = read_pending_data(&self.pending_data, &txn).await?.state_update;
+            let state_update =
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?.state_update;
             return Ok(StateUpdate::PendingStateUpdate(PendingStateUpdate {
                 old_root: state_update.old_root,
                 state_diff: state_update.state_diff.into(),
@@ -485,26 +519,30 @@ impl JsonRpcServer for JsonRpcServerImpl {
             Ok(parent_block_number) => {
                 BlockHeader::from(get_block_header_by_number(&txn, parent_block_number)?).new_root
             }
-            Err(_) => GlobalRoot(StarkHash::from_hex_unchecked(GENESIS_HASH)),
+            Err(_) => GlobalRoot::default(),
         };
 
-        // Get the block state diff.
-        let mut thin_state_diff = txn
-            .get_state_diff(block_number)
-            .map_err(internal_server_error)?
-            .ok_or_else(|| ErrorObjectOwned::from(BLOCK_NOT_FOUND))?;
-        // Remove empty storage diffs. Some blocks contain empty storage diffs that must be kept for
-        // the computation of state diff commitment.
-        thin_state_diff.storage_diffs.retain(|_k, v| !v.is_empty());
-
         Ok(StateUpdate::AcceptedStateUpdate(AcceptedStateUpdate {
             block_hash: header.block_hash,
             new_root: header.new_root,
             old_root,
-            state_diff: thin_state_diff.into(),
+            state_diff: get_thin_state_diff_by_number(&txn, block_number)?,
         }))
     }
 
+    #[instrument(skip(self), level = "debug", err, ret)]
+    async fn get_thin_state_diff(&self, block_id: BlockId) -> RpcResult<ThinStateDiff> {
+        let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
+        if let BlockId::Tag(Tag::Pending) = block_id {
+            let state_update =
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?.state_update;
+            return Ok(state_update.state_diff.into());
+        }
+
+        let block_number = get_accepted_block_number(&txn, block_id)?;
+        get_thin_state_diff_by_number(&txn, block_number)
+    }
+
     async fn get_transaction_status(
         &self,
         transaction_hash: TransactionHash,
@@ -552,7 +590,8 @@ impl JsonRpcServer for JsonRpcServerImpl {
 
             // TODO(shahak): Consider cloning the transactions and the receipts in order to free
             // the lock sooner (Check which is better).
-            let pending_data = read_pending_data(&self.pending_data, &txn).await?;
+            let pending_data =
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?;
 
             let client_transaction_receipt = pending_data
                 .block
@@ -632,7 +671,10 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let maybe_pending_deployed_contracts_and_replaced_classes =
             if let BlockId::Tag(Tag::Pending) = block_id {
                 let pending_state_diff =
-                    read_pending_data(&self.pending_data, &txn).await?.state_update.state_diff;
+                    read_pending_data(&self.pending_data, &txn, self.genesis_hash)
+                        .await?
+                        .state_update
+                        .state_diff;
                 Some((pending_state_diff.deployed_contracts, pending_state_diff.replaced_classes))
             } else {
                 None
@@ -660,7 +702,13 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
         let maybe_pending_nonces = if let BlockId::Tag(Tag::Pending) = block_id {
-            Some(read_pending_data(&self.pending_data, &txn).await?.state_update.state_diff.nonces)
+            Some(
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash)
+                    .await?
+                    .state_update
+                    .state_diff
+                    .nonces,
+            )
         } else {
             None
         };
@@ -790,7 +838,8 @@ impl JsonRpcServer for JsonRpcServerImpl {
         }
 
         if include_pending_block {
-            let pending_block = read_pending_data(&self.pending_data, &txn).await?.block;
+            let pending_block =
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?.block;
             let pending_transaction_receipts = pending_block.transaction_receipts();
             // Extract the first transaction offset and event offset from the starting EventIndex.
             let (transaction_start, event_start) = if start_event_index.0.0 > latest_block_number {
@@ -864,10 +913,11 @@ impl JsonRpcServer for JsonRpcServerImpl {
 
     #[instrument(skip(self), level = "debug", err, ret)]
     async fn call(&self, request: CallRequest, block_id: BlockId) -> RpcResult<Vec<Felt>> {
+        self.verify_execution_request_size(1, request.calldata.0.len())?;
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         let maybe_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(client_pending_data_to_execution_pending_data(
-                read_pending_data(&self.pending_data, &txn).await?,
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?,
                 self.pending_classes.read().await.clone(),
             ))
         } else {
@@ -876,8 +926,9 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let block_number = get_accepted_block_number(&txn, block_id)?;
         let block_not_reverted_validator = BlockNotRevertedValidator::new(block_number, &txn)?;
         drop(txn);
-        let state_number = StateNumber::unchecked_right_after_block(block_number);
+        let execution_inputs = ExecutionInputs::at_block_end(block_number);
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -888,12 +939,14 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 reader,
                 maybe_pending_data,
                 &chain_id,
-                state_number,
-                block_number,
+                execution_inputs,
                 &contract_address_copy,
                 request.entry_point_selector,
                 request.calldata,
+                None,
+                None,
                 &execution_config,
+                &class_cache,
                 DONT_IGNORE_L1_DA_MODE,
             )
         })
@@ -967,28 +1020,35 @@ impl JsonRpcServer for JsonRpcServerImpl {
         block_id: BlockId,
     ) -> RpcResult<Vec<FeeEstimation>> {
         trace!("Estimating fee of transactions: {:#?}", transactions);
+        self.verify_execution_request_size(
+            transactions.len(),
+            transactions.iter().map(broadcasted_transaction_calldata_length).sum(),
+        )?;
         let validate = !simulation_flags.contains(&SimulationFlag::SkipValidate);
+        let executable_txns = broadcasted_to_executable_txns(
+            transactions,
+            self.execution_config.max_casm_bytecode_size,
+        )
+        .await?;
 
         let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
         let maybe_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(client_pending_data_to_execution_pending_data(
-                read_pending_data(&self.pending_data, &storage_txn).await?,
+                read_pending_data(&self.pending_data, &storage_txn, self.genesis_hash).await?,
                 self.pending_classes.read().await.clone(),
             ))
         } else {
             None
         };
 
-        let executable_txns =
-            transactions.into_iter().map(|tx| tx.try_into()).collect::<Result<_, _>>()?;
-
         let block_number = get_accepted_block_number(&storage_txn, block_id)?;
         let block_not_reverted_validator =
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
         drop(storage_txn);
-        let state_number = StateNumber::unchecked_right_after_block(block_number);
+        let execution_inputs = ExecutionInputs::at_block_end(block_number);
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -999,11 +1059,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 &chain_id,
                 reader,
                 maybe_pending_data,
-                state_number,
-                block_number,
+                execution_inputs,
                 &execution_config,
+                &class_cache,
                 validate,
                 DONT_IGNORE_L1_DA_MODE,
+                None,
             )
         })
         .await
@@ -1033,14 +1094,21 @@ impl JsonRpcServer for JsonRpcServerImpl {
         simulation_flags: Vec<SimulationFlag>,
     ) -> RpcResult<Vec<SimulatedTransaction>> {
         trace!("Simulating transactions: {:#?}", transactions);
-        let executable_txns =
-            transactions.into_iter().map(|tx| tx.try_into()).collect::<Result<_, _>>()?;
+        self.verify_execution_request_size(
+            transactions.len(),
+            transactions.iter().map(broadcasted_transaction_calldata_length).sum(),
+        )?;
+        let executable_txns = broadcasted_to_executable_txns(
+            transactions,
+            self.execution_config.max_casm_bytecode_size,
+        )
+        .await?;
 
         let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
         let maybe_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(client_pending_data_to_execution_pending_data(
-                read_pending_data(&self.pending_data, &storage_txn).await?,
+                read_pending_data(&self.pending_data, &storage_txn, self.genesis_hash).await?,
                 self.pending_classes.read().await.clone(),
             ))
         } else {
@@ -1053,6 +1121,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         drop(storage_txn);
         let state_number = StateNumber::unchecked_right_after_block(block_number);
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1070,9 +1139,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 state_number,
                 block_number,
                 &execution_config,
+                &class_cache,
                 charge_fee,
                 validate,
                 DONT_IGNORE_L1_DA_MODE,
+                None,
+                None,
             )
         })
         .await
@@ -1101,7 +1173,8 @@ impl JsonRpcServer for JsonRpcServerImpl {
     ) -> RpcResult<TransactionTrace> {
         let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
-        let pending_block = read_pending_data(&self.pending_data, &storage_txn).await?.block;
+        let pending_block =
+            read_pending_data(&self.pending_data, &storage_txn, self.genesis_hash).await?.block;
         // Search for the transaction inside the pending block.
         let (
             maybe_pending_data,
@@ -1204,6 +1277,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         drop(storage_txn);
 
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1218,9 +1292,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 state_number,
                 block_number,
                 &execution_config,
+                &class_cache,
                 true,
                 true,
                 DONT_IGNORE_L1_DA_MODE,
+                None,
+                None,
             )
         })
         .await
@@ -1242,7 +1319,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
 
         let maybe_client_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
-            Some(read_pending_data(&self.pending_data, &storage_txn).await?)
+            Some(read_pending_data(&self.pending_data, &storage_txn, self.genesis_hash).await?)
         } else {
             None
         };
@@ -1318,6 +1395,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         drop(storage_txn);
 
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1333,9 +1411,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 state_number,
                 block_number,
                 &execution_config,
+                &class_cache,
                 true,
                 true,
                 DONT_IGNORE_L1_DA_MODE,
+                None,
+                None,
             )
         })
         .await
@@ -1368,7 +1449,7 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         let maybe_pending_data = if let BlockId::Tag(Tag::Pending) = block_id {
             Some(client_pending_data_to_execution_pending_data(
-                read_pending_data(&self.pending_data, &storage_txn).await?,
+                read_pending_data(&self.pending_data, &storage_txn, self.genesis_hash).await?,
                 self.pending_classes.read().await.clone(),
             ))
         } else {
@@ -1385,8 +1466,9 @@ impl JsonRpcServer for JsonRpcServerImpl {
         let block_not_reverted_validator =
             BlockNotRevertedValidator::new(block_number, &storage_txn)?;
         drop(storage_txn);
-        let state_number = StateNumber::unchecked_right_after_block(block_number);
+        let execution_inputs = ExecutionInputs::at_block_end(block_number);
         let execution_config = self.execution_config;
+        let class_cache = self.class_cache.clone();
 
         let chain_id = self.chain_id.clone();
         let reader = self.storage_reader.clone();
@@ -1397,11 +1479,12 @@ impl JsonRpcServer for JsonRpcServerImpl {
                 &chain_id,
                 reader,
                 maybe_pending_data,
-                state_number,
-                block_number,
+                execution_inputs,
                 &execution_config,
+                &class_cache,
                 false,
                 DONT_IGNORE_L1_DA_MODE,
+                None,
             )
         })
         .await
@@ -1468,18 +1551,95 @@ impl JsonRpcServer for JsonRpcServerImpl {
             .ok_or_else(|| ErrorObjectOwned::from(CLASS_HASH_NOT_FOUND))?;
         Ok(CompiledContractClass::V0(deprecated_compiled_contract_class))
     }
+
+    #[instrument(skip(self), level = "debug", err)]
+    fn get_compiled_casm(&self, class_hash: ClassHash) -> RpcResult<CasmContractClass> {
+        self.storage_reader
+            .begin_ro_txn()
+            .map_err(internal_server_error)?
+            .get_casm(&class_hash)
+            .map_err(internal_server_error)?
+            .ok_or_else(|| ErrorObjectOwned::from(COMPILED_CLASS_NOT_FOUND))
+    }
+
+    #[instrument(skip(self), level = "debug", err)]
+    fn get_deployed_contracts(
+        &self,
+        block_id: BlockId,
+        chunk_size: usize,
+        continuation_token: Option<ContinuationToken>,
+    ) -> RpcResult<DeployedContractsChunk> {
+        // Deployed contracts chunks share the size limit of events chunks.
+        if chunk_size > self.max_events_chunk_size {
+            return Err(ErrorObjectOwned::from(PAGE_SIZE_TOO_BIG));
+        }
+
+        let storage_txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
+        let block_number = get_accepted_block_number(&storage_txn, block_id)?;
+        let state_number = StateNumber::right_after_block(block_number)
+            .ok_or_else(|| internal_server_error("Could not compute state number"))?;
+        // The continuation token is the address of the first contract of the next chunk.
+        let start_address = match continuation_token {
+            Some(token) => serde_json::from_str(&token.0)
+                .map_err(|_| ErrorObjectOwned::from(INVALID_CONTINUATION_TOKEN))?,
+            None => ContractAddress::default(),
+        };
+
+        let (deployed_contracts, next_address) = storage_txn
+            .get_state_reader()
+            .map_err(internal_server_error)?
+            .get_deployed_contracts_at(
+                state_number,
+                &start_address,
+                chunk_size,
+                MAX_SCANNED_DEPLOYED_CONTRACTS_ENTRIES,
+            )
+            .map_err(internal_server_error)?;
+        let continuation_token = next_address
+            .map(|next_address| serde_json::to_string(&next_address).map(ContinuationToken))
+            .transpose()
+            .map_err(internal_server_error)?;
+
+        Ok(DeployedContractsChunk {
+            deployed_contracts: deployed_contracts
+                .into_iter()
+                .map(|(address, class_hash)| DeployedContract { address, class_hash })
+                .collect(),
+            continuation_token,
+        })
+    }
+
+    #[instrument(skip(self), level = "debug", err)]
+    async fn get_pending_block_age(&self) -> RpcResult<Option<u64>> {
+        get_pending_block_age_seconds(self.storage_reader.clone(), &self.pending_data)
+            .await
+            .map_err(internal_server_error)
+    }
+}
+
+fn get_thin_state_diff_by_number<Mode: TransactionKind>(
+    txn: &StorageTxn<'_, Mode>,
+    block_number: BlockNumber,
+) -> RpcResult<ThinStateDiff> {
+    let mut thin_state_diff = txn
+        .get_state_diff(block_number)
+        .map_err(internal_server_error)?
+        .ok_or_else(|| ErrorObjectOwned::from(BLOCK_NOT_FOUND))?;
+    // Remove empty storage diffs. Some blocks contain empty storage diffs that must be kept for
+    // the computation of state diff commitment.
+    thin_state_diff.storage_diffs.retain(|_k, v| !v.is_empty());
+    Ok(thin_state_diff.into())
 }
 
 async fn read_pending_data<Mode: TransactionKind>(
     pending_data: &Arc<RwLock<PendingData>>,
     txn: &StorageTxn<'_, Mode>,
+    genesis_hash: BlockHash,
 ) -> RpcResult<PendingData> {
     let latest_header = match get_latest_block_number(txn)? {
         Some(latest_block_number) => get_block_header_by_number(txn, latest_block_number)?,
-        None => starknet_api::block::BlockHeader {
-            parent_hash: BlockHash(StarkHash::from_hex_unchecked(GENESIS_HASH)),
-            ..Default::default()
-        },
+        // Before the first block, the pending block is on top of the genesis hash.
+        None => starknet_api::block::BlockHeader { block_hash: genesis_hash, ..Default::default() },
     };
     let pending_data = &pending_data.read().await;
     if pending_data.block.parent_block_hash() == latest_header.block_hash {
@@ -1514,7 +1674,8 @@ impl JsonRpcServerImpl {
         verify_storage_scope(&self.storage_reader)?;
         let txn = self.storage_reader.begin_ro_txn().map_err(internal_server_error)?;
         if let BlockId::Tag(Tag::Pending) = block_id {
-            let pending_data = read_pending_data(&self.pending_data, &txn).await?;
+            let pending_data =
+                read_pending_data(&self.pending_data, &txn, self.genesis_hash).await?;
             let block = &pending_data.block;
             let pending_block_header = PendingBlockHeader {
                 parent_hash: block.parent_block_hash(),
@@ -1618,13 +1779,55 @@ fn do_event_keys_match_filter(event_content: &EventContent, filter: &EventFilter
     })
 }
 
+fn broadcasted_transaction_calldata_length(tx: &BroadcastedTransaction) -> usize {
+    match tx {
+        BroadcastedTransaction::Declare(_) => 0,
+        BroadcastedTransaction::DeployAccount(DeployAccountTransaction::Version1(tx)) => {
+            tx.constructor_calldata.0.len()
+        }
+        BroadcastedTransaction::DeployAccount(DeployAccountTransaction::Version3(tx)) => {
+            tx.constructor_calldata.0.len()
+        }
+        BroadcastedTransaction::Invoke(InvokeTransaction::Version0(tx)) => tx.calldata.0.len(),
+        BroadcastedTransaction::Invoke(InvokeTransaction::Version1(tx)) => tx.calldata.0.len(),
+        BroadcastedTransaction::Invoke(InvokeTransaction::Version3(tx)) => tx.calldata.0.len(),
+    }
+}
+
+impl JsonRpcServerImpl {
+    // Rejects execution requests that exceed the configured limits before executing them.
+    fn verify_execution_request_size(
+        &self,
+        n_transactions: usize,
+        calldata_length: usize,
+    ) -> RpcResult<()> {
+        if n_transactions > self.max_execution_transactions {
+            return Err(ErrorObjectOwned::from(execution_request_too_large(format!(
+                "Got {n_transactions} transactions, the maximum is {}.",
+                self.max_execution_transactions
+            ))));
+        }
+        if calldata_length > self.max_execution_calldata_length {
+            return Err(ErrorObjectOwned::from(execution_request_too_large(format!(
+                "Got a total calldata length of {calldata_length}, the maximum is {}.",
+                self.max_execution_calldata_length
+            ))));
+        }
+        Ok(())
+    }
+}
+
 impl JsonRpcServerTrait for JsonRpcServerImpl {
     fn new(
         chain_id: ChainId,
+        genesis_hash: BlockHash,
         execution_config: ExecutionConfig,
+        class_cache: ClassCache,
         storage_reader: StorageReader,
         max_events_chunk_size: usize,
         max_events_keys: usize,
+        max_execution_transactions: usize,
+        max_execution_calldata_length: usize,
         starting_block: BlockHashAndNumber,
         shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
         pending_data: Arc<RwLock<PendingData>>,
@@ -1633,10 +1836,14 @@ impl JsonRpcServerTrait for JsonRpcServerImpl {
     ) -> Self {
         Self {
             chain_id,
+            genesis_hash,
             execution_config,
+            class_cache,
             storage_reader,
             max_events_chunk_size,
             max_events_keys,
+            max_execution_transactions,
+            max_execution_calldata_length,
             starting_block,
             shared_highest_block,
             pending_data,
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use std::iter;
 use std::net::SocketAddr;
 use std::ops::Index;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use assert_matches::assert_matches;
 use async_trait::async_trait;
@@ -32,6 +33,7 @@ use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{
     Block as StarknetApiBlock,
+    BlockBody,
     BlockHash,
     BlockHeader,
     BlockNumber,
@@ -122,6 +124,7 @@ use super::super::error::{
     BLOCK_NOT_FOUND,
     CLASS_HASH_NOT_FOUND,
     COMPILATION_FAILED,
+    COMPILED_CLASS_NOT_FOUND,
     CONTRACT_NOT_FOUND,
     DUPLICATE_TX,
     INVALID_CONTINUATION_TOKEN,
@@ -171,7 +174,7 @@ use super::super::write_api_result::{
     AddInvokeOkResult,
 };
 use super::api_impl::{JsonRpcServerImpl, BLOCK_HASH_TABLE_ADDRESS};
-use super::{ContinuationToken, EventFilter, GatewayContractClass};
+use super::{ContinuationToken, DeployedContractsChunk, EventFilter, GatewayContractClass};
 use crate::api::{BlockHashOrNumber, BlockId, Tag};
 use crate::syncing_state::SyncStatus;
 use crate::test_utils::{
@@ -187,6 +190,7 @@ use crate::test_utils::{
     get_test_rpc_config,
     get_test_rpc_server_and_storage_writer,
     get_test_rpc_server_and_storage_writer_from_params,
+    get_test_rpc_server_and_storage_writer_with_genesis_hash,
     method_name_to_spec_method_name,
     raw_call,
     validate_schema,
@@ -199,7 +203,6 @@ use crate::{
     internal_server_error_with_msg,
     run_server,
     ContinuationTokenAsStruct,
-    GENESIS_HASH,
 };
 
 const NODE_VERSION: &str = "NODE VERSION";
@@ -387,6 +390,44 @@ async fn syncing() {
     .await;
 }
 
+#[tokio::test]
+async fn get_pending_block_age() {
+    const API_METHOD_NAME: &str = "starknet_V0_7_getPendingBlockAge";
+    const PENDING_BLOCK_AGE_SECONDS: u64 = 100;
+
+    let pending_data = get_test_pending_data();
+    let (module, mut storage_writer) = get_test_rpc_server_and_storage_writer_from_params::<
+        JsonRpcServerImpl,
+    >(None, None, Some(pending_data.clone()), None, None);
+
+    // No blocks in storage.
+    let res = module.call::<_, Option<u64>>(API_METHOD_NAME, Vec::<u8>::new()).await.unwrap();
+    assert_eq!(res, None);
+
+    let header = BlockHeader { block_hash: BlockHash(felt!("0x1")), ..Default::default() };
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(header.block_number, &header)
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    // The pending data isn't on top of the latest block.
+    let res = module.call::<_, Option<u64>>(API_METHOD_NAME, Vec::<u8>::new()).await.unwrap();
+    assert_eq!(res, None);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    *pending_data.write().await.block.parent_block_hash_mutable() = header.block_hash;
+    *pending_data.write().await.block.timestamp_mutable() =
+        BlockTimestamp(now - PENDING_BLOCK_AGE_SECONDS);
+    let pending_block_age_seconds =
+        module.call::<_, Option<u64>>(API_METHOD_NAME, Vec::<u8>::new()).await.unwrap().unwrap();
+    // Allow for time passing during the test.
+    assert!((PENDING_BLOCK_AGE_SECONDS..PENDING_BLOCK_AGE_SECONDS + 10)
+        .contains(&pending_block_age_seconds));
+}
+
 #[tokio::test]
 async fn get_block_transaction_count() {
     let method_name = "starknet_V0_7_getBlockTransactionCount";
@@ -843,6 +884,49 @@ async fn get_block_w_full_transactions_and_receipts() {
     };
 }
 
+#[tokio::test]
+async fn get_block_l1_accepted_tag() {
+    let method_name = "starknet_V0_7_getBlockWithTxHashes";
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+
+    let mut txn = storage_writer.begin_rw_txn().unwrap();
+    for i in 0..3 {
+        let block_number = BlockNumber(i);
+        let header =
+            BlockHeader { block_hash: BlockHash(felt!(i + 1)), block_number, ..Default::default() };
+        txn = txn
+            .append_header(block_number, &header)
+            .unwrap()
+            .append_body(block_number, BlockBody::default())
+            .unwrap()
+            .append_state_diff(block_number, starknet_api::state::ThinStateDiff::default())
+            .unwrap();
+    }
+    txn.commit().unwrap();
+
+    // No block is accepted on L1 yet.
+    let err =
+        module.call::<_, Block>(method_name, [BlockId::Tag(Tag::L1Accepted)]).await.unwrap_err();
+    assert_matches!(err, Error::Call(err) if err == BLOCK_NOT_FOUND.into());
+
+    // Blocks 0 and 1 are accepted on L1.
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .update_base_layer_block_marker(&BlockNumber(2))
+        .unwrap()
+        .commit()
+        .unwrap();
+    let block =
+        module.call::<_, Block>(method_name, [BlockId::Tag(Tag::L1Accepted)]).await.unwrap();
+    let GeneralBlockHeader::BlockHeader(block_header) = block.header else {
+        panic!("Unexpected block_header type. Expected BlockHeader.");
+    };
+    assert_eq!(block_header.block_number, BlockNumber(1));
+    assert_eq!(block.status, Some(BlockStatus::AcceptedOnL1));
+}
+
 #[tokio::test]
 async fn get_block_w_transaction_hashes() {
     let method_name = "starknet_V0_7_getBlockWithTxHashes";
@@ -1021,6 +1105,38 @@ async fn get_block_w_transaction_hashes() {
     };
 }
 
+#[tokio::test]
+async fn get_pending_block_on_top_of_genesis_hash() {
+    let method_name = "starknet_V0_7_getBlockWithTxHashes";
+    let genesis_hash = BlockHash(felt!("0x1234"));
+    let timestamp = BlockTimestamp(5678);
+    let pending_data = get_test_pending_data();
+    let (module, _storage_writer) = get_test_rpc_server_and_storage_writer_with_genesis_hash::<
+        JsonRpcServerImpl,
+    >(genesis_hash, pending_data.clone());
+    *pending_data.write().await.block.parent_block_hash_mutable() = genesis_hash;
+    *pending_data.write().await.block.timestamp_mutable() = timestamp;
+
+    // Before the first block, the pending data on top of the genesis hash is returned.
+    let res_block =
+        module.call::<_, Block>(method_name, [BlockId::Tag(Tag::Pending)]).await.unwrap();
+    let GeneralBlockHeader::PendingBlockHeader(pending_block_header) = res_block.header else {
+        panic!("Unexpected block_header type. Expected PendingBlockHeader.")
+    };
+    assert_eq!(pending_block_header.parent_hash, genesis_hash);
+    assert_eq!(pending_block_header.timestamp, timestamp);
+
+    // Pending data on top of another hash is outdated.
+    *pending_data.write().await.block.parent_block_hash_mutable() = BlockHash::default();
+    let res_block =
+        module.call::<_, Block>(method_name, [BlockId::Tag(Tag::Pending)]).await.unwrap();
+    let GeneralBlockHeader::PendingBlockHeader(pending_block_header) = res_block.header else {
+        panic!("Unexpected block_header type. Expected PendingBlockHeader.")
+    };
+    assert_eq!(pending_block_header.parent_hash, genesis_hash);
+    assert_eq!(pending_block_header.timestamp, BlockTimestamp::default());
+}
+
 #[tokio::test]
 async fn get_class() {
     let method_name = "starknet_V0_7_getClass";
@@ -2389,6 +2505,66 @@ async fn get_transaction_by_block_id_and_index() {
     .await;
 }
 
+#[tokio::test]
+async fn get_thin_state_diff() {
+    let method_name = "starknet_V0_7_getThinStateDiff";
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    let parent_header = BlockHeader::default();
+    let header = BlockHeader {
+        block_hash: BlockHash(felt!("0x1")),
+        block_number: BlockNumber(1),
+        parent_hash: parent_header.block_hash,
+        ..BlockHeader::default()
+    };
+    let diff = starknet_api::state::ThinStateDiff::from(get_test_state_diff());
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(parent_header.block_number, &parent_header)
+        .unwrap()
+        .append_state_diff(
+            parent_header.block_number,
+            starknet_api::state::ThinStateDiff::default(),
+        )
+        .unwrap()
+        .append_header(header.block_number, &header)
+        .unwrap()
+        .append_state_diff(header.block_number, diff)
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    // The thin state diff is the state diff of the full state update.
+    let block_id = BlockId::HashOrNumber(BlockHashOrNumber::Number(header.block_number));
+    let StateUpdate::AcceptedStateUpdate(state_update) =
+        module.call::<_, StateUpdate>("starknet_V0_7_getStateUpdate", [block_id]).await.unwrap()
+    else {
+        panic!("Expected an accepted state update");
+    };
+    let res = module.call::<_, ThinStateDiff>(method_name, [block_id]).await.unwrap();
+    assert_eq!(res, state_update.state_diff);
+
+    let res = module
+        .call::<_, ThinStateDiff>(
+            method_name,
+            [BlockId::HashOrNumber(BlockHashOrNumber::Hash(header.block_hash))],
+        )
+        .await
+        .unwrap();
+    assert_eq!(res, state_update.state_diff);
+
+    // Ask for an invalid block.
+    let err = module
+        .call::<_, ThinStateDiff>(
+            method_name,
+            [BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(2)))],
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(err, Error::Call(err) if err == BLOCK_NOT_FOUND.into());
+}
+
 #[tokio::test]
 async fn get_state_update() {
     let method_name = "starknet_V0_7_getStateUpdate";
@@ -2699,7 +2875,7 @@ async fn test_get_events(
     let mut rng = get_rng();
 
     let mut event_index_to_event = HashMap::<EventIndex, Event>::new();
-    let mut parent_hash = BlockHash(felt!(GENESIS_HASH));
+    let mut parent_hash = BlockHash::default();
     let mut rw_txn = storage_writer.begin_rw_txn().unwrap();
     for (i, block_metadata) in block_metadatas.iter().enumerate() {
         let block_number = BlockNumber(i as u64);
@@ -3359,6 +3535,7 @@ async fn serialize_returns_valid_json() {
         get_test_pending_data(),
         get_test_pending_classes(),
         storage_reader,
+        BlockHash::default(),
         NODE_VERSION,
     )
     .await
@@ -3632,6 +3809,95 @@ async fn get_compiled_contract_class() {
     assert_matches!(err, Error::Call(err) if err == CLASS_HASH_NOT_FOUND.into());
 }
 
+#[tokio::test]
+async fn get_compiled_casm() {
+    let class_hash = ClassHash(Felt::ONE);
+    let missing_class_hash = ClassHash(Felt::TWO);
+
+    let method_name = "starknet_V0_7_getCompiledCasm";
+    let (module, mut storage_writer) = get_test_rpc_server_and_storage_writer_from_params::<
+        JsonRpcServerImpl,
+    >(None, None, None, None, None);
+    let casm = CasmContractClass::get_test_instance(&mut get_rng());
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_casm(&class_hash, &casm)
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let res = module.call::<_, CasmContractClass>(method_name, [class_hash]).await.unwrap();
+    assert_eq!(res, casm);
+
+    let err =
+        module.call::<_, CasmContractClass>(method_name, [missing_class_hash]).await.unwrap_err();
+    assert_matches!(err, Error::Call(err) if err == COMPILED_CLASS_NOT_FOUND.into());
+}
+
+#[tokio::test]
+async fn get_deployed_contracts() {
+    const NUM_CONTRACTS: u64 = 5;
+    const CHUNK_SIZE: usize = 2;
+    let method_name = "starknet_V0_7_getDeployedContracts";
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+
+    let deployed_contracts = (0..NUM_CONTRACTS)
+        .map(|i| (ContractAddress::from(i), ClassHash(Felt::from(i + 100))))
+        .collect::<IndexMap<_, _>>();
+    let header = BlockHeader::default();
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(header.block_number, &header)
+        .unwrap()
+        .append_state_diff(
+            header.block_number,
+            starknet_api::state::ThinStateDiff {
+                deployed_contracts: deployed_contracts.clone(),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    // Page through all the deployed contracts.
+    let block_id = BlockId::HashOrNumber(BlockHashOrNumber::Number(header.block_number));
+    let mut continuation_token = None;
+    let mut paged_contracts = Vec::new();
+    loop {
+        let chunk = module
+            .call::<_, DeployedContractsChunk>(
+                method_name,
+                (block_id, CHUNK_SIZE, continuation_token.clone()),
+            )
+            .await
+            .unwrap();
+        assert!(chunk.deployed_contracts.len() <= CHUNK_SIZE);
+        paged_contracts.extend(chunk.deployed_contracts);
+        continuation_token = chunk.continuation_token;
+        if continuation_token.is_none() {
+            break;
+        }
+    }
+    let expected_contracts = deployed_contracts
+        .into_iter()
+        .map(|(address, class_hash)| DeployedContract { address, class_hash })
+        .collect::<Vec<_>>();
+    assert_eq!(paged_contracts, expected_contracts);
+
+    let err = module
+        .call::<_, DeployedContractsChunk>(
+            method_name,
+            (block_id, CHUNK_SIZE, Some(ContinuationToken("invalid".to_string()))),
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(err, Error::Call(err) if err == INVALID_CONTINUATION_TOKEN.into());
+}
+
 #[async_trait]
 trait AddTransactionTest
 where
@@ -1,4 +1,5 @@
 use jsonrpsee::types::ErrorObjectOwned;
+use papyrus_storage::base_layer::BaseLayerStorageReader;
 use papyrus_storage::db::TransactionKind;
 use papyrus_storage::header::HeaderStorageReader;
 use papyrus_storage::{StorageError, StorageReader, StorageTxn};
@@ -129,6 +130,22 @@ pub(crate) fn get_accepted_block_number<Mode: TransactionKind>(
         BlockId::Tag(Tag::Latest | Tag::Pending) => {
             get_latest_block_number(txn)?.ok_or_else(|| ErrorObjectOwned::from(BLOCK_NOT_FOUND))?
         }
+        BlockId::Tag(Tag::L1Accepted) => {
+            // The base layer marker is the first block that isn't accepted on L1.
+            let block_number = txn
+                .get_base_layer_block_marker()
+                .map_err(internal_server_error)?
+                .prev()
+                .ok_or_else(|| ErrorObjectOwned::from(BLOCK_NOT_FOUND))?;
+
+            // Check that the block has state diff.
+            let last_block_number = get_latest_block_number(txn)?
+                .ok_or_else(|| ErrorObjectOwned::from(BLOCK_NOT_FOUND))?;
+            if block_number > last_block_number {
+                return Err(ErrorObjectOwned::from(BLOCK_NOT_FOUND));
+            }
+            block_number
+        }
     })
 }
 
@@ -7,6 +7,7 @@ use assert_matches::assert_matches;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use indexmap::indexmap;
 use jsonrpsee::core::Error;
+use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
 use lazy_static::lazy_static;
 use papyrus_common::pending_classes::{ApiContractClass, PendingClasses, PendingClassesTrait};
@@ -60,9 +61,14 @@ use starknet_api::deprecated_contract_class::{
     EntryPointType,
 };
 use starknet_api::hash::StarkHash;
-use starknet_api::state::{StorageKey, ThinStateDiff as StarknetApiStateDiff};
+use starknet_api::state::{
+    ContractClass as StarknetApiContractClass,
+    StorageKey,
+    ThinStateDiff as StarknetApiStateDiff,
+};
 use starknet_api::transaction::{
     Calldata,
+    DeclareTransactionV2,
     Fee,
     L1HandlerTransaction,
     TransactionHash,
@@ -94,6 +100,7 @@ use tokio::sync::RwLock;
 
 use super::api::api_impl::JsonRpcServerImpl;
 use super::api::{
+    broadcasted_to_executable_txn,
     decompress_program,
     SimulatedTransaction,
     SimulationFlag,
@@ -102,9 +109,18 @@ use super::api::{
 use super::broadcasted_transaction::{
     BroadcastedDeclareTransaction,
     BroadcastedDeclareV1Transaction,
+    BroadcastedDeclareV2Transaction,
     BroadcastedTransaction,
 };
-use super::error::{TransactionExecutionError, BLOCK_NOT_FOUND, CONTRACT_NOT_FOUND};
+use super::error::{
+    execution_request_too_large,
+    ClassNotDeclaredError,
+    JsonRpcError,
+    TransactionExecutionError,
+    BLOCK_NOT_FOUND,
+    COMPILATION_FAILED,
+    CONTRACT_NOT_FOUND,
+};
 use super::execution::{
     DeclareTransactionTrace,
     DeployAccountTransactionTrace,
@@ -148,6 +164,10 @@ use crate::test_utils::{
 };
 use crate::version_config::VERSION_0_7 as VERSION;
 
+// A Sierra class, shared with the tests of other crates. The path is relative to the resources
+// directory.
+const CLASS_JSON: &str = "../../papyrus_common/resources/class.json";
+
 lazy_static! {
     pub static ref GAS_PRICE: GasPricePerToken = GasPricePerToken{
         price_in_wei: GasPrice(100 * u128::pow(10, 9)),
@@ -534,6 +554,108 @@ async fn pending_call_estimate_fee() {
     // calculated inside the hash.
 }
 
+#[tokio::test]
+async fn call_class_not_declared() {
+    let (module, mut storage_writer) =
+        get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+    let class = serde_json::from_value::<SN_API_DeprecatedContractClass>(read_json_file(
+        "deprecated_class.json",
+    ))
+    .unwrap();
+    let class_hash = class_hash!("0x1");
+
+    // The contract is deployed in block 0, but its class is declared only in block 1.
+    storage_writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(
+            BlockNumber(0),
+            &BlockHeader {
+                l1_gas_price: *GAS_PRICE,
+                l1_data_gas_price: *DATA_GAS_PRICE,
+                sequencer: *SEQUENCER_ADDRESS,
+                timestamp: *BLOCK_TIMESTAMP,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .append_body(BlockNumber(0), BlockBody::default())
+        .unwrap()
+        .append_state_diff(
+            BlockNumber(0),
+            StarknetApiStateDiff {
+                deployed_contracts: indexmap!(*DEPRECATED_CONTRACT_ADDRESS => class_hash),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .append_classes(BlockNumber(0), &[], &[])
+        .unwrap()
+        .append_header(
+            BlockNumber(1),
+            &BlockHeader {
+                l1_gas_price: *GAS_PRICE,
+                l1_data_gas_price: *DATA_GAS_PRICE,
+                sequencer: *SEQUENCER_ADDRESS,
+                timestamp: *BLOCK_TIMESTAMP,
+                block_hash: BlockHash(felt!("0x1")),
+                block_number: BlockNumber(1),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .append_body(BlockNumber(1), BlockBody::default())
+        .unwrap()
+        .append_state_diff(
+            BlockNumber(1),
+            StarknetApiStateDiff {
+                deprecated_declared_classes: vec![class_hash],
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .append_classes(BlockNumber(1), &[], &[(class_hash, &class)])
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let block_id = BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(0)));
+    let err = module
+        .call::<_, Vec<Felt>>(
+            "starknet_V0_7_call",
+            (
+                CallRequest {
+                    contract_address: *DEPRECATED_CONTRACT_ADDRESS,
+                    entry_point_selector: selector_from_name("without_arg"),
+                    calldata: calldata![],
+                },
+                block_id,
+            ),
+        )
+        .await
+        .unwrap_err();
+
+    let expected_err: JsonRpcError<ClassNotDeclaredError> =
+        ClassNotDeclaredError { class_hash, block_id }.into();
+    assert_matches!(err, Error::Call(err) if err == expected_err.into());
+
+    // After the class is declared, the call succeeds.
+    module
+        .call::<_, Vec<Felt>>(
+            "starknet_V0_7_call",
+            (
+                CallRequest {
+                    contract_address: *DEPRECATED_CONTRACT_ADDRESS,
+                    entry_point_selector: selector_from_name("without_arg"),
+                    calldata: calldata![],
+                },
+                BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(1))),
+            ),
+        )
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn call_simulate() {
     let (module, storage_writer) = get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
@@ -744,6 +866,39 @@ async fn call_simulate_skip_fee_charge() {
     assert_matches!(invoke_trace.fee_transfer_invocation, None);
 }
 
+#[tokio::test]
+async fn call_simulate_too_many_transactions() {
+    let (module, storage_writer) = get_test_rpc_server_and_storage_writer::<JsonRpcServerImpl>();
+
+    prepare_storage_for_execution(storage_writer);
+
+    let max_transactions = get_test_rpc_config().max_execution_transactions;
+    let invoke = BroadcastedTransaction::Invoke(InvokeTransaction::Version1(InvokeTransactionV1 {
+        max_fee: Fee(1000000 * GAS_PRICE.price_in_wei.0),
+        version: TransactionVersion1::Version1,
+        sender_address: *ACCOUNT_ADDRESS,
+        ..Default::default()
+    }));
+
+    let err = module
+        .call::<_, Vec<SimulatedTransaction>>(
+            "starknet_V0_7_simulateTransactions",
+            (
+                BlockId::HashOrNumber(BlockHashOrNumber::Number(BlockNumber(0))),
+                vec![invoke; max_transactions + 1],
+                Vec::<SimulationFlag>::new(),
+            ),
+        )
+        .await
+        .unwrap_err();
+
+    let expected_err = execution_request_too_large(format!(
+        "Got {} transactions, the maximum is {max_transactions}.",
+        max_transactions + 1
+    ));
+    assert_matches!(err, Error::Call(err) if err == expected_err.into());
+}
+
 // TODO(shahak): Add test for trace_transaction that doesn't depend on trace_block_transactions
 #[tokio::test]
 async fn trace_block_transactions_regular_and_pending() {
@@ -1234,7 +1389,7 @@ fn broadcasted_to_executable_declare_v1() {
     let broadcasted_declare_v1 =
         BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V1(tx));
     assert_matches!(
-        broadcasted_declare_v1.try_into(),
+        to_executable(broadcasted_declare_v1),
         Ok(ExecutableTransactionInput::DeclareV1(_tx, _class, _abi_length, _only_query))
     );
 }
@@ -1299,7 +1454,7 @@ fn broadcasted_to_executable_deploy_account() {
         DeployAccountTransaction::get_test_instance(&mut rng),
     );
     assert_matches!(
-        broadcasted_deploy_account.try_into(),
+        to_executable(broadcasted_deploy_account),
         Ok(ExecutableTransactionInput::DeployAccount(_tx, _only_query))
     );
 }
@@ -1307,12 +1462,76 @@ fn broadcasted_to_executable_deploy_account() {
 #[test]
 fn broadcasted_to_executable_invoke() {
     let mut rng = get_rng();
-    let broadcasted_deploy_account =
-        BroadcastedTransaction::Invoke(InvokeTransaction::get_test_instance(&mut rng));
-    assert_matches!(
-        broadcasted_deploy_account.try_into(),
-        Ok(ExecutableTransactionInput::Invoke(_tx, _only_query))
+    let tx = InvokeTransaction::get_test_instance(&mut rng);
+    let broadcasted_invoke = BroadcastedTransaction::Invoke(tx.clone());
+    let executable = to_executable(broadcasted_invoke).unwrap();
+    assert_eq!(executable, ExecutableTransactionInput::Invoke(tx.into(), false));
+}
+
+#[test]
+fn broadcasted_to_executable_declare_v2() {
+    let mut rng = get_rng();
+    let contract_class =
+        serde_json::from_value::<StarknetApiContractClass>(read_json_file(CLASS_JSON)).unwrap();
+    // The hash of the class in class.json.
+    let class_hash =
+        class_hash!("0x29927c8af6bccf3f6fda035981e765a7bdbf18a2dc0d630494f8758aa908e2b");
+    let mut tx = BroadcastedDeclareV2Transaction::get_test_instance(&mut rng);
+    tx.contract_class = contract_class.clone().into();
+    let broadcasted_declare_v2 =
+        BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V2(tx.clone()));
+
+    let Ok(ExecutableTransactionInput::DeclareV2(
+        declare_tx,
+        casm,
+        sierra_program_length,
+        abi_length,
+        only_query,
+    )) = to_executable(broadcasted_declare_v2)
+    else {
+        panic!("Expected a DeclareV2 executable transaction.");
+    };
+    assert_eq!(
+        declare_tx,
+        DeclareTransactionV2 {
+            max_fee: tx.max_fee,
+            signature: tx.signature,
+            nonce: tx.nonce,
+            class_hash,
+            compiled_class_hash: tx.compiled_class_hash,
+            sender_address: tx.sender_address,
+        }
     );
+    assert_eq!(sierra_program_length, contract_class.sierra_program.len());
+    assert_eq!(abi_length, contract_class.abi.len());
+    assert!(!only_query);
+    assert_eq!(
+        casm.entry_points_by_type.external.len(),
+        tx.contract_class.entry_points_by_type.external.len()
+    );
+}
+
+#[test]
+fn broadcasted_to_executable_declare_v2_compilation_failure() {
+    let mut rng = get_rng();
+    let mut tx = BroadcastedDeclareV2Transaction::get_test_instance(&mut rng);
+    tx.contract_class =
+        serde_json::from_value::<StarknetApiContractClass>(read_json_file(CLASS_JSON))
+            .unwrap()
+            .into();
+    let broadcasted_declare_v2 =
+        BroadcastedTransaction::Declare(BroadcastedDeclareTransaction::V2(tx));
+
+    // The compiled class exceeds the max bytecode size.
+    let err = broadcasted_to_executable_txn(broadcasted_declare_v2, 1).unwrap_err();
+    assert_eq!(err.code(), COMPILATION_FAILED.code);
+}
+
+// Converts with the max bytecode size of the test config.
+fn to_executable(
+    tx: BroadcastedTransaction,
+) -> Result<ExecutableTransactionInput, ErrorObjectOwned> {
+    broadcasted_to_executable_txn(tx, get_test_rpc_config().execution_config.max_casm_bytecode_size)
 }
 
 #[test]
@@ -39,9 +39,11 @@
 #[path = "base_layer_test.rs"]
 mod base_layer_test;
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use starknet_api::block::BlockNumber;
 
-use crate::db::table_types::Table;
+use crate::db::table_types::{NoValue, Table};
 use crate::db::{TransactionKind, RW};
 use crate::{MarkerKind, StorageResult, StorageTxn};
 
@@ -49,6 +51,12 @@ use crate::{MarkerKind, StorageResult, StorageTxn};
 pub trait BaseLayerStorageReader {
     /// The block number marker is the first block number that doesn't exist yet in the base layer.
     fn get_base_layer_block_marker(&self) -> StorageResult<BlockNumber>;
+
+    /// Returns the base layer block marker and the time it was last updated at, if it was ever
+    /// updated.
+    fn get_base_layer_block_marker_with_timestamp(
+        &self,
+    ) -> StorageResult<(BlockNumber, Option<SystemTime>)>;
 }
 
 /// Interface for writing data related to the base layer.
@@ -56,7 +64,8 @@ pub trait BaseLayerStorageWriter
 where
     Self: Sized,
 {
-    /// Updates the block marker of the base layer.
+    /// Updates the block marker of the base layer and records the current time as its update
+    /// time.
     // To enforce that no commit happen after a failure, we consume and return Self on success.
     fn update_base_layer_block_marker(self, block_number: &BlockNumber) -> StorageResult<Self>;
 
@@ -73,12 +82,27 @@ impl<'env, Mode: TransactionKind> BaseLayerStorageReader for StorageTxn<'env, Mo
         let markers_table = self.open_table(&self.tables.markers)?;
         Ok(markers_table.get(&self.txn, &MarkerKind::BaseLayerBlock)?.unwrap_or_default())
     }
+
+    fn get_base_layer_block_marker_with_timestamp(
+        &self,
+    ) -> StorageResult<(BlockNumber, Option<SystemTime>)> {
+        let update_time_table = self.open_table(&self.tables.base_layer_marker_update_time)?;
+        let update_time = update_time_table
+            .get(&self.txn, &NoValue)?
+            .map(|time_since_epoch| UNIX_EPOCH + time_since_epoch);
+        Ok((self.get_base_layer_block_marker()?, update_time))
+    }
 }
 
 impl<'env> BaseLayerStorageWriter for StorageTxn<'env, RW> {
     fn update_base_layer_block_marker(self, block_number: &BlockNumber) -> StorageResult<Self> {
         let markers_table = self.open_table(&self.tables.markers)?;
         markers_table.upsert(&self.txn, &MarkerKind::BaseLayerBlock, block_number)?;
+        let update_time_table = self.open_table(&self.tables.base_layer_marker_update_time)?;
+        let time_since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Current time should be after the unix epoch");
+        update_time_table.upsert(&self.txn, &NoValue, &time_since_epoch)?;
         Ok(self)
     }
 
@@ -1,3 +1,5 @@
+use std::time::{Duration, SystemTime};
+
 use starknet_api::block::BlockNumber;
 
 use crate::base_layer::{BaseLayerStorageReader, BaseLayerStorageWriter};
@@ -50,3 +52,37 @@ fn try_revert_base_layer_marker() {
     let cur_marker = reader.begin_ro_txn().unwrap().get_base_layer_block_marker().unwrap();
     assert_eq!(cur_marker, BlockNumber(1));
 }
+
+#[test]
+fn base_layer_marker_update_time() {
+    let (reader, mut writer) = get_test_storage().0;
+    let get_marker_with_timestamp =
+        || reader.begin_ro_txn().unwrap().get_base_layer_block_marker_with_timestamp().unwrap();
+    let mut update_marker = |block_number| {
+        writer
+            .begin_rw_txn()
+            .unwrap()
+            .update_base_layer_block_marker(&block_number)
+            .unwrap()
+            .commit()
+            .unwrap();
+    };
+
+    assert_eq!(get_marker_with_timestamp(), (BlockNumber(0), None));
+
+    let time_before_first_update = SystemTime::now();
+    update_marker(BlockNumber(1));
+    let (marker, Some(first_update_time)) = get_marker_with_timestamp() else {
+        panic!("Expected the update time to be stored.");
+    };
+    assert_eq!(marker, BlockNumber(1));
+    assert!(first_update_time >= time_before_first_update);
+
+    std::thread::sleep(Duration::from_millis(10));
+    update_marker(BlockNumber(2));
+    let (marker, Some(second_update_time)) = get_marker_with_timestamp() else {
+        panic!("Expected the update time to be stored.");
+    };
+    assert_eq!(marker, BlockNumber(2));
+    assert!(second_update_time > first_update_time);
+}
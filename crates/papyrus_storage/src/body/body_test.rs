@@ -1,7 +1,13 @@
 use assert_matches::assert_matches;
 use pretty_assertions::assert_eq;
 use starknet_api::block::{BlockBody, BlockNumber};
-use starknet_api::transaction::TransactionOffsetInBlock;
+use starknet_api::transaction::{
+    DeclareTransaction,
+    DeployAccountTransaction,
+    InvokeTransaction,
+    Transaction,
+    TransactionOffsetInBlock,
+};
 use test_case::test_case;
 use test_utils::{get_test_block, get_test_body};
 
@@ -11,6 +17,42 @@ use crate::db::{DbError, KeyAlreadyExistsError};
 use crate::test_utils::{get_test_storage, get_test_storage_by_scope};
 use crate::{OffsetKind, StorageError, StorageScope, StorageWriter};
 
+#[test]
+fn append_body_with_v3_transactions() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let body = get_test_block(300, None, None, None).body;
+    assert!(
+        body.transactions
+            .iter()
+            .any(|tx| matches!(tx, Transaction::Invoke(InvokeTransaction::V3(_))))
+    );
+    assert!(
+        body.transactions
+            .iter()
+            .any(|tx| matches!(tx, Transaction::Declare(DeclareTransaction::V3(_))))
+    );
+    assert!(
+        body.transactions
+            .iter()
+            .any(|tx| matches!(tx, Transaction::DeployAccount(DeployAccountTransaction::V3(_))))
+    );
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_body(BlockNumber(0), body.clone())
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_block_transactions(BlockNumber(0)).unwrap(), Some(body.transactions));
+    assert_eq!(
+        txn.get_block_transaction_outputs(BlockNumber(0)).unwrap(),
+        Some(body.transaction_outputs)
+    );
+}
+
 #[tokio::test]
 async fn append_body() {
     let ((reader, mut writer), _temp_dir) = get_test_storage();
@@ -194,6 +236,30 @@ async fn append_body_state_only() {
     assert_eq!(txn.get_body_marker().unwrap(), BlockNumber(1));
 }
 
+#[tokio::test]
+async fn append_inconsistent_body_fails() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let mut block_body = get_test_body(3, None, None, None);
+    block_body.transaction_hashes.pop();
+
+    let Err(err) = writer.begin_rw_txn().unwrap().append_body(BlockNumber(0), block_body) else {
+        panic!("Unexpected Ok.");
+    };
+    assert_matches!(
+        err,
+        StorageError::InconsistentBlockBody { block_number } if block_number == BlockNumber(0)
+    );
+
+    let mut block_body = get_test_body(3, None, None, None);
+    block_body.transaction_outputs.pop();
+    let Err(err) = writer.begin_rw_txn().unwrap().append_body(BlockNumber(0), block_body) else {
+        panic!("Unexpected Ok.");
+    };
+    assert_matches!(err, StorageError::InconsistentBlockBody { .. });
+
+    assert_eq!(reader.begin_ro_txn().unwrap().get_body_marker().unwrap(), BlockNumber(0));
+}
+
 #[test_case(StorageScope::FullArchive; "revert non existing body fails full archive")]
 #[test_case(StorageScope::StateOnly; "revert non existing body fails state only")]
 #[tokio::test]
@@ -149,6 +149,8 @@ where
     Self: Sized,
 {
     /// Appends a block body to the storage.
+    /// Fails if the body's transactions, transaction outputs and transaction hashes differ in
+    /// length.
     // To enforce that no commit happen after a failure, we consume and return Self on success.
     // The body is consumed to avoid unnecessary copying while converting transaction outputs into
     // thin transaction outputs.
@@ -344,6 +346,13 @@ impl<'env, Mode: TransactionKind> StorageTxn<'env, Mode> {
 impl<'env> BodyStorageWriter for StorageTxn<'env, RW> {
     #[latency_histogram("storage_append_body_latency_seconds", false)]
     fn append_body(self, block_number: BlockNumber, block_body: BlockBody) -> StorageResult<Self> {
+        let n_transactions = block_body.transactions.len();
+        if block_body.transaction_outputs.len() != n_transactions
+            || block_body.transaction_hashes.len() != n_transactions
+        {
+            return Err(StorageError::InconsistentBlockBody { block_number });
+        }
+
         let markers_table = self.open_table(&self.tables.markers)?;
         update_marker(&self.txn, &markers_table, block_number)?;
 
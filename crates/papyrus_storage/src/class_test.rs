@@ -9,9 +9,10 @@ use starknet_api::state::{ContractClass, StateNumber, ThinStateDiff};
 use test_utils::read_json_file;
 
 use super::{ClassStorageReader, ClassStorageWriter};
+use crate::compression_utils::CompressionAlgorithm;
 use crate::state::{StateStorageReader, StateStorageWriter};
-use crate::test_utils::get_test_storage;
-use crate::StorageError;
+use crate::test_utils::{get_test_config, get_test_storage};
+use crate::{open_storage, StorageError};
 
 #[test]
 fn append_classes_writes_correct_data() {
@@ -58,6 +59,53 @@ fn append_classes_writes_correct_data() {
     assert_eq!(deprecated_class, expected_deprecated_class);
 }
 
+#[test]
+fn append_classes_with_different_compressions() {
+    let class_json = read_json_file("class.json");
+    let expected_class: ContractClass = serde_json::from_value(class_json).unwrap();
+    let (mut config, _temp_dir) = get_test_config(None);
+
+    let compressions = [
+        (CompressionAlgorithm::Zstd, 1),
+        (CompressionAlgorithm::Zstd, 19),
+        (CompressionAlgorithm::None, zstd::DEFAULT_COMPRESSION_LEVEL),
+    ];
+    let mut class_sizes = Vec::new();
+    for (block_number, (compression_algorithm, compression_level)) in (0..).zip(compressions) {
+        config.mmap_file_config.compression_algorithm = compression_algorithm;
+        config.mmap_file_config.compression_level = compression_level;
+        let (reader, mut writer) = open_storage(config.clone()).unwrap();
+        let offset_before = reader.mmap_files_stats()["contract_class"].offset();
+        let class_hash = ClassHash(StarkHash::from(block_number));
+        writer
+            .begin_rw_txn()
+            .unwrap()
+            .append_state_diff(
+                BlockNumber(block_number),
+                ThinStateDiff {
+                    declared_classes: indexmap! { class_hash => CompiledClassHash::default() },
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .append_classes(BlockNumber(block_number), &[(class_hash, &expected_class)], &[])
+            .unwrap()
+            .commit()
+            .unwrap();
+        class_sizes.push(reader.mmap_files_stats()["contract_class"].offset() - offset_before);
+    }
+    // A higher level compresses better, and not compressing at all is the largest.
+    assert!(class_sizes[0] > class_sizes[1], "Class sizes: {class_sizes:?}");
+    assert!(class_sizes[2] > class_sizes[0], "Class sizes: {class_sizes:?}");
+
+    let (reader, _) = open_storage(config).unwrap();
+    let txn = reader.begin_ro_txn().unwrap();
+    for block_number in 0..3 {
+        let class = txn.get_class(&ClassHash(StarkHash::from(block_number))).unwrap().unwrap();
+        assert_eq!(class, expected_class);
+    }
+}
+
 #[test]
 fn append_classes_marker_mismatch() {
     let ((_reader, mut writer), _temp_dir) = get_test_storage();
@@ -41,7 +41,10 @@ use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use papyrus_proc_macros::latency_histogram;
 use starknet_api::block::BlockNumber;
 use starknet_api::core::ClassHash;
+use starknet_api::state::ContractClass;
+use tracing::warn;
 
+use crate::class::ClassStorageReader;
 use crate::db::serialization::VersionZeroWrapper;
 use crate::db::table_types::{SimpleTable, Table};
 use crate::db::{DbTransaction, TableHandle, TransactionKind, RW};
@@ -52,6 +55,12 @@ use crate::{FileHandlers, MarkerKind, MarkersTable, OffsetKind, StorageResult, S
 pub trait CasmStorageReader {
     /// Returns the Cairo assembly of a class given its Sierra class hash.
     fn get_casm(&self, class_hash: &ClassHash) -> StorageResult<Option<CasmContractClass>>;
+    /// Returns the Sierra class and the Cairo assembly of a class given its Sierra class hash.
+    /// Returns None if either of them is missing.
+    fn get_class_and_casm(
+        &self,
+        class_hash: &ClassHash,
+    ) -> StorageResult<Option<(ContractClass, CasmContractClass)>>;
     /// The block marker is the first block number that doesn't exist yet.
     ///
     /// Note: If the last blocks don't contain any declared classes, the marker will point at the
@@ -76,6 +85,31 @@ impl<'env, Mode: TransactionKind> CasmStorageReader for StorageTxn<'env, Mode> {
         casm_location.map(|location| self.file_handlers.get_casm_unchecked(location)).transpose()
     }
 
+    fn get_class_and_casm(
+        &self,
+        class_hash: &ClassHash,
+    ) -> StorageResult<Option<(ContractClass, CasmContractClass)>> {
+        let Some(class) = self.get_class(class_hash)? else {
+            return Ok(None);
+        };
+        let Some(casm) = self.get_casm(class_hash)? else {
+            // The casm may be missing only if the compiled classes sync didn't reach the block in
+            // which the class was declared.
+            let declared_classes_block_table =
+                self.open_table(&self.tables.declared_classes_block)?;
+            if let Some(block_number) = declared_classes_block_table.get(&self.txn, class_hash)? {
+                if block_number < self.get_compiled_class_marker()? {
+                    warn!(
+                        "Class {class_hash} was declared in block {block_number}, which is below \
+                         the compiled class marker, but its casm is missing."
+                    );
+                }
+            }
+            return Ok(None);
+        };
+        Ok(Some((class, casm)))
+    }
+
     fn get_compiled_class_marker(&self) -> StorageResult<BlockNumber> {
         let markers_table = self.open_table(&self.tables.markers)?;
         Ok(markers_table.get(&self.txn, &MarkerKind::CompiledClass)?.unwrap_or_default())
@@ -91,7 +125,7 @@ impl<'env> CasmStorageWriter for StorageTxn<'env, RW> {
         let file_offset_table = self.txn.open_table(&self.tables.file_offsets)?;
 
         let location = self.file_handlers.append_casm(casm);
-        casm_table.insert(&self.txn, class_hash, &location)?;
+        casm_table.insert_new(&self.txn, class_hash, &location)?;
         file_offset_table.upsert(&self.txn, &OffsetKind::Casm, &location.next_offset())?;
         update_marker(
             &self.txn,
@@ -1,11 +1,15 @@
 use assert_matches::assert_matches;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use indexmap::indexmap;
 use pretty_assertions::assert_eq;
-use starknet_api::core::ClassHash;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ClassHash, CompiledClassHash};
+use starknet_api::state::{ContractClass, ThinStateDiff};
 use test_utils::read_json_file;
 
+use crate::class::ClassStorageWriter;
 use crate::compiled_class::{CasmStorageReader, CasmStorageWriter};
-use crate::db::{DbError, KeyAlreadyExistsError};
+use crate::state::StateStorageWriter;
 use crate::test_utils::get_test_storage;
 use crate::StorageError;
 
@@ -47,9 +51,44 @@ fn casm_rewrite() {
         panic!("Unexpected Ok.");
     };
 
-    assert_matches!(err, StorageError::InnerError(DbError::KeyAlreadyExists(KeyAlreadyExistsError {
-        table_name: _,
-        key,
-        value: _
-    })) if key == format!("{:?}", ClassHash::default()));
+    assert_matches!(
+        err,
+        StorageError::KeyAlreadyExists { table: "casms", key_debug }
+        if key_debug == format!("{:?}", ClassHash::default())
+    );
+}
+
+#[test]
+fn get_class_and_casm() {
+    let class: ContractClass = serde_json::from_value(read_json_file("class.json")).unwrap();
+    let casm: CasmContractClass =
+        serde_json::from_value(read_json_file("compiled_class.json")).unwrap();
+    let class_hash = ClassHash::default();
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(
+            BlockNumber(0),
+            ThinStateDiff {
+                declared_classes: indexmap! { class_hash => CompiledClassHash::default() },
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .append_classes(BlockNumber(0), &[(class_hash, &class)], &[])
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    // Only the sierra class exists.
+    assert_eq!(reader.begin_ro_txn().unwrap().get_class_and_casm(&class_hash).unwrap(), None);
+
+    writer.begin_rw_txn().unwrap().append_casm(&class_hash, &casm).unwrap().commit().unwrap();
+
+    assert_eq!(
+        reader.begin_ro_txn().unwrap().get_class_and_casm(&class_hash).unwrap(),
+        Some((class, casm))
+    );
 }
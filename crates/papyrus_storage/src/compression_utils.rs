@@ -2,6 +2,8 @@
 #[path = "compression_utils_test.rs"]
 mod compression_utils_test;
 
+use serde::{Deserialize, Serialize};
+
 use crate::db::serialization::{StorageSerde, StorageSerdeError};
 
 // TODO(dvir): create one compressor/decompressor only once (maybe only once per thread) to prevent
@@ -12,31 +14,93 @@ use crate::db::serialization::{StorageSerde, StorageSerdeError};
 // TODO(Dvir): consider defining this for each type separately and pass it as an argument to the
 // decompress function.
 pub(crate) const MAX_DECOMPRESSED_SIZE: usize = 1 << 28; // 256 MB
-// The compression level to use. Higher levels are slower but compress better.
+// The compression level to use by default. Higher levels are slower but compress better.
 const COMPRESSION_LEVEL: i32 = zstd::DEFAULT_COMPRESSION_LEVEL;
 
+// The zstd frame format, used to store uncompressed data so that it's read like compressed data.
+const ZSTD_MAGIC_NUMBER: u32 = 0xFD2FB528;
+// A single segment frame with an 8 bytes content size, without a checksum or a dictionary.
+const ZSTD_RAW_FRAME_HEADER_DESCRIPTOR: u8 = 0b1110_0000;
+const ZSTD_MAX_BLOCK_SIZE: usize = 1 << 17;
+
+/// The algorithm used to compress the data written to the storage.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionAlgorithm {
+    /// The data isn't compressed.
+    None,
+    /// The data is compressed with zstd.
+    #[default]
+    Zstd,
+}
+
+/// How data is compressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// The data isn't compressed. It's stored in raw zstd blocks, so it's decompressed like data
+    /// that was compressed with zstd.
+    None,
+    /// The data is compressed with zstd using the given level.
+    Zstd(i32),
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self::Zstd(COMPRESSION_LEVEL)
+    }
+}
+
 /// Returns the compressed data in a vector.
 ///
 /// # Arguments
 /// * data - bytes to compress.
+/// * compression - how to compress the data.
 ///
 /// # Errors
 /// Returns [`std::io::Error`] if any read error is encountered.
-pub fn compress(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
-    zstd::bulk::compress(data, COMPRESSION_LEVEL)
+pub fn compress(data: &[u8], compression: Compression) -> Result<Vec<u8>, std::io::Error> {
+    match compression {
+        Compression::None => Ok(store_in_zstd_frame(data)),
+        Compression::Zstd(level) => zstd::bulk::compress(data, level),
+    }
+}
+
+// Wraps the data in a zstd frame of raw blocks, which zstd decompresses into the data itself.
+fn store_in_zstd_frame(data: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::new();
+    frame.extend_from_slice(&ZSTD_MAGIC_NUMBER.to_le_bytes());
+    frame.push(ZSTD_RAW_FRAME_HEADER_DESCRIPTOR);
+    frame.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    let mut blocks: Vec<&[u8]> = data.chunks(ZSTD_MAX_BLOCK_SIZE).collect();
+    if blocks.is_empty() {
+        blocks.push(&[]);
+    }
+    let last_block_index = blocks.len() - 1;
+    for (index, block) in blocks.into_iter().enumerate() {
+        // The 3 bytes block header holds the size, the type (zero for raw blocks) and whether it's
+        // the last block.
+        let header = ((block.len() as u32) << 3) | u32::from(index == last_block_index);
+        frame.extend_from_slice(&header.to_le_bytes()[..3]);
+        frame.extend_from_slice(block);
+    }
+    frame
 }
 
 /// Serialized and then compress object.
 ///
 /// # Arguments
 /// * object - the object to serialize and compress.
+/// * compression - how to compress the serialized object.
 ///
 /// # Errors
 /// Returns [`StorageSerdeError`] if any error is encountered in the serialization or compression.
-pub fn serialize_and_compress(object: &impl StorageSerde) -> Result<Vec<u8>, StorageSerdeError> {
+pub fn serialize_and_compress(
+    object: &impl StorageSerde,
+    compression: Compression,
+) -> Result<Vec<u8>, StorageSerdeError> {
     let mut buf = Vec::new();
     object.serialize_into(&mut buf)?;
-    Ok(compress(buf.as_slice())?)
+    Ok(compress(buf.as_slice(), compression)?)
 }
 
 /// Decompress data and returns it as bytes in a vector.
@@ -1,22 +1,36 @@
 use pretty_assertions::assert_eq;
 use starknet_api::deprecated_contract_class::Program;
+use test_case::test_case;
 use test_utils::read_json_file;
 
-use super::{compress, decompress, decompress_from_reader, serialize_and_compress};
+use super::{compress, decompress, decompress_from_reader, serialize_and_compress, Compression};
 use crate::db::serialization::StorageSerde;
 
-#[test]
-fn bytes_compression() {
+#[test_case(Compression::Zstd(1); "zstd level 1")]
+#[test_case(Compression::Zstd(19); "zstd level 19")]
+#[test_case(Compression::None; "none")]
+fn bytes_compression(compression: Compression) {
     let bytes = vec![30, 5, 23, 12, 47];
-    let x = decompress(compress(bytes.as_slice()).unwrap().as_slice()).unwrap();
+    let x = decompress(compress(bytes.as_slice(), compression).unwrap().as_slice()).unwrap();
     assert_eq!(bytes, x);
 }
 
+#[test]
+fn uncompressed_data_is_decompressed() {
+    // Data of a few raw blocks, the last one partial, and empty data.
+    let large_data = (0..300_000_u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    for data in [large_data, vec![]] {
+        let stored = compress(data.as_slice(), Compression::None).unwrap();
+        assert!(stored.len() > data.len());
+        assert_eq!(decompress(stored.as_slice()).unwrap(), data);
+    }
+}
+
 #[test]
 fn object_compression() {
     let program_json = read_json_file("program.json");
     let program = serde_json::from_value::<Program>(program_json).unwrap();
-    let compressed = serialize_and_compress(&program).unwrap();
+    let compressed = serialize_and_compress(&program, Compression::default()).unwrap();
     let mut buf = Vec::new();
     compressed.serialize_into(&mut buf).unwrap();
     let decompressed = decompress_from_reader(&mut buf.as_slice()).unwrap();
@@ -1,9 +1,22 @@
+use std::{mem, ptr};
+
 use human_bytes::human_bytes;
 use libmdbx::Info;
 use serde::{Deserialize, Serialize};
 
 use super::{DbReader, DbResult};
 
+// The handle of the free list (GC) table of the database.
+const FREE_DBI: mdbx_sys::MDBX_dbi = 0;
+
+// Converts an error code of mdbx to a result.
+fn mdbx_result(err_code: std::os::raw::c_int) -> Result<(), libmdbx::Error> {
+    match err_code {
+        mdbx_sys::MDBX_SUCCESS => Ok(()),
+        _ => Err(libmdbx::Error::from_err_code(err_code)),
+    }
+}
+
 /// A single table statistics.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DbTableStats {
@@ -44,6 +57,9 @@ pub struct DbWholeStats {
     pub page_size: u64,
     /// The number of pages in the free list.
     pub freelist_size: usize,
+    /// Total size of the pages in the free list, which can be reused without growing the database.
+    #[serde(serialize_with = "readable_bytes")]
+    pub reclaimable_size: u64,
 }
 
 impl DbReader {
@@ -66,6 +82,7 @@ impl DbReader {
     // Returns statistics about the whole database.
     pub(crate) fn get_db_stats(&self) -> DbResult<DbWholeStats> {
         let stat = self.env.stat()?;
+        let freelist_size = self.get_free_pages()?;
         Ok(DbWholeStats {
             entries: stat.entries(),
             branch_pages: stat.branch_pages(),
@@ -73,7 +90,8 @@ impl DbReader {
             overflow_pages: stat.overflow_pages(),
             total_size: stat.total_size(),
             page_size: stat.page_size().into(),
-            freelist_size: self.env.freelist()?,
+            freelist_size,
+            reclaimable_size: freelist_size as u64 * u64::from(stat.page_size()),
         })
     }
 
@@ -82,12 +100,40 @@ impl DbReader {
         Ok(self.env.info()?)
     }
 
-    // Returns the the number of free pages in the database.
-    // NOTICE: currently, this function will return a garbage value due to a bug in the binding
-    // freelist function.
-    // TODO(dvir): bump libmdbx version when the bug is fixed.
+    // Returns the number of pages in the free list (GC) of the database.
+    // The freelist function of the binding can't be used, since it reads the page counts of the GC
+    // records as usize instead of as the 32-bit page number type, so it returns a garbage value.
+    // TODO(dvir): use the binding freelist function when the bug is fixed.
     pub(crate) fn get_free_pages(&self) -> DbResult<usize> {
-        Ok(self.env.freelist()?)
+        let txn = self.env.begin_ro_txn()?;
+        let mut cursor: *mut mdbx_sys::MDBX_cursor = ptr::null_mut();
+        // SAFETY: the transaction outlives the cursor, which is closed before returning.
+        mdbx_result(unsafe { mdbx_sys::mdbx_cursor_open(txn.txn(), FREE_DBI, &mut cursor) })?;
+
+        let mut free_pages = 0;
+        let res = loop {
+            let mut key = mdbx_sys::MDBX_val { iov_len: 0, iov_base: ptr::null_mut() };
+            let mut data = mdbx_sys::MDBX_val { iov_len: 0, iov_base: ptr::null_mut() };
+            // SAFETY: the cursor is open, and the returned data is valid while the transaction is.
+            let err_code = unsafe {
+                mdbx_sys::mdbx_cursor_get(cursor, &mut key, &mut data, mdbx_sys::MDBX_NEXT)
+            };
+            match err_code {
+                mdbx_sys::MDBX_NOTFOUND => break Ok(free_pages),
+                mdbx_sys::MDBX_SUCCESS => {}
+                _ => break Err(libmdbx::Error::from_err_code(err_code)),
+            }
+            // A GC record is a list of page numbers, prefixed by the length of the list.
+            if data.iov_len < mem::size_of::<u32>() {
+                break Err(libmdbx::Error::Corrupted);
+            }
+            // SAFETY: the data holds at least one page number, which may be unaligned.
+            let record_pages = unsafe { ptr::read_unaligned(data.iov_base as *const u32) };
+            free_pages += record_pages as usize;
+        };
+        // SAFETY: the cursor is open and isn't used afterwards.
+        unsafe { mdbx_sys::mdbx_cursor_close(cursor) };
+        Ok(res?)
     }
 }
 
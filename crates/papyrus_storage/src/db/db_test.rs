@@ -1,3 +1,6 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
 use assert_matches::assert_matches;
 use libmdbx::PageSize;
 use pretty_assertions::assert_eq;
@@ -7,6 +10,7 @@ use crate::db::serialization::{NoVersionValueWrapper, ValueSerde, VersionZeroWra
 use crate::db::table_types::Table;
 use crate::db::{get_page_size, open_env, DbError, DbIter, DbReader, DbResult, DbWriter};
 use crate::test_utils::get_test_config;
+use crate::StorageError;
 
 pub(crate) fn get_test_env() -> ((DbReader, DbWriter), TempDir) {
     let (config, temp_dir) = get_test_config(None);
@@ -105,7 +109,34 @@ fn txns_scenarios() {
 }
 
 #[test]
+fn read_txn_does_not_wait_for_write_txn() {
+    const MAX_BEGIN_DURATION: Duration = Duration::from_secs(1);
+    let ((reader, mut writer), _temp_dir) = get_test_env();
+    let table_id =
+        writer.create_simple_table::<[u8; 3], NoVersionValueWrapper<[u8; 5]>>("table").unwrap();
 
+    // The writer holds a transaction with uncommitted changes while the readers start theirs.
+    let wtxn = writer.begin_rw_txn().unwrap();
+    let table = wtxn.open_table(&table_id).unwrap();
+    table.insert(&wtxn, b"key", b"data0").unwrap();
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            let reader = reader.clone();
+            let table_id = &table_id;
+            scope.spawn(move || {
+                let start = Instant::now();
+                let txn = reader.begin_ro_txn().unwrap();
+                assert!(start.elapsed() < MAX_BEGIN_DURATION);
+                let table = txn.open_table(table_id).unwrap();
+                assert_eq!(table.get(&txn, b"key").unwrap(), None);
+            });
+        }
+    });
+    wtxn.commit().unwrap();
+}
+
+#[test]
 fn table_stats() {
     // Create an environment and a table.
     let ((reader, mut writer), _temp_dir) = get_test_env();
@@ -149,6 +180,37 @@ fn table_stats() {
     assert_eq!(empty_stat.leaf_pages, 0);
 }
 
+#[test]
+fn freelist_stats() {
+    let ((reader, mut writer), _temp_dir) = get_test_env();
+    let table_id =
+        writer.create_simple_table::<[u8; 4], NoVersionValueWrapper<[u8; 64]>>("table").unwrap();
+
+    // Fill a few pages of the table.
+    const N_ENTRIES: u32 = 1000;
+    let wtxn = writer.begin_rw_txn().unwrap();
+    let table = wtxn.open_table(&table_id).unwrap();
+    for i in 0..N_ENTRIES {
+        table.insert(&wtxn, &i.to_be_bytes(), &[0; 64]).unwrap();
+    }
+    wtxn.commit().unwrap();
+
+    // Delete the entries, which releases their pages to the free list.
+    let wtxn = writer.begin_rw_txn().unwrap();
+    let table = wtxn.open_table(&table_id).unwrap();
+    for i in 0..N_ENTRIES {
+        table.delete(&wtxn, &i.to_be_bytes()).unwrap();
+    }
+    wtxn.commit().unwrap();
+
+    let stats = reader.get_db_stats().unwrap();
+    assert!(stats.freelist_size > 0);
+    // The free pages are a part of the pages of the database.
+    let last_page = reader.get_db_info().unwrap().last_pgno();
+    assert!(stats.freelist_size <= last_page);
+    assert_eq!(stats.reclaimable_size, stats.freelist_size as u64 * stats.page_size);
+}
+
 use super::serialization::{Migratable, StorageSerde, StorageSerdeError, VersionWrapper};
 use super::{MDBX_MAX_PAGESIZE, MDBX_MIN_PAGESIZE};
 #[test]
@@ -379,3 +441,26 @@ fn version_migration() {
         assert_matches!(v1_as_v0, DbError::InnerDeserialization);
     }
 }
+
+#[test]
+fn insert_new_duplicate_key() {
+    let ((reader, mut writer), _temp_dir) = get_test_env();
+    let table_id =
+        writer.create_simple_table::<[u8; 4], NoVersionValueWrapper<[u8; 4]>>("table").unwrap();
+
+    let wtxn = writer.begin_rw_txn().unwrap();
+    let table = wtxn.open_table(&table_id).unwrap();
+    table.insert_new(&wtxn, b"key1", b"val1").unwrap();
+    let err = table.insert_new(&wtxn, b"key1", b"val2").unwrap_err();
+    assert_matches!(
+        err,
+        StorageError::KeyAlreadyExists { table: "table", key_debug }
+        if key_debug == format!("{:?}", b"key1")
+    );
+    wtxn.commit().unwrap();
+
+    // The original value is kept.
+    let txn = reader.begin_ro_txn().unwrap();
+    let table = txn.open_table(&table_id).unwrap();
+    assert_eq!(table.get(&txn, b"key1").unwrap(), Some(*b"val1"));
+}
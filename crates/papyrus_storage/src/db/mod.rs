@@ -30,8 +30,13 @@ use std::result;
 use std::sync::Arc;
 
 use libmdbx::{DatabaseFlags, Geometry, PageSize, WriteMap};
-use papyrus_config::dumping::{ser_param, SerializeConfig};
-use papyrus_config::validators::{validate_ascii, validate_path_exists};
+use papyrus_config::converters::{
+    deserialize_human_bytes,
+    format_human_bytes,
+    serialize_human_bytes,
+};
+use papyrus_config::dumping::{ser_byte_size_param, ser_param, SerializeConfig};
+use papyrus_config::validators::{validate_non_empty_ascii, validate_path_exists};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use starknet_api::core::ChainId;
@@ -42,7 +47,7 @@ use self::table_types::{DbCursor, DbCursorTrait};
 use crate::db::table_types::TableType;
 
 // Maximum number of Sub-Databases.
-const MAX_DBS: usize = 18;
+const MAX_DBS: usize = 20;
 
 // Note that NO_TLS mode is used by default.
 type EnvironmentKind = WriteMap;
@@ -59,16 +64,28 @@ pub struct DbConfig {
     #[validate(custom = "validate_path_exists")]
     pub path_prefix: PathBuf,
     /// The [chain id](https://docs.rs/starknet_api/latest/starknet_api/core/struct.ChainId.html) of the Starknet network.
-    #[validate(custom = "validate_ascii")]
+    #[validate(custom = "validate_non_empty_ascii")]
     pub chain_id: ChainId,
     /// Whether to enforce that the path exists. If true, `open_env` fails when the mdbx.dat file
     /// does not exist.
     pub enforce_file_exists: bool,
     /// The minimum size of the database.
+    #[serde(
+        serialize_with = "serialize_human_bytes",
+        deserialize_with = "deserialize_human_bytes"
+    )]
     pub min_size: usize,
     /// The maximum size of the database.
+    #[serde(
+        serialize_with = "serialize_human_bytes",
+        deserialize_with = "deserialize_human_bytes"
+    )]
     pub max_size: usize,
     /// The growth step of the database.
+    #[serde(
+        serialize_with = "serialize_human_bytes",
+        deserialize_with = "deserialize_human_bytes"
+    )]
     pub growth_step: isize,
 }
 
@@ -87,6 +104,12 @@ impl Default for DbConfig {
 
 impl SerializeConfig for DbConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        // Negative growth steps aren't loadable byte sizes, so they are dumped with their sign and
+        // rejected when loaded, instead of silently becoming zero.
+        let growth_step = match u64::try_from(self.growth_step) {
+            Ok(growth_step) => format_human_bytes(growth_step),
+            Err(_) => self.growth_step.to_string(),
+        };
         BTreeMap::from_iter([
             ser_param(
                 "path_prefix",
@@ -108,23 +131,23 @@ impl SerializeConfig for DbConfig {
                 mdbx.dat file does not exist.",
                 ParamPrivacyInput::Public,
             ),
-            ser_param(
+            ser_byte_size_param(
                 "min_size",
-                &self.min_size,
-                "The minimum size of the node's storage in bytes.",
+                &format_human_bytes(self.min_size as u64),
+                "The minimum size of the node's storage in bytes, e.g. 1GiB or 1073741824.",
                 ParamPrivacyInput::Public,
             ),
-            ser_param(
+            ser_byte_size_param(
                 "max_size",
-                &self.max_size,
-                "The maximum size of the node's storage in bytes.",
+                &format_human_bytes(self.max_size as u64),
+                "The maximum size of the node's storage in bytes, e.g. 1GiB or 1073741824.",
                 ParamPrivacyInput::Public,
             ),
-            ser_param(
+            ser_byte_size_param(
                 "growth_step",
-                &self.growth_step,
-                "The growth step in bytes, must be greater than zero to allow the database to \
-                 grow.",
+                &growth_step,
+                "The growth step in bytes, e.g. 4GiB. Must be greater than zero to allow the \
+                 database to grow.",
                 ParamPrivacyInput::Public,
             ),
         ])
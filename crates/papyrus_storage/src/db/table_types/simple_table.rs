@@ -137,6 +137,17 @@ impl<'env, K: KeyTrait + Debug, V: ValueSerde + Debug> Table<'env>
     }
 }
 
+impl<'txn, Mode: TransactionKind, K: KeyTrait + Debug, V: ValueSerde + Debug>
+    DbCursor<'txn, Mode, K, V, SimpleTable>
+{
+    // Position at the specified key. Returns whether the key exists, without deserializing its
+    // value.
+    pub(crate) fn seek(&mut self, key: &K) -> DbResult<bool> {
+        let key_bytes = key.serialize()?;
+        Ok(self.cursor.set::<DbValueType<'_>>(&key_bytes)?.is_some())
+    }
+}
+
 impl<'txn, Mode: TransactionKind, K: KeyTrait + Debug, V: ValueSerde + Debug> DbCursorTrait
     for DbCursor<'txn, Mode, K, V, SimpleTable>
 {
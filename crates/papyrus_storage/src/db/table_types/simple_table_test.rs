@@ -1,7 +1,33 @@
-use crate::db::table_types::test_utils::table_test;
-use crate::db::DbWriter;
+use pretty_assertions::assert_eq;
+
+use crate::db::db_test::get_test_env;
+use crate::db::table_types::test_utils::{table_test, TableKey, TableValue};
+use crate::db::table_types::{DbCursorTrait, SimpleTable, Table};
+use crate::db::{DbWriter, TableIdentifier};
 
 #[test]
 fn simple_table_test() {
     table_test(DbWriter::create_simple_table);
 }
+
+#[test]
+fn simple_table_lower_bound_in_gap() {
+    let ((_reader, mut writer), _temp_dir) = get_test_env();
+    let table_id: TableIdentifier<TableKey, TableValue, SimpleTable> =
+        writer.create_simple_table("lower_bound_test").unwrap();
+    let txn = writer.begin_rw_txn().unwrap();
+    let table = txn.open_table(&table_id).unwrap();
+    for key in [(1, 0), (5, 0), (9, 0)] {
+        table.insert(&txn, &key, &key.0).unwrap();
+    }
+
+    let mut cursor = table.cursor(&txn).unwrap();
+    // Seeking to a missing key positions the cursor at the next existing key.
+    assert_eq!(cursor.lower_bound(&(2, 0)).unwrap(), Some(((5, 0), 5)));
+    assert_eq!(cursor.next().unwrap(), Some(((9, 0), 9)));
+    assert_eq!(cursor.lower_bound(&(5, 1)).unwrap(), Some(((9, 0), 9)));
+    // Seeking to an existing key positions the cursor at it.
+    assert_eq!(cursor.lower_bound(&(1, 0)).unwrap(), Some(((1, 0), 1)));
+    // Seeking past the last key returns None.
+    assert_eq!(cursor.lower_bound(&(9, 1)).unwrap(), None);
+}
@@ -94,6 +94,9 @@ pub trait HeaderStorageReader {
     /// Returns the header of the block with the given number.
     fn get_block_header(&self, block_number: BlockNumber) -> StorageResult<Option<BlockHeader>>;
 
+    /// Returns whether a header of the block with the given number exists, without reading it.
+    fn block_exists(&self, block_number: BlockNumber) -> StorageResult<bool>;
+
     /// Returns the block number of the block with the given hash.
     fn get_block_number_by_hash(
         &self,
@@ -111,6 +114,13 @@ pub trait HeaderStorageReader {
         &self,
         block_number: BlockNumber,
     ) -> StorageResult<Option<BlockSignature>>;
+
+    /// Returns the header of the block with the given number together with its signature, if
+    /// it's stored.
+    fn get_block_header_and_signature(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<(BlockHeader, Option<BlockSignature>)>>;
 }
 
 /// Interface for writing data related to the block headers.
@@ -119,6 +129,7 @@ where
     Self: Sized,
 {
     /// Appends a header to the storage.
+    /// Fails with [`StorageError::MarkerMismatch`] if the block number is not the header marker.
     // To enforce that no commit happen after a failure, we consume and return Self on success.
     fn append_header(
         self,
@@ -155,6 +166,12 @@ impl<'env, Mode: TransactionKind> HeaderStorageReader for StorageTxn<'env, Mode>
         Ok(markers_table.get(&self.txn, &MarkerKind::Header)?.unwrap_or_default())
     }
 
+    fn block_exists(&self, block_number: BlockNumber) -> StorageResult<bool> {
+        let headers_table = self.open_table(&self.tables.headers)?;
+        let mut cursor = headers_table.cursor(&self.txn)?;
+        Ok(cursor.seek(&block_number)?)
+    }
+
     fn get_block_header(&self, block_number: BlockNumber) -> StorageResult<Option<BlockHeader>> {
         let headers_table = self.open_table(&self.tables.headers)?;
         let Some(block_header) = headers_table.get(&self.txn, &block_number)? else {
@@ -227,6 +244,17 @@ impl<'env, Mode: TransactionKind> HeaderStorageReader for StorageTxn<'env, Mode>
         let block_signature = block_signatures_table.get(&self.txn, &block_number)?;
         Ok(block_signature)
     }
+
+    fn get_block_header_and_signature(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<(BlockHeader, Option<BlockSignature>)>> {
+        let Some(block_header) = self.get_block_header(block_number)? else {
+            return Ok(None);
+        };
+        let block_signature = self.get_block_signature(block_number)?;
+        Ok(Some((block_header, block_signature)))
+    }
 }
 
 impl<'env> HeaderStorageWriter for StorageTxn<'env, RW> {
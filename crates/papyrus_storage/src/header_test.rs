@@ -50,6 +50,37 @@ async fn append_header() {
     assert_eq!(txn.get_block_number_by_hash(&BlockHash::default()).unwrap(), Some(BlockNumber(0)));
 }
 
+#[test]
+fn append_header_after_gap_fails() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &BlockHeader::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let header = BlockHeader { block_hash: BlockHash(felt!(1_u8)), ..BlockHeader::default() };
+    let Err(err) = writer.begin_rw_txn().unwrap().append_header(BlockNumber(2), &header) else {
+        panic!("Unexpected Ok.");
+    };
+    assert_matches!(
+        err,
+        StorageError::MarkerMismatch { expected: BlockNumber(1), found: BlockNumber(2) }
+    );
+    assert_eq!(reader.begin_ro_txn().unwrap().get_header_marker().unwrap(), BlockNumber(1));
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(1), &header)
+        .unwrap()
+        .commit()
+        .unwrap();
+    assert_eq!(reader.begin_ro_txn().unwrap().get_header_marker().unwrap(), BlockNumber(2));
+}
+
 #[tokio::test]
 async fn revert_non_existing_header_fails() {
     let ((_, mut writer), _temp_dir) = get_test_storage();
@@ -122,6 +153,27 @@ async fn get_reverted_block_number_by_hash_returns_none() {
     );
 }
 
+#[tokio::test]
+async fn block_exists() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    append_2_headers(&mut writer);
+
+    let txn = reader.begin_ro_txn().unwrap();
+    for block_number in [BlockNumber(0), BlockNumber(1), BlockNumber(2), BlockNumber(10)] {
+        assert_eq!(
+            txn.block_exists(block_number).unwrap(),
+            txn.get_block_header(block_number).unwrap().is_some(),
+            "Mismatch for block {block_number}."
+        );
+    }
+    assert!(txn.block_exists(BlockNumber(1)).unwrap());
+    assert!(!txn.block_exists(BlockNumber(2)).unwrap());
+
+    // A reverted block no longer exists.
+    writer.begin_rw_txn().unwrap().revert_header(BlockNumber(1)).unwrap().0.commit().unwrap();
+    assert!(!reader.begin_ro_txn().unwrap().block_exists(BlockNumber(1)).unwrap());
+}
+
 fn append_2_headers(writer: &mut StorageWriter) {
     writer
         .begin_rw_txn()
@@ -260,6 +312,77 @@ fn block_signature() {
     );
 }
 
+#[test]
+fn append_block_signature_for_non_existing_block() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let signature = BlockSignature::default();
+
+    let Err(err) =
+        writer.begin_rw_txn().unwrap().append_block_signature(BlockNumber(0), &signature)
+    else {
+        panic!("Unexpected Ok.");
+    };
+    assert_matches!(
+        err,
+        StorageError::BlockSignatureForNonExistingBlock { block_number, block_signature }
+        if block_number == BlockNumber(0) && block_signature == signature
+    );
+    assert!(reader.begin_ro_txn().unwrap().get_block_signature(BlockNumber(0)).unwrap().is_none());
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &BlockHeader::default())
+        .unwrap()
+        .append_block_signature(BlockNumber(0), &signature)
+        .unwrap()
+        .commit()
+        .unwrap();
+    assert_eq!(
+        reader.begin_ro_txn().unwrap().get_block_signature(BlockNumber(0)).unwrap(),
+        Some(signature)
+    );
+}
+
+#[test]
+fn block_header_and_signature() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    assert!(
+        reader
+            .begin_ro_txn()
+            .unwrap()
+            .get_block_header_and_signature(BlockNumber(0))
+            .unwrap()
+            .is_none()
+    );
+
+    let header0 = BlockHeader::default();
+    let header1 = BlockHeader {
+        block_number: BlockNumber(1),
+        block_hash: BlockHash(felt!("0x1")),
+        ..BlockHeader::default()
+    };
+    let signature = BlockSignature::default();
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &header0)
+        .unwrap()
+        .append_block_signature(BlockNumber(0), &signature)
+        .unwrap()
+        .append_header(BlockNumber(1), &header1)
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(
+        txn.get_block_header_and_signature(BlockNumber(0)).unwrap(),
+        Some((header0, Some(signature)))
+    );
+    assert_eq!(txn.get_block_header_and_signature(BlockNumber(1)).unwrap(), Some((header1, None)));
+}
+
 #[test]
 fn get_reverted_block_signature_returns_none() {
     let ((reader, mut writer), _temp_dir) = get_test_storage();
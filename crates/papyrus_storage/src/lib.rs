@@ -100,9 +100,14 @@ mod test_instances;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
 
+#[cfg(test)]
+#[path = "lib_test.rs"]
+mod lib_test;
+
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 
 use body::events::EventIndex;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
@@ -123,7 +128,7 @@ use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_proc_macros::latency_histogram;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockNumber, BlockSignature, StarknetVersion};
-use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::core::{ChainId, ClassHash, ContractAddress, Nonce};
 use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use starknet_api::state::{ContractClass, StateNumber, StorageKey, ThinStateDiff};
 use starknet_api::transaction::{Transaction, TransactionHash, TransactionOutput};
@@ -132,7 +137,8 @@ use tracing::{debug, warn};
 use validator::Validate;
 use version::{StorageVersionError, Version};
 
-use crate::body::TransactionIndex;
+use crate::base_layer::{BaseLayerStorageReader, BaseLayerStorageWriter};
+use crate::body::{BodyStorageReader, BodyStorageWriter, TransactionIndex};
 use crate::db::table_types::SimpleTable;
 use crate::db::{
     open_env,
@@ -141,15 +147,17 @@ use crate::db::{
     DbReader,
     DbTransaction,
     DbWriter,
+    KeyAlreadyExistsError,
     TableHandle,
     TableIdentifier,
     TransactionKind,
     RO,
     RW,
 };
-use crate::header::StorageBlockHeader;
+use crate::header::{HeaderStorageReader, HeaderStorageWriter, StorageBlockHeader};
 use crate::mmap_file::MMapFileStats;
 use crate::state::data::IndexedDeprecatedContractClass;
+use crate::state::{StateStorageReader, StateStorageWriter};
 pub use crate::utils::update_storage_metrics;
 use crate::version::{VersionStorageReader, VersionStorageWriter};
 
@@ -165,9 +173,12 @@ pub fn open_storage(
 ) -> StorageResult<(StorageReader, StorageWriter)> {
     let (db_reader, mut db_writer) = open_env(&storage_config.db_config)?;
     let tables = Arc::new(Tables {
+        base_layer_marker_update_time: db_writer
+            .create_simple_table("base_layer_marker_update_time")?,
         block_hash_to_number: db_writer.create_simple_table("block_hash_to_number")?,
         block_signatures: db_writer.create_simple_table("block_signatures")?,
         casms: db_writer.create_simple_table("casms")?,
+        chain_id: db_writer.create_simple_table("chain_id")?,
         contract_storage: db_writer.create_common_prefix_table("contract_storage")?,
         declared_classes: db_writer.create_simple_table("declared_classes")?,
         declared_classes_block: db_writer.create_simple_table("declared_classes_block")?,
@@ -204,9 +215,38 @@ pub fn open_storage(
 
     let writer = set_version_if_needed(reader.clone(), writer)?;
     verify_storage_version(reader.clone())?;
+    let writer = set_or_verify_chain_id(&storage_config.db_config.chain_id, &reader, writer)?;
     Ok((reader, writer))
 }
 
+// In case the chain id does not exist, set it to the configured one. Otherwise, verify that the
+// storage belongs to the configured chain, since the chain id is only part of the storage path and
+// the directory might have been copied or renamed.
+fn set_or_verify_chain_id(
+    chain_id: &ChainId,
+    reader: &StorageReader,
+    mut writer: StorageWriter,
+) -> StorageResult<StorageWriter> {
+    let txn = reader.begin_ro_txn()?;
+    let stored_chain_id = txn.open_table(&txn.tables.chain_id)?.get(&txn.txn, &NoValue)?;
+    match stored_chain_id {
+        None => {
+            let wtxn = writer.begin_rw_txn()?;
+            wtxn.open_table(&wtxn.tables.chain_id)?.insert(&wtxn.txn, &NoValue, chain_id)?;
+            wtxn.commit()?;
+            debug!("Storage was initialized with chain id {chain_id}.");
+        }
+        Some(stored_chain_id) if stored_chain_id != *chain_id => {
+            return Err(StorageError::ChainIdMismatch {
+                stored_chain_id,
+                configured_chain_id: chain_id.clone(),
+            });
+        }
+        Some(_) => {}
+    }
+    Ok(writer)
+}
+
 // In case storage version does not exist, set it to the crate version.
 // Expected to happen once - when the node is launched for the first time.
 // If the storage scope has changed, update accordingly.
@@ -411,6 +451,10 @@ pub struct StorageReader {
 impl StorageReader {
     /// Takes a snapshot of the current state of the storage and returns a [`StorageTxn`] for
     /// reading data from the storage.
+    ///
+    /// This doesn't block on the writer: a read transaction never waits for a write transaction,
+    /// even a long one, and sees the last committed state. If there is no free reader slot, an
+    /// error is returned immediately.
     pub fn begin_ro_txn(&self) -> StorageResult<StorageTxn<'_, RO>> {
         Ok(StorageTxn {
             txn: self.db_reader.begin_ro_txn()?,
@@ -461,10 +505,61 @@ impl StorageWriter {
             scope: self.scope,
         })
     }
+
+    /// Reverts all the data of the blocks after `block_number`, leaving `block_number` as the last
+    /// block in the storage. The reverts are committed in batches of
+    /// [`TRUNCATE_BLOCKS_PER_TXN`] blocks.
+    ///
+    /// Returns an error if `block_number` is not below the header marker.
+    pub fn truncate_to(&mut self, block_number: BlockNumber) -> StorageResult<()> {
+        let txn = self.begin_rw_txn()?;
+        let header_marker = txn.get_header_marker()?;
+        if block_number >= header_marker {
+            return Err(StorageError::TruncateAboveTip { block_number, header_marker });
+        }
+        // Data that is not header based (e.g. the base layer marker) may be ahead of the headers.
+        let mut current = [
+            header_marker,
+            txn.get_body_marker()?,
+            txn.get_state_marker()?,
+            txn.get_base_layer_block_marker()?,
+        ]
+        .into_iter()
+        .max()
+        .expect("Markers array should not be empty.");
+        drop(txn);
+
+        let target_marker = block_number.unchecked_next();
+        while current > target_marker {
+            let batch_end =
+                BlockNumber(current.0.saturating_sub(TRUNCATE_BLOCKS_PER_TXN).max(target_marker.0));
+            let mut txn = self.begin_rw_txn()?;
+            while current > batch_end {
+                current =
+                    current.prev().expect("Block number above the target should be positive.");
+                txn = txn.try_revert_base_layer_marker(current)?;
+                txn = txn.revert_header(current)?.0;
+                txn = txn.revert_body(current)?.0;
+                txn = txn.revert_state_diff(current)?.0;
+            }
+            txn.commit()?;
+            debug!("Truncated storage to {current}.");
+        }
+        Ok(())
+    }
 }
 
+/// The maximal number of blocks reverted in a single transaction by
+/// [`StorageWriter::truncate_to`].
+pub const TRUNCATE_BLOCKS_PER_TXN: u64 = 100;
+
 /// A struct for interacting with the storage.
 /// The actually functionality is implemented on the transaction in multiple traits.
+///
+/// # Isolation
+/// Reads on a RW transaction see the writes made earlier in the same transaction, even before
+/// it is committed. A RO transaction reads a snapshot of the storage taken when it began, so it
+/// never sees uncommitted writes, nor writes committed after it began.
 pub struct StorageTxn<'env, Mode: TransactionKind> {
     txn: DbTransaction<'env, Mode>,
     file_handlers: FileHandlers<Mode>,
@@ -510,9 +605,12 @@ pub fn table_names() -> &'static [&'static str] {
 
 struct_field_names! {
     struct Tables {
+        // The time since the unix epoch at which the base layer marker was last updated.
+        base_layer_marker_update_time: TableIdentifier<NoValue, NoVersionValueWrapper<Duration>, SimpleTable>,
         block_hash_to_number: TableIdentifier<BlockHash, NoVersionValueWrapper<BlockNumber>, SimpleTable>,
         block_signatures: TableIdentifier<BlockNumber, VersionZeroWrapper<BlockSignature>, SimpleTable>,
         casms: TableIdentifier<ClassHash, VersionZeroWrapper<LocationInFile>, SimpleTable>,
+        chain_id: TableIdentifier<NoValue, NoVersionValueWrapper<ChainId>, SimpleTable>,
         // Empirically, defining the common prefix as (ContractAddress, StorageKey) is better space-wise than defining the
         // common prefix only as ContractAddress.
         contract_storage: TableIdentifier<((ContractAddress, StorageKey), BlockNumber), NoVersionValueWrapper<Felt>, CommonPrefix>,
@@ -603,11 +701,46 @@ pub enum StorageError {
          {block_number}."
     )]
     BlockSignatureForNonExistingBlock { block_number: BlockNumber, block_signature: BlockSignature },
+    #[error(
+        "The body of block {block_number} has a different amount of transactions, transaction \
+         outputs and transaction hashes."
+    )]
+    InconsistentBlockBody { block_number: BlockNumber },
+    #[error("Key {key_debug} already exists in table {table}.")]
+    KeyAlreadyExists { table: &'static str, key_debug: String },
+    #[error("Cannot truncate to block {block_number}, the header marker is {header_marker}.")]
+    TruncateAboveTip { block_number: BlockNumber, header_marker: BlockNumber },
+    #[error(
+        "The storage belongs to chain {stored_chain_id}, but the configured chain is \
+         {configured_chain_id}."
+    )]
+    ChainIdMismatch { stored_chain_id: ChainId, configured_chain_id: ChainId },
 }
 
 /// A type alias that maps to std::result::Result<T, StorageError>.
 pub type StorageResult<V> = std::result::Result<V, StorageError>;
 
+impl<'env, K: Key + Debug, V: ValueSerde + Debug, T: TableType> TableHandle<'env, K, V, T>
+where
+    Self: Table<'env, Key = K, Value = V>,
+{
+    // Insert a key value pair only if the key is absent (libmdbx NO_OVERWRITE). If the key already
+    // exists, StorageError::KeyAlreadyExists is returned and the stored value is left untouched.
+    pub(crate) fn insert_new(
+        &'env self,
+        txn: &DbTransaction<'env, RW>,
+        key: &K,
+        value: &<V as ValueSerde>::Value,
+    ) -> StorageResult<()> {
+        self.insert(txn, key, value).map_err(|err| match err {
+            DbError::KeyAlreadyExists(KeyAlreadyExistsError { table_name, key, .. }) => {
+                StorageError::KeyAlreadyExists { table: table_name, key_debug: key }
+            }
+            err => err.into(),
+        })
+    }
+}
+
 /// A struct for the configuration of the storage.
 #[allow(missing_docs)]
 #[derive(Serialize, Debug, Default, Deserialize, Clone, PartialEq, Validate)]
@@ -739,6 +872,9 @@ impl<Mode: TransactionKind> FileHandlers<Mode> {
         &self,
         location: LocationInFile,
     ) -> StorageResult<ThinStateDiff> {
+        if location == LocationInFile::EMPTY {
+            return Ok(ThinStateDiff::default());
+        }
         self.thin_state_diff.get(location)?.ok_or(StorageError::DBInconsistency {
             msg: format!("ThinStateDiff at location {:?} not found.", location),
         })
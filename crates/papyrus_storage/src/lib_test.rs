@@ -0,0 +1,113 @@
+use assert_matches::assert_matches;
+use pretty_assertions::assert_eq;
+use starknet_api::block::{BlockBody, BlockHash, BlockHeader, BlockNumber};
+use starknet_api::felt;
+use starknet_api::state::ThinStateDiff;
+
+use crate::base_layer::{BaseLayerStorageReader, BaseLayerStorageWriter};
+use crate::body::{BodyStorageReader, BodyStorageWriter};
+use crate::class::{ClassStorageReader, ClassStorageWriter};
+use crate::compiled_class::CasmStorageReader;
+use crate::header::{HeaderStorageReader, HeaderStorageWriter};
+use crate::state::{StateStorageReader, StateStorageWriter};
+use crate::test_utils::get_test_storage;
+use crate::StorageError;
+
+const N_BLOCKS: u64 = 20;
+
+#[test]
+fn truncate_to() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    for i in 0..N_BLOCKS {
+        let block_number = BlockNumber(i);
+        writer
+            .begin_rw_txn()
+            .unwrap()
+            .append_header(
+                block_number,
+                &BlockHeader {
+                    block_hash: BlockHash(felt!(i)),
+                    block_number,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+            .append_body(block_number, BlockBody::default())
+            .unwrap()
+            .append_state_diff(block_number, ThinStateDiff::default())
+            .unwrap()
+            .append_classes(block_number, &[], &[])
+            .unwrap()
+            .commit()
+            .unwrap();
+    }
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .update_base_layer_block_marker(&BlockNumber(N_BLOCKS))
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    writer.truncate_to(BlockNumber(5)).unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    let expected_marker = BlockNumber(6);
+    assert_eq!(txn.get_header_marker().unwrap(), expected_marker);
+    assert_eq!(txn.get_body_marker().unwrap(), expected_marker);
+    assert_eq!(txn.get_state_marker().unwrap(), expected_marker);
+    assert_eq!(txn.get_class_marker().unwrap(), expected_marker);
+    assert_eq!(txn.get_compiled_class_marker().unwrap(), expected_marker);
+    assert_eq!(txn.get_base_layer_block_marker().unwrap(), expected_marker);
+    assert!(txn.get_block_header(BlockNumber(5)).unwrap().is_some());
+    assert!(txn.get_block_header(BlockNumber(6)).unwrap().is_none());
+}
+
+#[test]
+fn truncate_above_tip() {
+    let ((_reader, mut writer), _temp_dir) = get_test_storage();
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &BlockHeader::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    assert_matches!(
+        writer.truncate_to(BlockNumber(1)),
+        Err(StorageError::TruncateAboveTip {
+            block_number: BlockNumber(1),
+            header_marker: BlockNumber(1)
+        })
+    );
+    // Truncating to the last block is a no-op.
+    writer.truncate_to(BlockNumber(0)).unwrap();
+}
+
+#[test]
+fn rw_txn_reads_its_own_writes() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let snapshot_before_commit = reader.begin_ro_txn().unwrap();
+
+    let txn = writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &BlockHeader::default())
+        .unwrap();
+    assert_eq!(txn.get_header_marker().unwrap(), BlockNumber(1));
+    assert_eq!(txn.get_block_header(BlockNumber(0)).unwrap(), Some(BlockHeader::default()));
+
+    let concurrent_snapshot = reader.begin_ro_txn().unwrap();
+    assert_eq!(concurrent_snapshot.get_header_marker().unwrap(), BlockNumber(0));
+    assert_eq!(concurrent_snapshot.get_block_header(BlockNumber(0)).unwrap(), None);
+
+    txn.commit().unwrap();
+
+    // Snapshots taken before the commit are not affected by it.
+    assert_eq!(snapshot_before_commit.get_header_marker().unwrap(), BlockNumber(0));
+    assert_eq!(concurrent_snapshot.get_header_marker().unwrap(), BlockNumber(0));
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_header_marker().unwrap(), BlockNumber(1));
+    assert_eq!(txn.get_block_header(BlockNumber(0)).unwrap(), Some(BlockHeader::default()));
+}
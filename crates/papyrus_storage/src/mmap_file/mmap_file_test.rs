@@ -132,6 +132,8 @@ fn grow_file() {
         max_size: 10 * serialization_size,
         max_object_size: serialization_size, // 3 (len + data)
         growth_step: serialization_size + 1, // 4
+        compression_algorithm: CompressionAlgorithm::Zstd,
+        compression_level: zstd::DEFAULT_COMPRESSION_LEVEL,
     };
 
     let file_path = dir.path().to_path_buf().join("test_grow_file");
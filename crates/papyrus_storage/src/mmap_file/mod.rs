@@ -24,9 +24,10 @@ use serde::{Deserialize, Serialize};
 #[cfg(test)]
 use test_utils::GetTestInstance;
 use thiserror::Error;
-use tracing::{debug, instrument, trace};
+use tracing::{debug, info, instrument, trace};
 use validator::{Validate, ValidationError};
 
+use crate::compression_utils::{Compression, CompressionAlgorithm};
 use crate::db::serialization::{StorageSerde, StorageSerdeError, ValueSerde};
 use crate::db::{TransactionKind, RO, RW};
 
@@ -42,6 +43,20 @@ pub struct MmapFileConfig {
     pub growth_step: usize,
     /// The maximum size of an object in bytes.
     pub max_object_size: usize,
+    /// The compression algorithm of the compressed parts of the objects written to the file.
+    pub compression_algorithm: CompressionAlgorithm,
+    /// The zstd compression level of the compressed parts of the objects written to the file.
+    #[validate(range(min = 1, max = 22))]
+    pub compression_level: i32,
+}
+
+impl MmapFileConfig {
+    fn compression(&self) -> Compression {
+        match self.compression_algorithm {
+            CompressionAlgorithm::None => Compression::None,
+            CompressionAlgorithm::Zstd => Compression::Zstd(self.compression_level),
+        }
+    }
 }
 
 impl SerializeConfig for MmapFileConfig {
@@ -66,6 +81,22 @@ impl SerializeConfig for MmapFileConfig {
                 "The maximum size of a single object in the file in bytes",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "compression_algorithm",
+                &self.compression_algorithm,
+                "The compression algorithm of the classes and other compressed data written to \
+                 the file, either zstd or none. Changing it doesn't affect reading data that was \
+                 already written.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "compression_level",
+                &self.compression_level,
+                "The zstd compression level (1-22) of the classes and other compressed data \
+                 written to the file. Higher levels are slower but compress better. Changing it \
+                 doesn't affect reading data that was already written.",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
@@ -76,6 +107,8 @@ impl Default for MmapFileConfig {
             max_size: 1 << 40,        // 1TB
             growth_step: 1 << 30,     // 1GB
             max_object_size: 1 << 28, // 256MB
+            compression_algorithm: CompressionAlgorithm::Zstd,
+            compression_level: zstd::DEFAULT_COMPRESSION_LEVEL,
         }
     }
 }
@@ -127,6 +160,9 @@ pub struct LocationInFile {
 }
 
 impl LocationInFile {
+    /// The location of an object that isn't written to the file.
+    pub(crate) const EMPTY: Self = Self { offset: 0, len: 0 };
+
     /// returns the next offset in the file.
     pub fn next_offset(&self) -> usize {
         self.offset + self.len
@@ -137,6 +173,7 @@ impl LocationInFile {
 #[derive(Debug)]
 struct MMapFile<V: ValueSerde> {
     config: MmapFileConfig,
+    path: PathBuf,
     file: File,
     size: usize,
     mmap: MmapMut,
@@ -150,7 +187,10 @@ impl<V: ValueSerde> MMapFile<V> {
     fn grow(&mut self) {
         self.flush();
         let new_size = self.size + self.config.growth_step;
-        debug!("Growing file to size: {}", new_size);
+        info!(
+            "Growing file {:?} from {} to {} bytes (offset: {}, mapped size: {}).",
+            self.path, self.size, new_size, self.offset, self.config.max_size
+        );
         self.file.set_len(new_size as u64).expect("Failed to set the file size");
         self.size = new_size;
     }
@@ -170,12 +210,14 @@ pub(crate) fn open_file<V: ValueSerde>(
     path: PathBuf,
     offset: usize,
 ) -> MmapFileResult<(FileHandler<V, RW>, FileHandler<V, RO>)> {
-    let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+    let file =
+        OpenOptions::new().read(true).write(true).create(true).truncate(false).open(&path)?;
     let size = file.metadata()?.len();
     let mmap = unsafe { MmapOptions::new().len(config.max_size).map_mut(&file)? };
     let mmap_ptr = mmap.as_ptr();
     let mmap_file = MMapFile {
         config,
+        path,
         file,
         mmap,
         size: size.try_into().expect("size should fit in usize"),
@@ -225,7 +267,10 @@ impl<V: ValueSerde> FileHandler<V, RW> {
 impl<V: ValueSerde + Debug> Writer<V> for FileHandler<V, RW> {
     fn append(&mut self, val: &V::Value) -> LocationInFile {
         trace!("Inserting object: {:?}", val);
-        let serialized = V::serialize(val).expect("Should be able to serialize");
+        let compression =
+            self.mmap_file.lock().expect("Lock should not be poisoned").config.compression();
+        let serialized =
+            V::serialize_with_compression(val, compression).expect("Should be able to serialize");
         let len = serialized.len();
         let offset;
         {
@@ -278,6 +323,18 @@ pub struct MMapFileStats {
     offset: usize,
 }
 
+impl MMapFileStats {
+    /// Returns the current size of the file in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the amount of data that has been written to the file in bytes.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
 impl<V: ValueSerde, Mode: TransactionKind> FileHandler<V, Mode> {
     pub fn stats(&self) -> MMapFileStats {
         let mmap_file = self.mmap_file.lock().expect("Lock should not be poisoned");
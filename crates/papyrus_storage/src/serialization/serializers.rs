@@ -3,6 +3,7 @@ use std::convert::TryFrom;
 use std::hash::Hash;
 use std::ops::Deref;
 use std::sync::Arc;
+use std::time::Duration;
 
 use byteorder::BigEndian;
 use cairo_lang_casm::hints::Hint;
@@ -19,7 +20,10 @@ use num_bigint::BigUint;
 use parity_scale_codec::{Decode, Encode};
 use primitive_types::H160;
 use starknet_api::block::{
+    Block,
+    BlockBody,
     BlockHash,
+    BlockHeader,
     BlockNumber,
     BlockSignature,
     BlockStatus,
@@ -29,6 +33,7 @@ use starknet_api::block::{
     StarknetVersion,
 };
 use starknet_api::core::{
+    ChainId,
     ClassHash,
     CompiledClassHash,
     ContractAddress,
@@ -132,6 +137,7 @@ use crate::compression_utils::{
     decompress,
     decompress_from_reader,
     serialize_and_compress,
+    Compression,
     IsCompressed,
 };
 use crate::db::serialization::{StorageSerde, StorageSerdeError};
@@ -149,7 +155,35 @@ const COMPRESSION_THRESHOLD_BYTES: usize = 384;
 
 auto_storage_serde! {
     pub struct AccountDeploymentData(pub Vec<Felt>);
+    pub struct Block {
+        pub header: BlockHeader,
+        pub body: BlockBody,
+    }
+    pub struct BlockBody {
+        pub transactions: Vec<Transaction>,
+        pub transaction_outputs: Vec<TransactionOutput>,
+        pub transaction_hashes: Vec<TransactionHash>,
+    }
     pub struct BlockHash(pub StarkHash);
+    pub struct BlockHeader {
+        pub block_hash: BlockHash,
+        pub parent_hash: BlockHash,
+        pub block_number: BlockNumber,
+        pub l1_gas_price: GasPricePerToken,
+        pub l1_data_gas_price: GasPricePerToken,
+        pub state_root: GlobalRoot,
+        pub sequencer: SequencerContractAddress,
+        pub timestamp: BlockTimestamp,
+        pub l1_da_mode: L1DataAvailabilityMode,
+        pub state_diff_commitment: Option<StateDiffCommitment>,
+        pub state_diff_length: Option<usize>,
+        pub transaction_commitment: Option<TransactionCommitment>,
+        pub event_commitment: Option<EventCommitment>,
+        pub n_transactions: usize,
+        pub n_events: usize,
+        pub receipt_commitment: Option<ReceiptCommitment>,
+        pub starknet_version: StarknetVersion,
+    }
     pub struct StorageBlockHeader {
         pub block_hash: BlockHash,
         pub parent_hash: BlockHash,
@@ -574,12 +608,21 @@ macro_rules! auto_storage_serde {
     ($(pub)? enum $name:ident { $($variant:ident $( ($ty:ty) )? = $num:expr ,)* } $($rest:tt)*) => {
         impl StorageSerde for $name {
             fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+                self.serialize_into_with_compression(res, Compression::default())
+            }
+            // The compression is unused in enums without values.
+            #[allow(unused_variables)]
+            fn serialize_into_with_compression(
+                &self,
+                res: &mut impl std::io::Write,
+                compression: Compression,
+            ) -> Result<(), StorageSerdeError> {
                 match self {
                     $(
                         variant!( value, $variant $( ($ty) )?) => {
                             res.write_all(&[$num as u8])?;
                             $(
-                                (value as &$ty).serialize_into(res)?;
+                                (value as &$ty).serialize_into_with_compression(res, compression)?;
                             )?
                             Ok(())
                         }
@@ -634,6 +677,16 @@ pub(crate) use variant;
 ////////////////////////////////////////////////////////////////////////
 // Starknet API structs.
 ////////////////////////////////////////////////////////////////////////
+impl StorageSerde for ChainId {
+    fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+        self.to_string().serialize_into(res)
+    }
+
+    fn deserialize_from(bytes: &mut impl std::io::Read) -> Option<Self> {
+        Some(Self::from(String::deserialize_from(bytes)?))
+    }
+}
+
 impl StorageSerde for ContractAddress {
     fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
         self.0.serialize_into(res)
@@ -753,6 +806,19 @@ impl StorageSerde for usize {
     }
 }
 
+impl StorageSerde for Duration {
+    fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+        self.as_secs().serialize_into(res)?;
+        self.subsec_nanos().serialize_into(res)
+    }
+
+    fn deserialize_from(bytes: &mut impl std::io::Read) -> Option<Self> {
+        let secs = u64::deserialize_from(bytes)?;
+        let nanos = u32::deserialize_from(bytes)?;
+        Some(Duration::new(secs, nanos))
+    }
+}
+
 impl<T: StorageSerde> StorageSerde for Vec<T> {
     fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
         res.write_varint(self.len())?;
@@ -939,9 +1005,17 @@ impl StorageSerde for TransactionOffsetInBlock {
 ////////////////////////////////////////////////////////////////////////
 impl StorageSerde for ContractClass {
     fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
-        serialize_and_compress(&self.sierra_program)?.serialize_into(res)?;
+        self.serialize_into_with_compression(res, Compression::default())
+    }
+
+    fn serialize_into_with_compression(
+        &self,
+        res: &mut impl std::io::Write,
+        compression: Compression,
+    ) -> Result<(), StorageSerdeError> {
+        serialize_and_compress(&self.sierra_program, compression)?.serialize_into(res)?;
         self.entry_points_by_type.serialize_into(res)?;
-        serialize_and_compress(&self.abi)?.serialize_into(res)?;
+        serialize_and_compress(&self.abi, compression)?.serialize_into(res)?;
         Ok(())
     }
 
@@ -962,6 +1036,14 @@ create_storage_serde_test!(ContractClass);
 
 impl StorageSerde for DeprecatedContractClass {
     fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+        self.serialize_into_with_compression(res, Compression::default())
+    }
+
+    fn serialize_into_with_compression(
+        &self,
+        res: &mut impl std::io::Write,
+        compression: Compression,
+    ) -> Result<(), StorageSerdeError> {
         // Compress together the program and abi for better compression results.
         let mut to_compress: Vec<u8> = Vec::new();
         self.abi.serialize_into(&mut to_compress)?;
@@ -973,7 +1055,7 @@ impl StorageSerde for DeprecatedContractClass {
                 to_compress.len()
             );
         }
-        let compressed = compress(to_compress.as_slice())?;
+        let compressed = compress(to_compress.as_slice(), compression)?;
         compressed.serialize_into(res)?;
         self.entry_points_by_type.serialize_into(res)?;
         Ok(())
@@ -1019,6 +1101,14 @@ impl<TYPE: Default> StorageSerde for FunctionAbiEntry<TYPE> {
 
 impl StorageSerde for CasmContractClass {
     fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+        self.serialize_into_with_compression(res, Compression::default())
+    }
+
+    fn serialize_into_with_compression(
+        &self,
+        res: &mut impl std::io::Write,
+        compression: Compression,
+    ) -> Result<(), StorageSerdeError> {
         let mut to_compress: Vec<u8> = Vec::new();
         self.prime.serialize_into(&mut to_compress)?;
         self.compiler_version.serialize_into(&mut to_compress)?;
@@ -1034,7 +1124,7 @@ impl StorageSerde for CasmContractClass {
                 to_compress.len()
             );
         }
-        let compressed = compress(to_compress.as_slice())?;
+        let compressed = compress(to_compress.as_slice(), compression)?;
         compressed.serialize_into(res)?;
 
         Ok(())
@@ -1062,6 +1152,14 @@ create_storage_serde_test!(CasmContractClass);
 
 impl StorageSerde for ThinStateDiff {
     fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+        self.serialize_into_with_compression(res, Compression::default())
+    }
+
+    fn serialize_into_with_compression(
+        &self,
+        res: &mut impl std::io::Write,
+        compression: Compression,
+    ) -> Result<(), StorageSerdeError> {
         let mut to_compress: Vec<u8> = Vec::new();
         self.deployed_contracts.serialize_into(&mut to_compress)?;
         self.storage_diffs.serialize_into(&mut to_compress)?;
@@ -1076,7 +1174,7 @@ impl StorageSerde for ThinStateDiff {
                 to_compress.len()
             );
         }
-        let compressed = compress(to_compress.as_slice())?;
+        let compressed = compress(to_compress.as_slice(), compression)?;
         compressed.serialize_into(res)?;
         Ok(())
     }
@@ -1106,6 +1204,13 @@ macro_rules! auto_storage_serde_conditionally_compressed {
     ($(pub)? struct $name:ident { $(pub $field:ident : $ty:ty ,)* } $($rest:tt)*) => {
         impl StorageSerde for $name {
             fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+                self.serialize_into_with_compression(res, Compression::default())
+            }
+            fn serialize_into_with_compression(
+                &self,
+                res: &mut impl std::io::Write,
+                compression: Compression,
+            ) -> Result<(), StorageSerdeError> {
                 let mut to_compress: Vec<u8> = Vec::new();
                 $(
                     self.$field.serialize_into(&mut to_compress)?;
@@ -1120,7 +1225,7 @@ macro_rules! auto_storage_serde_conditionally_compressed {
                             to_compress.len()
                         );
                     }
-                    let compressed = compress(to_compress.as_slice())?;
+                    let compressed = compress(to_compress.as_slice(), compression)?;
                     compressed.serialize_into(res)?;
                 } else {
                     IsCompressed::No.serialize_into(res)?;
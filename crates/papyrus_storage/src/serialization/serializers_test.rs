@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use std::sync::Arc;
 
 use cairo_lang_casm::hints::CoreHintBase;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
@@ -7,9 +8,16 @@ use starknet_api::block::BlockNumber;
 use starknet_api::core::ContractAddress;
 use starknet_api::hash::StarkHash;
 use starknet_api::state::StorageKey;
-use starknet_api::transaction::TransactionOffsetInBlock;
+use starknet_api::transaction::{
+    Calldata,
+    InvokeTransaction,
+    InvokeTransactionV1,
+    Transaction,
+    TransactionOffsetInBlock,
+};
 use test_utils::{get_rng, read_json_file, GetTestInstance};
 
+use crate::compression_utils::Compression;
 use crate::db::serialization::StorageSerde;
 
 pub trait StorageSerdeTest: StorageSerde {
@@ -131,3 +139,25 @@ fn serialization_precision() {
     let deserialized = serde_json::to_string(&serialized).unwrap();
     assert_eq!(input, deserialized);
 }
+
+#[test]
+fn transaction_compression_is_forwarded() {
+    // Calldata large enough to be compressed.
+    let calldata = Calldata(Arc::new(vec![StarkHash::ONE; 1000]));
+    let transaction = Transaction::Invoke(InvokeTransaction::V1(InvokeTransactionV1 {
+        calldata,
+        ..Default::default()
+    }));
+    let serialize_with_compression = |compression| {
+        let mut serialized = Vec::new();
+        transaction.serialize_into_with_compression(&mut serialized, compression).unwrap();
+        serialized
+    };
+
+    let compressed = serialize_with_compression(Compression::Zstd(1));
+    let uncompressed = serialize_with_compression(Compression::None);
+    assert!(compressed.len() < uncompressed.len());
+    for serialized in [compressed, uncompressed] {
+        assert_eq!(Transaction::deserialize_from(&mut serialized.as_slice()).unwrap(), transaction);
+    }
+}
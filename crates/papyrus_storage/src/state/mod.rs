@@ -260,6 +260,57 @@ impl<'env, Mode: TransactionKind> StateReader<'env, Mode> {
         }
     }
 
+    /// Returns the contracts deployed at a given state number together with their class hashes at
+    /// that state, ordered by address, and the address to continue from if the scan stopped before
+    /// the end of the table.
+    ///
+    /// The scan stops at the first address after `limit` contracts were found or after
+    /// `max_scanned_entries` entries were read, so fewer than `limit` contracts may be returned
+    /// even if more contracts follow.
+    ///
+    /// # Arguments
+    /// * state_number - state number to search before.
+    /// * start_address - the first contract address to scan, used for paging.
+    /// * limit - the maximal number of contracts to return.
+    /// * max_scanned_entries - the number of table entries after which the scan stops.
+    ///
+    /// # Errors
+    /// Returns [`StorageError`] if there was an error searching the table.
+    #[allow(clippy::type_complexity)]
+    pub fn get_deployed_contracts_at(
+        &self,
+        state_number: StateNumber,
+        start_address: &ContractAddress,
+        limit: usize,
+        max_scanned_entries: usize,
+    ) -> StorageResult<(Vec<(ContractAddress, ClassHash)>, Option<ContractAddress>)> {
+        let first_irrelevant_block: BlockNumber = state_number.block_after();
+        let mut cursor = self.deployed_contracts_table.cursor(self.txn)?;
+        let mut current = cursor.lower_bound(&(*start_address, BlockNumber(0)))?;
+        let mut deployed_contracts = Vec::new();
+        let mut current_address = None;
+        // The entries of an address are ordered by block number, so the last entry before
+        // `first_irrelevant_block` holds the class hash of the address at the state number.
+        let mut last_relevant_entry: Option<(ContractAddress, ClassHash)> = None;
+        let mut n_scanned_entries = 0;
+        while let Some(((address, block_number), class_hash)) = current {
+            if current_address != Some(address) {
+                deployed_contracts.extend(last_relevant_entry.take());
+                if deployed_contracts.len() >= limit || n_scanned_entries >= max_scanned_entries {
+                    return Ok((deployed_contracts, Some(address)));
+                }
+                current_address = Some(address);
+            }
+            n_scanned_entries += 1;
+            if block_number < first_irrelevant_block {
+                last_relevant_entry = Some((address, class_hash));
+            }
+            current = cursor.next()?;
+        }
+        deployed_contracts.extend(last_relevant_entry);
+        Ok((deployed_contracts, None))
+    }
+
     /// Returns the nonce at a given state number.
     /// If there is no nonce at the given state number, returns `None`.
     ///
@@ -397,6 +448,18 @@ impl<'env, Mode: TransactionKind> StateReader<'env, Mode> {
         Ok(self.declared_classes_block_table.get(self.txn, class_hash)?)
     }
 
+    /// Returns the block number in which a deprecated class was declared, without reading the
+    /// class itself.
+    pub fn get_deprecated_class_definition_block_number(
+        &self,
+        class_hash: &ClassHash,
+    ) -> StorageResult<Option<BlockNumber>> {
+        Ok(self
+            .deprecated_declared_classes_table
+            .get(self.txn, class_hash)?
+            .map(|indexed_class| indexed_class.block_number))
+    }
+
     /// Returns the deprecated contract class at a given state number for a given class hash.
     /// If class is not found, returns `None`.
     /// If class is defined but in a block after given state number, returns `None`.
@@ -468,10 +531,19 @@ impl<'env> StateStorageWriter for StorageTxn<'env, RW> {
             declared_classes_block_table.insert(&self.txn, class_hash, &block_number)?;
         }
 
-        // Write state diff.
-        let location = self.file_handlers.append_state_diff(&thin_state_diff);
+        // Write state diff. Empty state diffs aren't written to the file.
+        let location = if thin_state_diff.is_empty() {
+            LocationInFile::EMPTY
+        } else {
+            let location = self.file_handlers.append_state_diff(&thin_state_diff);
+            file_offset_table.upsert(
+                &self.txn,
+                &OffsetKind::ThinStateDiff,
+                &location.next_offset(),
+            )?;
+            location
+        };
         state_diffs_table.append(&self.txn, &block_number, &location)?;
-        file_offset_table.upsert(&self.txn, &OffsetKind::ThinStateDiff, &location.next_offset())?;
 
         update_marker_to_next_block(&self.txn, &markers_table, MarkerKind::State, block_number)?;
 
@@ -13,9 +13,10 @@ use test_utils::get_test_state_diff;
 
 use crate::class::{ClassStorageReader, ClassStorageWriter};
 use crate::compiled_class::{CasmStorageReader, CasmStorageWriter};
+use crate::db::table_types::Table;
 use crate::state::{StateStorageReader, StateStorageWriter};
 use crate::test_utils::get_test_storage;
-use crate::StorageWriter;
+use crate::{OffsetKind, StorageWriter};
 
 #[test]
 fn get_class_definition_at() {
@@ -359,6 +360,43 @@ async fn get_reverted_state_diff_returns_none() {
     assert!(reader.begin_ro_txn().unwrap().get_state_diff(BlockNumber(1)).unwrap().is_none());
 }
 
+#[test]
+fn append_empty_state_diff() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let (diff, _, _) = ThinStateDiff::from_state_diff(get_test_state_diff());
+    assert!(!diff.is_empty());
+    assert!(ThinStateDiff::default().is_empty());
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(BlockNumber(0), diff.clone())
+        .unwrap()
+        .commit()
+        .unwrap();
+    let get_file_offset = || {
+        let txn = reader.begin_ro_txn().unwrap();
+        let file_offsets_table = txn.txn.open_table(&txn.tables.file_offsets).unwrap();
+        file_offsets_table.get(&txn.txn, &OffsetKind::ThinStateDiff).unwrap()
+    };
+    let file_offset = get_file_offset();
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(BlockNumber(1), ThinStateDiff::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    // The marker advances past the empty state diff without writing it to the file.
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_state_marker().unwrap(), BlockNumber(2));
+    assert_eq!(txn.get_state_diff(BlockNumber(0)).unwrap(), Some(diff));
+    assert_eq!(txn.get_state_diff(BlockNumber(1)).unwrap(), Some(ThinStateDiff::default()));
+    assert_eq!(get_file_offset(), file_offset);
+}
+
 fn append_2_state_diffs(writer: &mut StorageWriter) {
     writer
         .begin_rw_txn()
@@ -685,6 +723,77 @@ fn replace_class() {
     assert_eq!(current_class_hash, class_hash0);
 }
 
+#[test]
+fn get_deployed_contracts_at() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let address0 = ContractAddress(patricia_key!("0x0"));
+    let address1 = ContractAddress(patricia_key!("0x1"));
+    let address2 = ContractAddress(patricia_key!("0x2"));
+    let class_hash0 = ClassHash(felt!("0x10"));
+    let class_hash1 = ClassHash(felt!("0x11"));
+
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(
+            BlockNumber(0),
+            ThinStateDiff {
+                deployed_contracts: indexmap! {
+                    address1 => class_hash0,
+                    address2 => class_hash0,
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .append_state_diff(
+            BlockNumber(1),
+            ThinStateDiff {
+                deployed_contracts: indexmap! { address0 => class_hash0 },
+                replaced_classes: indexmap! { address1 => class_hash1 },
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let txn = reader.begin_ro_txn().unwrap();
+    let state_reader = txn.get_state_reader().unwrap();
+
+    let state1 = StateNumber(BlockNumber(1));
+    assert_eq!(
+        state_reader.get_deployed_contracts_at(state1, &address0, 10, 10).unwrap(),
+        (vec![(address1, class_hash0), (address2, class_hash0)], None)
+    );
+
+    let state2 = StateNumber(BlockNumber(2));
+    assert_eq!(
+        state_reader.get_deployed_contracts_at(state2, &address0, 10, 10).unwrap(),
+        (vec![(address0, class_hash0), (address1, class_hash1), (address2, class_hash0)], None)
+    );
+    assert_eq!(
+        state_reader.get_deployed_contracts_at(state2, &address1, 1, 10).unwrap(),
+        (vec![(address1, class_hash1)], Some(address2))
+    );
+    assert_eq!(
+        state_reader.get_deployed_contracts_at(state2, &address0, 0, 10).unwrap(),
+        (vec![], Some(address0))
+    );
+
+    // The scan stops at the first address after the scanned entries bound, even if no contract
+    // was found, so that contracts deployed after the state number aren't all scanned.
+    assert_eq!(
+        state_reader.get_deployed_contracts_at(state1, &address0, 10, 1).unwrap(),
+        (vec![], Some(address1))
+    );
+    // The entries of an address are all scanned before stopping.
+    assert_eq!(
+        state_reader.get_deployed_contracts_at(state2, &address1, 10, 1).unwrap(),
+        (vec![(address1, class_hash1)], Some(address2))
+    );
+}
+
 // TODO(shahak): Add test where the state was reverted before the class definitions were written.
 #[test]
 fn declare_revert_declare_scenario() {
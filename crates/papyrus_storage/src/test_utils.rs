@@ -4,6 +4,7 @@
 use starknet_api::core::ChainId;
 use tempfile::{tempdir, TempDir};
 
+use crate::compression_utils::CompressionAlgorithm;
 use crate::db::DbConfig;
 use crate::mmap_file::MmapFileConfig;
 use crate::{open_storage, StorageConfig, StorageReader, StorageScope, StorageWriter};
@@ -47,6 +48,8 @@ pub fn get_mmap_file_test_config() -> MmapFileConfig {
         max_size: 1 << 24,        // 16MB
         growth_step: 1 << 20,     // 1MB
         max_object_size: 1 << 16, // 64KB
+        compression_algorithm: CompressionAlgorithm::Zstd,
+        compression_level: zstd::DEFAULT_COMPRESSION_LEVEL,
     }
 }
 
@@ -9,17 +9,33 @@ use std::io::{BufWriter, Write};
 
 use metrics::{absolute_counter, gauge};
 use serde::Serialize;
+#[cfg(any(feature = "block_serialization", test))]
+use starknet_api::block::Block;
 use starknet_api::block::BlockNumber;
 use starknet_api::core::{ChainId, ClassHash, CompiledClassHash};
 use starknet_api::state::{EntryPoint, EntryPointType};
+use starknet_api::transaction::TransactionOffsetInBlock;
 use starknet_types_core::felt::Felt;
 use tracing::debug;
 
+use crate::base_layer::BaseLayerStorageReader;
+use crate::body::{BodyStorageReader, TransactionIndex};
 use crate::compiled_class::CasmStorageReader;
+#[cfg(any(feature = "block_serialization", test))]
+use crate::db::serialization::{ValueSerde, VersionZeroWrapper};
 use crate::db::table_types::Table;
 use crate::db::RO;
+use crate::header::HeaderStorageReader;
 use crate::state::StateStorageReader;
-use crate::{open_storage, StorageConfig, StorageError, StorageReader, StorageResult, StorageTxn};
+use crate::{
+    open_storage,
+    StorageConfig,
+    StorageError,
+    StorageReader,
+    StorageResult,
+    StorageScope,
+    StorageTxn,
+};
 
 #[derive(Serialize)]
 struct DumpDeclaredClass {
@@ -98,5 +114,107 @@ pub fn update_storage_metrics(reader: &StorageReader) -> StorageResult<()> {
     let info = reader.db_reader.get_db_info()?;
     absolute_counter!("storage_last_page_number", info.last_pgno() as u64);
     absolute_counter!("storage_last_transaction_index", info.last_txnid() as u64);
+    for (file_name, stats) in reader.mmap_files_stats() {
+        gauge!("storage_mmap_file_size_bytes", stats.size() as f64, "file" => file_name.clone());
+        gauge!("storage_mmap_file_offset_bytes", stats.offset() as f64, "file" => file_name);
+    }
     Ok(())
 }
+
+/// Serializes a block into a compact, version-tagged binary representation.
+///
+/// The representation starts with a version tag, and the layout of the rest is fixed for a given
+/// tag. Any change to the layout comes with a new tag, so bytes are never read with a different
+/// layout than the one they were written with.
+#[cfg(any(feature = "block_serialization", test))]
+pub fn serialize_block(block: &Block) -> StorageResult<Vec<u8>> {
+    Ok(VersionZeroWrapper::<Block>::serialize(block)?)
+}
+
+/// Deserializes a block from the representation created by [`serialize_block`]. Returns None if
+/// the bytes are malformed or tagged with an unknown version.
+#[cfg(any(feature = "block_serialization", test))]
+pub fn deserialize_block(mut bytes: &[u8]) -> Option<Block> {
+    VersionZeroWrapper::<Block>::deserialize(&mut bytes)
+}
+
+/// An inconsistency found in the storage by [`check_integrity`].
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IntegrityIssue {
+    /// A marker is ahead of a marker that should bound it.
+    MarkerAhead {
+        marker: &'static str,
+        marker_value: BlockNumber,
+        bounding_marker: &'static str,
+        bounding_marker_value: BlockNumber,
+    },
+    /// A block below the body marker has a header with transactions but no stored body.
+    MissingBody { block_number: BlockNumber },
+    /// A class declared in a block below the compiled class marker has no stored casm.
+    MissingCasm { block_number: BlockNumber, class_hash: ClassHash },
+}
+
+/// Checks the storage for inconsistencies without modifying it and returns all the issues found.
+/// The checked invariants are:
+/// - CompiledClass <= State <= Header, Body <= Header and BaseLayerBlock <= Header.
+/// - Every block below the body marker has its body stored (only for a full archive storage).
+/// - Every class declared below the compiled class marker has its casm stored.
+pub fn check_integrity(reader: &StorageReader) -> StorageResult<Vec<IntegrityIssue>> {
+    let txn = reader.begin_ro_txn()?;
+    let mut issues = Vec::new();
+
+    let header_marker = txn.get_header_marker()?;
+    let body_marker = txn.get_body_marker()?;
+    let state_marker = txn.get_state_marker()?;
+    let compiled_class_marker = txn.get_compiled_class_marker()?;
+    let base_layer_marker = txn.get_base_layer_block_marker()?;
+    let marker_bounds = [
+        (("CompiledClass", compiled_class_marker), ("State", state_marker)),
+        (("State", state_marker), ("Header", header_marker)),
+        (("Body", body_marker), ("Header", header_marker)),
+        (("BaseLayerBlock", base_layer_marker), ("Header", header_marker)),
+    ];
+    for ((marker, marker_value), (bounding_marker, bounding_marker_value)) in marker_bounds {
+        if marker_value > bounding_marker_value {
+            issues.push(IntegrityIssue::MarkerAhead {
+                marker,
+                marker_value,
+                bounding_marker,
+                bounding_marker_value,
+            });
+        }
+    }
+
+    if reader.scope == StorageScope::FullArchive {
+        let transaction_metadata_table = txn.open_table(&txn.tables.transaction_metadata)?;
+        for block_number in 0..body_marker.min(header_marker).0 {
+            let block_number = BlockNumber(block_number);
+            let Some(header) = txn.get_block_header(block_number)? else {
+                continue;
+            };
+            let first_transaction_index =
+                TransactionIndex(block_number, TransactionOffsetInBlock(0));
+            if header.n_transactions > 0
+                && transaction_metadata_table.get(&txn.txn, &first_transaction_index)?.is_none()
+            {
+                issues.push(IntegrityIssue::MissingBody { block_number });
+            }
+        }
+    }
+
+    let casms_table = txn.open_table(&txn.tables.casms)?;
+    for block_number in 0..compiled_class_marker.min(state_marker).0 {
+        let block_number = BlockNumber(block_number);
+        let Some(state_diff) = txn.get_state_diff(block_number)? else {
+            continue;
+        };
+        for class_hash in state_diff.declared_classes.keys() {
+            if casms_table.get(&txn.txn, class_hash)?.is_none() {
+                issues.push(IntegrityIssue::MissingCasm { block_number, class_hash: *class_hash });
+            }
+        }
+    }
+
+    Ok(issues)
+}
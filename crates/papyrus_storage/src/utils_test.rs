@@ -5,18 +5,38 @@ use indexmap::indexmap;
 use metrics_exporter_prometheus::PrometheusBuilder;
 use pretty_assertions::assert_eq;
 use prometheus_parse::Value::{Counter, Gauge};
-use starknet_api::block::BlockNumber;
-use starknet_api::core::{ClassHash, CompiledClassHash};
+use starknet_api::block::{
+    Block,
+    BlockBody,
+    BlockHash,
+    BlockHeader,
+    BlockNumber,
+    BlockTimestamp,
+    GasPrice,
+    GasPricePerToken,
+    StarknetVersion,
+};
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress};
 use starknet_api::hash::StarkHash;
-use starknet_api::state::{ContractClass, ThinStateDiff};
+use starknet_api::state::{ContractClass, StorageKey, ThinStateDiff};
 use starknet_types_core::felt::Felt;
-use test_utils::prometheus_is_contained;
+use test_utils::{get_test_block, prometheus_is_contained};
 
 use super::update_storage_metrics;
 use crate::class::ClassStorageWriter;
+use crate::db::table_types::Table;
+use crate::header::HeaderStorageWriter;
 use crate::state::StateStorageWriter;
-use crate::test_utils::get_test_storage;
-use crate::utils::{dump_declared_classes_table_by_block_range_internal, DumpDeclaredClass};
+use crate::test_utils::{get_test_config, get_test_storage};
+use crate::utils::{
+    check_integrity,
+    deserialize_block,
+    dump_declared_classes_table_by_block_range_internal,
+    serialize_block,
+    DumpDeclaredClass,
+    IntegrityIssue,
+};
+use crate::{open_storage, MarkerKind, StorageWriter};
 
 // TODO(yael): fix dump_table_to_file.
 #[test]
@@ -80,7 +100,11 @@ fn test_dump_declared_classes() {
 
 #[test]
 fn update_storage_metrics_test() {
-    let ((reader, _writer), _temp_dir) = get_test_storage();
+    let (mut config, _temp_dir) = get_test_config(None);
+    // Small growth step so that writing a few state diffs grows the file.
+    config.mmap_file_config.max_object_size = 1 << 10;
+    config.mmap_file_config.growth_step = 1 << 11;
+    let (reader, mut writer) = open_storage(config).unwrap();
     let handle = PrometheusBuilder::new().install_recorder().unwrap();
 
     assert!(prometheus_is_contained(handle.render(), "storage_free_pages_number", &[]).is_none());
@@ -96,8 +120,8 @@ fn update_storage_metrics_test() {
     else {
         panic!("storage_free_pages_number is not a Gauge")
     };
-    // TODO(dvir): add an upper limit when the bug in the binding freelist function will be fixed.
     assert!(0f64 < free_pages);
+    assert!(free_pages < 1000f64);
 
     let Counter(last_page) =
         prometheus_is_contained(handle.render(), "storage_last_page_number", &[]).unwrap()
@@ -114,4 +138,214 @@ fn update_storage_metrics_test() {
     };
     assert!(0f64 < last_transaction);
     assert!(last_transaction < 100f64);
+
+    let get_mmap_gauge = |metric_name: &str| {
+        let labels = [("file", "thin_state_diff")];
+        let Some(Gauge(value)) = prometheus_is_contained(handle.render(), metric_name, &labels)
+        else {
+            panic!("{metric_name} is not a Gauge")
+        };
+        value
+    };
+    let initial_size = get_mmap_gauge("storage_mmap_file_size_bytes");
+    assert_eq!(initial_size, f64::from(1 << 11));
+    assert_eq!(get_mmap_gauge("storage_mmap_file_offset_bytes"), 0f64);
+
+    for i in 0..40 {
+        let state_diff = ThinStateDiff {
+            storage_diffs: indexmap! {
+                ContractAddress::from(i as u128) => (0..10_u128)
+                    .map(|key| (StorageKey::from(key), Felt::from(u128::MAX - key)))
+                    .collect(),
+            },
+            ..Default::default()
+        };
+        writer
+            .begin_rw_txn()
+            .unwrap()
+            .append_state_diff(BlockNumber(i), state_diff)
+            .unwrap()
+            .commit()
+            .unwrap();
+    }
+    update_storage_metrics(&reader).unwrap();
+
+    let stats = reader.mmap_files_stats()["thin_state_diff"];
+    assert_eq!(get_mmap_gauge("storage_mmap_file_size_bytes"), stats.size() as f64);
+    assert!(initial_size < stats.size() as f64);
+    assert_eq!(get_mmap_gauge("storage_mmap_file_offset_bytes"), stats.offset() as f64);
+}
+
+#[test]
+fn block_binary_round_trip() {
+    let block = get_test_block(5, Some(2), None, None);
+    let bytes = serialize_block(&block).unwrap();
+    assert_eq!(deserialize_block(&bytes).unwrap(), block);
+    assert!(bytes.len() < serde_json::to_vec(&block).unwrap().len());
+}
+
+#[test]
+fn block_binary_unknown_version() {
+    let mut bytes = serialize_block(&get_test_block(1, None, None, None)).unwrap();
+    bytes[0] = 1;
+    assert!(deserialize_block(&bytes).is_none());
+}
+
+// The layout of a version tag must never change. If this test fails, bump the version tag instead
+// of updating the expected bytes.
+#[test]
+fn block_binary_layout() {
+    let block = Block {
+        header: BlockHeader {
+            block_hash: BlockHash(Felt::from(0x1234_u128)),
+            parent_hash: BlockHash(Felt::ONE),
+            block_number: BlockNumber(2),
+            l1_gas_price: GasPricePerToken { price_in_fri: GasPrice(3), price_in_wei: GasPrice(4) },
+            timestamp: BlockTimestamp(5),
+            n_transactions: 6,
+            starknet_version: StarknetVersion("0.13.1".to_string()),
+            ..Default::default()
+        },
+        body: BlockBody::default(),
+    };
+    let expected_bytes = [
+        // Version tag.
+        &[0_u8][..],
+        // Block hash and parent hash.
+        &[0x20, 0x12, 0x34],
+        &[0x01],
+        // Block number.
+        &[0, 0, 0, 2],
+        // L1 gas price and L1 data gas price, in fri and in wei.
+        &3_u128.to_be_bytes(),
+        &4_u128.to_be_bytes(),
+        &[0; 32],
+        // State root, sequencer, timestamp and L1 DA mode.
+        &[0x00],
+        &[0x00],
+        &5_u64.to_be_bytes(),
+        &[0x00],
+        // Missing state diff commitment, state diff length, transaction commitment and event
+        // commitment.
+        &[0, 0, 0, 0],
+        // Number of transactions and events.
+        &6_u64.to_be_bytes(),
+        &0_u64.to_be_bytes(),
+        // Missing receipt commitment.
+        &[0x00],
+        // Starknet version.
+        &[0x06],
+        b"0.13.1",
+        // Transactions, transaction outputs and transaction hashes.
+        &[0, 0, 0],
+    ]
+    .concat();
+
+    assert_eq!(serialize_block(&block).unwrap(), expected_bytes);
+    assert_eq!(deserialize_block(&expected_bytes).unwrap(), block);
+}
+
+fn append_headers(writer: &mut StorageWriter, n_transactions: &[usize]) {
+    let mut txn = writer.begin_rw_txn().unwrap();
+    for (i, n_transactions) in n_transactions.iter().enumerate() {
+        let header = BlockHeader {
+            block_hash: BlockHash(Felt::from(i as u128)),
+            block_number: BlockNumber(i as u64),
+            n_transactions: *n_transactions,
+            ..Default::default()
+        };
+        txn = txn.append_header(BlockNumber(i as u64), &header).unwrap();
+    }
+    txn.commit().unwrap();
+}
+
+// Sets markers directly, bypassing the checks of the storage API.
+fn set_markers(writer: &mut StorageWriter, markers: &[(MarkerKind, BlockNumber)]) {
+    let txn = writer.begin_rw_txn().unwrap();
+    let markers_table = txn.open_table(&txn.tables.markers).unwrap();
+    for (marker_kind, block_number) in markers {
+        markers_table.upsert(&txn.txn, marker_kind, block_number).unwrap();
+    }
+    txn.commit().unwrap();
+}
+
+#[test]
+fn check_integrity_consistent_storage() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    append_headers(&mut writer, &[0, 0]);
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(BlockNumber(0), ThinStateDiff::default())
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    assert_eq!(check_integrity(&reader).unwrap(), vec![]);
+}
+
+#[test]
+fn check_integrity_markers_ahead() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    append_headers(&mut writer, &[0, 0]);
+    set_markers(
+        &mut writer,
+        &[(MarkerKind::Body, BlockNumber(3)), (MarkerKind::BaseLayerBlock, BlockNumber(5))],
+    );
+
+    assert_eq!(
+        check_integrity(&reader).unwrap(),
+        vec![
+            IntegrityIssue::MarkerAhead {
+                marker: "Body",
+                marker_value: BlockNumber(3),
+                bounding_marker: "Header",
+                bounding_marker_value: BlockNumber(2),
+            },
+            IntegrityIssue::MarkerAhead {
+                marker: "BaseLayerBlock",
+                marker_value: BlockNumber(5),
+                bounding_marker: "Header",
+                bounding_marker_value: BlockNumber(2),
+            },
+        ]
+    );
+}
+
+#[test]
+fn check_integrity_missing_body() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    append_headers(&mut writer, &[0, 2]);
+    set_markers(&mut writer, &[(MarkerKind::Body, BlockNumber(2))]);
+
+    assert_eq!(
+        check_integrity(&reader).unwrap(),
+        vec![IntegrityIssue::MissingBody { block_number: BlockNumber(1) }]
+    );
+}
+
+#[test]
+fn check_integrity_missing_casm() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    append_headers(&mut writer, &[0]);
+    let class_hash = ClassHash(Felt::ONE);
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(
+            BlockNumber(0),
+            ThinStateDiff {
+                declared_classes: indexmap! { class_hash => CompiledClassHash::default() },
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .commit()
+        .unwrap();
+    set_markers(&mut writer, &[(MarkerKind::CompiledClass, BlockNumber(1))]);
+
+    assert_eq!(
+        check_integrity(&reader).unwrap(),
+        vec![IntegrityIssue::MissingCasm { block_number: BlockNumber(0), class_hash }]
+    );
 }
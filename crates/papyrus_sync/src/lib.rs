@@ -16,50 +16,47 @@ use std::time::Duration;
 use async_stream::try_stream;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use chrono::{TimeZone, Utc};
-use futures_util::{pin_mut, select, Stream, StreamExt};
+use futures_util::{pin_mut, select, FutureExt, Stream, StreamExt};
 use indexmap::IndexMap;
-use papyrus_common::pending_classes::PendingClasses;
+use papyrus_common::block_hash::{calculate_block_hashes, verify_block_hash, BlockHashError};
+use papyrus_common::pending_classes::{PendingClasses, DEFAULT_PENDING_CLASSES_MAX_SIZE};
 use papyrus_common::{metrics as papyrus_metrics, BlockHashAndNumber};
-use papyrus_config::converters::deserialize_seconds_to_duration;
-use papyrus_config::dumping::{ser_param, SerializeConfig};
+use papyrus_config::converters::{
+    deserialize_milliseconds_to_duration,
+    deserialize_seconds_to_duration,
+    serialize_duration_as_milliseconds,
+};
+use papyrus_config::dumping::{ser_optional_param, ser_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use papyrus_proc_macros::latency_histogram;
 use papyrus_storage::base_layer::{BaseLayerStorageReader, BaseLayerStorageWriter};
-use papyrus_storage::body::BodyStorageWriter;
+use papyrus_storage::body::{BodyStorageReader, BodyStorageWriter};
 use papyrus_storage::class::ClassStorageWriter;
 use papyrus_storage::compiled_class::{CasmStorageReader, CasmStorageWriter};
-use papyrus_storage::db::DbError;
 use papyrus_storage::header::{HeaderStorageReader, HeaderStorageWriter};
 use papyrus_storage::state::{StateStorageReader, StateStorageWriter};
-use papyrus_storage::{StorageError, StorageReader, StorageWriter};
+use papyrus_storage::{StorageError, StorageReader, StorageResult, StorageWriter};
 use serde::{Deserialize, Serialize};
 use sources::base_layer::BaseLayerSourceError;
 use starknet_api::block::{Block, BlockHash, BlockNumber, BlockSignature};
-use starknet_api::core::{ClassHash, CompiledClassHash, SequencerPublicKey};
+use starknet_api::core::{ChainId, ClassHash, CompiledClassHash, SequencerPublicKey};
 use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use starknet_api::state::{StateDiff, ThinStateDiff};
 use starknet_client::reader::PendingData;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, trace, warn};
+use validator::Validate;
 
 use crate::pending_sync::sync_pending_data;
 use crate::sources::base_layer::{BaseLayerSourceTrait, EthereumBaseLayerSource};
 use crate::sources::central::{CentralError, CentralSource, CentralSourceTrait};
 use crate::sources::pending::{PendingError, PendingSource, PendingSourceTrait};
 
-// TODO(shahak): Consider adding genesis hash to the config to support chains that have
-// different genesis hash.
-// TODO: Consider moving to a more general place.
-const GENESIS_HASH: &str = "0x0";
-
-// TODO(dvir): add to config.
-// Sleep duration between polling for pending data.
-const PENDING_SLEEP_DURATION: Duration = Duration::from_millis(500);
-
 // Sleep duration, in seconds, between sync progress checks.
 const SLEEP_TIME_SYNC_PROGRESS: Duration = Duration::from_secs(300);
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Validate)]
 pub struct SyncConfig {
     #[serde(deserialize_with = "deserialize_seconds_to_duration")]
     pub block_propagation_sleep_duration: Duration,
@@ -67,14 +64,25 @@ pub struct SyncConfig {
     pub base_layer_propagation_sleep_duration: Duration,
     #[serde(deserialize_with = "deserialize_seconds_to_duration")]
     pub recoverable_error_sleep_duration: Duration,
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub max_recoverable_error_sleep_duration: Duration,
+    #[serde(
+        serialize_with = "serialize_duration_as_milliseconds",
+        deserialize_with = "deserialize_milliseconds_to_duration"
+    )]
+    pub pending_sleep_duration: Duration,
     pub blocks_max_stream_size: u32,
     pub state_updates_max_stream_size: u32,
+    #[validate(range(min = 1))]
+    pub compiled_class_download_concurrency: usize,
     pub verify_blocks: bool,
+    pub pending_classes_max_size: usize,
+    pub sync_up_to: Option<BlockNumber>,
 }
 
 impl SerializeConfig for SyncConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut self_params_dump = BTreeMap::from_iter([
             ser_param(
                 "block_propagation_sleep_duration",
                 &self.block_propagation_sleep_duration.as_secs(),
@@ -94,6 +102,20 @@ impl SerializeConfig for SyncConfig {
                  error.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "max_recoverable_error_sleep_duration",
+                &self.max_recoverable_error_sleep_duration.as_secs(),
+                "Max waiting time in seconds before restarting synchronization after consecutive \
+                 recoverable errors. The waiting time doubles after each recoverable error until \
+                 reaching this value.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "pending_sleep_duration",
+                &self.pending_sleep_duration.as_millis(),
+                "Time in milliseconds between polls for pending data.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "blocks_max_stream_size",
                 &self.blocks_max_stream_size,
@@ -106,13 +128,35 @@ impl SerializeConfig for SyncConfig {
                 "Max amount of state updates to download in a stream.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "compiled_class_download_concurrency",
+                &self.compiled_class_download_concurrency,
+                "Max amount of compiled classes to download concurrently.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "verify_blocks",
                 &self.verify_blocks,
                 "Whether to verify incoming blocks.",
                 ParamPrivacyInput::Public,
             ),
-        ])
+            ser_param(
+                "pending_classes_max_size",
+                &self.pending_classes_max_size,
+                "Max amount of pending classes (and of pending compiled classes) to keep. Above \
+                 it, the least recently added ones are evicted.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        self_params_dump.extend(ser_optional_param(
+            &self.sync_up_to,
+            BlockNumber::default(),
+            "sync_up_to",
+            "The first block number the node won't sync. Once the headers and the state diffs \
+             reach it, the sync idles instead of advancing to the tip of the chain.",
+            ParamPrivacyInput::Public,
+        ));
+        self_params_dump
     }
 }
 
@@ -122,9 +166,53 @@ impl Default for SyncConfig {
             block_propagation_sleep_duration: Duration::from_secs(2),
             base_layer_propagation_sleep_duration: Duration::from_secs(10),
             recoverable_error_sleep_duration: Duration::from_secs(3),
+            max_recoverable_error_sleep_duration: Duration::from_secs(60),
+            pending_sleep_duration: Duration::from_millis(500),
             blocks_max_stream_size: 1000,
             state_updates_max_stream_size: 1000,
+            compiled_class_download_concurrency: 10,
             verify_blocks: true,
+            pending_classes_max_size: DEFAULT_PENDING_CLASSES_MAX_SIZE,
+            sync_up_to: None,
+        }
+    }
+}
+
+/// A description of the data that reverting a block would delete from the storage.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RevertPlan {
+    pub block_number: BlockNumber,
+    /// The hash of the header that would be reverted, or None if the block isn't the last block
+    /// in the storage.
+    pub block_hash: Option<BlockHash>,
+    pub transaction_count: usize,
+    /// A summary of the state diff that would be reverted, or None if the state diff of the block
+    /// isn't the last state diff in the storage.
+    pub state_diff_summary: Option<StateDiffSummary>,
+    /// Whether the base layer marker would be rolled back.
+    pub reverts_base_layer_marker: bool,
+}
+
+/// The number of entries of each kind in a state diff.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDiffSummary {
+    pub deployed_contracts: usize,
+    pub storage_entries: usize,
+    pub declared_classes: usize,
+    pub deprecated_declared_classes: usize,
+    pub nonces: usize,
+    pub replaced_classes: usize,
+}
+
+impl From<&ThinStateDiff> for StateDiffSummary {
+    fn from(diff: &ThinStateDiff) -> Self {
+        Self {
+            deployed_contracts: diff.deployed_contracts.len(),
+            storage_entries: diff.storage_diffs.values().map(|entries| entries.len()).sum(),
+            declared_classes: diff.declared_classes.len(),
+            deprecated_declared_classes: diff.deprecated_declared_classes.len(),
+            nonces: diff.nonces.len(),
+            replaced_classes: diff.replaced_classes.len(),
         }
     }
 }
@@ -145,7 +233,12 @@ pub struct GenericStateSync<
     base_layer_source: Arc<TBaseLayerSource>,
     reader: StorageReader,
     writer: StorageWriter,
+    chain_id: ChainId,
+    // The parent hash of the first block, used for the pending data while there are no blocks.
+    genesis_hash: BlockHash,
     sequencer_pub_key: Option<SequencerPublicKey>,
+    // Notified with the new sequencer public key whenever it changes.
+    sequencer_pub_key_sender: Option<watch::Sender<SequencerPublicKey>>,
 }
 
 pub type StateSyncResult = Result<(), StateSyncError>;
@@ -223,14 +316,30 @@ impl<
     TBaseLayerSource: BaseLayerSourceTrait + Sync + Send,
 > GenericStateSync<TCentralSource, TPendingSource, TBaseLayerSource>
 {
-    pub async fn run(&mut self) -> StateSyncResult {
+    /// Runs the sync until an unrecoverable error occurs or `shutdown` is cancelled. On shutdown,
+    /// the sync event being processed is committed before returning `Ok(())`.
+    pub async fn run(&mut self, shutdown: CancellationToken) -> StateSyncResult {
         info!("State sync started.");
+        let mut backoff = RecoverableErrorBackoff::new(
+            self.config.recoverable_error_sleep_duration,
+            self.config.max_recoverable_error_sleep_duration,
+        );
         loop {
-            match self.sync_while_ok().await {
+            match self.sync_while_ok(&shutdown, &mut backoff).await {
                 // A recoverable error occurred. Sleep and try syncing again.
                 Err(err) if is_recoverable(&err) => {
-                    warn!("Recoverable error encountered while syncing, error: {}", err);
-                    tokio::time::sleep(self.config.recoverable_error_sleep_duration).await;
+                    let sleep_duration = backoff.next_sleep_duration();
+                    warn!(
+                        "Recoverable error encountered while syncing, error: {}. Retrying in {:?}.",
+                        err, sleep_duration
+                    );
+                    tokio::select! {
+                        _ = tokio::time::sleep(sleep_duration) => {}
+                        _ = shutdown.cancelled() => {
+                            info!("State sync stopped.");
+                            return Ok(());
+                        }
+                    }
                     continue;
                 }
                 // Unrecoverable errors.
@@ -238,8 +347,10 @@ impl<
                     error!("Fatal error while syncing: {}", err);
                     return Err(err);
                 }
-                Ok(_) => {
-                    unreachable!("Sync should either return with an error or continue forever.")
+                // The sync was shut down.
+                Ok(()) => {
+                    info!("State sync stopped.");
+                    return Ok(());
                 }
             }
         }
@@ -277,8 +388,10 @@ impl<
                     warn!(
                         "Sequencer public key changed from {cur_key:?} to {sequencer_pub_key:?}."
                     );
-                    // TODO: Add alert.
                     self.sequencer_pub_key = Some(sequencer_pub_key);
+                    if let Some(sender) = &self.sequencer_pub_key_sender {
+                        sender.send_replace(sequencer_pub_key);
+                    }
                     return Err(StateSyncError::SequencerPubKeyChanged {
                         old: cur_key,
                         new: sequencer_pub_key,
@@ -289,11 +402,18 @@ impl<
         Ok(())
     }
 
-    // Sync until encountering an error:
+    // Sync until encountering an error or until shutdown is requested:
     //  1. If needed, revert blocks from the end of the chain.
     //  2. Create infinite block and state diff streams to fetch data from the central source.
     //  3. Fetch data from the streams with unblocking wait while there is no new data.
-    async fn sync_while_ok(&mut self) -> StateSyncResult {
+    async fn sync_while_ok(
+        &mut self,
+        shutdown: &CancellationToken,
+        backoff: &mut RecoverableErrorBackoff,
+    ) -> StateSyncResult {
+        if shutdown.is_cancelled() {
+            return Ok(());
+        }
         if self.config.verify_blocks {
             self.track_sequencer_public_key_changes().await?;
         }
@@ -305,9 +425,11 @@ impl<
             self.shared_highest_block.clone(),
             self.pending_data.clone(),
             self.pending_classes.clone(),
+            self.genesis_hash,
             self.config.block_propagation_sleep_duration,
-            PENDING_SLEEP_DURATION,
+            self.config.pending_sleep_duration,
             self.config.blocks_max_stream_size,
+            self.config.sync_up_to,
         )
         .fuse();
         let state_diff_stream = stream_new_state_diffs(
@@ -315,6 +437,7 @@ impl<
             self.central_source.clone(),
             self.config.block_propagation_sleep_duration,
             self.config.state_updates_max_stream_size,
+            self.config.sync_up_to,
         )
         .fuse();
         let compiled_class_stream = stream_new_compiled_classes(
@@ -323,6 +446,7 @@ impl<
             self.config.block_propagation_sleep_duration,
             // TODO(yair): separate config param.
             self.config.state_updates_max_stream_size,
+            self.config.compiled_class_download_concurrency,
         )
         .fuse();
         let base_layer_block_stream = stream_new_base_layer_block(
@@ -334,12 +458,14 @@ impl<
         // TODO(dvir): try use interval instead of stream.
         // TODO: fix the bug and remove this check.
         let check_sync_progress = check_sync_progress(self.reader.clone()).fuse();
+        let shutdown_requested = shutdown.cancelled().fuse();
         pin_mut!(
             block_stream,
             state_diff_stream,
             compiled_class_stream,
             base_layer_block_stream,
-            check_sync_progress
+            check_sync_progress,
+            shutdown_requested
         );
 
         loop {
@@ -350,10 +476,16 @@ impl<
               res = compiled_class_stream.next() => res,
               res = base_layer_block_stream.next() => res,
               res = check_sync_progress.next() => res,
+              _ = shutdown_requested => {
+                  info!("Shutdown requested, stopping state sync.");
+                  return Ok(());
+              },
               complete => break,
             }
             .expect("Received None as a sync event.")?;
             self.process_sync_event(sync_event).await?;
+            // The sync made progress, so the next recoverable error starts a new backoff.
+            backoff.reset();
             debug!("Finished processing sync event.");
         }
         unreachable!("Fetching data loop should never return.");
@@ -404,8 +536,15 @@ impl<
         // Assuming the central source is trusted, detect reverts by comparing the incoming block's
         // parent hash to the current hash.
         self.verify_parent_block_hash(block_number, &block)?;
+        if self.config.verify_blocks {
+            self.verify_block_hash(&block);
+        }
 
-        debug!("Storing block.");
+        debug!(
+            %block_number,
+            block_hash = %format_args!("{:#064x}", block.header.block_hash.0),
+            "Storing block."
+        );
         trace!("Block data: {block:#?}, signature: {signature:?}");
         self.writer
             .begin_rw_txn()?
@@ -421,6 +560,7 @@ impl<
             papyrus_metrics::PAPYRUS_BODY_MARKER,
             block_number.unchecked_next().0 as f64
         );
+        self.update_lag_metrics()?;
         let dt = Utc::now()
             - Utc
                 .timestamp_opt(block.header.timestamp.0 as i64, 0)
@@ -444,13 +584,13 @@ impl<
         deployed_contract_class_definitions: IndexMap<ClassHash, DeprecatedContractClass>,
     ) -> StateSyncResult {
         // TODO(dan): verifications - verify state diff against stored header.
-        debug!("Storing state diff.");
         trace!("StateDiff data: {state_diff:#?}");
 
         // TODO(shahak): split the state diff stream to 2 separate streams for blocks and for
         // classes.
         let (thin_state_diff, classes, deprecated_classes) =
             ThinStateDiff::from_state_diff(state_diff);
+        debug!(summary = ?StateDiffSummary::from(&thin_state_diff), "Storing state diff.");
         self.writer
             .begin_rw_txn()?
             .append_state_diff(block_number, thin_state_diff)?
@@ -474,9 +614,16 @@ impl<
             papyrus_metrics::PAPYRUS_COMPILED_CLASS_MARKER,
             compiled_class_marker.0 as f64
         );
+        self.update_lag_metrics()?;
 
         // Info the user on syncing the block once all the data is stored.
-        info!("Added block {} with hash {:#064x}.", block_number, block_hash.0);
+        info!(
+            %block_number,
+            block_hash = %format_args!("{:#064x}", block_hash.0),
+            "Added block {} with hash {:#064x}.",
+            block_number,
+            block_hash.0
+        );
 
         Ok(())
     }
@@ -494,24 +641,22 @@ impl<
         match txn.append_casm(&class_hash, &compiled_class) {
             Ok(txn) => {
                 txn.commit()?;
-                let compiled_class_marker =
-                    self.reader.begin_ro_txn()?.get_compiled_class_marker()?;
-                metrics::gauge!(
-                    papyrus_metrics::PAPYRUS_COMPILED_CLASS_MARKER,
-                    compiled_class_marker.0 as f64
-                );
                 debug!("Added compiled class.");
-                Ok(())
             }
             // TODO(yair): Modify the stream so it skips already stored classes.
             // Compiled classes rewrite is valid because the stream downloads from the beginning of
             // the block instead of the last downloaded class.
-            Err(StorageError::InnerError(DbError::KeyAlreadyExists(..))) => {
+            Err(StorageError::KeyAlreadyExists { .. }) => {
                 debug!("Compiled class of {class_hash} already stored.");
-                Ok(())
             }
-            Err(err) => Err(StateSyncError::StorageError(err)),
+            Err(err) => return Err(StateSyncError::StorageError(err)),
         }
+        let compiled_class_marker = self.reader.begin_ro_txn()?.get_compiled_class_marker()?;
+        metrics::gauge!(
+            papyrus_metrics::PAPYRUS_COMPILED_CLASS_MARKER,
+            compiled_class_marker.0 as f64
+        );
+        self.update_lag_metrics()
     }
 
     #[instrument(skip(self), level = "debug", err)]
@@ -548,6 +693,50 @@ impl<
         Ok(())
     }
 
+    // Updates the gauges of how far the state and compiled class streams are behind the stream
+    // that precedes them.
+    fn update_lag_metrics(&self) -> StateSyncResult {
+        let (state_lag, casm_lag) = self.get_lags()?;
+        metrics::gauge!(papyrus_metrics::PAPYRUS_STATE_LAG, state_lag as f64);
+        metrics::gauge!(papyrus_metrics::PAPYRUS_CASM_LAG, casm_lag as f64);
+        Ok(())
+    }
+
+    // Returns how many blocks the state stream is behind the header stream, and how many blocks
+    // the compiled class stream is behind the state stream.
+    fn get_lags(&self) -> StateSyncResult<(u64, u64)> {
+        let txn = self.reader.begin_ro_txn()?;
+        let header_marker = txn.get_header_marker()?;
+        let state_marker = txn.get_state_marker()?;
+        let compiled_class_marker = txn.get_compiled_class_marker()?;
+        Ok((
+            header_marker.0.saturating_sub(state_marker.0),
+            state_marker.0.saturating_sub(compiled_class_marker.0),
+        ))
+    }
+
+    // Recalculates the block hash and logs the stored and calculated values on a mismatch. Blocks
+    // whose block hash version isn't implemented in papyrus_common (Starknet 0.13.2 or later) are
+    // skipped.
+    fn verify_block_hash(&self, block: &Block) {
+        match verify_block_hash(&block.header, &block.body, &self.chain_id) {
+            Ok(()) => {}
+            Err(BlockHashError::MissingHeaderData) => {
+                debug!(
+                    block_number = %block.header.block_number,
+                    "Skipping block hash verification, the header is missing its commitments."
+                );
+            }
+            Err(BlockHashError::UnsupportedBlockHashVersion { block_number }) => {
+                trace!(
+                    %block_number,
+                    "Skipping block hash verification, the block hash version is not supported."
+                );
+            }
+            Err(err) => warn!("Block hash verification failed: {err}"),
+        }
+    }
+
     // Compares the block's parent hash to the stored block.
     fn verify_parent_block_hash(
         &self,
@@ -558,17 +747,35 @@ impl<
             None => return Ok(()),
             Some(bn) => bn,
         };
-        let prev_hash = self
-            .reader
-            .begin_ro_txn()?
-            .get_block_header(prev_block_number)?
-            .ok_or(StorageError::DBInconsistency {
+        let prev_header = self.reader.begin_ro_txn()?.get_block_header(prev_block_number)?.ok_or(
+            StorageError::DBInconsistency {
                 msg: format!(
                     "Missing block {prev_block_number} in the storage (for verifying block \
                      {block_number}).",
                 ),
-            })?
-            .block_hash;
+            },
+        )?;
+        let prev_hash = prev_header.block_hash;
+
+        // Early or partially-imported data may have a placeholder instead of the block hash. In
+        // that case, compare against the hash recomputed from the stored header.
+        if prev_hash == BlockHash::default() {
+            match calculate_block_hashes(&prev_header, &self.chain_id) {
+                Ok(calculated_hashes) if calculated_hashes.contains(&block.header.parent_hash) => {
+                    info!(
+                        "Block {prev_block_number} is stored without a hash. Recomputed its hash \
+                         {} for verifying block {block_number}.",
+                        block.header.parent_hash
+                    );
+                    return Ok(());
+                }
+                Ok(_) => {}
+                Err(err) => debug!(
+                    "Block {prev_block_number} is stored without a hash and its hash couldn't be \
+                     recomputed: {err}"
+                ),
+            }
+        }
 
         if prev_hash != block.header.parent_hash {
             // A revert detected, log and restart sync loop.
@@ -629,11 +836,56 @@ impl<
 
         txn.commit()?;
         if let Some(hash) = reverted_block_hash {
-            info!(%hash, "Reverted block.");
+            info!(
+                %block_number,
+                block_hash = %format_args!("{:#064x}", hash.0),
+                "Reverted block {}.",
+                block_number
+            );
         }
         Ok(())
     }
 
+    /// Returns a description of the data that [`revert_block`](Self::revert_block) would delete
+    /// for the given block, without modifying the storage.
+    pub fn describe_revert(&self, block_number: BlockNumber) -> StorageResult<RevertPlan> {
+        let txn = self.reader.begin_ro_txn()?;
+        let is_last = |marker: BlockNumber| block_number.next() == Some(marker);
+
+        let reverts_base_layer_marker = is_last(txn.get_base_layer_block_marker()?);
+        let header = if is_last(txn.get_header_marker()?) {
+            txn.get_block_header(block_number)?
+        } else {
+            None
+        };
+        let Some(header) = header else {
+            return Ok(RevertPlan {
+                block_number,
+                reverts_base_layer_marker,
+                ..Default::default()
+            });
+        };
+
+        let transaction_count = if is_last(txn.get_body_marker()?) {
+            txn.get_block_transactions_count(block_number)?.unwrap_or_default()
+        } else {
+            0
+        };
+        let state_diff_summary = if is_last(txn.get_state_marker()?) {
+            txn.get_state_diff(block_number)?.as_ref().map(StateDiffSummary::from)
+        } else {
+            None
+        };
+
+        Ok(RevertPlan {
+            block_number,
+            block_hash: Some(header.block_hash),
+            transaction_count,
+            state_diff_summary,
+            reverts_base_layer_marker,
+        })
+    }
+
     /// Checks if centrals block hash at the block number is different from ours (or doesn't exist).
     /// If so, a revert is required.
     async fn should_revert_block(&self, block_number: BlockNumber) -> Result<bool, StateSyncError> {
@@ -651,6 +903,34 @@ impl<
         }
     }
 }
+
+// Exponential backoff of the sleep between recoverable errors. The sleep starts at the base
+// duration and doubles after each consecutive recoverable error, up to the max duration.
+#[derive(Debug)]
+struct RecoverableErrorBackoff {
+    base_duration: Duration,
+    max_duration: Duration,
+    next_duration: Duration,
+}
+
+impl RecoverableErrorBackoff {
+    fn new(base_duration: Duration, max_duration: Duration) -> Self {
+        let max_duration = max_duration.max(base_duration);
+        Self { base_duration, max_duration, next_duration: base_duration }
+    }
+
+    // Returns the duration to sleep after the current error and advances the backoff.
+    fn next_sleep_duration(&mut self) -> Duration {
+        let sleep_duration = self.next_duration;
+        self.next_duration = min(self.next_duration.saturating_mul(2), self.max_duration);
+        sleep_duration
+    }
+
+    fn reset(&mut self) {
+        self.next_duration = self.base_duration;
+    }
+}
+
 // TODO(dvir): consider gathering in a single pending argument instead.
 #[allow(clippy::too_many_arguments)]
 fn stream_new_blocks<
@@ -663,9 +943,11 @@ fn stream_new_blocks<
     shared_highest_block: Arc<RwLock<Option<BlockHashAndNumber>>>,
     pending_data: Arc<RwLock<PendingData>>,
     pending_classes: Arc<RwLock<PendingClasses>>,
+    genesis_hash: BlockHash,
     block_propagation_sleep_duration: Duration,
     pending_sleep_duration: Duration,
     max_stream_size: u32,
+    sync_up_to: Option<BlockNumber>,
 ) -> impl Stream<Item = Result<SyncEvent, StateSyncError>> {
     try_stream! {
         loop {
@@ -678,6 +960,11 @@ fn stream_new_blocks<
             metrics::gauge!(
                 papyrus_metrics::PAPYRUS_CENTRAL_BLOCK_MARKER, central_block_marker.0 as f64
             );
+            if sync_up_to.is_some_and(|sync_up_to| header_marker >= sync_up_to) {
+                debug!("Blocks syncing reached the configured sync_up_to block, idling.");
+                tokio::time::sleep(block_propagation_sleep_duration).await;
+                continue;
+            }
             if header_marker == central_block_marker {
                 // Only if the node have the last block and state (without casms), sync pending data.
                 if reader.begin_ro_txn()?.get_state_marker()? == header_marker{
@@ -689,6 +976,7 @@ fn stream_new_blocks<
                         pending_source.clone(),
                         pending_data.clone(),
                         pending_classes.clone(),
+                        genesis_hash,
                         pending_sleep_duration,
                     ).await?;
                 }
@@ -698,7 +986,10 @@ fn stream_new_blocks<
                 };
                 continue;
             }
-            let up_to = min(central_block_marker, BlockNumber(header_marker.0 + max_stream_size as u64));
+            let limit = sync_up_to.map_or(central_block_marker, |sync_up_to| {
+                min(central_block_marker, sync_up_to)
+            });
+            let up_to = stream_range_end(header_marker, max_stream_size, limit);
             debug!("Downloading blocks [{} - {}).", header_marker, up_to);
             let block_stream =
                 central_source.stream_new_blocks(header_marker, up_to).fuse();
@@ -711,24 +1002,33 @@ fn stream_new_blocks<
     }
 }
 
+// Returns the (exclusive) end of the next range of blocks to download: at most max_stream_size
+// blocks from `from`, and not beyond `limit`. Saturates instead of overflowing near u64::MAX.
+fn stream_range_end(from: BlockNumber, max_stream_size: u32, limit: BlockNumber) -> BlockNumber {
+    min(limit, BlockNumber(from.0.saturating_add(max_stream_size.into())))
+}
+
 fn stream_new_state_diffs<TCentralSource: CentralSourceTrait + Sync + Send>(
     reader: StorageReader,
     central_source: Arc<TCentralSource>,
     block_propagation_sleep_duration: Duration,
     max_stream_size: u32,
+    sync_up_to: Option<BlockNumber>,
 ) -> impl Stream<Item = Result<SyncEvent, StateSyncError>> {
     try_stream! {
         loop {
             let txn = reader.begin_ro_txn()?;
             let state_marker = txn.get_state_marker()?;
-            let last_block_number = txn.get_header_marker()?;
+            let header_marker = txn.get_header_marker()?;
             drop(txn);
-            if state_marker == last_block_number {
+            let last_block_number = sync_up_to
+                .map_or(header_marker, |sync_up_to| min(header_marker, sync_up_to));
+            if state_marker >= last_block_number {
                 debug!("State updates syncing reached the last downloaded block, waiting for more blocks.");
                 tokio::time::sleep(block_propagation_sleep_duration).await;
                 continue;
             }
-            let up_to = min(last_block_number, BlockNumber(state_marker.0 + max_stream_size as u64));
+            let up_to = stream_range_end(state_marker, max_stream_size, last_block_number);
             debug!("Downloading state diffs [{} - {}).", state_marker, up_to);
             let state_diff_stream =
                 central_source.stream_state_updates(state_marker, up_to).fuse();
@@ -779,6 +1079,9 @@ impl StateSync {
         base_layer_source: EthereumBaseLayerSource,
         reader: StorageReader,
         writer: StorageWriter,
+        chain_id: ChainId,
+        genesis_hash: BlockHash,
+        sequencer_pub_key_sender: Option<watch::Sender<SequencerPublicKey>>,
     ) -> Self {
         Self {
             config,
@@ -790,7 +1093,10 @@ impl StateSync {
             base_layer_source: Arc::new(base_layer_source),
             reader,
             writer,
+            chain_id,
+            genesis_hash,
             sequencer_pub_key: None,
+            sequencer_pub_key_sender,
         }
     }
 }
@@ -800,6 +1106,7 @@ fn stream_new_compiled_classes<TCentralSource: CentralSourceTrait + Sync + Send>
     central_source: Arc<TCentralSource>,
     block_propagation_sleep_duration: Duration,
     max_stream_size: u32,
+    download_concurrency: usize,
 ) -> impl Stream<Item = Result<SyncEvent, StateSyncError>> {
     try_stream! {
         loop {
@@ -825,10 +1132,10 @@ fn stream_new_compiled_classes<TCentralSource: CentralSourceTrait + Sync + Send>
                 tokio::time::sleep(block_propagation_sleep_duration).await;
                 continue;
             }
-            let up_to = min(state_marker, BlockNumber(from.0 + max_stream_size as u64));
+            let up_to = stream_range_end(from, max_stream_size, state_marker);
             debug!("Downloading compiled classes of blocks [{} - {}).", from, up_to);
             let compiled_classes_stream =
-                central_source.stream_compiled_classes(from, up_to).fuse();
+                central_source.stream_compiled_classes(from, up_to, download_concurrency).fuse();
             pin_mut!(compiled_classes_stream);
 
             while let Some(maybe_compiled_class) = compiled_classes_stream.next().await {
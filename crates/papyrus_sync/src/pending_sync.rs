@@ -10,7 +10,6 @@ use papyrus_storage::StorageReader;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ClassHash;
 use starknet_client::reader::{DeclaredClassHashEntry, PendingData};
-use starknet_types_core::felt::Felt;
 use tokio::sync::RwLock;
 use tracing::{debug, trace};
 
@@ -28,13 +27,14 @@ pub(crate) async fn sync_pending_data<
     pending_source: Arc<TPendingSource>,
     pending_data: Arc<RwLock<PendingData>>,
     pending_classes: Arc<RwLock<PendingClasses>>,
+    genesis_hash: BlockHash,
     sleep_duration: Duration,
 ) -> Result<(), StateSyncError> {
     let txn = reader.begin_ro_txn()?;
     let header_marker = txn.get_header_marker()?;
     // TODO: Consider extracting this functionality to different а function.
     let latest_block_hash = match header_marker {
-        BlockNumber(0) => BlockHash(Felt::from_hex_unchecked(crate::GENESIS_HASH)),
+        BlockNumber(0) => genesis_hash,
         _ => {
             txn.get_block_header(
                 header_marker
@@ -56,6 +56,10 @@ pub(crate) async fn sync_pending_data<
         )
         .boxed(),
     );
+    // The hashes of the classes (and of the compiled classes) that were requested. A class that
+    // is evicted from the pending classes stays here, so that it isn't downloaded again on every
+    // poll. These sets live only until a new block is found, so they're reset whenever the pending
+    // block changes.
     let mut processed_classes = HashSet::new();
     let mut processed_compiled_classes = HashSet::new();
     loop {
@@ -71,7 +75,7 @@ pub(crate) async fn sync_pending_data<
                         pending_state_diff.old_declared_contracts.clone(),
                     )
                 };
-                for DeclaredClassHashEntry { class_hash, compiled_class_hash } in declared_classes {
+                for DeclaredClassHashEntry { class_hash, .. } in declared_classes {
                     if processed_classes.insert(class_hash) {
                         tasks.push(
                             get_pending_class(
@@ -82,7 +86,7 @@ pub(crate) async fn sync_pending_data<
                             .boxed(),
                         );
                     }
-                    if processed_compiled_classes.insert(compiled_class_hash) {
+                    if processed_compiled_classes.insert(class_hash) {
                         tasks.push(
                             get_pending_compiled_class(
                                 class_hash,
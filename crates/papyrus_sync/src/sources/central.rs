@@ -4,8 +4,10 @@ mod central_test;
 mod state_update_stream;
 
 use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
 use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use async_stream::stream;
 use async_trait::async_trait;
@@ -17,6 +19,7 @@ use itertools::chain;
 use lru::LruCache;
 #[cfg(test)]
 use mockall::automock;
+use papyrus_common::central_health::{CentralHealth, CentralHealthTracker};
 use papyrus_common::pending_classes::ApiContractClass;
 use papyrus_common::BlockHashAndNumber;
 use papyrus_config::converters::{deserialize_optional_map, serialize_optional_map};
@@ -38,6 +41,7 @@ use starknet_client::reader::{
     StarknetReader,
 };
 use starknet_client::{ClientCreationError, RetryConfig};
+use tokio::sync::RwLock;
 use tracing::{debug, trace};
 
 use self::state_update_stream::{StateUpdateStream, StateUpdateStreamConfig};
@@ -135,6 +139,32 @@ pub struct GenericCentralSource<TStarknetClient: StarknetReader + Send + Sync> {
     pub state_update_stream_config: StateUpdateStreamConfig,
     pub(crate) class_cache: Arc<Mutex<LruCache<ClassHash, ApiContractClass>>>,
     compiled_class_cache: Arc<Mutex<LruCache<ClassHash, CasmContractClass>>>,
+    health_tracker: Arc<RwLock<CentralHealthTracker>>,
+}
+
+impl<TStarknetClient: StarknetReader + Send + Sync> GenericCentralSource<TStarknetClient> {
+    /// Returns the health of the connection to central, as measured by the requests sent to it.
+    pub async fn health(&self) -> CentralHealth {
+        self.health_tracker.read().await.health()
+    }
+
+    async fn track_request<T, E>(
+        &self,
+        request: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, E> {
+        track_request(&self.health_tracker, request).await
+    }
+}
+
+// Awaits a request to central and records its latency and whether it succeeded.
+async fn track_request<T, E>(
+    health_tracker: &RwLock<CentralHealthTracker>,
+    request: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let start = Instant::now();
+    let result = request.await;
+    health_tracker.write().await.record_request(start.elapsed(), result.is_ok());
+    result
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -183,15 +213,24 @@ pub trait CentralSourceTrait {
         block_number: BlockNumber,
     ) -> Result<Option<BlockHash>, CentralError>;
 
+    // Downloads up to `download_concurrency` compiled classes concurrently, but yields them in
+    // the order they were declared.
     fn stream_compiled_classes(
         &self,
         initial_block_number: BlockNumber,
         up_to_block_number: BlockNumber,
+        download_concurrency: usize,
     ) -> CompiledClassesStream<'_>;
 
     // TODO(shahak): Remove once pending block is removed.
     async fn get_class(&self, class_hash: ClassHash) -> Result<ApiContractClass, CentralError>;
 
+    // Fetches a single class on demand. Returns None if central doesn't have the class.
+    async fn get_class_by_hash(
+        &self,
+        class_hash: ClassHash,
+    ) -> Result<Option<ApiContractClass>, CentralError>;
+
     // TODO(shahak): Remove once pending block is removed.
     async fn get_compiled_class(
         &self,
@@ -215,12 +254,12 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> CentralSourceTrait
 {
     // Returns the block hash and the block number of the latest block from the central source.
     async fn get_latest_block(&self) -> Result<Option<BlockHashAndNumber>, CentralError> {
-        self.starknet_client.latest_block().await.map_err(Arc::new)?.map_or(Ok(None), |block| {
-            Ok(Some(BlockHashAndNumber {
-                block_hash: block.block_hash(),
-                block_number: block.block_number(),
-            }))
-        })
+        let latest_block =
+            self.track_request(self.starknet_client.latest_block()).await.map_err(Arc::new)?;
+        Ok(latest_block.map(|block| BlockHashAndNumber {
+            block_hash: block.block_hash(),
+            block_number: block.block_number(),
+        }))
     }
 
     // Returns the current block hash of the given block number from the central source.
@@ -228,8 +267,7 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> CentralSourceTrait
         &self,
         block_number: BlockNumber,
     ) -> Result<Option<BlockHash>, CentralError> {
-        self.starknet_client
-            .block(block_number)
+        self.track_request(self.starknet_client.block(block_number))
             .await
             .map_err(Arc::new)?
             .map_or(Ok(None), |block| Ok(Some(block.block_hash())))
@@ -248,6 +286,7 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> CentralSourceTrait
             self.storage_reader.clone(),
             self.state_update_stream_config.clone(),
             self.class_cache.clone(),
+            self.health_tracker.clone(),
         )
         .boxed()
     }
@@ -264,10 +303,14 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> CentralSourceTrait
             let mut res =
                 futures_util::stream::iter(initial_block_number.iter_up_to(up_to_block_number))
                     .map(|bn| async move {
-                        let block_and_signature = futures_util::try_join!(
-                            self.starknet_client.block(bn),
-                            self.starknet_client.block_signature(bn)
-                        );
+                        let block_and_signature = self
+                            .track_request(async {
+                                futures_util::try_join!(
+                                    self.starknet_client.block(bn),
+                                    self.starknet_client.block_signature(bn)
+                                )
+                            })
+                            .await;
                         (bn, block_and_signature)
                     })
                     .buffered(self.concurrent_requests);
@@ -294,6 +337,7 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> CentralSourceTrait
         &self,
         initial_block_number: BlockNumber,
         up_to_block_number: BlockNumber,
+        download_concurrency: usize,
     ) -> CompiledClassesStream<'_> {
         stream! {
             let txn = self.storage_reader.begin_ro_txn().map_err(CentralError::StorageError)?;
@@ -330,7 +374,7 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> CentralSourceTrait
                         Err(err) => Err(err),
                     }
                 })
-                .buffered(self.concurrent_requests);
+                .buffered(download_concurrency);
 
             while let Some(maybe_compiled_class) = compiled_classes.next().await {
                 match maybe_compiled_class {
@@ -355,8 +399,10 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> CentralSourceTrait
                 return Ok(class.clone());
             }
         }
-        let client_class =
-            self.starknet_client.class_by_hash(class_hash).await.map_err(Arc::new)?;
+        let client_class = self
+            .track_request(self.starknet_client.class_by_hash(class_hash))
+            .await
+            .map_err(Arc::new)?;
         match client_class {
             None => Err(CentralError::ClassNotFound),
             Some(class) => {
@@ -370,6 +416,17 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> CentralSourceTrait
         }
     }
 
+    async fn get_class_by_hash(
+        &self,
+        class_hash: ClassHash,
+    ) -> Result<Option<ApiContractClass>, CentralError> {
+        Ok(self
+            .track_request(self.starknet_client.class_by_hash(class_hash))
+            .await
+            .map_err(Arc::new)?
+            .map(ApiContractClass::from))
+    }
+
     async fn get_compiled_class(
         &self,
         class_hash: ClassHash,
@@ -381,7 +438,7 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> CentralSourceTrait
                 return Ok(class.clone());
             }
         }
-        match self.starknet_client.compiled_class_by_hash(class_hash).await {
+        match self.track_request(self.starknet_client.compiled_class_by_hash(class_hash)).await {
             Ok(Some(compiled_class)) => {
                 let mut compiled_class_cache =
                     self.compiled_class_cache.lock().expect("Failed to lock class cache.");
@@ -394,7 +451,7 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> CentralSourceTrait
     }
 
     async fn get_sequencer_pub_key(&self) -> Result<SequencerPublicKey, CentralError> {
-        Ok(self.starknet_client.sequencer_pub_key().await.map_err(Arc::new)?)
+        Ok(self.track_request(self.starknet_client.sequencer_pub_key()).await.map_err(Arc::new)?)
     }
 }
 
@@ -447,6 +504,7 @@ impl CentralSource {
         config: CentralSourceConfig,
         node_version: &'static str,
         storage_reader: StorageReader,
+        health_tracker: Arc<RwLock<CentralHealthTracker>>,
     ) -> Result<CentralSource, ClientCreationError> {
         let starknet_client = StarknetFeederGatewayClient::new(
             &config.url,
@@ -472,6 +530,7 @@ impl CentralSource {
                 NonZeroUsize::new(config.class_cache_size)
                     .expect("class_cache_size should be a positive integer."),
             ))),
+            health_tracker,
         })
     }
 }
@@ -7,16 +7,18 @@ use futures_util::stream::FuturesOrdered;
 use futures_util::{Future, Stream, StreamExt};
 use indexmap::IndexMap;
 use lru::LruCache;
+use papyrus_common::central_health::CentralHealthTracker;
 use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::StorageReader;
 use starknet_api::block::BlockNumber;
 use starknet_api::core::ClassHash;
 use starknet_api::state::{StateDiff, StateNumber};
 use starknet_client::reader::{ReaderClientResult, StarknetReader, StateUpdate};
+use tokio::sync::RwLock;
 use tracing::log::trace;
 use tracing::{debug, instrument};
 
-use super::{ApiContractClass, CentralResult, CentralStateUpdate};
+use super::{track_request, ApiContractClass, CentralResult, CentralStateUpdate};
 use crate::CentralError;
 
 type TasksQueue<T> = FuturesOrdered<Pin<Box<dyn Future<Output = T> + Send>>>;
@@ -41,6 +43,7 @@ pub(crate) struct StateUpdateStream<TStarknetClient: StarknetReader + Send + 'st
     download_class_tasks: TasksQueue<CentralResult<Option<ApiContractClass>>>,
     downloaded_classes: VecDeque<ApiContractClass>,
     class_cache: Arc<Mutex<LruCache<ClassHash, ApiContractClass>>>,
+    health_tracker: Arc<RwLock<CentralHealthTracker>>,
     config: StateUpdateStreamConfig,
 }
 
@@ -92,6 +95,7 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> StateUpdateStream<
         storage_reader: StorageReader,
         config: StateUpdateStreamConfig,
         class_cache: Arc<Mutex<LruCache<ClassHash, ApiContractClass>>>,
+        health_tracker: Arc<RwLock<CentralHealthTracker>>,
     ) -> Self {
         StateUpdateStream {
             initial_block_number,
@@ -111,6 +115,7 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> StateUpdateStream<
             ),
             config,
             class_cache,
+            health_tracker,
         }
     }
 
@@ -156,11 +161,13 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> StateUpdateStream<
             let starknet_client = self.starknet_client.clone();
             let storage_reader = self.storage_reader.clone();
             let cache = self.class_cache.clone();
+            let health_tracker = self.health_tracker.clone();
             self.download_class_tasks.push_back(Box::pin(download_class_if_necessary(
                 cache,
                 class_hash,
                 starknet_client,
                 storage_reader,
+                health_tracker,
             )));
             *should_poll_again = true;
         }
@@ -200,9 +207,15 @@ impl<TStarknetClient: StarknetReader + Send + Sync + 'static> StateUpdateStream<
         {
             let current_block_number = self.initial_block_number;
             let starknet_client = self.starknet_client.clone();
+            let health_tracker = self.health_tracker.clone();
             *should_poll_again = true;
             self.download_state_update_tasks.push_back(Box::pin(async move {
-                (current_block_number, starknet_client.state_update(current_block_number).await)
+                let state_update = track_request(
+                    &health_tracker,
+                    starknet_client.state_update(current_block_number),
+                )
+                .await;
+                (current_block_number, state_update)
             }));
             self.initial_block_number = self.initial_block_number.unchecked_next();
         }
@@ -334,12 +347,13 @@ fn client_to_central_state_update(
 // Given a class hash, returns the corresponding class definition.
 // First tries to retrieve the class from the storage.
 // If not found in the storage, the class is downloaded.
-#[instrument(skip(starknet_client, storage_reader), level = "debug", err)]
+#[instrument(skip(starknet_client, storage_reader, health_tracker), level = "debug", err)]
 async fn download_class_if_necessary<TStarknetClient: StarknetReader>(
     cache: Arc<Mutex<LruCache<ClassHash, ApiContractClass>>>,
     class_hash: ClassHash,
     starknet_client: Arc<TStarknetClient>,
     storage_reader: StorageReader,
+    health_tracker: Arc<RwLock<CentralHealthTracker>>,
 ) -> CentralResult<Option<ApiContractClass>> {
     {
         let mut cache = cache.lock().expect("Failed to lock class cache.");
@@ -377,7 +391,9 @@ async fn download_class_if_necessary<TStarknetClient: StarknetReader>(
 
     // Class not found in storage - download.
     trace!("Downloading class {:?}.", class_hash);
-    let client_class = starknet_client.class_by_hash(class_hash).await.map_err(Arc::new)?;
+    let client_class = track_request(&health_tracker, starknet_client.class_by_hash(class_hash))
+        .await
+        .map_err(Arc::new)?;
     match client_class {
         None => Ok(None),
         Some(class) => {
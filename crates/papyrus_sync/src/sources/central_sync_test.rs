@@ -7,7 +7,11 @@ use async_trait::async_trait;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use futures::StreamExt;
 use indexmap::IndexMap;
-use papyrus_common::pending_classes::{ApiContractClass, PendingClasses};
+use papyrus_common::pending_classes::{
+    ApiContractClass,
+    PendingClasses,
+    DEFAULT_PENDING_CLASSES_MAX_SIZE,
+};
 use papyrus_common::BlockHashAndNumber;
 use papyrus_storage::base_layer::BaseLayerStorageReader;
 use papyrus_storage::header::HeaderStorageReader;
@@ -15,12 +19,13 @@ use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::test_utils::get_test_storage;
 use papyrus_storage::{StorageError, StorageReader, StorageWriter};
 use starknet_api::block::{Block, BlockBody, BlockHash, BlockHeader, BlockNumber, BlockSignature};
-use starknet_api::core::{ClassHash, SequencerPublicKey};
+use starknet_api::core::{ChainId, ClassHash, SequencerPublicKey};
 use starknet_api::crypto::utils::PublicKey;
 use starknet_api::felt;
 use starknet_api::state::StateDiff;
 use starknet_client::reader::PendingData;
 use tokio::sync::{Mutex, RwLock};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error};
 
 use super::pending::MockPendingSourceTrait;
@@ -91,19 +96,25 @@ fn get_test_sync_config(verify_blocks: bool) -> SyncConfig {
         block_propagation_sleep_duration: SYNC_SLEEP_DURATION,
         base_layer_propagation_sleep_duration: BASE_LAYER_SLEEP_DURATION,
         recoverable_error_sleep_duration: SYNC_SLEEP_DURATION,
+        max_recoverable_error_sleep_duration: SYNC_SLEEP_DURATION,
+        pending_sleep_duration: SYNC_SLEEP_DURATION,
         blocks_max_stream_size: STREAM_SIZE,
         state_updates_max_stream_size: STREAM_SIZE,
+        compiled_class_download_concurrency: STREAM_SIZE as usize,
         verify_blocks,
+        pending_classes_max_size: DEFAULT_PENDING_CLASSES_MAX_SIZE,
+        sync_up_to: None,
     }
 }
 
-// Runs sync loop with a mocked central - infinite loop unless panicking.
+// Runs sync loop with a mocked central - infinite loop unless panicking or shut down.
 async fn run_sync(
     reader: StorageReader,
     writer: StorageWriter,
     central: impl CentralSourceTrait + Send + Sync + 'static,
     base_layer: impl BaseLayerSourceTrait + Send + Sync,
     config: SyncConfig,
+    shutdown: CancellationToken,
 ) -> StateSyncResult {
     // Mock to the pending source that always returns the default pending data.
     let mut pending_source = MockPendingSourceTrait::new();
@@ -119,10 +130,15 @@ async fn run_sync(
         base_layer_source: Arc::new(base_layer),
         reader,
         writer,
+        chain_id: ChainId::Mainnet,
+        genesis_hash: BlockHash::default(),
         sequencer_pub_key: None,
+        sequencer_pub_key_sender: None,
     };
 
-    state_sync.run().await?;
+    state_sync.run(shutdown).await?;
+    // Make sure the sync didn't leave a write transaction open.
+    state_sync.writer.begin_rw_txn()?.commit()?;
     Ok(())
 }
 
@@ -145,6 +161,7 @@ async fn sync_empty_chain() {
         central_mock,
         base_layer_mock,
         get_test_sync_config(false),
+        CancellationToken::new(),
     );
 
     // Check that the header marker is 0.
@@ -244,6 +261,7 @@ async fn sync_happy_flow() {
         central_mock,
         base_layer_mock,
         get_test_sync_config(false),
+        CancellationToken::new(),
     );
 
     // Check that the storage reached N_BLOCKS within MAX_TIME_TO_SYNC_MS.
@@ -286,6 +304,192 @@ async fn sync_happy_flow() {
     }
 }
 
+#[tokio::test]
+async fn sync_stops_at_sync_up_to() {
+    const N_BLOCKS: u64 = 10;
+    const LATEST_BLOCK_NUMBER: BlockNumber = BlockNumber(N_BLOCKS - 1);
+    const SYNC_UP_TO: BlockNumber = BlockNumber(4);
+    const MAX_TIME_TO_SYNC_MS: u64 = 800;
+    let _ = simple_logger::init_with_env();
+
+    // Mock having N_BLOCKS chain in central, failing on requests beyond SYNC_UP_TO.
+    let mut central_mock = MockCentralSourceTrait::new();
+    central_mock.expect_get_latest_block().returning(|| {
+        Ok(Some(BlockHashAndNumber {
+            block_number: LATEST_BLOCK_NUMBER,
+            block_hash: create_block_hash(LATEST_BLOCK_NUMBER, false),
+        }))
+    });
+    central_mock.expect_stream_new_blocks().returning(move |initial, up_to| {
+        assert!(up_to <= SYNC_UP_TO, "Requested blocks up to {up_to}.");
+        let blocks_stream: BlocksStream<'_> = stream! {
+            for block_number in initial.iter_up_to(up_to) {
+                let header = BlockHeader {
+                    block_number,
+                    block_hash: create_block_hash(block_number, false),
+                    parent_hash: create_block_hash(block_number.prev().unwrap_or_default(), false),
+                    ..BlockHeader::default()
+                };
+                yield Ok((
+                    block_number,
+                    Block { header, body: BlockBody::default() },
+                    BlockSignature::default(),
+                ));
+            }
+        }
+        .boxed();
+        blocks_stream
+    });
+    central_mock.expect_stream_state_updates().returning(move |initial, up_to| {
+        assert!(up_to <= SYNC_UP_TO, "Requested state updates up to {up_to}.");
+        let state_stream: StateUpdatesStream<'_> = stream! {
+            for block_number in initial.iter_up_to(up_to) {
+                yield Ok((
+                    block_number,
+                    create_block_hash(block_number, false),
+                    StateDiff::default(),
+                    IndexMap::new(),
+                ));
+            }
+        }
+        .boxed();
+        state_stream
+    });
+
+    let mut base_layer_mock = MockBaseLayerSourceTrait::new();
+    base_layer_mock.expect_latest_proved_block().returning(|| Ok(None));
+
+    let ((reader, writer), _temp_dir) = get_test_storage();
+    let config = SyncConfig { sync_up_to: Some(SYNC_UP_TO), ..get_test_sync_config(false) };
+    let sync_future = run_sync(
+        reader.clone(),
+        writer,
+        central_mock,
+        base_layer_mock,
+        config,
+        CancellationToken::new(),
+    );
+
+    // Check that the header and state markers reach SYNC_UP_TO.
+    let check_storage_future =
+        check_storage(reader.clone(), Duration::from_millis(MAX_TIME_TO_SYNC_MS), |reader| {
+            let txn = reader.begin_ro_txn().unwrap();
+            let header_marker = txn.get_header_marker().unwrap();
+            let state_marker = txn.get_state_marker().unwrap();
+            debug!("Header marker at {header_marker}, state marker at {state_marker}.");
+            if header_marker > SYNC_UP_TO || state_marker > SYNC_UP_TO {
+                return CheckStoragePredicateResult::Error;
+            }
+            if header_marker < SYNC_UP_TO || state_marker < SYNC_UP_TO {
+                return CheckStoragePredicateResult::InProgress;
+            }
+            CheckStoragePredicateResult::Passed
+        });
+    // Let the sync run a few more iterations and check that the markers didn't advance.
+    let check_markers_stay_future = async {
+        assert!(check_storage_future.await);
+        tokio::time::sleep(SYNC_SLEEP_DURATION.saturating_mul(3)).await;
+        let txn = reader.begin_ro_txn().unwrap();
+        assert_eq!(txn.get_header_marker().unwrap(), SYNC_UP_TO);
+        assert_eq!(txn.get_state_marker().unwrap(), SYNC_UP_TO);
+    };
+
+    tokio::select! {
+        sync_result = sync_future => sync_result.unwrap(),
+        _ = check_markers_stay_future => {}
+    }
+}
+
+#[tokio::test]
+async fn sync_stops_on_shutdown() {
+    const N_BLOCKS: u64 = 3;
+    const LATEST_BLOCK_NUMBER: BlockNumber = BlockNumber(N_BLOCKS - 1);
+    const MAX_TIME_TO_SYNC_MS: u64 = 800;
+    let _ = simple_logger::init_with_env();
+
+    // Mock having N_BLOCKS chain in central.
+    let mut central_mock = MockCentralSourceTrait::new();
+    central_mock.expect_get_latest_block().returning(|| {
+        Ok(Some(BlockHashAndNumber {
+            block_number: LATEST_BLOCK_NUMBER,
+            block_hash: create_block_hash(LATEST_BLOCK_NUMBER, false),
+        }))
+    });
+    central_mock.expect_stream_new_blocks().returning(move |initial, up_to| {
+        let blocks_stream: BlocksStream<'_> = stream! {
+            for block_number in initial.iter_up_to(up_to) {
+                let header = BlockHeader {
+                    block_number,
+                    block_hash: create_block_hash(block_number, false),
+                    parent_hash: create_block_hash(block_number.prev().unwrap_or_default(), false),
+                    ..BlockHeader::default()
+                };
+                yield Ok((
+                    block_number,
+                    Block { header, body: BlockBody::default() },
+                    BlockSignature::default(),
+                ));
+            }
+        }
+        .boxed();
+        blocks_stream
+    });
+    central_mock.expect_stream_state_updates().returning(move |initial, up_to| {
+        let state_stream: StateUpdatesStream<'_> = stream! {
+            for block_number in initial.iter_up_to(up_to) {
+                yield Ok((
+                    block_number,
+                    create_block_hash(block_number, false),
+                    StateDiff::default(),
+                    IndexMap::new(),
+                ));
+            }
+        }
+        .boxed();
+        state_stream
+    });
+    central_mock.expect_get_block_hash().returning(|bn| Ok(Some(create_block_hash(bn, false))));
+
+    let mut base_layer_mock = MockBaseLayerSourceTrait::new();
+    base_layer_mock.expect_latest_proved_block().returning(|| Ok(None));
+
+    let ((reader, writer), _temp_dir) = get_test_storage();
+    let shutdown = CancellationToken::new();
+    let sync_handle = tokio::spawn(run_sync(
+        reader.clone(),
+        writer,
+        central_mock,
+        base_layer_mock,
+        get_test_sync_config(false),
+        shutdown.clone(),
+    ));
+
+    let synced =
+        check_storage(reader.clone(), Duration::from_millis(MAX_TIME_TO_SYNC_MS), |reader| {
+            let txn = reader.begin_ro_txn().unwrap();
+            if txn.get_header_marker().unwrap() < BlockNumber(N_BLOCKS)
+                || txn.get_state_marker().unwrap() < BlockNumber(N_BLOCKS)
+            {
+                return CheckStoragePredicateResult::InProgress;
+            }
+            CheckStoragePredicateResult::Passed
+        })
+        .await;
+    assert!(synced);
+
+    shutdown.cancel();
+    // run_sync also verifies that a write transaction can be opened after the sync returned.
+    let sync_result = tokio::time::timeout(SYNC_SLEEP_DURATION * 4, sync_handle)
+        .await
+        .expect("Sync didn't stop after shutdown was requested.")
+        .unwrap();
+    assert_matches!(sync_result, Ok(()));
+
+    let txn = reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_header_marker().unwrap(), BlockNumber(N_BLOCKS));
+    assert_eq!(txn.get_state_marker().unwrap(), BlockNumber(N_BLOCKS));
+}
+
 #[tokio::test]
 async fn sync_with_revert() {
     let _ = simple_logger::init_with_env();
@@ -301,8 +505,14 @@ async fn sync_with_revert() {
     let mock = MockedCentralWithRevert { reverted: reverted_mutex.clone() };
     let mut base_layer_mock = MockBaseLayerSourceTrait::new();
     base_layer_mock.expect_latest_proved_block().returning(|| Ok(None));
-    let sync_future =
-        run_sync(reader.clone(), writer, mock, base_layer_mock, get_test_sync_config(false));
+    let sync_future = run_sync(
+        reader.clone(),
+        writer,
+        mock,
+        base_layer_mock,
+        get_test_sync_config(false),
+        CancellationToken::new(),
+    );
 
     // Prepare functions that check that the sync worked up to N_BLOCKS_BEFORE_REVERT and then
     // reacted correctly to the revert.
@@ -550,6 +760,7 @@ async fn sync_with_revert() {
             &self,
             _initial_block_number: BlockNumber,
             _up_to_block_number: BlockNumber,
+            _download_concurrency: usize,
         ) -> CompiledClassesStream<'_> {
             // An empty stream.
             let res: CompiledClassesStream<'_> = stream! {
@@ -568,6 +779,13 @@ async fn sync_with_revert() {
             unimplemented!();
         }
 
+        async fn get_class_by_hash(
+            &self,
+            _class_hash: ClassHash,
+        ) -> Result<Option<ApiContractClass>, CentralError> {
+            unimplemented!();
+        }
+
         async fn get_compiled_class(
             &self,
             _class_hash: ClassHash,
@@ -637,6 +855,7 @@ async fn test_unrecoverable_sync_error_flow() {
         mock,
         MockBaseLayerSourceTrait::new(),
         get_test_sync_config(false),
+        CancellationToken::new(),
     );
     let sync_res = tokio::join! {sync_future};
     assert!(sync_res.0.is_err());
@@ -671,7 +890,14 @@ async fn sequencer_pub_key_management() {
 
     let ((reader, writer), _temp_dir) = get_test_storage();
     let config = get_test_sync_config(true);
-    let sync_future = run_sync(reader.clone(), writer, central_mock, base_layer_mock, config);
+    let sync_future = run_sync(
+        reader.clone(),
+        writer,
+        central_mock,
+        base_layer_mock,
+        config,
+        CancellationToken::new(),
+    );
 
     let sync_result =
         tokio::time::timeout(config.block_propagation_sleep_duration * 4, sync_future)
@@ -1,5 +1,7 @@
 use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use assert_matches::assert_matches;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
@@ -7,6 +9,7 @@ use futures_util::pin_mut;
 use indexmap::{indexmap, IndexMap};
 use lru::LruCache;
 use mockall::predicate;
+use papyrus_common::central_health::CentralHealth;
 use papyrus_storage::class::ClassStorageWriter;
 use papyrus_storage::state::StateStorageWriter;
 use papyrus_storage::test_utils::get_test_storage;
@@ -71,12 +74,46 @@ async fn last_block_number() {
         state_update_stream_config: state_update_stream_config_for_test(),
         class_cache: get_test_class_cache(),
         compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
     };
 
     let last_block_number = central_source.get_latest_block().await.unwrap().unwrap().block_number;
     assert_eq!(last_block_number, EXPECTED_LAST_BLOCK_NUMBER);
 }
 
+#[tokio::test]
+async fn health_is_recorded_after_a_request() {
+    let mut mock = MockStarknetReader::new();
+    mock.expect_latest_block().times(1).returning(|| Ok(None));
+    mock.expect_latest_block().times(1).returning(|| {
+        Err(ReaderClientError::ClientError(ClientError::BadResponseStatus {
+            code: StatusCode::BAD_GATEWAY,
+            message: String::from("Bad gateway"),
+        }))
+    });
+
+    let ((reader, _), _temp_dir) = get_test_storage();
+    let central_source = GenericCentralSource {
+        starknet_client: Arc::new(mock),
+        concurrent_requests: TEST_CONCURRENT_REQUESTS,
+        storage_reader: reader,
+        state_update_stream_config: state_update_stream_config_for_test(),
+        class_cache: get_test_class_cache(),
+        compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
+    };
+    assert_eq!(central_source.health().await, CentralHealth::default());
+
+    central_source.get_latest_block().await.unwrap();
+    let health = central_source.health().await;
+    assert!(health.average_latency_millis.is_some());
+    let last_success = health.last_success_unix_seconds.unwrap();
+
+    // A failed request is counted in the latency, but it's not a success.
+    central_source.get_latest_block().await.unwrap_err();
+    assert_eq!(central_source.health().await.last_success_unix_seconds, Some(last_success));
+}
+
 #[tokio::test]
 async fn stream_block_headers() {
     const START_BLOCK_NUMBER: u64 = 5;
@@ -110,6 +147,7 @@ async fn stream_block_headers() {
         state_update_stream_config: state_update_stream_config_for_test(),
         class_cache: get_test_class_cache(),
         compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
     };
 
     let mut expected_block_num = BlockNumber(START_BLOCK_NUMBER);
@@ -190,6 +228,7 @@ async fn stream_block_headers_some_are_missing() {
             state_update_stream_config: state_update_stream_config_for_test(),
             class_cache: get_test_class_cache(),
             compiled_class_cache: get_test_compiled_class_cache(),
+            health_tracker: Arc::default(),
         };
 
         let mut expected_block_num = BlockNumber(START_BLOCK_NUMBER);
@@ -254,6 +293,7 @@ async fn stream_block_headers_error() {
         state_update_stream_config: state_update_stream_config_for_test(),
         class_cache: get_test_class_cache(),
         compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
     };
 
     let mut expected_block_num = BlockNumber(START_BLOCK_NUMBER);
@@ -393,6 +433,7 @@ async fn stream_state_updates() {
         // TODO(shahak): Check that downloaded classes appear in the cache.
         class_cache: get_test_class_cache(),
         compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
     };
     let initial_block_num = BlockNumber(START_BLOCK_NUMBER);
 
@@ -454,6 +495,11 @@ async fn stream_state_updates() {
     assert_eq!(state_diff, starknet_api::state::StateDiff::default());
 
     assert!(stream.next().await.is_none());
+
+    // The state update and class requests are recorded in the health of central.
+    let health = central_source.health().await;
+    assert!(health.last_success_unix_seconds.is_some());
+    assert!(health.average_latency_millis.is_some());
 }
 
 #[tokio::test]
@@ -529,9 +575,14 @@ async fn stream_compiled_classes() {
         state_update_stream_config: state_update_stream_config_for_test(),
         class_cache: get_test_class_cache(),
         compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
     };
 
-    let stream = central_source.stream_compiled_classes(BlockNumber(0), BlockNumber(2));
+    let stream = central_source.stream_compiled_classes(
+        BlockNumber(0),
+        BlockNumber(2),
+        TEST_CONCURRENT_REQUESTS,
+    );
     pin_mut!(stream);
 
     let expected_compiled_class = CasmContractClass::default();
@@ -546,6 +597,84 @@ async fn stream_compiled_classes() {
     }
 }
 
+#[tokio::test]
+async fn stream_compiled_classes_with_concurrency() {
+    const DOWNLOAD_CONCURRENCY: usize = 3;
+    const N_CLASSES: usize = 6;
+
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let class_hashes: Vec<_> =
+        (0..N_CLASSES).map(|i| ClassHash(felt!(format!("0x{i}").as_str()))).collect();
+    let sierra_class = sn_api_ContractClass::default();
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_state_diff(
+            BlockNumber(0),
+            ThinStateDiff {
+                declared_classes: class_hashes
+                    .iter()
+                    .map(|class_hash| (*class_hash, CompiledClassHash(class_hash.0)))
+                    .collect(),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .append_classes(
+            BlockNumber(0),
+            &class_hashes.iter().map(|class_hash| (*class_hash, &sierra_class)).collect::<Vec<_>>(),
+            &[],
+        )
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let mut mock = MockStarknetReader::new();
+    let n_started_downloads = Arc::new(AtomicUsize::new(0));
+    for (i, class_hash) in class_hashes.iter().enumerate() {
+        // The classes that are declared first take the longest to download.
+        let delay = Duration::from_millis(10 * (N_CLASSES - i) as u64);
+        let n_started_downloads = n_started_downloads.clone();
+        mock.expect_compiled_class_by_hash().with(predicate::eq(*class_hash)).times(1).returning(
+            move |_x| {
+                n_started_downloads.fetch_add(1, Ordering::SeqCst);
+                std::thread::sleep(delay);
+                Ok(Some(CasmContractClass::default()))
+            },
+        );
+    }
+    let central_source = GenericCentralSource {
+        concurrent_requests: TEST_CONCURRENT_REQUESTS,
+        starknet_client: Arc::new(mock),
+        storage_reader: reader,
+        state_update_stream_config: state_update_stream_config_for_test(),
+        class_cache: get_test_class_cache(),
+        compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
+    };
+
+    let stream = central_source.stream_compiled_classes(
+        BlockNumber(0),
+        BlockNumber(1),
+        DOWNLOAD_CONCURRENCY,
+    );
+    pin_mut!(stream);
+
+    // The classes are yielded in declaration order, and the downloads in flight, which are the
+    // ones that started and weren't yielded yet, never exceed the concurrency.
+    let mut max_downloads_in_flight = 0;
+    for (n_yielded_classes, expected_class_hash) in class_hashes.into_iter().enumerate() {
+        let (class_hash, compiled_class_hash, _) = stream.next().await.unwrap().unwrap();
+        assert_eq!(class_hash, expected_class_hash);
+        assert_eq!(compiled_class_hash, CompiledClassHash(expected_class_hash.0));
+        let downloads_in_flight = n_started_downloads.load(Ordering::SeqCst) - n_yielded_classes;
+        assert!(downloads_in_flight <= DOWNLOAD_CONCURRENCY);
+        max_downloads_in_flight = max_downloads_in_flight.max(downloads_in_flight);
+    }
+    assert!(stream.next().await.is_none());
+    assert_eq!(max_downloads_in_flight, DOWNLOAD_CONCURRENCY);
+}
+
 #[tokio::test]
 async fn get_class() {
     let mut mock = MockStarknetReader::new();
@@ -575,6 +704,7 @@ async fn get_class() {
         state_update_stream_config: state_update_stream_config_for_test(),
         class_cache: get_test_class_cache(),
         compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
     };
 
     assert_eq!(
@@ -592,6 +722,42 @@ async fn get_class() {
     assert_eq!(central_source.get_class(class_hash).await.unwrap(), contract_class.into());
 }
 
+#[tokio::test]
+async fn get_class_by_hash() {
+    let mut mock = MockStarknetReader::new();
+
+    let class_hash = ClassHash(StarkHash::ONE);
+    let contract_class = GenericContractClass::Cairo1ContractClass(ContractClass::default());
+    let contract_class_clone = contract_class.clone();
+    mock.expect_class_by_hash()
+        .with(predicate::eq(class_hash))
+        .times(1)
+        .return_once(move |_x| Ok(Some(contract_class_clone)));
+
+    let missing_class_hash = ClassHash(StarkHash::TWO);
+    mock.expect_class_by_hash()
+        .with(predicate::eq(missing_class_hash))
+        .times(1)
+        .return_once(|_x| Ok(None));
+
+    let ((reader, _), _temp_dir) = get_test_storage();
+    let central_source = GenericCentralSource {
+        concurrent_requests: TEST_CONCURRENT_REQUESTS,
+        starknet_client: Arc::new(mock),
+        storage_reader: reader,
+        state_update_stream_config: state_update_stream_config_for_test(),
+        class_cache: get_test_class_cache(),
+        compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
+    };
+
+    assert_eq!(
+        central_source.get_class_by_hash(class_hash).await.unwrap(),
+        Some(contract_class.into())
+    );
+    assert_eq!(central_source.get_class_by_hash(missing_class_hash).await.unwrap(), None);
+}
+
 #[tokio::test]
 async fn get_compiled_class() {
     let mut mock = MockStarknetReader::new();
@@ -612,6 +778,7 @@ async fn get_compiled_class() {
         state_update_stream_config: state_update_stream_config_for_test(),
         class_cache: get_test_class_cache(),
         compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
     };
 
     assert_eq!(central_source.get_compiled_class(class_hash).await.unwrap(), compiled_class);
@@ -636,6 +803,7 @@ async fn get_sequencer_pub_key() {
         state_update_stream_config: state_update_stream_config_for_test(),
         class_cache: get_test_class_cache(),
         compiled_class_cache: get_test_compiled_class_cache(),
+        health_tracker: Arc::default(),
     };
 
     assert_eq!(central_source.get_sequencer_pub_key().await.unwrap(), sequencer_pub_key);
@@ -5,17 +5,31 @@ use assert_matches::assert_matches;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use futures_util::StreamExt;
 use indexmap::IndexMap;
+use papyrus_common::block_hash::calculate_block_hashes;
 use papyrus_common::pending_classes::{ApiContractClass, PendingClasses, PendingClassesTrait};
-use papyrus_storage::base_layer::BaseLayerStorageReader;
-use papyrus_storage::header::HeaderStorageWriter;
+use papyrus_storage::base_layer::{BaseLayerStorageReader, BaseLayerStorageWriter};
+use papyrus_storage::body::BodyStorageReader;
+use papyrus_storage::header::{HeaderStorageReader, HeaderStorageWriter};
+use papyrus_storage::state::StateStorageReader;
 use papyrus_storage::test_utils::get_test_storage;
 use papyrus_storage::{StorageReader, StorageWriter};
 use pretty_assertions::assert_eq;
-use starknet_api::block::{BlockHash, BlockHeader, BlockNumber};
-use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce, PatriciaKey};
+use starknet_api::block::{Block, BlockHash, BlockHeader, BlockNumber, BlockSignature};
+use starknet_api::core::{
+    ChainId,
+    ClassHash,
+    CompiledClassHash,
+    ContractAddress,
+    EventCommitment,
+    Nonce,
+    PatriciaKey,
+    SequencerPublicKey,
+    TransactionCommitment,
+};
+use starknet_api::crypto::utils::PublicKey;
 use starknet_api::deprecated_contract_class::ContractClass as DeprecatedContractClass;
 use starknet_api::hash::StarkHash;
-use starknet_api::state::{ContractClass, StateDiff, StorageKey};
+use starknet_api::state::{ContractClass, StateDiff, StorageKey, ThinStateDiff};
 use starknet_api::{felt, patricia_key};
 use starknet_client::reader::objects::pending_data::{
     AcceptedOnL2ExtraData,
@@ -26,8 +40,8 @@ use starknet_client::reader::objects::pending_data::{
 use starknet_client::reader::objects::state::StateDiff as ClientStateDiff;
 use starknet_client::reader::objects::transaction::Transaction as ClientTransaction;
 use starknet_client::reader::{DeclaredClassHashEntry, PendingData};
-use test_utils::{get_rng, GetTestInstance};
-use tokio::sync::RwLock;
+use test_utils::{get_rng, get_test_body, get_test_state_diff, GetTestInstance};
+use tokio::sync::{watch, RwLock};
 
 use crate::sources::base_layer::MockBaseLayerSourceTrait;
 use crate::sources::central::MockCentralSourceTrait;
@@ -35,14 +49,19 @@ use crate::sources::pending::MockPendingSourceTrait;
 use crate::{
     sort_state_diff,
     stream_new_base_layer_block,
+    stream_range_end,
     sync_pending_data,
     GenericStateSync,
+    RecoverableErrorBackoff,
+    RevertPlan,
+    StateDiffSummary,
     StateSyncError,
     SyncConfig,
     SyncEvent,
-    GENESIS_HASH,
 };
 
+const GENESIS_HASH: BlockHash = BlockHash(StarkHash::ZERO);
+
 // TODO(anatg): Add a test to check that the sync calls the sort_state_diff function
 // before writing to the storage.
 #[test]
@@ -189,18 +208,7 @@ fn store_base_layer_block_test() {
         .commit()
         .unwrap();
 
-    let mut gen_state_sync = GenericStateSync {
-        config: SyncConfig::default(),
-        shared_highest_block: Arc::new(RwLock::new(None)),
-        pending_data: Arc::new(RwLock::new(PendingData::default())),
-        central_source: Arc::new(MockCentralSourceTrait::new()),
-        pending_source: Arc::new(MockPendingSourceTrait::new()),
-        pending_classes: Arc::new(RwLock::new(PendingClasses::default())),
-        base_layer_source: Arc::new(MockBaseLayerSourceTrait::new()),
-        reader,
-        writer,
-        sequencer_pub_key: None,
-    };
+    let mut gen_state_sync = get_test_state_sync(reader, writer);
 
     // Trying to store a block without a header in the storage.
     let res = gen_state_sync.store_base_layer_block(BlockNumber(1), BlockHash::default());
@@ -218,6 +226,253 @@ fn store_base_layer_block_test() {
     assert_eq!(base_layer_marker, BlockNumber(1));
 }
 
+#[test]
+fn describe_revert_matches_revert() {
+    let (reader, writer) = get_test_storage().0;
+    let mut gen_state_sync = get_test_state_sync(reader, writer);
+
+    let block_hash = BlockHash(felt!("0x1"));
+    let block = Block {
+        header: BlockHeader { block_number: BlockNumber(0), block_hash, ..BlockHeader::default() },
+        body: get_test_body(3, None, None, None),
+    };
+    let state_diff = get_test_state_diff();
+    let (thin_state_diff, _, _) = ThinStateDiff::from_state_diff(state_diff.clone());
+    gen_state_sync.store_block(BlockNumber(0), block, &BlockSignature::default()).unwrap();
+    gen_state_sync
+        .store_state_diff(BlockNumber(0), block_hash, state_diff, IndexMap::new())
+        .unwrap();
+    gen_state_sync
+        .writer
+        .begin_rw_txn()
+        .unwrap()
+        .update_base_layer_block_marker(&BlockNumber(1))
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    // A block that isn't the last block in the storage won't be reverted.
+    assert_eq!(
+        gen_state_sync.describe_revert(BlockNumber(1)).unwrap(),
+        RevertPlan { block_number: BlockNumber(1), ..Default::default() }
+    );
+
+    let plan = gen_state_sync.describe_revert(BlockNumber(0)).unwrap();
+    assert_eq!(
+        plan,
+        RevertPlan {
+            block_number: BlockNumber(0),
+            block_hash: Some(block_hash),
+            transaction_count: 3,
+            state_diff_summary: Some(StateDiffSummary::from(&thin_state_diff)),
+            reverts_base_layer_marker: true,
+        }
+    );
+
+    // Describing the revert doesn't modify the storage.
+    let txn = gen_state_sync.reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_header_marker().unwrap(), BlockNumber(1));
+    assert_eq!(txn.get_body_marker().unwrap(), BlockNumber(1));
+    assert_eq!(txn.get_state_marker().unwrap(), BlockNumber(1));
+    assert_eq!(txn.get_base_layer_block_marker().unwrap(), BlockNumber(1));
+    drop(txn);
+
+    gen_state_sync.revert_block(BlockNumber(0)).unwrap();
+    let txn = gen_state_sync.reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_header_marker().unwrap(), BlockNumber(0));
+    assert_eq!(txn.get_body_marker().unwrap(), BlockNumber(0));
+    assert_eq!(txn.get_state_marker().unwrap(), BlockNumber(0));
+    assert_eq!(txn.get_base_layer_block_marker().unwrap(), BlockNumber(0));
+    assert!(txn.get_block_header(BlockNumber(0)).unwrap().is_none());
+    assert!(txn.get_block_transactions_count(BlockNumber(0)).unwrap().is_none());
+    assert!(txn.get_state_diff(BlockNumber(0)).unwrap().is_none());
+}
+
+// Collects the output of a tracing subscriber.
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl std::io::Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn block_store_logs_have_structured_fields() {
+    let (reader, writer) = get_test_storage().0;
+    let mut gen_state_sync = get_test_state_sync(reader, writer);
+
+    let block_hash = BlockHash(felt!("0x1"));
+    let block = Block {
+        header: BlockHeader { block_number: BlockNumber(0), block_hash, ..BlockHeader::default() },
+        body: get_test_body(1, None, None, None),
+    };
+    let logs = CapturedLogs::default();
+    let captured_logs = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(move || captured_logs.clone())
+        .finish();
+    tracing::subscriber::with_default(subscriber, || {
+        gen_state_sync.store_block(BlockNumber(0), block, &BlockSignature::default()).unwrap();
+        gen_state_sync
+            .store_state_diff(BlockNumber(0), block_hash, StateDiff::default(), IndexMap::new())
+            .unwrap();
+        gen_state_sync.revert_block(BlockNumber(0)).unwrap();
+    });
+
+    let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    let expected_fields = format!("block_number=0 block_hash={:#064x}", block_hash.0);
+    for message in ["Storing block.", "Added block 0 with hash", "Reverted block 0."] {
+        let line = logs
+            .lines()
+            .find(|line| line.contains(message))
+            .unwrap_or_else(|| panic!("Missing log {message:?} in:\n{logs}"));
+        assert!(line.contains(&expected_fields), "Missing fields in log line: {line}");
+    }
+}
+
+#[test]
+fn state_diff_summary_counts() {
+    let contract0 = ContractAddress(patricia_key!("0x1"));
+    let contract1 = ContractAddress(patricia_key!("0x2"));
+    let class_hash = ClassHash(felt!("0x3"));
+    let state_diff = ThinStateDiff {
+        deployed_contracts: IndexMap::from([(contract0, class_hash), (contract1, class_hash)]),
+        storage_diffs: IndexMap::from([
+            (
+                contract0,
+                IndexMap::from([
+                    (StorageKey(patricia_key!("0x4")), felt!("0x1")),
+                    (StorageKey(patricia_key!("0x5")), felt!("0x2")),
+                ]),
+            ),
+            (contract1, IndexMap::from([(StorageKey(patricia_key!("0x4")), felt!("0x3"))])),
+        ]),
+        declared_classes: IndexMap::from([(class_hash, CompiledClassHash(felt!("0x6")))]),
+        deprecated_declared_classes: vec![ClassHash(felt!("0x7")), ClassHash(felt!("0x8"))],
+        nonces: IndexMap::from([(contract0, Nonce(felt!("0x1")))]),
+        replaced_classes: IndexMap::from([(contract1, ClassHash(felt!("0x7")))]),
+    };
+
+    assert_eq!(
+        StateDiffSummary::from(&state_diff),
+        StateDiffSummary {
+            deployed_contracts: 2,
+            storage_entries: 3,
+            declared_classes: 1,
+            deprecated_declared_classes: 2,
+            nonces: 1,
+            replaced_classes: 1,
+        }
+    );
+}
+
+#[test]
+fn store_empty_state_diff_advances_state_marker() {
+    let (reader, mut writer) = get_test_storage().0;
+    add_headers(2, &mut writer);
+    let mut gen_state_sync = get_test_state_sync(reader, writer);
+
+    for block_number in [BlockNumber(0), BlockNumber(1)] {
+        gen_state_sync
+            .store_state_diff(
+                block_number,
+                BlockHash::default(),
+                StateDiff::default(),
+                IndexMap::new(),
+            )
+            .unwrap();
+    }
+    let txn = gen_state_sync.reader.begin_ro_txn().unwrap();
+    assert_eq!(txn.get_state_marker().unwrap(), BlockNumber(2));
+    assert_eq!(txn.get_state_diff(BlockNumber(1)).unwrap(), Some(ThinStateDiff::default()));
+}
+
+#[test]
+fn lags_reflect_a_held_back_state_stream() {
+    let (reader, mut writer) = get_test_storage().0;
+    add_headers(3, &mut writer);
+    let mut gen_state_sync = get_test_state_sync(reader, writer);
+
+    // The state stream is held back at the first block.
+    gen_state_sync
+        .store_state_diff(
+            BlockNumber(0),
+            BlockHash::default(),
+            StateDiff::default(),
+            IndexMap::new(),
+        )
+        .unwrap();
+    assert_eq!(gen_state_sync.get_lags().unwrap(), (2, 0));
+
+    // A declared class holds the compiled class stream back until its casm is stored.
+    let mut rng = get_rng();
+    let class_hash = ClassHash(felt!("0x1"));
+    let compiled_class_hash = CompiledClassHash(felt!("0x2"));
+    let state_diff = StateDiff {
+        declared_classes: IndexMap::from([(
+            class_hash,
+            (compiled_class_hash, ContractClass::get_test_instance(&mut rng)),
+        )]),
+        ..Default::default()
+    };
+    gen_state_sync
+        .store_state_diff(BlockNumber(1), BlockHash::default(), state_diff, IndexMap::new())
+        .unwrap();
+    assert_eq!(gen_state_sync.get_lags().unwrap(), (1, 1));
+
+    gen_state_sync
+        .store_compiled_class(
+            class_hash,
+            compiled_class_hash,
+            CasmContractClass::get_test_instance(&mut rng),
+        )
+        .unwrap();
+    assert_eq!(gen_state_sync.get_lags().unwrap(), (1, 0));
+}
+
+#[test]
+fn verify_parent_block_hash_recomputes_a_missing_stored_hash() {
+    let (reader, mut writer) = get_test_storage().0;
+    // A header stored with a placeholder hash.
+    let stored_header = BlockHeader {
+        block_hash: BlockHash::default(),
+        block_number: BlockNumber(0),
+        transaction_commitment: Some(TransactionCommitment::default()),
+        event_commitment: Some(EventCommitment::default()),
+        ..Default::default()
+    };
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .append_header(BlockNumber(0), &stored_header)
+        .unwrap()
+        .commit()
+        .unwrap();
+    let gen_state_sync = get_test_state_sync(reader, writer);
+
+    let mut block = Block::default();
+    block.header.block_number = BlockNumber(1);
+    block.header.parent_hash =
+        calculate_block_hashes(&stored_header, &ChainId::Mainnet).unwrap()[0];
+    gen_state_sync.verify_parent_block_hash(BlockNumber(1), &block).unwrap();
+
+    // A parent hash that doesn't match the recomputed hash is still a revert.
+    block.header.parent_hash = BlockHash(felt!("0x1"));
+    assert_matches!(
+        gen_state_sync.verify_parent_block_hash(BlockNumber(1), &block),
+        Err(StateSyncError::ParentBlockHashMismatch { .. })
+    );
+}
+
 // Adds to the storage 'headers_num' headers.
 fn add_headers(headers_num: u64, writer: &mut StorageWriter) {
     for i in 0..headers_num {
@@ -236,6 +491,27 @@ fn add_headers(headers_num: u64, writer: &mut StorageWriter) {
     }
 }
 
+fn get_test_state_sync(
+    reader: StorageReader,
+    writer: StorageWriter,
+) -> GenericStateSync<MockCentralSourceTrait, MockPendingSourceTrait, MockBaseLayerSourceTrait> {
+    GenericStateSync {
+        config: SyncConfig::default(),
+        shared_highest_block: Arc::new(RwLock::new(None)),
+        pending_data: Arc::new(RwLock::new(PendingData::default())),
+        central_source: Arc::new(MockCentralSourceTrait::new()),
+        pending_source: Arc::new(MockPendingSourceTrait::new()),
+        pending_classes: Arc::new(RwLock::new(PendingClasses::default())),
+        base_layer_source: Arc::new(MockBaseLayerSourceTrait::new()),
+        reader,
+        writer,
+        chain_id: ChainId::Mainnet,
+        genesis_hash: BlockHash::default(),
+        sequencer_pub_key: None,
+        sequencer_pub_key_sender: None,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn test_pending_sync(
     reader: StorageReader,
@@ -279,6 +555,7 @@ async fn test_pending_sync(
         Arc::new(mock_pending_source),
         pending_data_lock.clone(),
         pending_classes_lock.clone(),
+        GENESIS_HASH,
         Duration::ZERO,
     )
     .await
@@ -292,7 +569,7 @@ async fn test_pending_sync(
 
 #[tokio::test]
 async fn pending_sync_advances_only_when_new_data_has_more_transactions() {
-    let genesis_hash = BlockHash(felt!(GENESIS_HASH));
+    let genesis_hash = GENESIS_HASH;
     // Storage with no block headers.
     let (reader, _) = get_test_storage().0;
     let mut rng = get_rng();
@@ -359,7 +636,7 @@ async fn pending_sync_advances_only_when_new_data_has_more_transactions() {
 #[tokio::test]
 async fn pending_sync_new_data_has_more_advanced_hash_and_less_transactions() {
     const FIRST_BLOCK_HASH: BlockHash = BlockHash(StarkHash::ONE);
-    let genesis_hash = BlockHash(felt!(GENESIS_HASH));
+    let genesis_hash = GENESIS_HASH;
     // Storage with one block header.
     let (reader, mut writer) = get_test_storage().0;
     writer
@@ -427,7 +704,7 @@ async fn pending_sync_new_data_has_more_advanced_hash_and_less_transactions() {
 
 #[tokio::test]
 async fn pending_sync_stops_when_data_has_block_hash_field_with_a_different_hash() {
-    let genesis_hash = BlockHash(felt!(GENESIS_HASH));
+    let genesis_hash = GENESIS_HASH;
     // Storage with no block headers.
     let (reader, _) = get_test_storage().0;
     let mut rng = get_rng();
@@ -473,7 +750,7 @@ async fn pending_sync_stops_when_data_has_block_hash_field_with_a_different_hash
 #[tokio::test]
 async fn pending_sync_doesnt_stop_when_data_has_block_hash_field_with_the_same_hash() {
     const FIRST_BLOCK_HASH: BlockHash = BlockHash(StarkHash::ONE);
-    let genesis_hash = BlockHash(felt!(GENESIS_HASH));
+    let genesis_hash = GENESIS_HASH;
     // Storage with one block header.
     let (reader, mut writer) = get_test_storage().0;
     writer
@@ -547,7 +824,7 @@ async fn pending_sync_doesnt_stop_when_data_has_block_hash_field_with_the_same_h
 async fn pending_sync_updates_when_data_has_block_hash_field_with_the_same_hash_and_more_transactions()
  {
     const FIRST_BLOCK_HASH: BlockHash = BlockHash(StarkHash::ONE);
-    let genesis_hash = BlockHash(felt!(GENESIS_HASH));
+    let genesis_hash = GENESIS_HASH;
     // Storage with one block header.
     let (reader, mut writer) = get_test_storage().0;
     writer
@@ -619,7 +896,7 @@ async fn pending_sync_updates_when_data_has_block_hash_field_with_the_same_hash_
 
 #[tokio::test]
 async fn pending_sync_classes_request_only_new_classes() {
-    let genesis_hash = BlockHash(felt!(GENESIS_HASH));
+    let genesis_hash = GENESIS_HASH;
     // Storage with no blocks.
     let (reader, _writer) = get_test_storage().0;
     let mut rng = get_rng();
@@ -696,10 +973,94 @@ async fn pending_sync_classes_request_only_new_classes() {
     .await
 }
 
+#[tokio::test]
+async fn pending_sync_doesnt_request_evicted_classes_again() {
+    let genesis_hash = GENESIS_HASH;
+    // Storage with no blocks.
+    let (reader, _writer) = get_test_storage().0;
+    let mut rng = get_rng();
+
+    let first_class_hash = ClassHash(StarkHash::ONE);
+    let second_class_hash = ClassHash(StarkHash::TWO);
+
+    let first_new_pending_data = PendingData {
+        block: PendingBlockOrDeprecated::Deprecated(DeprecatedPendingBlock {
+            parent_block_hash: genesis_hash,
+            transactions: vec![ClientTransaction::get_test_instance(&mut rng)],
+            ..Default::default()
+        }),
+        state_update: PendingStateUpdate {
+            state_diff: ClientStateDiff {
+                old_declared_contracts: vec![first_class_hash],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    };
+    // Declaring the second class evicts the first one.
+    let mut second_new_pending_data = first_new_pending_data.clone();
+    second_new_pending_data
+        .block
+        .transactions_mutable()
+        .push(ClientTransaction::get_test_instance(&mut rng));
+    second_new_pending_data.state_update.state_diff.old_declared_contracts.push(second_class_hash);
+    // The first class is still declared, but it was already requested for this pending block.
+    let mut third_new_pending_data = second_new_pending_data.clone();
+    third_new_pending_data
+        .block
+        .transactions_mutable()
+        .push(ClientTransaction::get_test_instance(&mut rng));
+    let new_block_pending_data = PendingData {
+        block: PendingBlockOrDeprecated::Deprecated(DeprecatedPendingBlock {
+            parent_block_hash: BlockHash(StarkHash::ONE),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let first_class = ApiContractClass::DeprecatedContractClass(
+        DeprecatedContractClass::get_test_instance(&mut rng),
+    );
+    let second_class = ApiContractClass::ContractClass(ContractClass::get_test_instance(&mut rng));
+
+    let mut expected_pending_classes = PendingClasses::new(1);
+    expected_pending_classes.add_class(second_class_hash, second_class.clone());
+
+    let old_pending_data = PendingData {
+        block: PendingBlockOrDeprecated::Deprecated(DeprecatedPendingBlock {
+            parent_block_hash: genesis_hash,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let new_pending_datas = vec![
+        first_new_pending_data,
+        second_new_pending_data,
+        third_new_pending_data.clone(),
+        new_block_pending_data,
+    ];
+    let expected_pending_data = third_new_pending_data;
+    let old_pending_classes_data = PendingClasses::new(1);
+    let new_pending_classes =
+        vec![(first_class_hash, first_class), (second_class_hash, second_class)];
+    let new_pending_compiled_classes = vec![];
+    test_pending_sync(
+        reader,
+        old_pending_data,
+        new_pending_datas,
+        expected_pending_data,
+        Some(old_pending_classes_data),
+        new_pending_classes,
+        new_pending_compiled_classes,
+        Some(expected_pending_classes),
+    )
+    .await
+}
+
 #[tokio::test]
 async fn pending_sync_classes_are_cleaned_on_first_pending_data_from_latest_block() {
     const FIRST_BLOCK_HASH: BlockHash = BlockHash(StarkHash::ONE);
-    let genesis_hash = BlockHash(felt!(GENESIS_HASH));
+    let genesis_hash = GENESIS_HASH;
     // Storage with one block header.
     let (reader, mut writer) = get_test_storage().0;
     writer
@@ -770,3 +1131,73 @@ async fn pending_sync_classes_are_cleaned_on_first_pending_data_from_latest_bloc
     )
     .await
 }
+
+#[test]
+fn recoverable_error_backoff_grows_up_to_max() {
+    let base = Duration::from_secs(1);
+    let max = Duration::from_secs(10);
+    let mut backoff = RecoverableErrorBackoff::new(base, max);
+
+    let sleep_durations: Vec<_> = (0..6).map(|_| backoff.next_sleep_duration()).collect();
+    assert_eq!(
+        sleep_durations,
+        [1, 2, 4, 8, 10, 10].map(Duration::from_secs).to_vec(),
+        "Sleep duration should double after each recoverable error until reaching the max."
+    );
+
+    // After progress is made, the backoff starts again from the base duration.
+    backoff.reset();
+    assert_eq!(backoff.next_sleep_duration(), base);
+    assert_eq!(backoff.next_sleep_duration(), base * 2);
+}
+
+#[tokio::test]
+async fn sequencer_pub_key_change_is_sent_to_subscribers() {
+    let old_key = SequencerPublicKey(PublicKey(felt!("0x1")));
+    let new_key = SequencerPublicKey(PublicKey(felt!("0x2")));
+
+    let mut central_mock = MockCentralSourceTrait::new();
+    central_mock.expect_get_sequencer_pub_key().returning(move || Ok(new_key));
+
+    let (sender, mut receiver) = watch::channel(old_key);
+    let (reader, writer) = get_test_storage().0;
+    let mut gen_state_sync = GenericStateSync {
+        central_source: Arc::new(central_mock),
+        sequencer_pub_key: Some(old_key),
+        sequencer_pub_key_sender: Some(sender),
+        ..get_test_state_sync(reader, writer)
+    };
+
+    let res = gen_state_sync.track_sequencer_public_key_changes().await;
+    assert_matches!(
+        res,
+        Err(StateSyncError::SequencerPubKeyChanged { old, new }) if old == old_key && new == new_key
+    );
+    assert!(receiver.has_changed().unwrap());
+    assert_eq!(*receiver.borrow_and_update(), new_key);
+
+    // An unchanged key isn't sent again.
+    gen_state_sync.track_sequencer_public_key_changes().await.unwrap();
+    assert!(!receiver.has_changed().unwrap());
+}
+
+#[test]
+fn stream_range_end_bounds() {
+    // Bounded by the stream size.
+    assert_eq!(stream_range_end(BlockNumber(5), 10, BlockNumber(100)), BlockNumber(15));
+    // Bounded by the limit.
+    assert_eq!(stream_range_end(BlockNumber(5), 10, BlockNumber(8)), BlockNumber(8));
+    // Saturates instead of overflowing.
+    assert_eq!(
+        stream_range_end(BlockNumber(u64::MAX - 1), 10, BlockNumber(u64::MAX)),
+        BlockNumber(u64::MAX)
+    );
+
+    // Empty and single block ranges.
+    let from = BlockNumber(5);
+    assert_eq!(from.iter_up_to(stream_range_end(from, 10, from)).count(), 0);
+    assert_eq!(
+        from.iter_up_to(stream_range_end(from, 1, BlockNumber(100))).collect::<Vec<_>>(),
+        vec![from]
+    );
+}
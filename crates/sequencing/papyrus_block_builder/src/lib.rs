@@ -15,9 +15,9 @@ use tracing::instrument;
 mod test;
 
 /// A block builder.
-struct BlockBuilder {
+#[derive(Clone)]
+pub struct BlockBuilder {
     // A storage reader to read blocks from. Will be replaced with mempool.
-    #[allow(unused)]
     storage_reader: StorageReader,
 }
 
@@ -35,7 +35,6 @@ pub enum BlockBuilderError {
 
 impl BlockBuilder {
     /// Create a new block builder.
-    #[allow(unused)]
     pub fn new(storage_reader: StorageReader) -> Self {
         Self { storage_reader }
     }
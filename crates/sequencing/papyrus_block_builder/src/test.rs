@@ -1,3 +1,4 @@
+use assert_matches::assert_matches;
 use papyrus_storage::body::BodyStorageWriter;
 use papyrus_storage::test_utils::get_test_storage_by_scope;
 use papyrus_storage::StorageScope;
@@ -5,7 +6,7 @@ use pretty_assertions::assert_eq;
 use starknet_api::block::BlockNumber;
 use test_utils::get_test_block;
 
-use crate::{BlockBuilder, BlockBuilderTrait};
+use crate::{BlockBuilder, BlockBuilderError, BlockBuilderTrait};
 
 #[test]
 fn block_proposer() {
@@ -28,5 +29,18 @@ fn block_proposer() {
     assert_eq!(proposal, block_body.transactions.as_slice());
 }
 
-// TODO: add test for non-existing block.
+#[test]
+fn block_not_found() {
+    let ((storage_reader, _storage_writer), _temp_dir) =
+        get_test_storage_by_scope(StorageScope::FullArchive);
+
+    let proposer = BlockBuilder::new(storage_reader);
+    let block_number = BlockNumber(0);
+    assert_matches!(
+        proposer.build(block_number),
+        Err(BlockBuilderError::BlockNotFound { block_number: err_block_number })
+            if err_block_number == block_number
+    );
+}
+
 // TODO: add test for sending info and dropping the sender before receiving.
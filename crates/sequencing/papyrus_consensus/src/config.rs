@@ -3,16 +3,22 @@
 //! such as the validator ID, the network topic of the consensus, and the starting block height.
 
 use std::collections::BTreeMap;
+use std::time::Duration;
 
+use papyrus_config::converters::{
+    deserialize_milliseconds_to_duration,
+    serialize_duration_as_milliseconds,
+};
 use papyrus_config::dumping::{ser_param, ser_required_param, SerializeConfig};
 use papyrus_config::{ParamPath, ParamPrivacyInput, SerializationType, SerializedParam};
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
+use validator::Validate;
 
 use super::types::ValidatorId;
 
 /// Configuration for consensus.
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Validate)]
 pub struct ConsensusConfig {
     /// The validator ID of the node.
     pub validator_id: ValidatorId,
@@ -23,6 +29,15 @@ pub struct ConsensusConfig {
     /// The number of validators in the consensus.
     // Used for testing in an early milestones.
     pub num_validators: u64,
+    /// The number of recently seen messages remembered per height, used to drop duplicates.
+    #[validate(range(min = 1))]
+    pub message_cache_size: usize,
+    /// The time to wait for a proposal to be validated before abandoning it.
+    #[serde(
+        serialize_with = "serialize_duration_as_milliseconds",
+        deserialize_with = "deserialize_milliseconds_to_duration"
+    )]
+    pub proposal_validation_timeout: Duration,
 }
 
 impl SerializeConfig for ConsensusConfig {
@@ -52,6 +67,19 @@ impl SerializeConfig for ConsensusConfig {
                 "The number of validators in the consensus.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "message_cache_size",
+                &self.message_cache_size,
+                "The number of recently seen messages remembered per height in order to drop \
+                 duplicates, must be a positive integer.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "proposal_validation_timeout",
+                &self.proposal_validation_timeout.as_millis(),
+                "Time in milliseconds to wait for a proposal to be validated before abandoning it.",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
@@ -63,6 +91,8 @@ impl Default for ConsensusConfig {
             topic: "consensus".to_string(),
             start_height: BlockNumber::default(),
             num_validators: 4,
+            message_cache_size: 1000,
+            proposal_validation_timeout: Duration::from_secs(60),
         }
     }
 }
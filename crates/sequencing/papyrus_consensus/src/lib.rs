@@ -3,13 +3,17 @@
 // TODO(Matan): fix #[allow(missing_docs)].
 //! A consensus implementation for a [`Starknet`](https://www.starknet.io/) node.
 
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
 use futures::channel::{mpsc, oneshot};
+use lru::LruCache;
 use papyrus_common::metrics as papyrus_metrics;
 use papyrus_network::network_manager::BroadcastSubscriberReceiver;
 use papyrus_protobuf::consensus::{ConsensusMessage, Proposal};
 use single_height_consensus::SingleHeightConsensus;
 use starknet_api::block::{BlockHash, BlockNumber};
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 use types::{
     ConsensusBlock,
     ConsensusContext,
@@ -20,6 +24,9 @@ use types::{
 };
 
 pub mod config;
+#[cfg(test)]
+#[path = "lib_test.rs"]
+mod lib_test;
 #[allow(missing_docs)]
 pub mod papyrus_consensus_context;
 #[allow(missing_docs)]
@@ -33,7 +40,17 @@ pub mod types;
 
 use futures::StreamExt;
 
-#[instrument(skip(context, validator_id, network_receiver, cached_messages), level = "info")]
+#[instrument(
+    skip(
+        context,
+        validator_id,
+        network_receiver,
+        cached_messages,
+        message_cache_size,
+        proposal_validation_timeout
+    ),
+    level = "info"
+)]
 #[allow(missing_docs)]
 async fn run_height<BlockT: ConsensusBlock, ContextT: ConsensusContext<Block = BlockT>>(
     context: &mut ContextT,
@@ -41,18 +58,30 @@ async fn run_height<BlockT: ConsensusBlock, ContextT: ConsensusContext<Block = B
     validator_id: ValidatorId,
     network_receiver: &mut BroadcastSubscriberReceiver<ConsensusMessage>,
     cached_messages: &mut Vec<ConsensusMessage>,
+    message_cache_size: usize,
+    proposal_validation_timeout: Duration,
 ) -> Result<Decision<BlockT>, ConsensusError>
 where
-    ProposalWrapper:
-        Into<(ProposalInit, mpsc::Receiver<BlockT::ProposalChunk>, oneshot::Receiver<BlockHash>)>,
+    ProposalWrapper: TryInto<
+            (ProposalInit, mpsc::Receiver<BlockT::ProposalChunk>, oneshot::Receiver<BlockHash>),
+            Error = ConsensusError,
+        >,
 {
     let validators = context.validators(height).await;
-    let mut shc = SingleHeightConsensus::new(height, validator_id, validators);
+    let mut shc =
+        SingleHeightConsensus::new(height, validator_id, validators, proposal_validation_timeout);
 
     if let Some(decision) = shc.start(context).await? {
         return Ok(decision);
     }
 
+    // The messages already handled in this height, so that a peer repeating the same message
+    // doesn't cause it to be processed again. The messages themselves are kept rather than their
+    // hashes, so that a hash collision can't drop a new message.
+    let mut seen_messages = LruCache::new(
+        NonZeroUsize::new(message_cache_size)
+            .expect("message_cache_size should be a positive integer."),
+    );
     let mut current_height_messages = Vec::new();
     for msg in std::mem::take(cached_messages) {
         match height.0.cmp(&msg.height()) {
@@ -63,16 +92,14 @@ where
     }
 
     loop {
-        let message = if let Some(msg) = current_height_messages.pop() {
-            msg
+        // Cached messages were already received, so there is no peer to report for them.
+        let (message, report_sender) = if let Some(msg) = current_height_messages.pop() {
+            (msg, None)
         } else {
-            // TODO(matan): Handle parsing failures and utilize ReportCallback.
-            network_receiver
-                .next()
-                .await
-                .expect("Network receiver closed unexpectedly")
-                .0
-                .expect("Failed to parse consensus message")
+            // TODO(matan): Handle parsing failures.
+            let (message, report_sender) =
+                network_receiver.next().await.expect("Network receiver closed unexpectedly");
+            (message.expect("Failed to parse consensus message"), Some(report_sender))
         };
 
         if message.height() != height.0 {
@@ -83,11 +110,28 @@ where
             continue;
         }
 
+        if seen_messages.put(message.clone(), ()).is_some() {
+            debug!("Dropping a duplicate message. {:?}", message);
+            continue;
+        }
+
         let maybe_decision = match message {
             ConsensusMessage::Proposal(proposal) => {
                 // Special case due to fake streaming.
+                // A malformed proposal is dropped rather than failing the height, since it only
+                // reflects on the peer that sent it, which is reported.
                 let (proposal_init, content_receiver, fin_receiver) =
-                    ProposalWrapper(proposal).into();
+                    match ProposalWrapper(proposal).try_into() {
+                        Ok(converted) => converted,
+                        Err(err) => {
+                            warn!("Dropping malformed proposal: {err}");
+                            if let Some(report_sender) = report_sender {
+                                // The peer may have already disconnected.
+                                let _ = report_sender.send(());
+                            }
+                            continue;
+                        }
+                    };
                 shc.handle_proposal(context, proposal_init, content_receiver, fin_receiver).await?
             }
             _ => shc.handle_message(context, message).await?,
@@ -107,10 +151,14 @@ pub async fn run_consensus<BlockT: ConsensusBlock, ContextT: ConsensusContext<Bl
     start_height: BlockNumber,
     validator_id: ValidatorId,
     mut network_receiver: BroadcastSubscriberReceiver<ConsensusMessage>,
+    message_cache_size: usize,
+    proposal_validation_timeout: Duration,
 ) -> Result<(), ConsensusError>
 where
-    ProposalWrapper:
-        Into<(ProposalInit, mpsc::Receiver<BlockT::ProposalChunk>, oneshot::Receiver<BlockHash>)>,
+    ProposalWrapper: TryInto<
+            (ProposalInit, mpsc::Receiver<BlockT::ProposalChunk>, oneshot::Receiver<BlockHash>),
+            Error = ConsensusError,
+        >,
 {
     let mut current_height = start_height;
     let mut future_messages = Vec::new();
@@ -121,6 +169,8 @@ where
             validator_id,
             &mut network_receiver,
             &mut future_messages,
+            message_cache_size,
+            proposal_validation_timeout,
         )
         .await?;
 
@@ -134,7 +184,7 @@ where
     }
 }
 
-// `Proposal` is defined in the protobuf crate so we can't implement `Into` for it because of the
-// orphan rule. This wrapper enables us to implement `Into` for the inner `Proposal`.
+// `Proposal` is defined in the protobuf crate so we can't implement `TryInto` for it because of the
+// orphan rule. This wrapper enables us to implement `TryInto` for the inner `Proposal`.
 #[allow(missing_docs)]
 pub struct ProposalWrapper(Proposal);
@@ -0,0 +1,103 @@
+use futures::channel::oneshot;
+use futures::SinkExt;
+use papyrus_network::network_manager::mock_register_broadcast_subscriber;
+use papyrus_protobuf::consensus::{ConsensusMessage, Proposal, Vote, VoteType};
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_types_core::felt::Felt;
+
+use super::run_height;
+use crate::config::ConsensusConfig;
+use crate::test_utils::{MockTestContext, TestBlock};
+use crate::types::{ConsensusBlock, Decision, ValidatorId};
+
+const NODE_ID: u32 = 1;
+const PROPOSER_ID: u32 = 2;
+
+fn proposal(block_hash: BlockHash) -> Proposal {
+    Proposal { height: 0, proposer: PROPOSER_ID.into(), transactions: vec![], block_hash }
+}
+
+fn vote(vote_type: VoteType, block_hash: BlockHash) -> ConsensusMessage {
+    ConsensusMessage::Vote(Vote { vote_type, height: 0, block_hash, voter: PROPOSER_ID.into() })
+}
+
+// Runs height 0 on a node that validates at most one proposal, which yields `block`, after the
+// given messages were received. Returns the decision and the report receiver of each message.
+async fn run_height_with_messages(
+    block: TestBlock,
+    messages: Vec<ConsensusMessage>,
+) -> (Decision<TestBlock>, Vec<oneshot::Receiver<()>>) {
+    let mut context = MockTestContext::new();
+    let node_id: ValidatorId = NODE_ID.into();
+    let proposer: ValidatorId = PROPOSER_ID.into();
+    context.expect_validators().returning(move |_| vec![node_id, proposer]);
+    context.expect_proposer().returning(move |_, _| proposer);
+    context.expect_validate_proposal().times(1).returning(move |_, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send(block.clone()).unwrap();
+        block_receiver
+    });
+    context.expect_broadcast().returning(move |_| Ok(()));
+
+    let mut test_channels = mock_register_broadcast_subscriber().unwrap();
+    let mut report_receivers = Vec::new();
+    for message in messages {
+        let (report_sender, report_receiver) = oneshot::channel();
+        test_channels
+            .mock_network
+            .broadcasted_messages_sender
+            .send((message, report_sender))
+            .await
+            .unwrap();
+        report_receivers.push(report_receiver);
+    }
+
+    let decision = run_height(
+        &mut context,
+        BlockNumber(0),
+        node_id,
+        &mut test_channels.subscriber_channels.broadcasted_messages_receiver,
+        &mut Vec::new(),
+        ConsensusConfig::default().message_cache_size,
+        ConsensusConfig::default().proposal_validation_timeout,
+    )
+    .await
+    .unwrap();
+    (decision, report_receivers)
+}
+
+#[tokio::test]
+async fn malformed_proposal_is_skipped() {
+    let block = TestBlock { content: vec![], id: BlockHash(Felt::ONE) };
+    let block_id = block.id();
+    let messages = vec![
+        ConsensusMessage::Proposal(Proposal {
+            block_hash: BlockHash::default(),
+            ..proposal(block_id)
+        }),
+        ConsensusMessage::Proposal(proposal(block_id)),
+        vote(VoteType::Prevote, block_id),
+        vote(VoteType::Precommit, block_id),
+    ];
+
+    let (decision, mut report_receivers) = run_height_with_messages(block, messages).await;
+    assert_eq!(decision.block.id(), block_id);
+    assert_eq!(report_receivers[0].try_recv(), Ok(Some(())));
+}
+
+#[tokio::test]
+async fn duplicate_proposal_is_processed_once() {
+    let block = TestBlock { content: vec![], id: BlockHash(Felt::ONE) };
+    let block_id = block.id();
+    // The duplicate proposal must not reach the context a second time.
+    let messages = vec![
+        ConsensusMessage::Proposal(proposal(block_id)),
+        ConsensusMessage::Proposal(proposal(block_id)),
+        vote(VoteType::Prevote, block_id),
+        vote(VoteType::Prevote, block_id),
+        vote(VoteType::Precommit, block_id),
+    ];
+
+    let (decision, _) = run_height_with_messages(block, messages).await;
+    assert_eq!(decision.block.id(), block_id);
+}
@@ -9,6 +9,7 @@ use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
 use futures::sink::SinkExt;
 use futures::StreamExt;
+use papyrus_block_builder::{BlockBuilder, BlockBuilderTrait};
 use papyrus_network::network_manager::BroadcastSubscriberSender;
 use papyrus_protobuf::consensus::{ConsensusMessage, Proposal};
 use papyrus_storage::body::BodyStorageReader;
@@ -45,6 +46,7 @@ impl ConsensusBlock for PapyrusConsensusBlock {
 
 pub struct PapyrusConsensusContext {
     storage_reader: StorageReader,
+    block_builder: BlockBuilder,
     broadcast_sender: BroadcastSubscriberSender<ConsensusMessage>,
     validators: Vec<ValidatorId>,
 }
@@ -58,6 +60,7 @@ impl PapyrusConsensusContext {
         num_validators: u64,
     ) -> Self {
         Self {
+            block_builder: BlockBuilder::new(storage_reader.clone()),
             storage_reader,
             broadcast_sender,
             validators: (0..num_validators).map(ContractAddress::from).collect(),
@@ -79,25 +82,26 @@ impl ConsensusContext for PapyrusConsensusContext {
         let (fin_sender, fin_receiver) = oneshot::channel();
 
         let storage_reader = self.storage_reader.clone();
+        let block_builder = self.block_builder.clone();
         tokio::spawn(async move {
             // TODO(dvir): consider fix this for the case of reverts. If between the check that the
             // block in storage and to getting the transaction was a revert this flow will fail.
             wait_for_block(&storage_reader, height).await.expect("Failed to wait to block");
 
-            let txn = storage_reader.begin_ro_txn().expect("Failed to begin ro txn");
-            let transactions = txn
-                .get_block_transactions(height)
-                .expect("Get transactions from storage failed")
-                .unwrap_or_else(|| {
-                    panic!("Block in {height} was not found in storage despite waiting for it")
-                });
+            let transactions: Vec<Transaction> = block_builder
+                .build(height)
+                .unwrap_or_else(|err| panic!("Failed to build a proposal for {height}: {err}"))
+                .iter()
+                .collect();
 
             for tx in transactions.clone() {
                 sender.try_send(tx).expect("Send should succeed");
             }
             sender.close_channel();
 
-            let block_hash = txn
+            let block_hash = storage_reader
+                .begin_ro_txn()
+                .expect("Failed to begin ro txn")
                 .get_block_header(height)
                 .expect("Get header from storage failed")
                 .unwrap_or_else(|| {
@@ -117,46 +121,60 @@ impl ConsensusContext for PapyrusConsensusContext {
         height: BlockNumber,
         mut content: mpsc::Receiver<Transaction>,
     ) -> oneshot::Receiver<PapyrusConsensusBlock> {
-        let (fin_sender, fin_receiver) = oneshot::channel();
+        let (mut fin_sender, fin_receiver) = oneshot::channel();
 
         let storage_reader = self.storage_reader.clone();
         tokio::spawn(async move {
-            // TODO(dvir): consider fix this for the case of reverts. If between the check that the
-            // block in storage and to getting the transaction was a revert this flow will fail.
-            wait_for_block(&storage_reader, height).await.expect("Failed to wait to block");
+            let validation = async move {
+                // TODO(dvir): consider fix this for the case of reverts. If between the check that
+                // the block in storage and to getting the transaction was a revert this flow will
+                // fail.
+                wait_for_block(&storage_reader, height).await.expect("Failed to wait to block");
+
+                let txn = storage_reader.begin_ro_txn().expect("Failed to begin ro txn");
+                let transactions = txn
+                    .get_block_transactions(height)
+                    .expect("Get transactions from storage failed")
+                    .unwrap_or_else(|| {
+                        panic!("Block in {height} was not found in storage despite waiting for it")
+                    });
+
+                for tx in transactions.iter() {
+                    let received_tx = content
+                        .next()
+                        .await
+                        .unwrap_or_else(|| panic!("Not received transaction equals to {tx:?}"));
+                    if tx != &received_tx {
+                        panic!("Transactions are not equal. In storage: {tx:?}, : {received_tx:?}");
+                    }
+                }
 
-            let txn = storage_reader.begin_ro_txn().expect("Failed to begin ro txn");
-            let transactions = txn
-                .get_block_transactions(height)
-                .expect("Get transactions from storage failed")
-                .unwrap_or_else(|| {
-                    panic!("Block in {height} was not found in storage despite waiting for it")
-                });
-
-            for tx in transactions.iter() {
-                let received_tx = content
-                    .next()
-                    .await
-                    .unwrap_or_else(|| panic!("Not received transaction equals to {tx:?}"));
-                if tx != &received_tx {
-                    panic!("Transactions are not equal. In storage: {tx:?}, : {received_tx:?}");
+                if content.next().await.is_some() {
+                    panic!("Received more transactions than expected");
                 }
-            }
 
-            if content.next().await.is_some() {
-                panic!("Received more transactions than expected");
-            }
+                let block_hash = txn
+                    .get_block_header(height)
+                    .expect("Get header from storage failed")
+                    .unwrap_or_else(|| {
+                        panic!("Block in {height} was not found in storage despite waiting for it")
+                    })
+                    .block_hash;
+                PapyrusConsensusBlock { content: transactions, id: block_hash }
+            };
 
-            let block_hash = txn
-                .get_block_header(height)
-                .expect("Get header from storage failed")
-                .unwrap_or_else(|| {
-                    panic!("Block in {height} was not found in storage despite waiting for it")
-                })
-                .block_hash;
-            fin_sender
-                .send(PapyrusConsensusBlock { content: transactions, id: block_hash })
-                .expect("Send should succeed");
+            // Consensus drops the receiver when it abandons the proposal, in which case there is
+            // no point in continuing the validation.
+            let block = tokio::select! {
+                _ = fin_sender.cancellation() => {
+                    debug!("Validation of the proposal for height {height} was cancelled");
+                    return;
+                }
+                block = validation => block,
+            };
+            if fin_sender.send(block).is_err() {
+                debug!("Validation of the proposal for height {height} was cancelled");
+            }
         });
 
         fin_receiver
@@ -228,22 +246,35 @@ async fn wait_for_block(
     Ok(())
 }
 
-impl From<ProposalWrapper>
+impl TryFrom<ProposalWrapper>
     for (ProposalInit, mpsc::Receiver<Transaction>, oneshot::Receiver<BlockHash>)
 {
-    fn from(val: ProposalWrapper) -> Self {
-        let transactions: Vec<Transaction> = val.0.transactions.into_iter().collect();
+    type Error = ConsensusError;
+
+    fn try_from(val: ProposalWrapper) -> Result<Self, Self::Error> {
+        let proposal = val.0;
         let proposal_init =
-            ProposalInit { height: BlockNumber(val.0.height), proposer: val.0.proposer };
-        let (mut content_sender, content_receiver) = mpsc::channel(transactions.len());
-        for tx in transactions {
-            content_sender.try_send(tx).expect("Send should succeed");
+            ProposalInit { height: BlockNumber(proposal.height), proposer: proposal.proposer };
+        let invalid_proposal = |reason: String| {
+            ConsensusError::InvalidProposal(proposal_init.proposer, proposal_init.height, reason)
+        };
+        if proposal.block_hash == BlockHash::default() {
+            return Err(invalid_proposal("Missing block hash".to_string()));
+        }
+
+        let (mut content_sender, content_receiver) = mpsc::channel(proposal.transactions.len());
+        for tx in proposal.transactions {
+            content_sender
+                .try_send(tx)
+                .map_err(|err| invalid_proposal(format!("Failed to stream content: {err}")))?;
         }
         content_sender.close_channel();
 
         let (fin_sender, fin_receiver) = oneshot::channel();
-        fin_sender.send(val.0.block_hash).expect("Send should succeed");
+        fin_sender
+            .send(proposal.block_hash)
+            .map_err(|_| invalid_proposal("Failed to stream fin".to_string()))?;
 
-        (proposal_init, content_receiver, fin_receiver)
+        Ok((proposal_init, content_receiver, fin_receiver))
     }
 }
@@ -5,13 +5,20 @@ use papyrus_protobuf::consensus::{ConsensusMessage, Proposal};
 use papyrus_storage::body::BodyStorageWriter;
 use papyrus_storage::header::HeaderStorageWriter;
 use papyrus_storage::test_utils::get_test_storage;
-use starknet_api::block::Block;
+use starknet_api::block::{Block, BlockHash};
 use starknet_api::core::ContractAddress;
-use starknet_api::transaction::Transaction;
+use starknet_api::transaction::{
+    DeployTransaction,
+    DeployTransactionOutput,
+    Transaction,
+    TransactionOutput,
+};
+use starknet_types_core::felt::Felt;
 use test_utils::get_test_block;
 
 use crate::papyrus_consensus_context::PapyrusConsensusContext;
-use crate::types::{ConsensusBlock, ConsensusContext, ProposalInit};
+use crate::types::{ConsensusBlock, ConsensusContext, ConsensusError, ProposalInit};
+use crate::ProposalWrapper;
 
 // TODO(dvir): consider adding tests for times, i.e, the calls are returned immediately and nothing
 // happen until it should (for example, not creating a block before we have it in storage).
@@ -98,8 +105,13 @@ async fn propose() {
 }
 
 fn test_setup() -> (Block, PapyrusConsensusContext, BroadcastNetworkMock<ConsensusMessage>) {
+    test_setup_with_block(get_test_block(5, None, None, None))
+}
+
+fn test_setup_with_block(
+    block: Block,
+) -> (Block, PapyrusConsensusContext, BroadcastNetworkMock<ConsensusMessage>) {
     let ((storage_reader, mut storage_writer), _temp_dir) = get_test_storage();
-    let block = get_test_block(5, None, None, None);
     let block_number = block.header.block_number;
     storage_writer
         .begin_rw_txn()
@@ -119,3 +131,49 @@ fn test_setup() -> (Block, PapyrusConsensusContext, BroadcastNetworkMock<Consens
     );
     (block, papyrus_context, test_channels.mock_network)
 }
+
+type ConvertedProposal = (ProposalInit, mpsc::Receiver<Transaction>, oneshot::Receiver<BlockHash>);
+
+#[test]
+fn malformed_proposal_conversion_fails() {
+    let valid_proposal = Proposal {
+        height: 0,
+        proposer: ContractAddress::default(),
+        transactions: vec![],
+        block_hash: BlockHash(Felt::ONE),
+    };
+    let missing_block_hash =
+        Proposal { block_hash: BlockHash::default(), ..valid_proposal.clone() };
+
+    let converted: Result<ConvertedProposal, _> = ProposalWrapper(valid_proposal).try_into();
+    assert!(converted.is_ok());
+    let converted: Result<ConvertedProposal, _> = ProposalWrapper(missing_block_hash).try_into();
+    assert!(matches!(converted, Err(ConsensusError::InvalidProposal(..))));
+}
+
+// Historical blocks contain deploy transactions, and the proposals built from them must be
+// accepted by the other validators.
+#[tokio::test]
+async fn built_proposal_with_deploy_conversion_succeeds() {
+    let mut block = get_test_block(5, None, None, None);
+    block.body.transactions[0] = Transaction::Deploy(DeployTransaction::default());
+    block.body.transaction_outputs[0] =
+        TransactionOutput::Deploy(DeployTransactionOutput::default());
+    let (block, papyrus_context, _mock_network) = test_setup_with_block(block);
+    let block_number = block.header.block_number;
+
+    let (proposal_receiver, fin_receiver) = papyrus_context.build_proposal(block_number).await;
+    let transactions = proposal_receiver.collect::<Vec<_>>().await;
+    let proposal = Proposal {
+        height: block_number.0,
+        proposer: ContractAddress::default(),
+        transactions,
+        block_hash: fin_receiver.await.unwrap().id(),
+    };
+
+    let (proposal_init, content_receiver, fin_receiver): ConvertedProposal =
+        ProposalWrapper(proposal).try_into().unwrap();
+    assert_eq!(proposal_init.height, block_number);
+    assert_eq!(content_receiver.collect::<Vec<_>>().await, block.body.transactions);
+    assert_eq!(fin_receiver.await.unwrap(), block.header.block_hash);
+}
@@ -3,11 +3,13 @@
 mod single_height_consensus_test;
 
 use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
 
 use futures::channel::{mpsc, oneshot};
+use papyrus_common::metrics as papyrus_metrics;
 use papyrus_protobuf::consensus::{ConsensusMessage, Vote, VoteType};
 use starknet_api::block::{BlockHash, BlockNumber};
-use tracing::{debug, info, instrument, trace};
+use tracing::{debug, info, instrument, trace, warn};
 
 use crate::state_machine::{StateMachine, StateMachineEvent};
 use crate::types::{
@@ -30,6 +32,7 @@ pub(crate) struct SingleHeightConsensus<BlockT: ConsensusBlock> {
     height: BlockNumber,
     validators: Vec<ValidatorId>,
     id: ValidatorId,
+    proposal_validation_timeout: Duration,
     state_machine: StateMachine,
     proposals: HashMap<Round, BlockT>,
     prevotes: HashMap<(Round, ValidatorId), Vote>,
@@ -37,13 +40,19 @@ pub(crate) struct SingleHeightConsensus<BlockT: ConsensusBlock> {
 }
 
 impl<BlockT: ConsensusBlock> SingleHeightConsensus<BlockT> {
-    pub(crate) fn new(height: BlockNumber, id: ValidatorId, validators: Vec<ValidatorId>) -> Self {
+    pub(crate) fn new(
+        height: BlockNumber,
+        id: ValidatorId,
+        validators: Vec<ValidatorId>,
+        proposal_validation_timeout: Duration,
+    ) -> Self {
         // TODO(matan): Use actual weights, not just `len`.
         let state_machine = StateMachine::new(validators.len() as u32);
         Self {
             height,
             validators,
             id,
+            proposal_validation_timeout,
             state_machine,
             proposals: HashMap::new(),
             prevotes: HashMap::new(),
@@ -90,9 +99,24 @@ impl<BlockT: ConsensusBlock> SingleHeightConsensus<BlockT> {
             return Err(ConsensusError::InvalidProposal(proposer_id, self.height, msg));
         }
 
-        let block_receiver = context.validate_proposal(self.height, p2p_messages_receiver).await;
+        let validation = async {
+            let block_receiver =
+                context.validate_proposal(self.height, p2p_messages_receiver).await;
+            block_receiver.await
+        };
+        // Dropping the validation on timeout drops the block receiver, which signals the context to
+        // cancel the validation.
+        let Ok(block_result) =
+            tokio::time::timeout(self.proposal_validation_timeout, validation).await
+        else {
+            warn!(
+                "Proposal validation timed out after {:?}. Abandoning the proposal.",
+                self.proposal_validation_timeout
+            );
+            return Ok(None);
+        };
         // TODO(matan): Actual Tendermint should handle invalid proposals.
-        let block = block_receiver.await.map_err(|_| {
+        let block = block_result.map_err(|_| {
             ConsensusError::InvalidProposal(
                 proposer_id,
                 self.height,
@@ -214,6 +238,8 @@ impl<BlockT: ConsensusBlock> SingleHeightConsensus<BlockT> {
         block_hash: Option<BlockHash>,
         round: Round,
     ) -> VecDeque<StateMachineEvent> {
+        // Rounds are zero-based, so a height in round `r` has gone through `r + 1` rounds.
+        metrics::gauge!(papyrus_metrics::PAPYRUS_CONSENSUS_ROUNDS, f64::from(round + 1));
         // TODO(matan): Support re-proposing validValue.
         assert!(block_hash.is_none(), "Reproposing is not yet supported");
         let proposer_id = context.proposer(&self.validators, self.height);
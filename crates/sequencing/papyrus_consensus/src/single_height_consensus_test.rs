@@ -1,12 +1,17 @@
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 
 use futures::channel::{mpsc, oneshot};
+use metrics_exporter_prometheus::PrometheusBuilder;
+use papyrus_common::metrics::PAPYRUS_CONSENSUS_ROUNDS;
 use papyrus_protobuf::consensus::{ConsensusMessage, Vote, VoteType};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_types_core::felt::Felt;
+use test_utils::prometheus_is_contained;
 use tokio;
 
 use super::SingleHeightConsensus;
+use crate::config::ConsensusConfig;
 use crate::test_utils::{MockTestContext, TestBlock};
 use crate::types::{ConsensusBlock, ProposalInit, ValidatorId};
 
@@ -30,6 +35,7 @@ async fn proposer() {
         BlockNumber(0),
         node_id,
         vec![node_id, 2_u32.into(), 3_u32.into(), 4_u32.into()],
+        ConsensusConfig::default().proposal_validation_timeout,
     );
 
     context.expect_proposer().returning(move |_, _| node_id);
@@ -106,6 +112,7 @@ async fn validator() {
         BlockNumber(0),
         node_id,
         vec![node_id, proposer, 3_u32.into(), 4_u32.into()],
+        ConsensusConfig::default().proposal_validation_timeout,
     );
 
     // Send the proposal from the peer.
@@ -162,3 +169,86 @@ async fn validator() {
             .all(|item| precommits.contains(&ConsensusMessage::Vote(item)))
     );
 }
+
+#[tokio::test]
+async fn validation_timeout() {
+    let mut context = MockTestContext::new();
+
+    let node_id: ValidatorId = 1_u32.into();
+    let proposer: ValidatorId = 2_u32.into();
+
+    let mut shc = SingleHeightConsensus::<TestBlock>::new(
+        BlockNumber(0),
+        node_id,
+        vec![node_id, proposer, 3_u32.into(), 4_u32.into()],
+        Duration::from_millis(10),
+    );
+
+    let (fin_sender, fin_receiver) = oneshot::channel();
+    fin_sender.send(BlockHash(Felt::ONE)).unwrap();
+
+    context.expect_proposer().returning(move |_, _| proposer);
+    // Keep the block sender alive so that the validation never completes.
+    let block_sender = Arc::new(Mutex::new(None));
+    let block_sender_clone = Arc::clone(&block_sender);
+    context.expect_validate_proposal().return_once(move |_, _| {
+        let (sender, block_receiver) = oneshot::channel();
+        *block_sender_clone.lock().unwrap() = Some(sender);
+        block_receiver
+    });
+    let res = shc
+        .handle_proposal(
+            &mut context,
+            ProposalInit { height: BlockNumber(0), proposer },
+            mpsc::channel(1).1,
+            fin_receiver,
+        )
+        .await;
+    assert_eq!(res, Ok(None));
+    // The abandoned validation was cancelled.
+    assert!(block_sender.lock().unwrap().as_ref().unwrap().is_canceled());
+
+    // Consensus proceeds with the height.
+    assert_eq!(
+        shc.handle_message(&mut context, prevote(BlockHash(Felt::ONE), 0, proposer)).await,
+        Ok(None)
+    );
+}
+
+#[tokio::test]
+async fn rounds_metric() {
+    let mut context = MockTestContext::new();
+    let handle = PrometheusBuilder::new().install_recorder().unwrap();
+
+    // A lone validator decides on its own proposal within the first round.
+    let node_id: ValidatorId = 1_u32.into();
+    let block = TestBlock { content: vec![], id: BlockHash(Felt::ONE) };
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        node_id,
+        vec![node_id],
+        ConsensusConfig::default().proposal_validation_timeout,
+    );
+
+    context.expect_proposer().returning(move |_, _| node_id);
+    context.expect_build_proposal().returning(move |_| {
+        let (_, content_receiver) = mpsc::channel(1);
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send(block.clone()).unwrap();
+        (content_receiver, block_receiver)
+    });
+    let fin_receiver = Arc::new(OnceLock::new());
+    let fin_receiver_clone = Arc::clone(&fin_receiver);
+    context.expect_propose().return_once(move |_, _, fin_receiver| {
+        fin_receiver_clone.set(fin_receiver).unwrap();
+        Ok(())
+    });
+    context.expect_broadcast().returning(move |_| Ok(()));
+
+    assert!(prometheus_is_contained(handle.render(), PAPYRUS_CONSENSUS_ROUNDS, &[]).is_none());
+    assert!(shc.start(&mut context).await.unwrap().is_some());
+    assert_eq!(
+        prometheus_is_contained(handle.render(), PAPYRUS_CONSENSUS_ROUNDS, &[]),
+        Some(prometheus_parse::Value::Gauge(1.0))
+    );
+}
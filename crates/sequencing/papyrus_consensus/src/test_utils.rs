@@ -3,8 +3,10 @@ use futures::channel::{mpsc, oneshot};
 use mockall::mock;
 use papyrus_protobuf::consensus::ConsensusMessage;
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::transaction::Transaction;
 
 use crate::types::{ConsensusBlock, ConsensusContext, ConsensusError, ProposalInit, ValidatorId};
+use crate::ProposalWrapper;
 
 /// Define a consensus block which can be used to enable auto mocking Context.
 #[derive(Debug, PartialEq, Clone)]
@@ -59,3 +61,23 @@ mock! {
         ) -> Result<(), ConsensusError>;
     }
 }
+
+// Delegates the validation to the conversion of `PapyrusConsensusContext`, so that proposals in
+// tests go through it. `TestBlock` content isn't carried over the network, so the content is left
+// empty.
+impl TryFrom<ProposalWrapper>
+    for (ProposalInit, mpsc::Receiver<u32>, oneshot::Receiver<BlockHash>)
+{
+    type Error = ConsensusError;
+
+    fn try_from(val: ProposalWrapper) -> Result<Self, Self::Error> {
+        let (proposal_init, _transactions, fin_receiver): (
+            ProposalInit,
+            mpsc::Receiver<Transaction>,
+            oneshot::Receiver<BlockHash>,
+        ) = val.try_into()?;
+        let (mut content_sender, content_receiver) = mpsc::channel(0);
+        content_sender.close_channel();
+        Ok((proposal_init, content_receiver, fin_receiver))
+    }
+}
@@ -87,31 +87,41 @@ enum RequestWithRetryError {
     ClientError(#[from] ClientError),
 }
 
+/// Builds an http client identifying itself as papyrus at `node_version`. The client can be shared
+/// between several starknet clients so they use the same connection pool.
+pub fn build_http_client(node_version: &'static str) -> Result<Client, ClientCreationError> {
+    let info = os_info::get();
+    let system_information = format!("{}; {}; {}", info.os_type(), info.version(), info.bitness());
+    let app_user_agent = format!(
+        "{product_name}/{product_version} ({system_information})",
+        product_name = "papyrus",
+        product_version = node_version,
+        system_information = system_information
+    );
+    Ok(Client::builder().user_agent(app_user_agent).build()?)
+}
+
 impl StarknetClient {
     /// Creates a new client for a starknet gateway at `url_str` with retry_config [`RetryConfig`].
     pub fn new(
         http_headers: Option<HashMap<String, String>>,
         node_version: &'static str,
         retry_config: RetryConfig,
+    ) -> Result<Self, ClientCreationError> {
+        Self::with_client(http_headers, retry_config, build_http_client(node_version)?)
+    }
+
+    /// Creates a new client that sends its requests through the given http client.
+    pub fn with_client(
+        http_headers: Option<HashMap<String, String>>,
+        retry_config: RetryConfig,
+        client: Client,
     ) -> Result<Self, ClientCreationError> {
         let header_map = match http_headers {
             Some(inner) => (&inner).try_into()?,
             None => HeaderMap::new(),
         };
-        let info = os_info::get();
-        let system_information =
-            format!("{}; {}; {}", info.os_type(), info.version(), info.bitness());
-        let app_user_agent = format!(
-            "{product_name}/{product_version} ({system_information})",
-            product_name = "papyrus",
-            product_version = node_version,
-            system_information = system_information
-        );
-        Ok(StarknetClient {
-            http_headers: header_map,
-            internal_client: Client::builder().user_agent(app_user_agent).build()?,
-            retry_config,
-        })
+        Ok(StarknetClient { http_headers: header_map, internal_client: client, retry_config })
     }
 
     fn get_retry_error_code(err: &ClientError) -> Option<RetryErrorCode> {
@@ -129,6 +139,9 @@ impl StarknetClient {
             ClientError::RequestError(internal_err) => {
                 if internal_err.is_timeout() {
                     Some(RetryErrorCode::Timeout)
+                } else if is_connection_reset(internal_err) {
+                    // A connection reset mid-request isn't reported as a connect error.
+                    Some(RetryErrorCode::Disconnect)
                 } else if internal_err.is_request() {
                     None
                 } else if internal_err.is_connect() {
@@ -210,3 +223,17 @@ impl StarknetClient {
         }
     }
 }
+
+// Returns true if the error was caused by the peer resetting the connection.
+fn is_connection_reset(err: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(err);
+    while let Some(err) = source {
+        if let Some(io_err) = err.downcast_ref::<std::io::Error>() {
+            if io_err.kind() == std::io::ErrorKind::ConnectionReset {
+                return true;
+            }
+        }
+        source = err.source();
+    }
+    false
+}
@@ -10,6 +10,7 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use futures::{stream, StreamExt, TryStreamExt};
 #[cfg(any(feature = "testing", test))]
 use mockall::automock;
 use papyrus_common::pending_classes::ApiContractClass;
@@ -29,7 +30,7 @@ pub use crate::reader::objects::block::{
     BlockSignatureMessage,
     TransactionReceiptsError,
 };
-pub use crate::reader::objects::pending_data::PendingData;
+pub use crate::reader::objects::pending_data::{PendingData, PendingStateUpdate};
 pub use crate::reader::objects::state::{
     ContractClass,
     DeclaredClassHashEntry,
@@ -81,6 +82,13 @@ pub trait StarknetReader {
     /// Returns a [`Block`] corresponding to `block_number`, returning [`None`] in case
     /// no such block exists in the system.
     async fn block(&self, block_number: BlockNumber) -> ReaderClientResult<Option<Block>>;
+    /// Returns the blocks in the range [`from`, `to`) in order, with [`None`] in the place of
+    /// blocks that don't exist in the system.
+    async fn blocks(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> ReaderClientResult<Vec<Option<Block>>>;
     /// Returns a [`GenericContractClass`] corresponding to `class_hash`.
     async fn class_by_hash(
         &self,
@@ -101,6 +109,9 @@ pub trait StarknetReader {
     /// Returns pending [`starknet_client`][`PendingData`].
     async fn pending_data(&self) -> ReaderClientResult<Option<PendingData>>;
 
+    /// Returns the pending [`starknet_client`][`PendingStateUpdate`], without the pending block.
+    async fn pending_state_update(&self) -> ReaderClientResult<Option<PendingStateUpdate>>;
+
     // Returns true if the reader is alive.
     async fn is_alive(&self) -> bool;
 
@@ -118,15 +129,21 @@ pub trait StarknetReader {
 pub struct StarknetFeederGatewayClient {
     urls: StarknetUrls,
     client: StarknetClient,
+    lenient_block_parsing: bool,
+    // Whether the is alive check was directed to a custom path, which may respond with any
+    // message.
+    custom_is_alive_path: bool,
 }
 
 #[derive(Clone, Debug)]
 struct StarknetUrls {
+    base_url: Url,
     get_block: Url,
     get_contract_by_hash: Url,
     get_compiled_class_by_class_hash: Url,
     get_state_update: Url,
     get_pending_data: Url,
+    get_pending_state_update: Url,
     feeder_gateway_is_alive: Url,
     get_block_signature: Url,
     get_sequencer_pub_key: Url,
@@ -146,6 +163,8 @@ const FEEDER_GATEWAY_IS_ALIVE: &str = "feeder_gateway/is_alive";
 const FEEDER_GATEWAY_ALIVE_RESPONSE: &str = "FeederGateway is alive!";
 const GET_BLOCK_SIGNATURE_URL: &str = "feeder_gateway/get_signature";
 const GET_SEQUENCER_PUB_KEY_URL: &str = "feeder_gateway/get_public_key";
+// Max amount of blocks requested concurrently when fetching a range of blocks.
+const MAX_CONCURRENT_BLOCK_REQUESTS: usize = 10;
 
 impl StarknetUrls {
     fn new(url_str: &str) -> Result<Self, ClientCreationError> {
@@ -174,9 +193,16 @@ impl StarknetUrls {
                 .append_pair(INCLUDE_BLOCK, "true")
                 .finish()
                 .clone(),
+            get_pending_state_update: base_url
+                .join(GET_STATE_UPDATE_URL)?
+                .query_pairs_mut()
+                .append_pair(BLOCK_NUMBER_QUERY, PENDING_BLOCK_ID)
+                .finish()
+                .clone(),
             feeder_gateway_is_alive: base_url.join(FEEDER_GATEWAY_IS_ALIVE)?,
             get_block_signature: base_url.join(GET_BLOCK_SIGNATURE_URL)?,
             get_sequencer_pub_key: base_url.join(GET_SEQUENCER_PUB_KEY_URL)?,
+            base_url,
         })
     }
 }
@@ -191,9 +217,45 @@ impl StarknetFeederGatewayClient {
         Ok(StarknetFeederGatewayClient {
             urls: StarknetUrls::new(url_str)?,
             client: StarknetClient::new(http_headers, node_version, retry_config)?,
+            lenient_block_parsing: false,
+            custom_is_alive_path: false,
+        })
+    }
+
+    /// Creates a new client that sends its requests through the given http client, e.g. one that
+    /// is shared with other clients (see [`build_http_client`](crate::build_http_client)).
+    pub fn with_client(
+        url_str: &str,
+        http_headers: Option<HashMap<String, String>>,
+        retry_config: RetryConfig,
+        client: reqwest::Client,
+    ) -> Result<Self, ClientCreationError> {
+        Ok(StarknetFeederGatewayClient {
+            urls: StarknetUrls::new(url_str)?,
+            client: StarknetClient::with_client(http_headers, retry_config, client)?,
+            lenient_block_parsing: false,
+            custom_is_alive_path: false,
         })
     }
 
+    /// Directs the is alive check to `is_alive_path`, relative to the feeder gateway url, instead
+    /// of the feeder gateway's is alive endpoint. Any successful response from that path counts as
+    /// alive.
+    pub fn with_is_alive_path(mut self, is_alive_path: &str) -> Result<Self, ClientCreationError> {
+        self.urls.feeder_gateway_is_alive = self.urls.base_url.join(is_alive_path)?;
+        self.custom_is_alive_path = true;
+        Ok(self)
+    }
+
+    /// Sets whether to skip transactions that fail to deserialize when parsing blocks, instead of
+    /// failing the entire block. The skipped transactions are available through
+    /// [`Block::unknown_transactions`]. Off by default, since a block with skipped transactions
+    /// doesn't match its header.
+    pub fn with_lenient_block_parsing(mut self, lenient_block_parsing: bool) -> Self {
+        self.lenient_block_parsing = lenient_block_parsing;
+        self
+    }
+
     async fn request_with_retry_url(&self, url: Url) -> ReaderClientResult<String> {
         self.client
             .request_with_retry(self.client.internal_client.get(url))
@@ -211,10 +273,20 @@ impl StarknetFeederGatewayClient {
         url.query_pairs_mut().append_pair(BLOCK_NUMBER_QUERY, block_number.as_str());
 
         let response = self.request_with_retry_url(url).await;
+        let error_message =
+            format!("Failed to get block number {block_number:?} from starknet server.");
+        if self.lenient_block_parsing {
+            return load_object_from_response_with_parser(
+                response,
+                Some(KnownStarknetErrorCode::BlockNotFound),
+                error_message,
+                Block::from_json_skipping_unknown_transactions,
+            );
+        }
         load_object_from_response(
             response,
             Some(KnownStarknetErrorCode::BlockNotFound),
-            format!("Failed to get block number {block_number:?} from starknet server."),
+            error_message,
         )
     }
 }
@@ -231,6 +303,19 @@ impl StarknetReader for StarknetFeederGatewayClient {
         self.request_block(Some(block_number)).await
     }
 
+    #[instrument(skip(self), level = "debug")]
+    async fn blocks(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+    ) -> ReaderClientResult<Vec<Option<Block>>> {
+        stream::iter(from.iter_up_to(to))
+            .map(|block_number| self.request_block(Some(block_number)))
+            .buffered(MAX_CONCURRENT_BLOCK_REQUESTS)
+            .try_collect()
+            .await
+    }
+
     #[instrument(skip(self), level = "debug")]
     async fn class_by_hash(
         &self,
@@ -328,9 +413,23 @@ impl StarknetReader for StarknetFeederGatewayClient {
         )
     }
 
+    #[instrument(skip(self), level = "debug")]
+    async fn pending_state_update(&self) -> ReaderClientResult<Option<PendingStateUpdate>> {
+        let response =
+            self.request_with_retry_url(self.urls.get_pending_state_update.clone()).await;
+        load_object_from_response(
+            response,
+            Some(KnownStarknetErrorCode::BlockNotFound),
+            "Failed to get pending state update from starknet server.".to_string(),
+        )
+    }
+
     async fn is_alive(&self) -> bool {
         let url = self.urls.feeder_gateway_is_alive.clone();
         let response = self.request_with_retry_url(url).await;
+        if self.custom_is_alive_path {
+            return response.is_ok();
+        }
         let expected_response = FEEDER_GATEWAY_ALIVE_RESPONSE.to_string();
         response.is_ok_and(|response| response == expected_response)
     }
@@ -368,10 +467,22 @@ fn load_object_from_response<Object: for<'a> Deserialize<'a>>(
     response: ReaderClientResult<String>,
     none_error_code: Option<KnownStarknetErrorCode>,
     error_message: String,
+) -> ReaderClientResult<Option<Object>> {
+    load_object_from_response_with_parser(response, none_error_code, error_message, |raw_object| {
+        serde_json::from_str(raw_object)
+    })
+}
+
+/// Like [`load_object_from_response`], but parses the object with `parse`.
+fn load_object_from_response_with_parser<Object>(
+    response: ReaderClientResult<String>,
+    none_error_code: Option<KnownStarknetErrorCode>,
+    error_message: String,
+    parse: impl FnOnce(&str) -> serde_json::Result<Object>,
 ) -> ReaderClientResult<Option<Object>> {
     match (response, none_error_code) {
         (Ok(raw_object), _) => {
-            let result = serde_json::from_str(&raw_object);
+            let result = parse(&raw_object);
             if let Err(err) = &result {
                 error!("Failed to deserialize {raw_object:?}. Error: {err}");
             }
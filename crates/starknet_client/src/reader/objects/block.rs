@@ -2,6 +2,7 @@
 #[path = "block_test.rs"]
 mod block_test;
 
+use std::collections::{HashMap, HashSet};
 use std::ops::Index;
 
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,7 @@ use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::transaction::TransactionOutput as starknet_api_transaction_output;
 use starknet_api::transaction::{TransactionHash, TransactionOffsetInBlock};
 use starknet_types_core::felt::Felt;
+use tracing::warn;
 
 use crate::reader::objects::transaction::{
     L1ToL2Message,
@@ -70,6 +72,10 @@ pub struct BlockPostV0_13_1 {
     pub receipt_commitment: Option<ReceiptCommitment>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state_diff_length: Option<usize>,
+    // Transactions that failed to deserialize, kept as raw JSON. Only filled by
+    // [`Block::from_json_skipping_unknown_transactions`].
+    #[serde(skip)]
+    pub unknown_transactions: Vec<serde_json::Value>,
 }
 
 impl BlockPostV0_13_1 {
@@ -153,12 +159,77 @@ pub enum TransactionReceiptsError {
 /// [Block](`starknet_api_block`) and String representing the Starknet version corresponding to
 /// that block.
 impl Block {
+    /// Deserializes a block, skipping transactions that fail to deserialize (e.g. transactions of
+    /// a type unknown to this version) instead of failing the entire block. The skipped
+    /// transactions are returned by [`Block::unknown_transactions`], and their receipts are dropped
+    /// so that the remaining receipts still match the remaining transactions.
+    pub fn from_json_skipping_unknown_transactions(raw_block: &str) -> serde_json::Result<Self> {
+        let mut raw_block: serde_json::Value = serde_json::from_str(raw_block)?;
+        let raw_transactions = match raw_block.get_mut("transactions") {
+            Some(serde_json::Value::Array(raw_transactions)) => std::mem::take(raw_transactions),
+            _ => Vec::new(),
+        };
+
+        let mut transactions = Vec::with_capacity(raw_transactions.len());
+        let mut unknown_transactions = Vec::new();
+        for raw_transaction in raw_transactions {
+            match Transaction::deserialize(&raw_transaction) {
+                Ok(transaction) => transactions.push(transaction),
+                Err(err) => {
+                    warn!("Skipping transaction {raw_transaction} of block. Error: {err}");
+                    unknown_transactions.push(raw_transaction);
+                }
+            }
+        }
+
+        let mut block: Block = serde_json::from_value(raw_block)?;
+        match &mut block {
+            Block::PostV0_13_1(block) => {
+                // The receipts are matched to the transactions by transaction hash. The receipts
+                // of the skipped transactions are dropped, and the rest are re-indexed to the
+                // positions of their transactions.
+                let transaction_indices: HashMap<TransactionHash, usize> = transactions
+                    .iter()
+                    .enumerate()
+                    .map(|(index, transaction)| (transaction.transaction_hash(), index))
+                    .collect();
+                let unknown_transaction_hashes: HashSet<TransactionHash> = unknown_transactions
+                    .iter()
+                    .filter_map(|raw_transaction| {
+                        TransactionHash::deserialize(raw_transaction.get("transaction_hash")?).ok()
+                    })
+                    .collect();
+                block.transaction_receipts = std::mem::take(&mut block.transaction_receipts)
+                    .into_iter()
+                    .filter(|receipt| {
+                        !unknown_transaction_hashes.contains(&receipt.transaction_hash)
+                    })
+                    .map(|mut receipt| {
+                        if let Some(index) = transaction_indices.get(&receipt.transaction_hash) {
+                            receipt.transaction_index = TransactionOffsetInBlock(*index);
+                        }
+                        receipt
+                    })
+                    .collect();
+                block.transactions = transactions;
+                block.unknown_transactions = unknown_transactions;
+            }
+        }
+        Ok(block)
+    }
+
     pub fn transactions(&self) -> &[Transaction] {
         match self {
             Block::PostV0_13_1(block) => &block.transactions,
         }
     }
 
+    pub fn unknown_transactions(&self) -> &[serde_json::Value] {
+        match self {
+            Block::PostV0_13_1(block) => &block.unknown_transactions,
+        }
+    }
+
     pub fn transaction_receipts(&self) -> &[TransactionReceipt] {
         match self {
             Block::PostV0_13_1(block) => &block.transaction_receipts,
@@ -36,6 +36,46 @@ fn load_block_succeeds() {
     }
 }
 
+#[test]
+fn load_block_skipping_unknown_transactions() {
+    let raw_block = read_resource_file("reader/block_post_0_13_1.json");
+    let expected_block: Block = serde_json::from_str(&raw_block).unwrap();
+    assert!(expected_block.unknown_transactions().is_empty());
+
+    let unknown_transaction = serde_json::json!({
+        "type": "SOME_FUTURE_TRANSACTION_TYPE",
+        "transaction_hash": "0x1",
+    });
+    let mut block_json: serde_json::Value = serde_json::from_str(&raw_block).unwrap();
+    block_json["transactions"].as_array_mut().unwrap().insert(1, unknown_transaction.clone());
+    // The unknown transaction comes with its own receipt, and the transactions after it are
+    // shifted. The receipts are matched to the transactions by hash, so the receipt of the unknown
+    // transaction is placed last rather than at the position of its transaction.
+    let receipts = block_json["transaction_receipts"].as_array_mut().unwrap();
+    for receipt in receipts.iter_mut().skip(1) {
+        receipt["transaction_index"] =
+            serde_json::json!(receipt["transaction_index"].as_u64().unwrap() + 1);
+    }
+    let mut unknown_transaction_receipt = receipts[1].clone();
+    unknown_transaction_receipt["transaction_hash"] = serde_json::json!("0x1");
+    unknown_transaction_receipt["transaction_index"] = serde_json::json!(1);
+    receipts.push(unknown_transaction_receipt);
+    let raw_block_with_unknown_transaction = block_json.to_string();
+
+    // Strict parsing fails on the unknown transaction.
+    assert!(serde_json::from_str::<Block>(&raw_block_with_unknown_transaction).is_err());
+
+    let block = Block::from_json_skipping_unknown_transactions(&raw_block_with_unknown_transaction)
+        .unwrap();
+    assert_eq!(block.transactions(), expected_block.transactions());
+    assert_eq!(block.transaction_receipts(), expected_block.transaction_receipts());
+    assert_eq!(block.unknown_transactions(), &[unknown_transaction]);
+    assert_eq!(
+        block.to_starknet_api_block_and_version().unwrap(),
+        expected_block.to_starknet_api_block_and_version().unwrap()
+    );
+}
+
 #[test]
 fn load_block_state_update_succeeds() {
     let expected_state_update = StateUpdate {
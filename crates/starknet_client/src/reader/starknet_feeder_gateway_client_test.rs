@@ -39,6 +39,7 @@ use super::{
     ContractClass,
     GenericContractClass,
     PendingData,
+    PendingStateUpdate,
     ReaderClientError,
     ReaderClientResult,
     StarknetFeederGatewayClient,
@@ -360,6 +361,30 @@ async fn deprecated_pending_data() {
     assert_eq!(pending_data.unwrap().unwrap(), expected_pending_data);
 }
 
+#[tokio::test]
+async fn pending_state_update() {
+    let starknet_client = StarknetFeederGatewayClient::new(
+        &mockito::server_url(),
+        None,
+        NODE_VERSION,
+        get_test_config(),
+    )
+    .unwrap();
+
+    let raw_pending_data: serde_json::Value =
+        serde_json::from_str(&read_resource_file("reader/deprecated_pending_data.json")).unwrap();
+    let raw_state_update = raw_pending_data["state_update"].to_string();
+    let mock_state_update = mock("GET", "/feeder_gateway/get_state_update?blockNumber=pending")
+        .with_status(200)
+        .with_body(&raw_state_update)
+        .create();
+    let pending_state_update = starknet_client.pending_state_update().await;
+    mock_state_update.assert();
+    let expected_state_update: PendingStateUpdate =
+        serde_json::from_str(&raw_state_update).unwrap();
+    assert_eq!(pending_state_update.unwrap().unwrap(), expected_state_update);
+}
+
 #[tokio::test]
 async fn get_block() {
     let starknet_client = StarknetFeederGatewayClient::new(
@@ -391,6 +416,82 @@ async fn get_block() {
     assert!(block.is_none());
 }
 
+#[tokio::test]
+async fn get_block_with_unknown_transaction() {
+    let strict_client = StarknetFeederGatewayClient::new(
+        &mockito::server_url(),
+        None,
+        NODE_VERSION,
+        get_test_config(),
+    )
+    .unwrap();
+    let lenient_client = StarknetFeederGatewayClient::new(
+        &mockito::server_url(),
+        None,
+        NODE_VERSION,
+        get_test_config(),
+    )
+    .unwrap()
+    .with_lenient_block_parsing(true);
+    let raw_block = read_resource_file("reader/block_post_0_13_1.json");
+    let expected_block: Block = serde_json::from_str(&raw_block).unwrap();
+    let unknown_transaction = serde_json::json!({
+        "type": "SOME_FUTURE_TRANSACTION_TYPE",
+        "transaction_hash": "0x1",
+    });
+    let mut block_json: serde_json::Value = serde_json::from_str(&raw_block).unwrap();
+    block_json["transactions"].as_array_mut().unwrap().push(unknown_transaction.clone());
+    let mock_block = mock("GET", &format!("/feeder_gateway/get_block?{BLOCK_NUMBER_QUERY}=20")[..])
+        .with_status(200)
+        .with_body(block_json.to_string())
+        .expect(2)
+        .create();
+
+    // By default, the unknown transaction fails the entire block.
+    assert_matches!(
+        strict_client.block(BlockNumber(20)).await,
+        Err(ReaderClientError::SerdeError(_))
+    );
+
+    // With lenient parsing, the unknown transaction is skipped.
+    let block = lenient_client.block(BlockNumber(20)).await.unwrap().unwrap();
+    mock_block.assert();
+    assert_eq!(block.transactions(), expected_block.transactions());
+    assert_eq!(block.unknown_transactions(), &[unknown_transaction]);
+}
+
+#[tokio::test]
+async fn get_blocks() {
+    let starknet_client = StarknetFeederGatewayClient::new(
+        &mockito::server_url(),
+        None,
+        NODE_VERSION,
+        get_test_config(),
+    )
+    .unwrap();
+    let raw_block = read_resource_file("reader/block_post_0_13_1.json");
+    let expected_block: Block = serde_json::from_str(&raw_block).unwrap();
+    let body =
+        r#"{"code": "StarknetErrorCode.BLOCK_NOT_FOUND", "message": "Block 31 was not found."}"#;
+    let mocks = [(30, 200, raw_block.as_str()), (31, 400, body), (32, 200, raw_block.as_str())]
+        .map(|(block_number, status, body)| {
+            mock(
+                "GET",
+                &format!("/feeder_gateway/get_block?{BLOCK_NUMBER_QUERY}={block_number}")[..],
+            )
+            .with_status(status)
+            .with_body(body)
+            .create()
+        });
+
+    // A missing block doesn't fail the other blocks in the range.
+    let blocks = starknet_client.blocks(BlockNumber(30), BlockNumber(33)).await.unwrap();
+    for mock in mocks {
+        mock.assert();
+    }
+    assert_eq!(blocks, vec![Some(expected_block.clone()), None, Some(expected_block)]);
+}
+
 #[tokio::test]
 async fn compiled_class_by_hash() {
     let starknet_client = StarknetFeederGatewayClient::new(
@@ -452,6 +553,24 @@ async fn is_alive() {
     assert!(response);
 }
 
+#[tokio::test]
+async fn is_alive_custom_path() {
+    let starknet_client = StarknetFeederGatewayClient::new(
+        &mockito::server_url(),
+        None,
+        NODE_VERSION,
+        get_test_config(),
+    )
+    .unwrap()
+    .with_is_alive_path("custom/feeder_health")
+    .unwrap();
+    let mock_is_alive =
+        mock("GET", "/custom/feeder_health").with_status(200).with_body("OK").create();
+    let response = starknet_client.is_alive().await;
+    mock_is_alive.assert();
+    assert!(response);
+}
+
 // Empty storage diffs were filtered out in the past, but should not anymore (part of the inputs to
 // the state diff commitment).
 #[tokio::test]
@@ -1,10 +1,16 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
 use assert_matches::assert_matches;
 use mockito::mock;
 use reqwest::StatusCode;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
 
 use crate::starknet_error::{KnownStarknetErrorCode, StarknetError, StarknetErrorCode};
 use crate::test_utils::retry::{get_test_config, MAX_RETRIES};
-use crate::{ClientError, RetryErrorCode, StarknetClient};
+use crate::{build_http_client, ClientError, RetryErrorCode, StarknetClient};
 
 const NODE_VERSION: &str = "NODE VERSION";
 const URL_SUFFIX: &str = "/query";
@@ -176,6 +182,77 @@ async fn request_with_retry_starknet_error_success_on_retry() {
     mock_success.assert();
 }
 
+#[tokio::test]
+async fn request_with_retry_connection_reset() {
+    // A server that resets every connection after reading the request.
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}{URL_SUFFIX}", listener.local_addr().unwrap());
+    let n_connections = Arc::new(AtomicUsize::new(0));
+    let server_n_connections = n_connections.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            server_n_connections.fetch_add(1, Ordering::SeqCst);
+            let mut buf = [0; 1024];
+            let _ = stream.read(&mut buf).await;
+            // Closing with a zero linger timeout sends a reset instead of a graceful close.
+            stream.set_linger(Some(Duration::ZERO)).unwrap();
+        }
+    });
+
+    let starknet_client = StarknetClient::new(None, NODE_VERSION, get_test_config()).unwrap();
+    let result =
+        starknet_client.request_with_retry(starknet_client.internal_client.get(&url)).await;
+    assert_matches!(
+        result,
+        Err(ClientError::RetryError { code, message: _ }) if code == RetryErrorCode::Disconnect
+    );
+    assert_eq!(n_connections.load(Ordering::SeqCst), MAX_RETRIES + 1);
+}
+
+#[tokio::test]
+async fn clients_with_shared_http_client_share_connections() {
+    const BODY: &str = "body";
+    // A keep-alive server that answers every request with BODY.
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}{URL_SUFFIX}", listener.local_addr().unwrap());
+    let n_connections = Arc::new(AtomicUsize::new(0));
+    let server_n_connections = n_connections.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            server_n_connections.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut buf = [0; 1024];
+                while stream.read(&mut buf).await.is_ok_and(|n_bytes| n_bytes > 0) {
+                    let response =
+                        format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{BODY}", BODY.len());
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                }
+            });
+        }
+    });
+
+    let shared_client = build_http_client(NODE_VERSION).unwrap();
+    let first_client =
+        StarknetClient::with_client(None, get_test_config(), shared_client.clone()).unwrap();
+    let second_client =
+        StarknetClient::with_client(None, get_test_config(), shared_client).unwrap();
+    for client in [&first_client, &second_client] {
+        let result = client.request_with_retry(client.internal_client.get(&url)).await;
+        assert_eq!(result.unwrap(), BODY);
+    }
+    // Both clients used the same pooled connection.
+    assert_eq!(n_connections.load(Ordering::SeqCst), 1);
+
+    // A client with its own http client opens a new connection.
+    let separate_client = StarknetClient::new(None, NODE_VERSION, get_test_config()).unwrap();
+    let result =
+        separate_client.request_with_retry(separate_client.internal_client.get(&url)).await;
+    assert_eq!(result.unwrap(), BODY);
+    assert_eq!(n_connections.load(Ordering::SeqCst), 2);
+}
+
 #[test]
 fn serialization_precision() {
     let input =
@@ -80,8 +80,12 @@ const GATEWAY_ALIVE_RESPONSE: &str = "Gateway is alive!";
 ///
 /// [`Starknet`]: https://starknet.io/
 pub struct StarknetGatewayClient {
+    base_url: Url,
     add_transaction_url: Url,
     is_alive_url: Url,
+    // Whether the is alive check was directed to a custom path, which may respond with any
+    // message.
+    custom_is_alive_path: bool,
     client: StarknetClient,
 }
 
@@ -114,6 +118,9 @@ impl StarknetWriter for StarknetGatewayClient {
     async fn is_alive(&self) -> bool {
         let url = self.is_alive_url.clone();
         let response = self.client.request_with_retry(self.client.internal_client.get(url)).await;
+        if self.custom_is_alive_path {
+            return response.is_ok();
+        }
         let expected_response = GATEWAY_ALIVE_RESPONSE.to_string();
         response.is_ok_and(|response| response == expected_response)
     }
@@ -125,13 +132,41 @@ impl StarknetGatewayClient {
         node_version: &'static str,
         retry_config: RetryConfig,
     ) -> Result<Self, ClientCreationError> {
+        let base_url = Url::parse(starknet_url)?;
         Ok(StarknetGatewayClient {
-            add_transaction_url: Url::parse(starknet_url)?.join(ADD_TRANSACTION_URL_SUFFIX)?,
-            is_alive_url: Url::parse(starknet_url)?.join(GATEWAY_IS_ALIVE)?,
+            add_transaction_url: base_url.join(ADD_TRANSACTION_URL_SUFFIX)?,
+            is_alive_url: base_url.join(GATEWAY_IS_ALIVE)?,
+            base_url,
+            custom_is_alive_path: false,
             client: StarknetClient::new(None, node_version, retry_config)?,
         })
     }
 
+    /// Creates a new client that sends its requests through the given http client, e.g. one that
+    /// is shared with other clients (see [`build_http_client`](crate::build_http_client)).
+    pub fn with_client(
+        starknet_url: &str,
+        retry_config: RetryConfig,
+        client: reqwest::Client,
+    ) -> Result<Self, ClientCreationError> {
+        let base_url = Url::parse(starknet_url)?;
+        Ok(StarknetGatewayClient {
+            add_transaction_url: base_url.join(ADD_TRANSACTION_URL_SUFFIX)?,
+            is_alive_url: base_url.join(GATEWAY_IS_ALIVE)?,
+            base_url,
+            custom_is_alive_path: false,
+            client: StarknetClient::with_client(None, retry_config, client)?,
+        })
+    }
+
+    /// Directs the is alive check to `is_alive_path`, relative to the gateway url, instead of the
+    /// gateway's is alive endpoint. Any successful response from that path counts as alive.
+    pub fn with_is_alive_path(mut self, is_alive_path: &str) -> Result<Self, ClientCreationError> {
+        self.is_alive_url = self.base_url.join(is_alive_path)?;
+        self.custom_is_alive_path = true;
+        Ok(self)
+    }
+
     async fn add_transaction<Transaction: Serialize, Response: for<'a> Deserialize<'a>>(
         &self,
         tx: &Transaction,
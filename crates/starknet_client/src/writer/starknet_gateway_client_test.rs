@@ -96,6 +96,20 @@ async fn is_alive() {
     assert!(response);
 }
 
+#[tokio::test]
+async fn is_alive_custom_path() {
+    let starknet_client =
+        StarknetGatewayClient::new(&mockito::server_url(), NODE_VERSION, get_test_config())
+            .unwrap()
+            .with_is_alive_path("custom/gateway_health")
+            .unwrap();
+    let mock_is_alive =
+        mock("GET", "/custom/gateway_health").with_status(200).with_body("OK").create();
+    let response = starknet_client.is_alive().await;
+    mock_is_alive.assert();
+    assert!(response);
+}
+
 #[tokio::test]
 async fn add_invoke_v1_transaction() {
     test_add_transaction_succeeds(
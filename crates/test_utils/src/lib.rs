@@ -1,6 +1,12 @@
 #![allow(clippy::unwrap_used)]
 #[cfg(test)]
 mod precision_test;
+#[cfg(test)]
+mod prometheus_test;
+#[cfg(test)]
+mod read_json_file_test;
+#[cfg(test)]
+mod rng_test;
 
 use std::cmp::max;
 use std::collections::{BTreeMap, HashMap};
@@ -176,13 +182,27 @@ pub fn get_absolute_path(relative_path: &str) -> PathBuf {
     Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("../..").join(relative_path)
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum TestUtilError {
+    #[error("Failed to read {}: {source}", path.display())]
+    ReadFile { path: PathBuf, source: std::io::Error },
+    #[error("Failed to parse {} as JSON: {source}", path.display())]
+    ParseJson { path: PathBuf, source: serde_json::Error },
+}
+
 /// Reads from the directory containing the manifest at run time, same as current working directory.
 pub fn read_json_file(path_in_resource_dir: &str) -> serde_json::Value {
+    try_read_json_file(path_in_resource_dir).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Like [`read_json_file`], but returns an error with the resolved path instead of panicking.
+pub fn try_read_json_file(path_in_resource_dir: &str) -> Result<serde_json::Value, TestUtilError> {
     let path = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
         .join("resources")
         .join(path_in_resource_dir);
-    let json_str = read_to_string(path.to_str().unwrap()).unwrap();
-    serde_json::from_str(&json_str).unwrap()
+    let json_str = read_to_string(&path)
+        .map_err(|source| TestUtilError::ReadFile { path: path.clone(), source })?;
+    serde_json::from_str(&json_str).map_err(|source| TestUtilError::ParseJson { path, source })
 }
 
 pub fn validate_load_and_dump<T: Serialize + for<'a> Deserialize<'a>>(path_in_resource_dir: &str) {
@@ -197,12 +217,22 @@ pub fn validate_load_and_dump<T: Serialize + for<'a> Deserialize<'a>>(path_in_re
 /// Used in random test to create a random generator, see for example storage_serde_test.
 /// Randomness can be seeded by setting and env variable `SEED` or by the OS (the rust default).
 pub fn get_rng() -> ChaCha8Rng {
-    let seed: u64 = match env::var("SEED") {
-        Ok(seed_str) => seed_str.parse().unwrap(),
-        _ => rand::thread_rng().gen(),
-    };
+    let seed = get_seed();
     // Will be printed if the test failed.
     println!("Testing with seed: {seed:?}");
+    get_rng_with_seed(seed)
+}
+
+/// Returns the seed from the env variable `SEED` if it's set, and a random seed otherwise.
+pub fn get_seed() -> u64 {
+    match env::var("SEED") {
+        Ok(seed_str) => seed_str.parse().unwrap(),
+        _ => rand::thread_rng().gen(),
+    }
+}
+
+/// Returns a random generator seeded with the given seed.
+pub fn get_rng_with_seed(seed: u64) -> ChaCha8Rng {
     // Create a new PRNG using a u64 seed. This is a convenience-wrapper around from_seed.
     // It is designed such that low Hamming Weight numbers like 0 and 1 can be used and
     // should still result in good, independent seeds to the returned PRNG.
@@ -210,6 +240,46 @@ pub fn get_rng() -> ChaCha8Rng {
     ChaCha8Rng::seed_from_u64(seed)
 }
 
+/// Returns the command that reruns the current test with the given seed.
+pub fn reproduction_command(seed: u64, package: &str) -> String {
+    let test_name = std::thread::current().name().unwrap_or_default().to_owned();
+    format!("SEED={seed} cargo test -p {package} -- --exact {test_name}")
+}
+
+/// Runs a test body with a random generator, and if the body panics, prints the command that
+/// reruns the test with the same seed.
+///
+/// ```rust,ignore
+/// #[test]
+/// fn random_test() {
+///     run_with_seed!(|rng| {
+///         let value = u64::get_test_instance(&mut rng);
+///         ...
+///     });
+/// }
+/// ```
+#[macro_export]
+macro_rules! run_with_seed {
+    (| $rng:ident | $body:block) => {{
+        let seed = $crate::get_seed();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            #[allow(unused_mut)]
+            let mut $rng = $crate::get_rng_with_seed(seed);
+            $body
+        }));
+        match result {
+            Ok(value) => value,
+            Err(panic) => {
+                eprintln!(
+                    "To reproduce, run: {}",
+                    $crate::reproduction_command(seed, env!("CARGO_PKG_NAME"))
+                );
+                std::panic::resume_unwind(panic)
+            }
+        }
+    }};
+}
+
 /// Use to get the value of a metric by name and labels.
 // If the data contains a metric with metric_name and labels returns its value else None.
 pub fn prometheus_is_contained(
@@ -231,6 +301,26 @@ pub fn prometheus_is_contained(
     None
 }
 
+/// Returns the values of all the samples of a metric whose labels contain the required labels.
+/// Unlike [`prometheus_is_contained`], samples with additional labels (e.g. global labels) match.
+pub fn prometheus_find_samples(
+    data: String,
+    metric_name: &str,
+    required_labels: &[(&str, &str)],
+) -> Vec<Value> {
+    let lines: Vec<_> = data.lines().map(|s| Ok(s.to_owned())).collect();
+    let metrics = prometheus_parse::Scrape::parse(lines.into_iter()).unwrap();
+    metrics
+        .samples
+        .into_iter()
+        .filter(|s| {
+            s.metric == metric_name
+                && required_labels.iter().all(|(k, v)| s.labels.get(k) == Some(*v))
+        })
+        .map(|s| s.value)
+        .collect()
+}
+
 //////////////////////////////////////////////////////////////////////////
 // INTERNAL FUNCTIONS
 //////////////////////////////////////////////////////////////////////////
@@ -255,15 +345,18 @@ fn get_rand_test_block_with_events(
     }
 }
 
-// TODO(Dan, 01/11/2023): Remove this util once v3 tests are ready and transaction generation is
-// using randomness more stably.
-fn is_v3_transaction(transaction: &Transaction) -> bool {
-    matches!(
-        transaction,
-        Transaction::Declare(DeclareTransaction::V3(_))
-            | Transaction::DeployAccount(DeployAccountTransaction::V3(_))
-            | Transaction::Invoke(InvokeTransaction::V3(_))
-    )
+// Sets a bound for each resource in v3 transactions, as required from valid v3 transactions.
+fn set_valid_resource_bounds(transaction: &mut Transaction, rng: &mut ChaCha8Rng) {
+    let resource_bounds = match transaction {
+        Transaction::Declare(DeclareTransaction::V3(tx)) => &mut tx.resource_bounds,
+        Transaction::DeployAccount(DeployAccountTransaction::V3(tx)) => &mut tx.resource_bounds,
+        Transaction::Invoke(InvokeTransaction::V3(tx)) => &mut tx.resource_bounds,
+        _ => return,
+    };
+    *resource_bounds = ResourceBoundsMapping(BTreeMap::from([
+        (Resource::L1Gas, ResourceBounds::get_test_instance(rng)),
+        (Resource::L2Gas, ResourceBounds::get_test_instance(rng)),
+    ]));
 }
 
 /// Returns a test block body with a variable number of transactions and events.
@@ -280,9 +373,7 @@ fn get_rand_test_body_with_events(
     let mut transaction_execution_statuses = vec![];
     for i in 0..transaction_count {
         let mut transaction = Transaction::get_test_instance(rng);
-        while is_v3_transaction(&transaction) {
-            transaction = Transaction::get_test_instance(rng);
-        }
+        set_valid_resource_bounds(&mut transaction, rng);
         transaction_hashes.push(TransactionHash(StarkHash::from(i as u128)));
         let transaction_output = get_test_transaction_output(&transaction);
         transactions.push(transaction);
@@ -422,6 +513,15 @@ pub trait GetTestInstance: Sized {
 
 auto_impl_get_test_instance! {
     pub struct AccountDeploymentData(pub Vec<Felt>);
+    pub struct Block {
+        pub header: BlockHeader,
+        pub body: BlockBody,
+    }
+    pub struct BlockBody {
+        pub transactions: Vec<Transaction>,
+        pub transaction_outputs: Vec<TransactionOutput>,
+        pub transaction_hashes: Vec<TransactionHash>,
+    }
     pub struct BlockHash(pub StarkHash);
     pub struct BlockHeader {
         pub block_hash: BlockHash,
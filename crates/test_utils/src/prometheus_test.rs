@@ -0,0 +1,27 @@
+use pretty_assertions::assert_eq;
+use prometheus_parse::Value;
+
+use crate::{prometheus_find_samples, prometheus_is_contained};
+
+const DATA: &str = "\
+# TYPE requests counter
+requests{method=\"get\",network=\"mainnet\"} 3
+requests{method=\"post\",network=\"mainnet\"} 5
+";
+
+#[test]
+fn find_samples_with_extra_labels() {
+    let data = DATA.to_owned();
+
+    // The samples have an extra "network" label.
+    assert_eq!(prometheus_is_contained(data.clone(), "requests", &[("method", "get")]), None);
+    assert_eq!(
+        prometheus_find_samples(data.clone(), "requests", &[("method", "get")]),
+        vec![Value::Counter(3.0)]
+    );
+    assert_eq!(
+        prometheus_find_samples(data.clone(), "requests", &[("network", "mainnet")]),
+        vec![Value::Counter(3.0), Value::Counter(5.0)]
+    );
+    assert_eq!(prometheus_find_samples(data, "requests", &[("method", "put")]), vec![]);
+}
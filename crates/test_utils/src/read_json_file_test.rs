@@ -0,0 +1,13 @@
+use assert_matches::assert_matches;
+
+use crate::{try_read_json_file, TestUtilError};
+
+#[test]
+fn missing_json_file() {
+    let err = try_read_json_file("missing_file.json").unwrap_err();
+    assert_matches!(err, TestUtilError::ReadFile { .. });
+    let expected_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("resources")
+        .join("missing_file.json");
+    assert!(err.to_string().contains(expected_path.to_str().unwrap()), "{err}");
+}
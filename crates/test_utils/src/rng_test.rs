@@ -0,0 +1,25 @@
+use pretty_assertions::assert_eq;
+use rand::Rng;
+
+use crate::{get_rng_with_seed, reproduction_command, run_with_seed};
+
+#[test]
+fn rng_with_seed_is_deterministic() {
+    let values = get_rng_with_seed(7).gen::<[u64; 4]>();
+    assert_eq!(get_rng_with_seed(7).gen::<[u64; 4]>(), values);
+    assert_ne!(get_rng_with_seed(8).gen::<[u64; 4]>(), values);
+}
+
+#[test]
+fn reproduction_command_names_the_test() {
+    assert_eq!(
+        reproduction_command(7, "test_utils"),
+        "SEED=7 cargo test -p test_utils -- --exact rng_test::reproduction_command_names_the_test"
+    );
+}
+
+#[test]
+fn run_with_seed_returns_the_body_value() {
+    let value = run_with_seed!(|rng| { rng.gen_range(0..10_u8) });
+    assert!(value < 10);
+}